@@ -0,0 +1,402 @@
+//! The accessibility tree is the semantic view of the widget tree that the
+//! assistive technologies consume. Only the widgets that describe
+//! [`Semantics`] become nodes, the other widgets are transparent and their
+//! semantic descendants are lifted to the nearest semantic ancestor.
+//!
+//! The window rebuilds the tree after a drawn frame that changes the layout,
+//! the semantics or the focus, and sends it to the shell window if it enables
+//! the accessibility, see
+//! [`ShellWindow::update_accessibility`](crate::window::ShellWindow::update_accessibility).
+//! You can also take a snapshot of the tree by
+//! [`Window::accessibility_tree`] to assert the semantics in the tests.
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use smallvec::SmallVec;
+
+use crate::{prelude::*, widget_tree::WidgetTree};
+
+/// A snapshot of the accessibility tree of a window.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessibilityTree {
+  /// The root node with the `Window` role.
+  pub root: AccessNode,
+  /// The widget id of the node that has the focus.
+  pub focus: Option<WidgetId>,
+}
+
+/// A node of the accessibility tree.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AccessNode {
+  /// The widget that describes the node.
+  pub id: WidgetId,
+  pub role: Role,
+  pub label: Option<CowArc<str>>,
+  pub value: Option<CowArc<str>>,
+  pub checked: Option<Toggled>,
+  pub expanded: Option<bool>,
+  pub selected: Option<bool>,
+  pub disabled: bool,
+  pub focusable: bool,
+  pub focused: bool,
+  pub actions: SmallVec<[SemanticsAction; 1]>,
+  /// The layout rect of the widget in the window coordinate.
+  pub rect: Rect,
+  pub children: Vec<AccessNode>,
+}
+
+impl AccessibilityTree {
+  pub(crate) fn new(tree: &WidgetTree, focusing: Option<WidgetId>) -> Self {
+    let root = tree.root();
+    let mut collector = Collector { tree, focusing, focused: false, focusable: false };
+    let mut children = vec![];
+    root
+      .children(tree)
+      .for_each(|c| collector.collect(c, &mut children));
+    let rect = Rect::from_size(
+      tree
+        .store
+        .layout_box_size(root)
+        .unwrap_or_default(),
+    );
+    let mut root = AccessNode::new(root, Semantics::new(Role::Window), rect, children);
+    root.focused = collector.focused;
+
+    let focus = root.iter().find(|n| n.focused).map(|n| n.id);
+    AccessibilityTree { root, focus }
+  }
+
+  /// Return the first node in depth-first order that matches the predicate.
+  pub fn find(&self, f: impl Fn(&AccessNode) -> bool) -> Option<&AccessNode> {
+    self.root.iter().find(|n| f(n))
+  }
+
+  /// Return the first node with the label.
+  pub fn find_by_label(&self, label: &str) -> Option<&AccessNode> {
+    self.find(|n| n.label.as_deref() == Some(label))
+  }
+
+  /// Return the node of the widget.
+  pub fn node(&self, id: WidgetId) -> Option<&AccessNode> { self.find(|n| n.id == id) }
+}
+
+impl AccessNode {
+  fn new(id: WidgetId, semantics: Semantics, rect: Rect, children: Vec<AccessNode>) -> Self {
+    let Semantics { role, label, value, checked, expanded, selected, disabled, actions, .. } =
+      semantics;
+    let role = role.unwrap_or_default();
+    let label = label.or_else(|| {
+      role
+        .name_from_content()
+        .then(|| content_text(&children))
+        .filter(|text| !text.is_empty())
+        .map(CowArc::from)
+    });
+    AccessNode {
+      id,
+      role,
+      label,
+      value,
+      checked,
+      expanded,
+      selected,
+      disabled: disabled.unwrap_or_default(),
+      focusable: false,
+      focused: false,
+      actions,
+      rect,
+      children,
+    }
+  }
+
+  /// Iterate the node and its descendants in depth-first order.
+  pub fn iter(&self) -> impl Iterator<Item = &AccessNode> {
+    let mut stack = vec![self];
+    std::iter::from_fn(move || {
+      let node = stack.pop()?;
+      stack.extend(node.children.iter().rev());
+      Some(node)
+    })
+  }
+
+  fn fmt_with_indent(&self, f: &mut Formatter<'_>, indent: usize) -> FmtResult {
+    write!(f, "{:indent$}{:?}", "", self.role)?;
+    if let Some(label) = &self.label {
+      write!(f, " {:?}", &**label)?;
+    }
+    if let Some(value) = &self.value {
+      write!(f, " value={:?}", &**value)?;
+    }
+    if let Some(checked) = self.checked {
+      let checked = match checked {
+        Toggled::False => "false",
+        Toggled::True => "true",
+        Toggled::Mixed => "mixed",
+      };
+      write!(f, " checked={checked}")?;
+    }
+    if let Some(expanded) = self.expanded {
+      write!(f, " expanded={expanded}")?;
+    }
+    if self.selected == Some(true) {
+      write!(f, " selected")?;
+    }
+    if self.disabled {
+      write!(f, " disabled")?;
+    }
+    if self.focusable {
+      write!(f, " focusable")?;
+    }
+    if self.focused {
+      write!(f, " focused")?;
+    }
+    if !self.actions.is_empty() {
+      write!(f, " actions={:?}", &self.actions[..])?;
+    }
+    for c in self.children.iter() {
+      writeln!(f)?;
+      c.fmt_with_indent(f, indent + 2)?;
+    }
+    Ok(())
+  }
+}
+
+/// The tree prints one node a line, indented by its depth. The rect of the
+/// nodes is omitted to keep the snapshot stable, for example:
+///
+/// ```text
+/// Window focused
+///   Button "Ok" focusable actions=[Click, Focus]
+///     StaticText "Ok"
+/// ```
+impl Display for AccessibilityTree {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { self.root.fmt_with_indent(f, 0) }
+}
+
+impl Display for AccessNode {
+  fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult { self.fmt_with_indent(f, 0) }
+}
+
+impl Role {
+  /// Whether the role uses the text of its descendants as its label, if it
+  /// has no label.
+  fn name_from_content(self) -> bool {
+    matches!(
+      self,
+      Role::Button
+        | Role::Checkbox
+        | Role::Radio
+        | Role::Switch
+        | Role::Link
+        | Role::Tab
+        | Role::ListItem
+        | Role::ListBoxOption
+        | Role::MenuItem
+    )
+  }
+}
+
+fn content_text(children: &[AccessNode]) -> String {
+  children
+    .iter()
+    .flat_map(|c| c.iter())
+    .filter(|n| n.role == Role::StaticText)
+    .filter_map(|n| n.label.as_deref())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+struct Collector<'a> {
+  tree: &'a WidgetTree,
+  focusing: Option<WidgetId>,
+  /// The focused widget is found, but not claimed by a node yet.
+  focused: bool,
+  /// A focusable widget is found, but not claimed by a node yet.
+  focusable: bool,
+}
+
+impl Collector<'_> {
+  fn collect(&mut self, id: WidgetId, out: &mut Vec<AccessNode>) {
+    let tree = self.tree;
+    let Some(size) = tree.store.layout_box_size(id) else {
+      // The widget is not laid out, it's invisible.
+      return;
+    };
+
+    let mut semantics = id.assert_get(tree).semantics();
+    for s in id.query_all_iter::<SemanticsWidget>(tree) {
+      semantics
+        .get_or_insert_with(Semantics::default)
+        .merge(&s.semantics);
+    }
+    if semantics
+      .as_ref()
+      .is_some_and(|s| s.hidden == Some(true))
+    {
+      return;
+    }
+
+    let (outer_focused, outer_focusable) = (self.focused, self.focusable);
+    self.focused = self.focusing == Some(id);
+    self.focusable = id
+      .query_all_iter::<MixBuiltin>(tree)
+      .any(|m| m.contain_flag(MixFlags::Focus));
+
+    let mut children = vec![];
+    id.children(tree)
+      .for_each(|c| self.collect(c, &mut children));
+
+    if let Some(semantics) = semantics {
+      let pos = tree.map_to_global(Point::zero(), id);
+      let mut node = AccessNode::new(id, semantics, Rect::new(pos, size), children);
+      node.focused = std::mem::take(&mut self.focused);
+      node.focusable = std::mem::take(&mut self.focusable);
+      if node.focusable && !node.actions.contains(&SemanticsAction::Focus) {
+        node.actions.push(SemanticsAction::Focus);
+      }
+      out.push(node);
+    } else {
+      out.extend(children);
+    }
+    self.focused |= outer_focused;
+    self.focusable |= outer_focusable;
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn lift_semantic_descendants() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(50., 20.),
+          semantics: Semantics::new(Role::Button),
+          @Text { text: "Ok" }
+        }
+        @MockBox {
+          size: Size::new(50., 20.),
+          @MockBox {
+            size: Size::new(10., 10.),
+            semantics: Semantics::new(Role::Image).with_label("logo"),
+          }
+        }
+        @MockBox {
+          size: Size::new(50., 20.),
+          semantics: Semantics::hidden(),
+          @Text { text: "decoration" }
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.accessibility_tree();
+    assert_eq!(
+      tree.to_string(),
+      "Window\n  Button \"Ok\"\n    StaticText \"Ok\"\n  Image \"logo\""
+    );
+    let logo = tree.find_by_label("logo").unwrap();
+    assert_eq!(logo.rect, Rect::new(Point::new(50., 0.), Size::new(10., 10.)));
+  }
+
+  #[test]
+  fn focus_and_states() {
+    reset_test_env!();
+
+    let (checked, w_checked) = split_value(false);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(50., 20.),
+        semantics: pipe!(
+          Semantics::new(Role::Checkbox)
+            .with_checked(if *$checked { Toggled::True } else { Toggled::False })
+            .with_label("Agree")
+            .with_action(SemanticsAction::Click)
+        ),
+        @MockBox {
+          size: Size::new(10., 10.),
+          auto_focus: true,
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.accessibility_tree();
+    assert_eq!(
+      tree.to_string(),
+      "Window\n  Checkbox \"Agree\" checked=false focusable focused actions=[Click, Focus]"
+    );
+    assert_eq!(tree.focus, tree.find_by_label("Agree").map(|n| n.id));
+
+    *w_checked.write() = true;
+    wnd.draw_frame();
+    let tree = wnd.accessibility_tree();
+    assert_eq!(tree.find_by_label("Agree").unwrap().checked, Some(Toggled::True));
+  }
+
+  #[test]
+  fn update_shell_only_when_changed() {
+    reset_test_env!();
+
+    let (checked, w_checked) = split_value(false);
+    let (color, w_color) = split_value(Color::RED);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(50., 20.),
+        background: pipe!(*$color),
+        semantics: pipe!(
+          Semantics::new(Role::Checkbox)
+            .with_checked(if *$checked { Toggled::True } else { Toggled::False })
+        ),
+      }
+    });
+    let updates = |wnd: &TestWindow| {
+      let mut shell = wnd.shell_wnd().borrow_mut();
+      let shell = shell
+        .as_any_mut()
+        .downcast_mut::<TestShellWindow>()
+        .unwrap();
+      *shell.accessibility_updates.get_or_insert(0)
+    };
+    updates(&wnd);
+    wnd.draw_frame();
+    assert_eq!(updates(&wnd), 1);
+
+    // Only the pixels change.
+    *w_color.write() = Color::BLUE;
+    wnd.draw_frame();
+    assert_eq!(updates(&wnd), 1);
+
+    *w_checked.write() = true;
+    wnd.draw_frame();
+    assert_eq!(updates(&wnd), 2);
+  }
+
+  #[test]
+  fn perform_click_action() {
+    reset_test_env!();
+
+    let (tapped, w_tapped) = split_value(0);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(50., 20.),
+        semantics: Semantics::new(Role::Button).with_label("Ok"),
+        on_tap: move |_| *$w_tapped.write() += 1,
+      }
+    });
+    wnd.draw_frame();
+
+    let id = wnd
+      .accessibility_tree()
+      .find_by_label("Ok")
+      .unwrap()
+      .id;
+    wnd.perform_semantics_action(id, SemanticsAction::Click);
+    wnd.draw_frame();
+    assert_eq!(*tapped.read(), 1);
+  }
+}
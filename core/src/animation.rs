@@ -1,3 +1,10 @@
+use std::{convert::Infallible, future::Future};
+
+use futures::channel::oneshot;
+use rxrust::{ops::box_it::BoxOp, prelude::*};
+
+use crate::prelude::Duration;
+
 pub mod easing;
 mod progress;
 mod transition;
@@ -12,8 +19,12 @@ mod animate_state;
 pub use animate_state::*;
 mod stagger;
 pub use stagger::Stagger;
+mod timeline;
+pub use timeline::Timeline;
 mod keyframes;
 pub use keyframes::*;
+mod physics;
+pub use physics::*;
 
 ///  Trait to describe how to control the animation.
 pub trait Animation {
@@ -25,4 +36,70 @@ pub trait Animation {
   fn is_running(&self) -> bool;
   /// clone the animation.
   fn box_clone(&self) -> Box<dyn Animation>;
+
+  // The playback controls do nothing by default, so an animation that can't
+  // be controlled only needs to implement the methods above.
+  /// Pause the running animation at its current progress, do nothing if it's
+  /// not running.
+  fn pause(&self) {}
+  /// Resume the paused animation from the progress it paused at.
+  fn resume(&self) {}
+  /// Check if the animation is running but paused.
+  fn is_paused(&self) -> bool { false }
+  /// Jump to the `progress` of the animation, in the range of `0.` to `1.` of
+  /// its duration. An animation that's not running starts paused at the
+  /// progress, so you can scrub it.
+  fn seek(&self, _progress: f32) {}
+  /// Return the progress of the running animation, in the range of `0.` to
+  /// `1.` of its duration, or `0.` if it's not running.
+  fn progress(&self) -> f32 { 0. }
+  /// Play the animation backward from its current progress, or play it
+  /// forward again if it's already backward. An animation that's not running
+  /// starts backward from its end.
+  ///
+  /// The animation that finishes backward keeps its start value.
+  fn reverse(&self) {}
+  /// Set the speed of the playback, `1.` is the normal speed and `2.` is twice
+  /// as fast. The negative rate is treated as `0.`, use `reverse` to play
+  /// backward.
+  fn set_playback_rate(&self, _rate: f32) {}
+  /// Return the duration of the animation from start to finish at the normal
+  /// speed.
+  fn duration(&self) -> Duration { Duration::ZERO }
+  /// Return a stream that emits every time the animation ends, the value is
+  /// `true` if the animation finishes, or `false` if it's stopped before
+  /// finishing.
+  fn end_stream(&self) -> BoxOp<'static, bool, Infallible> { observable::empty().box_it() }
+
+  /// Call `f` every time the animation finishes, the stopped animation not
+  /// calls it.
+  fn on_finish(&self, mut f: impl FnMut() + 'static) -> BoxSubscription<'static>
+  where
+    Self: Sized,
+  {
+    let u = self
+      .end_stream()
+      .filter(|finished| *finished)
+      .subscribe(move |_| f());
+    BoxSubscription::new(u)
+  }
+
+  /// Return a future that resolves when the animation ends next time, the
+  /// output is `true` if the animation finishes, or `false` if it's stopped.
+  fn finished(&self) -> impl Future<Output = bool> + 'static
+  where
+    Self: Sized,
+  {
+    let (tx, rx) = oneshot::channel();
+    let mut tx = Some(tx);
+    self
+      .end_stream()
+      .take(1)
+      .subscribe(move |finished| {
+        if let Some(tx) = tx.take() {
+          let _ = tx.send(finished);
+        }
+      });
+    async move { rx.await.unwrap_or(false) }
+  }
 }
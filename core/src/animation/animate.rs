@@ -1,3 +1,7 @@
+use std::convert::Infallible;
+
+use rxrust::ops::box_it::BoxOp;
+
 use crate::{
   prelude::*,
   ticker::FrameMsg,
@@ -15,6 +19,12 @@ where
   pub from: S::Value,
   #[declare(skip)]
   running_info: Option<AnimateInfo<S::Value>>,
+  #[declare(skip, default = 1.)]
+  playback_rate: f32,
+  #[declare(skip)]
+  reversed: bool,
+  #[declare(skip)]
+  ends: Subject<'static, bool, Infallible>,
   #[declare(skip, default = BuildCtx::get().window().id())]
   window_id: WindowId,
 }
@@ -22,8 +32,15 @@ where
 pub(crate) struct AnimateInfo<V> {
   from: V,
   to: V,
-  start_at: Instant,
+  /// The time of the last progress.
+  last_at: Instant,
+  /// The elapsed time of the animation, it goes back when the animation plays
+  /// backward.
+  elapsed: Duration,
+  /// The velocity of the rate that the animation starts with.
+  velocity: f32,
   last_progress: AnimateProgress,
+  paused: bool,
   // Determines if lerp value in current frame.
   already_lerp: bool,
   _tick_msg_guard: Option<Box<dyn Any>>,
@@ -38,7 +55,6 @@ where
   fn run(&self) {
     let mut animate_ref = self.write();
     let this = &mut *animate_ref;
-    let wnd_id = this.window_id;
     let Some(wnd) = AppCtx::get_window(this.window_id) else { return };
 
     if !wnd.flags().contains(WindowFlags::ANIMATIONS) {
@@ -46,91 +62,216 @@ where
     }
 
     let new_to = this.state.get();
-
-    if let Some(AnimateInfo { from, to, last_progress, start_at, .. }) = &mut this.running_info {
+    if let Some(info) = &mut this.running_info {
+      let AnimateInfo { from, to, last_progress, last_at, elapsed, velocity, paused, .. } = info;
       *from = this
         .state
         .calc_lerp_value(from, to, last_progress.value());
       *to = new_to;
+      // Continue with the velocity of the last progress, only the physics-based
+      // transitions carry it.
+      *velocity = this.transition.velocity_at(*elapsed, *velocity);
+      *elapsed = Duration::ZERO;
       *last_progress = AnimateProgress::Between(0.);
-      *start_at = Instant::now();
+      *last_at = AppCtx::now();
+      this.reversed = false;
+      if *paused {
+        *paused = false;
+        wnd.inc_running_animate();
+      }
     } else {
       drop(animate_ref);
+      start_animate(self, Duration::ZERO, false, false);
+    }
+  }
 
-      let animate = self.clone_writer();
-      let this = &mut *self.write();
-      let tick_handle = wnd
-        .frame_ticker
-        .clone()
-        .subscribe(move |msg| {
-          match msg {
-            FrameMsg::BeforeLayout(time) => {
-              animate.shallow().advance_to(time);
-            }
-            FrameMsg::Finish(_) => {
-              let mut w_ref = animate.write();
-              let info = w_ref.running_info.as_mut().unwrap();
-              let last_progress = info.last_progress;
-              let to = info.to.clone();
-              info.already_lerp = false;
-              w_ref.state.revert_value(to);
-              // Forgets modifies because we only modifies the inner info.
-              w_ref.forget_modifies();
-
-              if matches!(last_progress, AnimateProgress::Finish) {
-                drop(w_ref);
-                let wnd = AppCtx::get_window(wnd_id).unwrap();
-                let animate = animate.clone_writer();
-                wnd
-                  .frame_spawn(async move { animate.stop() })
-                  .unwrap();
-              }
-            }
-            _ => {}
-          }
-        })
-        .unsubscribe_when_dropped();
+  fn is_running(&self) -> bool { self.read().is_running() }
 
-      let animate = self.clone_writer();
-      let state_handle = this
-        .state
-        .animate_state_modifies()
-        .subscribe(move |_| {
-          let mut animate = animate.write();
-          let v = animate.state.get();
-          // if the animate state modified, we need to update the restore value.
-          if let Some(info) = animate.running_info.as_mut() {
-            info.to = v;
-          }
-          animate.forget_modifies();
-        })
-        .unsubscribe_when_dropped();
-
-      this.running_info = Some(AnimateInfo {
-        from: this.from.clone(),
-        to: new_to,
-        start_at: Instant::now(),
-        last_progress: AnimateProgress::Dismissed,
-        _tick_msg_guard: Some(Box::new((tick_handle, state_handle))),
-        already_lerp: false,
-      });
-      wnd.inc_running_animate();
+  fn stop(&self) { end_animate(self, false); }
+
+  fn box_clone(&self) -> Box<dyn Animation> { Box::new(self.clone_writer()) }
+
+  fn pause(&self) {
+    let mut this = self.write();
+    let wnd_id = this.window_id;
+    match this.running_info.as_mut() {
+      Some(info) if !info.paused => {
+        info.paused = true;
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.dec_running_animate();
+        }
+      }
+      _ => {
+        this.forget_modifies();
+      }
     }
   }
 
-  fn is_running(&self) -> bool { self.read().is_running() }
+  fn resume(&self) {
+    let mut this = self.write();
+    let wnd_id = this.window_id;
+    match this.running_info.as_mut() {
+      Some(info) if info.paused => {
+        info.paused = false;
+        info.last_at = AppCtx::now();
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.inc_running_animate();
+        }
+      }
+      _ => {
+        this.forget_modifies();
+      }
+    }
+  }
+
+  fn is_paused(&self) -> bool { self.read().is_paused() }
+
+  fn seek(&self, progress: f32) {
+    if !self.is_running() {
+      start_animate(self, Duration::ZERO, true, false);
+    }
+    let mut this = self.shallow();
+    let elapsed = this
+      .transition
+      .duration()
+      .mul_f32(progress.clamp(0., 1.));
+    let Some(info) = this.running_info.as_mut() else {
+      this.forget_modifies();
+      return;
+    };
+    let now = AppCtx::now();
+    info.elapsed = elapsed;
+    info.last_at = now;
+    info.already_lerp = false;
+    this.advance_to(now);
+  }
+
+  fn progress(&self) -> f32 { self.read().progress() }
+
+  fn reverse(&self) {
+    if self.is_running() {
+      let mut this = self.write();
+      this.reversed = !this.reversed;
+    } else {
+      let duration = self.read().transition.duration();
+      start_animate(self, duration, false, true);
+    }
+  }
+
+  fn set_playback_rate(&self, rate: f32) { self.write().playback_rate = rate.max(0.); }
+
+  fn duration(&self) -> Duration { self.read().transition.duration() }
+
+  fn end_stream(&self) -> BoxOp<'static, bool, Infallible> { self.read().ends.clone().box_it() }
+}
+
+/// Start the animation at the `elapsed` time, and tick it in every frame.
+fn start_animate<S>(
+  animate: &impl StateWriter<Value = Animate<S>>, elapsed: Duration, paused: bool, reversed: bool,
+) where
+  S: AnimateState + 'static,
+  S::Value: Clone,
+{
+  let this = &mut *animate.write();
+  let wnd_id = this.window_id;
+  let Some(wnd) = AppCtx::get_window(wnd_id) else { return };
+  if !wnd.flags().contains(WindowFlags::ANIMATIONS) {
+    return;
+  }
+
+  this.reversed = reversed;
+  let c_animate = animate.clone_writer();
+  let tick_handle = wnd
+    .frame_ticker
+    .clone()
+    .subscribe(move |msg| {
+      let animate = &c_animate;
+      match msg {
+        FrameMsg::BeforeLayout(time) => {
+          animate.shallow().advance_to(time);
+        }
+        FrameMsg::Finish(_) => {
+          let mut w_ref = animate.write();
+          let finished = w_ref.is_finished();
+          let reversed = w_ref.reversed;
+          let info = w_ref.running_info.as_mut().unwrap();
+          info.already_lerp = false;
+          // The animation finished backward keeps its start value.
+          let value = if finished && reversed { info.from.clone() } else { info.to.clone() };
+          w_ref.state.revert_value(value);
+          // Forgets modifies because we only modifies the inner info.
+          w_ref.forget_modifies();
+
+          if finished {
+            drop(w_ref);
+            let wnd = AppCtx::get_window(wnd_id).unwrap();
+            let animate = animate.clone_writer();
+            wnd
+              .frame_spawn(async move {
+                // The animation may be restarted before the task runs.
+                if animate.read().is_finished() {
+                  end_animate(&animate, true);
+                }
+              })
+              .unwrap();
+          }
+        }
+        _ => {}
+      }
+    })
+    .unsubscribe_when_dropped();
+
+  let c_animate = animate.clone_writer();
+  let state_handle = this
+    .state
+    .animate_state_modifies()
+    .subscribe(move |_| {
+      let mut animate = c_animate.write();
+      let v = animate.state.get();
+      // if the animate state modified, we need to update the restore value.
+      if let Some(info) = animate.running_info.as_mut() {
+        info.to = v;
+      }
+      animate.forget_modifies();
+    })
+    .unsubscribe_when_dropped();
 
-  fn stop(&self) {
-    let mut this = self.silent();
-    if this.is_running() {
+  this.running_info = Some(AnimateInfo {
+    from: this.from.clone(),
+    to: this.state.get(),
+    last_at: AppCtx::now(),
+    elapsed,
+    velocity: 0.,
+    last_progress: AnimateProgress::Dismissed,
+    paused,
+    _tick_msg_guard: Some(Box::new((tick_handle, state_handle))),
+    already_lerp: false,
+  });
+  if !paused {
+    wnd.inc_running_animate();
+  }
+}
+
+/// End the running animation, and notify it's finished or stopped.
+fn end_animate<S>(animate: &impl StateWriter<Value = Animate<S>>, finished: bool)
+where
+  S: AnimateState + 'static,
+{
+  let mut ends = {
+    let mut this = animate.silent();
+    let Some(info) = this.running_info.take() else { return };
+    if !info.paused {
       if let Some(wnd) = AppCtx::get_window(this.window_id) {
         wnd.dec_running_animate();
-        this.running_info.take();
       }
     }
-  }
-
-  fn box_clone(&self) -> Box<dyn Animation> { Box::new(self.clone_writer()) }
+    // The stopped animation may be in the middle of a frame, restore its value.
+    if !finished {
+      this.state.set(info.to);
+    }
+    this.ends.clone()
+  };
+  ends.next(finished);
 }
 
 impl<S> Animate<S>
@@ -139,15 +280,59 @@ where
 {
   pub fn is_running(&self) -> bool { self.running_info.is_some() }
 
+  /// Check if the animation is running but paused.
+  pub fn is_paused(&self) -> bool {
+    self
+      .running_info
+      .as_ref()
+      .is_some_and(|info| info.paused)
+  }
+
+  /// Check if the running animation reaches its end in the direction it
+  /// plays.
+  fn is_finished(&self) -> bool {
+    let Some(info) = self.running_info.as_ref() else { return false };
+    let ended = if self.reversed { info.elapsed.is_zero() } else { info.last_progress.is_finish() };
+    !info.paused && ended
+  }
+
+  /// Check if the animation plays backward.
+  pub fn is_reversed(&self) -> bool { self.reversed }
+
+  /// Return the progress of the running animation in its duration.
+  pub fn progress(&self) -> f32 {
+    let Some(info) = self.running_info.as_ref() else { return 0. };
+    let duration = self.transition.duration();
+    if duration.is_zero() {
+      info.last_progress.value().clamp(0., 1.)
+    } else {
+      (info.elapsed.as_secs_f32() / duration.as_secs_f32()).clamp(0., 1.)
+    }
+  }
+
+  /// The playback rate of the animation.
+  pub fn playback_rate(&self) -> f32 { self.playback_rate }
+
   /// Advance the animation to the given time, you must start the animation
-  /// before calling this method, the `at` relative to the start time.
+  /// before calling this method. The elapsed time of the animation goes by
+  /// the playback rate, and stays if it's paused.
   ///
   /// ## Panics
   ///
   /// Panics if the animation is not running.
   fn advance_to(&mut self, at: Instant) -> AnimateProgress {
-    let AnimateInfo { from, to, start_at, last_progress, already_lerp, .. } = self
-      .running_info
+    let Self { transition, state, running_info, playback_rate, reversed, .. } = self;
+    let AnimateInfo {
+      from,
+      to,
+      last_at,
+      elapsed,
+      velocity,
+      last_progress,
+      paused,
+      already_lerp,
+      ..
+    } = running_info
       .as_mut()
       .expect("This animation is not running.");
 
@@ -155,15 +340,22 @@ where
       return *last_progress;
     }
 
-    let elapsed = at - *start_at;
-    let progress = self.transition.rate_of_change(elapsed);
+    if !*paused {
+      let delta = at
+        .saturating_duration_since(*last_at)
+        .mul_f32(*playback_rate);
+      *elapsed = if *reversed { elapsed.saturating_sub(delta) } else { *elapsed + delta };
+    }
+    *last_at = at;
+
+    let progress = transition.rate_of_change_with_velocity(*elapsed, *velocity);
 
     let v = match progress {
-      AnimateProgress::Between(rate) => self.state.calc_lerp_value(from, to, rate),
+      AnimateProgress::Between(rate) => state.calc_lerp_value(from, to, rate),
       AnimateProgress::Dismissed => from.clone(),
       AnimateProgress::Finish => to.clone(),
     };
-    self.state.set(v);
+    state.set(v);
 
     *last_progress = progress;
     *already_lerp = true;
@@ -177,7 +369,11 @@ where
   P: AnimateState + 'static,
 {
   fn drop(&mut self) {
-    if self.running_info.is_some() {
+    if self
+      .running_info
+      .as_ref()
+      .is_some_and(|info| !info.paused)
+    {
       if let Some(wnd) = AppCtx::get_window(self.window_id) {
         wnd.dec_running_animate();
       }
@@ -188,7 +384,7 @@ where
 #[cfg(test)]
 mod tests {
   use super::*;
-  use crate::{reset_test_env, test_helper::TestWindow};
+  use crate::{reset_test_env, test_helper::*};
 
   #[test]
   fn fix_animate_circular_mut_borrow() {
@@ -235,4 +431,194 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*c_state.read(), 1);
   }
+
+  #[test]
+  fn retarget_carries_velocity() {
+    reset_test_env!();
+
+    let state = Stateful::new(0f32);
+    let holder = Stateful::new(None);
+    let (c_state, c_holder) = (state.clone_writer(), holder.clone_writer());
+    let wnd = TestWindow::new(fn_widget! {
+      let animate = @Animate {
+        transition: SpringTransition::default().box_it(),
+        state: c_state.clone_writer(),
+        from: 0.,
+      };
+      *$c_holder.write() = Some(animate);
+      @Void {}
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    let animate = holder.write().take().unwrap();
+
+    let advance = |dur: Duration| {
+      let mut w = animate.write();
+      let info = w.running_info.as_mut().unwrap();
+      let at = info.last_at + dur;
+      info.already_lerp = false;
+      w.advance_to(at);
+      drop(w);
+      *state.read()
+    };
+
+    *state.write() = 100.;
+    animate.run();
+    let frame = Duration::from_millis(16);
+    let before = advance(frame * 10);
+    let from = advance(frame);
+    assert!(before < from && from < 100.);
+
+    // Retarget the running animation.
+    *state.write() = 200.;
+    animate.run();
+    assert!(
+      animate
+        .read()
+        .running_info
+        .as_ref()
+        .unwrap()
+        .velocity
+        > 0.
+    );
+
+    // It keeps moving forward rather than restarting from still.
+    let still = from.lerp(
+      &200.,
+      SpringTransition::default()
+        .rate_of_change(frame)
+        .value(),
+    );
+    let moved = advance(frame);
+    assert!(moved > still);
+  }
+
+  fn linear_animate(
+    duration: Duration, state: &Stateful<f32>, from: f32,
+  ) -> (TestWindow, State<Animate<Stateful<f32>>>) {
+    let holder = Stateful::new(None);
+    let (c_state, c_holder) = (state.clone_writer(), holder.clone_writer());
+    let wnd = TestWindow::new(fn_widget! {
+      let animate = @Animate {
+        transition: EasingTransition { easing: easing::LINEAR, duration }.box_it(),
+        state: c_state.clone_writer(),
+        from: from,
+      };
+      *$c_holder.write() = Some(animate);
+      @Void {}
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    let animate = holder.write().take().unwrap();
+    (wnd, animate)
+  }
+
+  #[test]
+  fn playback_controls() {
+    reset_test_env!();
+
+    let state = Stateful::new(100f32);
+    let (_wnd, animate) = linear_animate(Duration::from_millis(100), &state, 0.);
+
+    let advance = |millis: u64| {
+      let mut w = animate.write();
+      let info = w.running_info.as_mut().unwrap();
+      let at = info.last_at + Duration::from_millis(millis);
+      info.already_lerp = false;
+      w.advance_to(at);
+      drop(w);
+      *state.read()
+    };
+    let approx = |a: f32, b: f32| (a - b).abs() < 0.01;
+
+    // Seek starts the animation paused.
+    animate.seek(0.5);
+    assert!(animate.is_running() && animate.is_paused());
+    assert!(approx(*state.read(), 50.));
+    assert!(approx(advance(20), 50.));
+
+    animate.resume();
+    assert!(!animate.is_paused());
+    assert!(approx(advance(20), 70.));
+
+    animate.set_playback_rate(2.);
+    assert!(approx(advance(5), 80.));
+
+    animate.reverse();
+    assert!(approx(advance(10), 60.));
+    assert!(approx(animate.progress(), 0.6));
+
+    animate.pause();
+    assert!(approx(advance(10), 60.));
+  }
+
+  #[test]
+  fn notify_end() {
+    reset_test_env!();
+
+    let state = Stateful::new(100f32);
+    let (mut wnd, animate) = linear_animate(Duration::from_millis(100), &state, 0.);
+    let ends = Stateful::new(vec![]);
+    let c_ends = ends.clone_writer();
+    animate
+      .end_stream()
+      .subscribe(move |finished| c_ends.write().push(finished));
+    let finished = Stateful::new(0);
+    let c_finished = finished.clone_writer();
+    let _guard = animate.on_finish(move || *c_finished.write() += 1);
+
+    animate.run();
+    animate.stop();
+    assert_eq!(*ends.read(), [false]);
+
+    animate.run();
+    wnd.advance_time(Duration::from_millis(200));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(!animate.is_running());
+    assert_eq!(*ends.read(), [false, true]);
+    assert_eq!(*finished.read(), 1);
+    assert_eq!(*state.read(), 100.);
+
+    // The animation finishes backward keeps the start value.
+    animate.reverse();
+    wnd.advance_time(Duration::from_millis(200));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(*ends.read(), [false, true, true]);
+    assert_eq!(*finished.read(), 2);
+    assert_eq!(*state.read(), 0.);
+  }
+
+  #[test]
+  fn frames_by_clock() {
+    reset_test_env!();
+
+    let holder = Stateful::new(None);
+    let c_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let mock_box = @MockBox { size: Size::new(100., 100.) };
+      let animate = @Animate {
+        transition: EasingTransition {
+          easing: easing::LINEAR,
+          duration: Duration::from_millis(100),
+        }.box_it(),
+        state: part_writer!(&mut mock_box.size),
+        from: Size::zero(),
+      };
+      *$c_holder.write() = Some(animate);
+      mock_box
+    };
+    let mut wnd = TestWindow::new(w);
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    let animate = holder.write().take().unwrap();
+    animate.run();
+
+    for size in [25., 50., 75.] {
+      wnd.advance_time(Duration::from_millis(25));
+      wnd.draw_frame();
+      wnd.assert_root_size(Size::splat(size));
+    }
+    wnd.advance_time(Duration::from_millis(25));
+    wnd.draw_frame();
+    wnd.assert_root_size(Size::splat(100.));
+  }
 }
@@ -0,0 +1,293 @@
+//! Physics-based transitions, they move the rate of an animation by a
+//! simulated motion instead of a curve over a fixed duration.
+//!
+//! The motion starts with the velocity that the interrupted animation carries,
+//! so retargeting a running animation keeps it smooth, and it finishes once
+//! the motion is settled.
+//!
+//! The velocity is measured in the rate per second, so it's relative to the
+//! distance between the `from` and the `to` value of the animation.
+
+use crate::prelude::*;
+
+/// The longest time that a simulation runs, the motion is considered settled
+/// after it.
+const MAX_SIMULATE_SECS: f32 = 60.;
+/// The time step to estimate the duration of a simulation.
+const ESTIMATE_STEP_SECS: f32 = 1. / 120.;
+
+/// A transition driven by a damped spring that pulls the rate to the target.
+///
+/// The animation finishes when the distance to the target and the velocity
+/// both fall under the thresholds.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let bouncy = SpringTransition::default().with_damping(10.);
+/// assert!(
+///   bouncy
+///     .rate_of_change(Duration::from_millis(100))
+///     .is_between()
+/// );
+/// assert!(
+///   bouncy
+///     .rate_of_change(Duration::from_secs(10))
+///     .is_finish()
+/// );
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SpringTransition {
+  /// The force of the spring per the distance to the target.
+  pub stiffness: f32,
+  /// The force that resists the motion per the velocity, a spring with the
+  /// damping less than `2 * sqrt(stiffness * mass)` oscillates around the
+  /// target.
+  pub damping: f32,
+  /// The mass of the object that the spring moves.
+  pub mass: f32,
+  /// The distance to the target in the rate, under which the spring can be
+  /// settled.
+  pub rest_distance: f32,
+  /// The velocity in the rate per second, under which the spring can be
+  /// settled.
+  pub rest_velocity: f32,
+}
+
+/// A transition that moves the rate with the velocity decelerated by the
+/// exponential friction, and just comes to rest at the target.
+///
+/// The motion continues with the velocity carried from the interrupted
+/// animation, and decelerates faster or slower to stop at the target. If the
+/// carried velocity is less than `friction`, it starts with `friction`
+/// instead.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrictionTransition {
+  /// The exponential decay rate of the velocity per second when the animation
+  /// starts from still.
+  pub friction: f32,
+  /// The distance to the target in the rate, under which the motion is
+  /// settled.
+  pub rest_distance: f32,
+}
+
+/// The state of a motion along the rate.
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Motion {
+  pos: f32,
+  velocity: f32,
+}
+
+impl SpringTransition {
+  /// A spring with the stiffness and the damping.
+  pub fn new(stiffness: f32, damping: f32) -> Self { Self { stiffness, damping, ..<_>::default() } }
+
+  pub fn with_stiffness(mut self, stiffness: f32) -> Self {
+    self.stiffness = stiffness;
+    self
+  }
+
+  pub fn with_damping(mut self, damping: f32) -> Self {
+    self.damping = damping;
+    self
+  }
+
+  pub fn with_mass(mut self, mass: f32) -> Self {
+    self.mass = mass;
+    self
+  }
+
+  /// Set the thresholds of the distance and the velocity to settle the spring.
+  pub fn with_rest(mut self, distance: f32, velocity: f32) -> Self {
+    self.rest_distance = distance;
+    self.rest_velocity = velocity;
+    self
+  }
+
+  /// The motion of the spring at `t` seconds, it starts at the rate `0` with
+  /// the `velocity`.
+  fn motion(&self, t: f32, velocity: f32) -> Motion {
+    let Self { stiffness, damping, mass, .. } = *self;
+    // The displacement from the target.
+    let x0 = -1.;
+    let v0 = velocity;
+    let omega = (stiffness / mass).sqrt();
+    let zeta = damping / (2. * (stiffness * mass).sqrt());
+
+    let (x, v) = if zeta < 1. {
+      let decay = zeta * omega;
+      let omega_d = omega * (1. - zeta * zeta).sqrt();
+      let b = (v0 + decay * x0) / omega_d;
+      let (sin, cos) = (omega_d * t).sin_cos();
+      let envelope = (-decay * t).exp();
+      let x = envelope * (x0 * cos + b * sin);
+      let v = envelope * (v0 * cos - (decay * b + x0 * omega_d) * sin);
+      (x, v)
+    } else if zeta == 1. {
+      let b = v0 + omega * x0;
+      let envelope = (-omega * t).exp();
+      (envelope * (x0 + b * t), envelope * (v0 - omega * b * t))
+    } else {
+      let root = omega * (zeta * zeta - 1.).sqrt();
+      let (r1, r2) = (-zeta * omega + root, -zeta * omega - root);
+      let c2 = (v0 - r1 * x0) / (r2 - r1);
+      let c1 = x0 - c2;
+      let (e1, e2) = ((r1 * t).exp(), (r2 * t).exp());
+      (c1 * e1 + c2 * e2, c1 * r1 * e1 + c2 * r2 * e2)
+    };
+    Motion { pos: 1. + x, velocity: v }
+  }
+
+  fn is_settled(&self, motion: &Motion) -> bool {
+    (1. - motion.pos).abs() < self.rest_distance && motion.velocity.abs() < self.rest_velocity
+  }
+}
+
+impl Default for SpringTransition {
+  fn default() -> Self {
+    Self { stiffness: 170., damping: 26., mass: 1., rest_distance: 0.001, rest_velocity: 0.01 }
+  }
+}
+
+impl Transition for SpringTransition {
+  fn rate_of_change(&self, dur: Duration) -> AnimateProgress {
+    self.rate_of_change_with_velocity(dur, 0.)
+  }
+
+  /// Return the time for the spring to settle from still.
+  fn duration(&self) -> Duration {
+    settle_duration(|t| {
+      let motion = self.motion(t, 0.);
+      self.is_settled(&motion)
+    })
+  }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    let t = dur.as_secs_f32();
+    let motion = self.motion(t, velocity);
+    if t >= MAX_SIMULATE_SECS || self.is_settled(&motion) {
+      AnimateProgress::Finish
+    } else {
+      AnimateProgress::Between(motion.pos)
+    }
+  }
+
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 {
+    self.motion(dur.as_secs_f32(), velocity).velocity
+  }
+}
+
+impl FrictionTransition {
+  pub fn new(friction: f32) -> Self { Self { friction, ..<_>::default() } }
+
+  /// The decay rate of the motion starts with the `velocity`, so it travels
+  /// just to the target.
+  fn drag(&self, velocity: f32) -> f32 { velocity.max(self.friction) }
+
+  /// The motion at `t` seconds, it starts at the rate `0` with the `velocity`.
+  fn motion(&self, t: f32, velocity: f32) -> Motion {
+    let drag = self.drag(velocity);
+    let decay = (-drag * t).exp();
+    Motion { pos: 1. - decay, velocity: drag * decay }
+  }
+}
+
+impl Default for FrictionTransition {
+  fn default() -> Self { Self { friction: 4., rest_distance: 0.001 } }
+}
+
+impl Transition for FrictionTransition {
+  fn rate_of_change(&self, dur: Duration) -> AnimateProgress {
+    self.rate_of_change_with_velocity(dur, 0.)
+  }
+
+  /// Return the time for the motion to settle from still.
+  fn duration(&self) -> Duration {
+    let secs = -self.rest_distance.ln() / self.drag(0.);
+    Duration::from_secs_f32(secs.clamp(0., MAX_SIMULATE_SECS))
+  }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    let motion = self.motion(dur.as_secs_f32(), velocity);
+    if 1. - motion.pos < self.rest_distance {
+      AnimateProgress::Finish
+    } else {
+      AnimateProgress::Between(motion.pos)
+    }
+  }
+
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 {
+    self.motion(dur.as_secs_f32(), velocity).velocity
+  }
+}
+
+/// Find the first time that the motion is settled by stepping the time.
+fn settle_duration(settled: impl Fn(f32) -> bool) -> Duration {
+  let mut t = 0.;
+  while t < MAX_SIMULATE_SECS && !settled(t) {
+    t += ESTIMATE_STEP_SECS;
+  }
+  Duration::from_secs_f32(t.min(MAX_SIMULATE_SECS))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn rate(transition: &impl Transition, secs: f32, velocity: f32) -> f32 {
+    transition
+      .rate_of_change_with_velocity(Duration::from_secs_f32(secs), velocity)
+      .value()
+  }
+
+  #[test]
+  fn spring_settles() {
+    let critical = SpringTransition::new(100., 20.);
+    let bouncy = SpringTransition::new(100., 5.);
+    let overdamped = SpringTransition::new(100., 40.);
+
+    for spring in [critical, bouncy, overdamped] {
+      assert!(rate(&spring, 0., 0.).abs() < 1e-5);
+      assert!((spring.velocity_at(Duration::ZERO, 2.) - 2.).abs() < 1e-5);
+      let dur = spring.duration();
+      assert!(dur > Duration::ZERO && dur.as_secs_f32() < MAX_SIMULATE_SECS);
+      assert!(spring.rate_of_change(dur).is_finish());
+    }
+
+    // Only the underdamped spring overshoots the target.
+    let max = |spring: &SpringTransition| {
+      (0..200)
+        .map(|i| rate(spring, i as f32 / 100., 0.))
+        .fold(0., f32::max)
+    };
+    assert!(max(&bouncy) > 1.1);
+    assert!(max(&critical) <= 1.);
+    assert!(max(&overdamped) <= 1.);
+
+    // The initial velocity pushes the motion ahead.
+    assert!(rate(&critical, 0.05, 10.) > rate(&critical, 0.05, 0.));
+  }
+
+  #[test]
+  fn friction_stops_at_target() {
+    let friction = FrictionTransition::new(4.);
+    assert_eq!(rate(&friction, 0., 0.), 0.);
+    assert!(rate(&friction, 0.5, 0.) < rate(&friction, 1., 0.));
+    assert!(
+      friction
+        .rate_of_change(friction.duration())
+        .is_finish()
+    );
+
+    // A fast motion keeps its velocity, and decelerates faster.
+    assert_eq!(friction.velocity_at(Duration::ZERO, 10.), 10.);
+    assert!(rate(&friction, 0.2, 10.) > rate(&friction, 0.2, 0.));
+    assert!(
+      friction
+        .rate_of_change_with_velocity(Duration::from_secs(1), 10.)
+        .is_finish()
+    );
+  }
+}
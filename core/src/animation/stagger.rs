@@ -40,20 +40,27 @@
 //! };
 //! ```
 
+use std::convert::Infallible;
+
 use ribir_algo::Sc;
 use ribir_macros::rdl;
+use rxrust::ops::box_it::BoxOp;
 
 use super::*;
 use crate::prelude::*;
 
 /// The controller of a stagger animation. It's allow you to transition states
 /// and run animation in a stagger way.
+///
+/// The animations are arranged on a [`Timeline`], so the stagger animation can
+/// be paused, sought and reversed as a whole.
 pub struct Stagger<T> {
   stagger: std::time::Duration,
   transition: Sc<T>,
-  running_handle: Option<TaskHandle<NormalReturn<()>>>,
-  next_to_run: Option<AnimationCursor>,
-  animations: Vec<(std::time::Duration, Box<dyn Animation>)>,
+  timeline: Option<Stateful<Timeline>>,
+  /// The offset of the last animation starts from the start of the stagger,
+  /// `None` if no animation is pushed.
+  last_start: Option<Duration>,
   run_times: usize,
 }
 
@@ -61,12 +68,14 @@ impl<T: Transition + 'static> Stagger<T> {
   /// **stagger**: the default duration between two adjacent animations start.
   /// **transition**: the transition for the states.
   pub fn new(stagger: Duration, transition: T) -> Stateful<Self> {
+    // The timeline runs in the window that is building, it's created by the
+    // first pushed animation if the stagger is not created when building.
+    let timeline = BuildCtx::try_get().map(|_| Timeline::new());
     Stateful::new(Self {
       stagger,
       transition: Sc::new(transition),
-      running_handle: None,
-      next_to_run: None,
-      animations: vec![],
+      timeline,
+      last_start: None,
       run_times: 0,
     })
   }
@@ -114,95 +123,87 @@ impl<T: Transition + 'static> Stagger<T> {
   pub fn push_animation_with(
     &mut self, stagger: Duration, animation: impl Animation + 'static,
   ) -> &mut Self {
+    let start = self.last_start.unwrap_or_default() + stagger;
+    self.last_start = Some(start);
     self
-      .animations
-      .push((stagger, Box::new(animation)));
+      .timeline
+      .get_or_insert_with(Timeline::new)
+      .write()
+      .add(start, animation);
     self
   }
 
   fn default_stagger(&self) -> Duration {
-    if self.animations.is_empty() { Duration::ZERO } else { self.stagger }
+    if self.last_start.is_none() { Duration::ZERO } else { self.stagger }
   }
 }
 
-#[derive(Clone)]
-struct AnimationCursor {
-  prev_at: Instant,
-  index: usize,
-}
-
 impl<T: Transition + 'static> Animation for Stateful<Stagger<T>> {
   fn run(&self) {
-    if self.is_running() {
-      self.stop()
-    }
-    {
+    let timeline = {
       let mut this = self.write();
-      this.next_to_run = Some(AnimationCursor { prev_at: Instant::now(), index: 0 });
       this.run_times += 1;
+      this.timeline.as_ref().map(|t| t.clone_writer())
+    };
+    if let Some(timeline) = timeline {
+      timeline.run();
     }
-
-    self.trigger_next();
   }
 
   fn is_running(&self) -> bool { self.read().is_running() }
 
-  fn stop(&self) {
-    if self.is_running() {
-      let mut this = self.write();
-      if let Some(h) = this.running_handle.take() {
-        h.unsubscribe();
-      }
-      this.next_to_run.take();
-
-      for (_, a) in this.animations.iter() {
-        a.stop();
-      }
-    }
-  }
+  fn stop(&self) { self.with_timeline(|t| t.stop()); }
 
   fn box_clone(&self) -> Box<dyn Animation> {
     let c = self.clone_writer();
     Box::new(c)
   }
+
+  fn pause(&self) { self.with_timeline(|t| t.pause()); }
+
+  fn resume(&self) { self.with_timeline(|t| t.resume()); }
+
+  fn is_paused(&self) -> bool { self.with_timeline(|t| t.is_paused()) }
+
+  fn seek(&self, progress: f32) { self.with_timeline(|t| t.seek(progress)); }
+
+  fn progress(&self) -> f32 { self.with_timeline(|t| t.progress()) }
+
+  fn reverse(&self) { self.with_timeline(|t| t.reverse()); }
+
+  fn set_playback_rate(&self, rate: f32) { self.with_timeline(|t| t.set_playback_rate(rate)); }
+
+  fn duration(&self) -> Duration { self.with_timeline(|t| t.duration()) }
+
+  fn end_stream(&self) -> BoxOp<'static, bool, Infallible> {
+    self
+      .read()
+      .timeline
+      .as_ref()
+      .map_or_else(|| observable::empty().box_it(), |t| t.end_stream())
+  }
 }
 
 impl<T: Transition + 'static> Stateful<Stagger<T>> {
-  fn trigger_next(&self) {
-    let mut this = self.write();
-    if let Some(step) = this.next_to_run.take() {
-      if let Some((delay, next)) = this.animations.get(step.index) {
-        let at = step.prev_at + *delay;
-        let next = next.box_clone();
-        this.next_to_run = Some(AnimationCursor { prev_at: at, index: step.index + 1 });
-
-        // the status not changed(running -> running), so we can ignore the
-        // modification.
-        this.forget_modifies();
-        drop(this);
-
-        let this = self.clone_writer();
-        let h = observable::timer_at((), at, AppCtx::scheduler()).subscribe(move |_| {
-          next.run();
-          this.trigger_next();
-        });
-        self.write().running_handle = Some(h);
-      } else {
-        this.running_handle = None;
-      }
-    }
+  /// Call `f` with the timeline of the stagger animation, or return the
+  /// default value if no animation is pushed.
+  fn with_timeline<R: Default>(&self, f: impl FnOnce(&Stateful<Timeline>) -> R) -> R {
+    let timeline = self
+      .read()
+      .timeline
+      .as_ref()
+      .map(|t| t.clone_writer());
+    timeline.as_ref().map(f).unwrap_or_default()
   }
 }
 
 impl<T> Stagger<T> {
   /// Check if the stagger animation is running.
   pub fn is_running(&self) -> bool {
-    self.running_handle.is_some()
-      || self.next_to_run.is_some()
-      || self
-        .animations
-        .iter()
-        .any(|(_, a)| a.is_running())
+    self
+      .timeline
+      .as_ref()
+      .is_some_and(|t| t.is_running())
   }
 
   /// How many times the stagger animation has run.
@@ -217,7 +218,7 @@ mod tests {
   use ribir_dev_helper::*;
 
   use super::*;
-  use crate::{reset_test_env, test_helper::*};
+  use crate::{reset_test_env, test_helper::*, window::WindowFlags};
 
   widget_layout_test!(
     stagger_run_and_stop,
@@ -274,4 +275,40 @@ mod tests {
     wnd.draw_frame();
     assert!(!c_stagger.is_running());
   }
+
+  #[test]
+  fn push_animation_after_building() {
+    reset_test_env!();
+
+    let state = Stateful::new(0f32);
+    let holder = Stateful::new(None);
+    let (c_state, c_holder) = (state.clone_writer(), holder.clone_writer());
+    let mut wnd = TestWindow::new(fn_widget! {
+      let transition = EasingTransition {
+        duration: Duration::from_millis(100),
+        easing: easing::LINEAR,
+      };
+      let stagger = Stagger::new(Duration::from_millis(100), transition.clone().box_it());
+      let animate = @Animate {
+        transition: transition.box_it(),
+        state: c_state.clone_writer(),
+        from: 100.,
+      };
+      *c_holder.write() = Some((stagger, animate));
+      @Void {}
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    wnd.draw_frame();
+
+    let (stagger, animate) = holder.write().take().unwrap();
+    stagger.write().push_animation(animate);
+    stagger.run();
+    assert!(stagger.is_running());
+
+    wnd.advance_time(Duration::from_millis(200));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(!stagger.is_running());
+    assert_eq!(*state.read(), 0.);
+  }
 }
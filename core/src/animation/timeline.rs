@@ -0,0 +1,457 @@
+//! A timeline composes animations on a shared time axis, every animation
+//! starts at an offset from the start of the timeline. So the animations can
+//! run in sequence, in parallel or overlapped, and be controlled as one unit.
+//!
+//! The animations in a timeline are driven by the timeline, you should control
+//! them by the timeline rather than themselves, and pausing, seeking or
+//! reversing the timeline applies to all of them.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ribir::prelude::*;
+//!
+//! let _ = fn_widget! {
+//!   let mut first = @Text { text: "first" };
+//!   let mut second = @Text { text: "second" };
+//!   let first_fade_in = @Animate {
+//!     state: part_writer!(&mut first.opacity),
+//!     from: 0.,
+//!   };
+//!   let second_fade_in = @Animate {
+//!     state: part_writer!(&mut second.opacity),
+//!     from: 0.,
+//!   };
+//!
+//!   let timeline = Timeline::new();
+//!   // The second text fades in after the first one.
+//!   timeline.write().then(first_fade_in).then(second_fade_in);
+//!   let c_timeline = timeline.clone_writer();
+//!
+//!   @Column {
+//!     on_mounted: move |_| timeline.run(),
+//!     // Jump to the middle, the first text is faded in.
+//!     on_tap: move |_| c_timeline.seek(0.5),
+//!     @{ [first, second] }
+//!   }
+//! };
+//! ```
+
+use std::convert::Infallible;
+
+use rxrust::ops::box_it::BoxOp;
+
+use crate::{
+  prelude::*,
+  ticker::FrameMsg,
+  window::{WindowFlags, WindowId},
+};
+
+/// The controller of animations that arranged on a shared time axis.
+pub struct Timeline {
+  entries: Vec<(Duration, Box<dyn Animation>)>,
+  running_info: Option<TimelineInfo>,
+  playback_rate: f32,
+  reversed: bool,
+  ends: Subject<'static, bool, Infallible>,
+  window_id: WindowId,
+}
+
+struct TimelineInfo {
+  /// The time of the last progress.
+  last_at: Instant,
+  /// The elapsed time of the timeline, it goes back when the timeline plays
+  /// backward.
+  elapsed: Duration,
+  paused: bool,
+  _tick_msg_guard: Option<Box<dyn Any>>,
+}
+
+impl Timeline {
+  /// Create an empty timeline in the window that is building.
+  pub fn new() -> Stateful<Self> {
+    Stateful::new(Self {
+      entries: vec![],
+      running_info: None,
+      playback_rate: 1.,
+      reversed: false,
+      ends: Subject::default(),
+      window_id: BuildCtx::get().window().id(),
+    })
+  }
+
+  /// Add an animation that starts at the `offset` from the start of the
+  /// timeline.
+  pub fn add(&mut self, offset: Duration, animation: impl Animation + 'static) -> &mut Self {
+    self.entries.push((offset, Box::new(animation)));
+    self
+  }
+
+  /// Add an animation that starts after all the added animations end.
+  pub fn then(&mut self, animation: impl Animation + 'static) -> &mut Self {
+    self.add(self.total_duration(), animation)
+  }
+
+  /// Add an animation that starts at the same time as the last added one.
+  pub fn with(&mut self, animation: impl Animation + 'static) -> &mut Self {
+    let offset = self
+      .entries
+      .last()
+      .map_or(Duration::ZERO, |(offset, _)| *offset);
+    self.add(offset, animation)
+  }
+
+  /// Check if the timeline is running.
+  pub fn is_running(&self) -> bool { self.running_info.is_some() }
+
+  /// Check if the timeline is running but paused.
+  pub fn is_paused(&self) -> bool {
+    self
+      .running_info
+      .as_ref()
+      .is_some_and(|info| info.paused)
+  }
+
+  /// Check if the timeline plays backward.
+  pub fn is_reversed(&self) -> bool { self.reversed }
+
+  /// The playback rate of the timeline.
+  pub fn playback_rate(&self) -> f32 { self.playback_rate }
+
+  /// Return the progress of the running timeline in its duration.
+  pub fn progress(&self) -> f32 {
+    let Some(info) = self.running_info.as_ref() else { return 0. };
+    let duration = self.total_duration();
+    if duration.is_zero() {
+      1.
+    } else {
+      (info.elapsed.as_secs_f32() / duration.as_secs_f32()).clamp(0., 1.)
+    }
+  }
+
+  /// The time from the start of the timeline to the end of its last
+  /// animation.
+  pub fn total_duration(&self) -> Duration {
+    self
+      .entries
+      .iter()
+      .map(|(offset, a)| *offset + a.duration())
+      .max()
+      .unwrap_or_default()
+  }
+
+  fn advance_to(&mut self, at: Instant) {
+    let duration = self.total_duration();
+    let Self { running_info, playback_rate, reversed, .. } = self;
+    let Some(info) = running_info.as_mut() else { return };
+    if !info.paused {
+      let delta = at
+        .saturating_duration_since(info.last_at)
+        .mul_f32(*playback_rate);
+      info.elapsed = if *reversed {
+        info.elapsed.saturating_sub(delta)
+      } else {
+        (info.elapsed + delta).min(duration)
+      };
+    }
+    info.last_at = at;
+    self.seek_animations();
+  }
+
+  /// Seek every animation to the progress of its own at the elapsed time.
+  fn seek_animations(&self) {
+    let Some(info) = self.running_info.as_ref() else { return };
+    for (offset, a) in self.entries.iter() {
+      let duration = a.duration();
+      let progress = if info.elapsed < *offset {
+        0.
+      } else if duration.is_zero() {
+        1.
+      } else {
+        (info.elapsed - *offset).as_secs_f32() / duration.as_secs_f32()
+      };
+      a.seek(progress.min(1.));
+    }
+  }
+
+  fn is_ended(&self) -> bool {
+    let Some(info) = self.running_info.as_ref() else { return false };
+    let ended =
+      if self.reversed { info.elapsed.is_zero() } else { info.elapsed >= self.total_duration() };
+    !info.paused && ended
+  }
+}
+
+impl Animation for Stateful<Timeline> {
+  /// Run the timeline from its start, the running timeline restarts.
+  fn run(&self) {
+    if self.is_running() {
+      self.stop();
+    }
+    start_timeline(self, Duration::ZERO, false, false);
+  }
+
+  fn stop(&self) { end_timeline(self, false); }
+
+  fn is_running(&self) -> bool { self.read().is_running() }
+
+  fn box_clone(&self) -> Box<dyn Animation> { Box::new(self.clone_writer()) }
+
+  fn pause(&self) {
+    let mut this = self.write();
+    let wnd_id = this.window_id;
+    match this.running_info.as_mut() {
+      Some(info) if !info.paused => {
+        info.paused = true;
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.dec_running_animate();
+        }
+      }
+      _ => {
+        this.forget_modifies();
+      }
+    }
+  }
+
+  fn resume(&self) {
+    let mut this = self.write();
+    let wnd_id = this.window_id;
+    match this.running_info.as_mut() {
+      Some(info) if info.paused => {
+        info.paused = false;
+        info.last_at = AppCtx::now();
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.inc_running_animate();
+        }
+      }
+      _ => {
+        this.forget_modifies();
+      }
+    }
+  }
+
+  fn is_paused(&self) -> bool { self.read().is_paused() }
+
+  fn seek(&self, progress: f32) {
+    if !self.is_running() {
+      start_timeline(self, Duration::ZERO, true, false);
+    }
+    let mut this = self.silent();
+    let elapsed = this
+      .total_duration()
+      .mul_f32(progress.clamp(0., 1.));
+    let Some(info) = this.running_info.as_mut() else { return };
+    info.elapsed = elapsed;
+    info.last_at = AppCtx::now();
+    this.seek_animations();
+  }
+
+  fn progress(&self) -> f32 { self.read().progress() }
+
+  fn reverse(&self) {
+    if self.is_running() {
+      let mut this = self.write();
+      this.reversed = !this.reversed;
+      // The animations finish in the same direction as the timeline.
+      for (_, a) in this.entries.iter() {
+        a.reverse();
+      }
+    } else {
+      let duration = self.read().total_duration();
+      start_timeline(self, duration, false, true);
+    }
+  }
+
+  fn set_playback_rate(&self, rate: f32) { self.write().playback_rate = rate.max(0.); }
+
+  fn duration(&self) -> Duration { self.read().total_duration() }
+
+  fn end_stream(&self) -> BoxOp<'static, bool, Infallible> { self.read().ends.clone().box_it() }
+}
+
+/// Start the timeline at the `elapsed` time, and take over its animations.
+fn start_timeline(timeline: &Stateful<Timeline>, elapsed: Duration, paused: bool, reversed: bool) {
+  {
+    let this = &mut *timeline.write();
+    let wnd_id = this.window_id;
+    let Some(wnd) = AppCtx::get_window(wnd_id) else { return };
+    if !wnd.flags().contains(WindowFlags::ANIMATIONS) {
+      return;
+    }
+
+    let c_timeline = timeline.clone_writer();
+    let tick_handle = wnd
+      .frame_ticker
+      .clone()
+      .subscribe(move |msg| match msg {
+        FrameMsg::BeforeLayout(time) => c_timeline.silent().advance_to(time),
+        FrameMsg::Finish(_) if c_timeline.read().is_ended() => {
+          let wnd = AppCtx::get_window(wnd_id).unwrap();
+          let timeline = c_timeline.clone_writer();
+          wnd
+            .frame_spawn(async move {
+              // The timeline may be restarted before the task runs.
+              if timeline.read().is_ended() {
+                end_timeline(&timeline, true);
+              }
+            })
+            .unwrap();
+        }
+        _ => {}
+      })
+      .unsubscribe_when_dropped();
+
+    this.reversed = reversed;
+    this.running_info = Some(TimelineInfo {
+      last_at: AppCtx::now(),
+      elapsed,
+      paused,
+      _tick_msg_guard: Some(Box::new(tick_handle)),
+    });
+    if !paused {
+      wnd.inc_running_animate();
+    }
+  }
+
+  // Restart the animations, they keep paused and follow the timeline.
+  let this = timeline.read();
+  for (_, a) in this.entries.iter() {
+    if a.is_running() {
+      a.stop();
+    }
+  }
+  this.seek_animations();
+  if reversed {
+    for (_, a) in this.entries.iter() {
+      a.reverse();
+    }
+  }
+}
+
+/// End the running timeline, and notify it's finished or stopped.
+fn end_timeline(timeline: &Stateful<Timeline>, finished: bool) {
+  let (animations, mut ends) = {
+    let mut this = timeline.silent();
+    let Some(info) = this.running_info.take() else { return };
+    if !info.paused {
+      if let Some(wnd) = AppCtx::get_window(this.window_id) {
+        wnd.dec_running_animate();
+      }
+    }
+    let animations: Vec<_> = this
+      .entries
+      .iter()
+      .map(|(_, a)| a.box_clone())
+      .collect();
+    (animations, this.ends.clone())
+  };
+
+  for a in animations {
+    // The finished animations are at their end, resume them to finish in their
+    // direction.
+    if finished { a.resume() } else { a.stop() }
+  }
+  ends.next(finished);
+}
+
+impl Drop for Timeline {
+  fn drop(&mut self) {
+    if self
+      .running_info
+      .as_ref()
+      .is_some_and(|info| !info.paused)
+    {
+      if let Some(wnd) = AppCtx::get_window(self.window_id) {
+        wnd.dec_running_animate();
+      }
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::TestWindow};
+
+  fn linear(millis: u64) -> Box<dyn Transition> {
+    EasingTransition { easing: easing::LINEAR, duration: Duration::from_millis(millis) }.box_it()
+  }
+
+  #[test]
+  fn scrub_sequence_and_parallel() {
+    reset_test_env!();
+
+    let (a, b, c) = (Stateful::new(100f32), Stateful::new(100f32), Stateful::new(100f32));
+    let holder = Stateful::new(None);
+    let (c_a, c_b, c_c, c_holder) =
+      (a.clone_writer(), b.clone_writer(), c.clone_writer(), holder.clone_writer());
+    let wnd = TestWindow::new(fn_widget! {
+      let timeline = Timeline::new();
+      timeline
+        .write()
+        .then(@Animate { transition: linear(100), state: c_a.clone_writer(), from: 0. })
+        .then(@Animate { transition: linear(100), state: c_b.clone_writer(), from: 0. })
+        .with(@Animate { transition: linear(50), state: c_c.clone_writer(), from: 0. });
+      *$c_holder.write() = Some(timeline);
+      @Void {}
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    let timeline = holder.write().take().unwrap();
+    let values = || (*a.read(), *b.read(), *c.read());
+
+    assert_eq!(timeline.duration(), Duration::from_millis(200));
+    timeline.seek(0.25);
+    assert!(timeline.is_paused());
+    assert_eq!(values(), (50., 0., 0.));
+
+    timeline.seek(0.625);
+    assert_eq!(timeline.progress(), 0.625);
+    assert_eq!(values(), (100., 25., 50.));
+
+    timeline.seek(0.);
+    assert_eq!(values(), (0., 0., 0.));
+
+    timeline.stop();
+    assert!(!timeline.is_running());
+  }
+
+  #[test]
+  fn run_to_end() {
+    reset_test_env!();
+
+    let (a, b) = (Stateful::new(100f32), Stateful::new(100f32));
+    let holder = Stateful::new(None);
+    let (c_a, c_b, c_holder) = (a.clone_writer(), b.clone_writer(), holder.clone_writer());
+    let mut wnd = TestWindow::new(fn_widget! {
+      let timeline = Timeline::new();
+      timeline
+        .write()
+        .then(@Animate { transition: linear(0), state: c_a.clone_writer(), from: 0. })
+        .then(@Animate { transition: linear(0), state: c_b.clone_writer(), from: 0. });
+      *$c_holder.write() = Some(timeline);
+      @Void {}
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    let timeline = holder.write().take().unwrap();
+    let ends = Stateful::new(vec![]);
+    let c_ends = ends.clone_writer();
+    timeline
+      .end_stream()
+      .subscribe(move |finished| c_ends.write().push(finished));
+
+    timeline.run();
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(!timeline.is_running());
+    assert_eq!(*ends.read(), [true]);
+    assert_eq!((*a.read(), *b.read()), (100., 100.));
+
+    // Play backward from the end, the animations keep their start values.
+    timeline.reverse();
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(!timeline.is_running());
+    assert_eq!(*ends.read(), [true, true]);
+    assert_eq!((*a.read(), *b.read()), (0., 0.));
+  }
+}
@@ -31,6 +31,27 @@ pub trait Transition {
   /// Return the duration of the animation from start to finish.
   fn duration(&self) -> Duration;
 
+  /// Calc the rate of change of the duration from animation start, the
+  /// animation starts with the `velocity` of the rate per second, which is
+  /// carried from the interrupted animation.
+  ///
+  /// Only the physics-based transitions use the velocity, the others ignore
+  /// it.
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    let _ = velocity;
+    self.rate_of_change(dur)
+  }
+
+  /// Return the velocity of the rate per second at the duration from the
+  /// animation start with the `velocity`. The animation retargeted at the
+  /// duration continues with it.
+  ///
+  /// Returns zero by default, so the retargeted animation restarts from still.
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 {
+    let _ = (dur, velocity);
+    0.
+  }
+
   /// Transition will apply with repeat times
   fn repeat(self, repeat: f32) -> RepeatTransition<Self>
   where
@@ -73,6 +94,24 @@ impl<T: Transition> Transition for DelayTransition<T> {
   }
 
   fn duration(&self) -> Duration { self.delay + self.transition.duration() }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    if dur < self.delay {
+      return AnimateProgress::Dismissed;
+    }
+    self
+      .transition
+      .rate_of_change_with_velocity(dur - self.delay, velocity)
+  }
+
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 {
+    if dur < self.delay {
+      return velocity;
+    }
+    self
+      .transition
+      .velocity_at(dur - self.delay, velocity)
+  }
 }
 
 impl<T: Transition> Transition for RepeatTransition<T> {
@@ -105,12 +144,24 @@ impl Transition for Box<dyn Transition> {
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { (**self).rate_of_change(dur) }
 
   fn duration(&self) -> Duration { (**self).duration() }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    (**self).rate_of_change_with_velocity(dur, velocity)
+  }
+
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 { (**self).velocity_at(dur, velocity) }
 }
 
 impl<T: Transition> Transition for Sc<T> {
   fn rate_of_change(&self, dur: Duration) -> AnimateProgress { (**self).rate_of_change(dur) }
 
   fn duration(&self) -> Duration { (**self).duration() }
+
+  fn rate_of_change_with_velocity(&self, dur: Duration, velocity: f32) -> AnimateProgress {
+    (**self).rate_of_change_with_velocity(dur, velocity)
+  }
+
+  fn velocity_at(&self, dur: Duration, velocity: f32) -> f32 { (**self).velocity_at(dur, velocity) }
 }
 
 impl<E: Easing> Transition for EasingTransition<E> {
@@ -73,6 +73,10 @@ mod text_style;
 pub use text_style::*;
 mod smooth_layout;
 pub use smooth_layout::*;
+mod hero;
+pub use hero::*;
+mod lottie;
+pub use lottie::*;
 
 mod track_widget_id;
 pub use track_widget_id::*;
@@ -88,6 +92,14 @@ mod radius;
 pub use radius::*;
 mod background;
 pub use background::*;
+mod box_shadow;
+pub use box_shadow::*;
+mod filter;
+pub use filter::*;
+mod blend_mode;
+pub use blend_mode::*;
+mod semantics;
+pub use semantics::*;
 
 use crate::prelude::*;
 
@@ -129,6 +141,7 @@ pub struct FatObj<T> {
   radius: Option<State<RadiusWidget>>,
   border: Option<State<BorderWidget>>,
   background: Option<State<Background>>,
+  box_shadow: Option<State<BoxShadowWidget>>,
   foreground: Option<State<Foreground>>,
   scrollable: Option<State<ScrollableWidget>>,
   layout_box: Option<State<LayoutBox>>,
@@ -138,6 +151,9 @@ pub struct FatObj<T> {
   margin: Option<State<Margin>>,
   transform: Option<State<TransformWidget>>,
   opacity: Option<State<Opacity>>,
+  filter: Option<State<FilterWidget>>,
+  backdrop_filter: Option<State<BackdropFilterWidget>>,
+  blend_mode: Option<State<BlendModeWidget>>,
   visibility: Option<State<Visibility>>,
   h_align: Option<State<HAlignWidget>>,
   v_align: Option<State<VAlignWidget>>,
@@ -148,6 +164,7 @@ pub struct FatObj<T> {
   keep_alive: Option<State<KeepAlive>>,
   keep_alive_unsubscribe_handle: Option<Box<dyn Any>>,
   tooltips: Option<State<Tooltips>>,
+  semantics: Option<State<SemanticsWidget>>,
   clip_boundary: Option<State<ClipBoundary>>,
   providers: Option<SmallVec<[Provider; 1]>>,
 }
@@ -180,6 +197,7 @@ impl<T> FatObj<T> {
       border: self.border,
       radius: self.radius,
       background: self.background,
+      box_shadow: self.box_shadow,
       foreground: self.foreground,
       padding: self.padding,
       layout_box: self.layout_box,
@@ -196,7 +214,11 @@ impl<T> FatObj<T> {
       text_style: self.text_style,
       visibility: self.visibility,
       opacity: self.opacity,
+      filter: self.filter,
+      backdrop_filter: self.backdrop_filter,
+      blend_mode: self.blend_mode,
       tooltips: self.tooltips,
+      semantics: self.semantics,
       clip_boundary: self.clip_boundary,
       keep_alive: self.keep_alive,
       keep_alive_unsubscribe_handle: self.keep_alive_unsubscribe_handle,
@@ -213,6 +235,7 @@ impl<T> FatObj<T> {
       && self.border.is_none()
       && self.radius.is_none()
       && self.background.is_none()
+      && self.box_shadow.is_none()
       && self.foreground.is_none()
       && self.padding.is_none()
       && self.layout_box.is_none()
@@ -230,8 +253,12 @@ impl<T> FatObj<T> {
       && self.text_style.is_none()
       && self.visibility.is_none()
       && self.opacity.is_none()
+      && self.filter.is_none()
+      && self.backdrop_filter.is_none()
+      && self.blend_mode.is_none()
       && self.keep_alive.is_none()
       && self.tooltips.is_none()
+      && self.semantics.is_none()
       && self.clip_boundary.is_none()
   }
 
@@ -330,6 +357,14 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<BoxShadowWidget>` widget from the FatObj. If it does
+  /// not exist, a new one will be created.
+  pub fn get_box_shadow_widget(&mut self) -> &State<BoxShadowWidget> {
+    self
+      .box_shadow
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<Foreground>` widget from the FatObj. If it does not
   /// exist, a new one will be created.
   pub fn get_foreground_widget(&mut self) -> &State<Foreground> {
@@ -460,6 +495,30 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<FilterWidget>` widget from the FatObj. If it doesn't
+  /// exist, a new one will be created.
+  pub fn get_filter_widget(&mut self) -> &State<FilterWidget> {
+    self
+      .filter
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
+  /// Returns the `State<BackdropFilterWidget>` widget from the FatObj. If it
+  /// doesn't exist, a new one will be created.
+  pub fn get_backdrop_filter_widget(&mut self) -> &State<BackdropFilterWidget> {
+    self
+      .backdrop_filter
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
+  /// Returns the `State<BlendModeWidget>` widget from the FatObj. If it
+  /// doesn't exist, a new one will be created.
+  pub fn get_blend_mode_widget(&mut self) -> &State<BlendModeWidget> {
+    self
+      .blend_mode
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<KeepAlive>` widget from the FatObj. If it doesn't
   /// exist, a new one will be created.
   pub fn get_keep_alive_widget(&mut self) -> &State<KeepAlive> {
@@ -476,6 +535,14 @@ impl<T> FatObj<T> {
       .get_or_insert_with(|| State::value(<_>::default()))
   }
 
+  /// Returns the `State<SemanticsWidget>` widget from the FatObj. If it
+  /// doesn't exist, a new one is created.
+  pub fn get_semantics_widget(&mut self) -> &State<SemanticsWidget> {
+    self
+      .semantics
+      .get_or_insert_with(|| State::value(<_>::default()))
+  }
+
   /// Returns the `State<ClipBoundary>` widget from the FatObj. If it doesn't
   /// exist, a new one is created.
   pub fn get_clip_boundary_widget(&mut self) -> &State<ClipBoundary> {
@@ -748,6 +815,84 @@ impl<T> FatObj<T> {
     on_mixin!(self, on_focus_out, f)
   }
 
+  /// Attaches a handler to the widget that is triggered when the user starts
+  /// dragging the widget or its descendants. Call `DragEvent::set_data` in the
+  /// handler to begin the drag.
+  pub fn on_drag_start(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_start, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragged data
+  /// enters the widget.
+  pub fn on_drag_enter(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_enter, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragged data
+  /// moves over the widget or its descendants. Call
+  /// `DragEvent::set_drop_effect` in the handler to accept the drop.
+  pub fn on_drag_over(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_over, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragged data
+  /// leaves the widget.
+  pub fn on_drag_leave(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_leave, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the dragged data
+  /// is dropped on the widget or its descendants.
+  pub fn on_drop(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drop, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the drag started
+  /// from the widget or its descendants is finished.
+  pub fn on_drag_end(mut self, f: impl FnMut(&mut DragEvent) + 'static) -> Self {
+    on_mixin!(self, on_drag_end, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the pressed
+  /// pointers move on the widget or its descendants, if the widget wins the
+  /// gesture arbitration. See [`PanEvent`] for more details.
+  pub fn on_pan(mut self, f: impl FnMut(&mut PanEvent) + 'static) -> Self {
+    on_mixin!(self, on_pan, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when two or more
+  /// pointers pinch on the widget or its descendants, if the widget wins the
+  /// gesture arbitration.
+  pub fn on_pinch(mut self, f: impl FnMut(&mut PinchEvent) + 'static) -> Self {
+    on_mixin!(self, on_pinch, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when two or more
+  /// pointers rotate on the widget or its descendants, if the widget wins the
+  /// gesture arbitration.
+  pub fn on_rotate(mut self, f: impl FnMut(&mut RotateEvent) + 'static) -> Self {
+    on_mixin!(self, on_rotate, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a pointer is held
+  /// on the widget or its descendants without moving, if the widget wins the
+  /// gesture arbitration.
+  pub fn on_long_press(mut self, f: impl FnMut(&mut LongPressEvent) + 'static) -> Self {
+    on_mixin!(self, on_long_press, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when a command is
+  /// fired from the focused widget in its subtree, see [`CommandEvent`].
+  pub fn on_command(mut self, f: impl FnMut(&mut CommandEvent) + 'static) -> Self {
+    on_mixin!(self, on_command, f)
+  }
+
+  /// Attaches a handler to the widget that is triggered when the user requests
+  /// a context menu on it, see [`ContextMenuEvent`].
+  pub fn on_context_menu(mut self, f: impl FnMut(&mut ContextMenuEvent) + 'static) -> Self {
+    on_mixin!(self, on_context_menu, f)
+  }
+
   /// Attaches a handler to the specific custom event that is bubbled from the
   /// descendants.
   pub fn on_custom_concrete_event<E: 'static>(
@@ -869,6 +1014,11 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_border_widget, |m, v| m.border = v)
   }
 
+  /// Initializes the shadow of the widget box.
+  pub fn box_shadow<const M: usize>(self, v: impl DeclareInto<BoxShadow, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_box_shadow_widget, |m, v| m.box_shadow = v)
+  }
+
   /// Initializes the border radius of the widget.
   pub fn radius<const M: usize>(self, v: impl DeclareInto<Radius, M>) -> Self {
     self.declare_builtin_init(v, Self::get_radius_widget, |m, v| m.radius = v)
@@ -939,11 +1089,33 @@ impl<T> FatObj<T> {
     self.declare_builtin_init(v, Self::get_opacity_widget, |m, v| m.opacity = v)
   }
 
+  /// Initializes the filter applied to the widget and its descendants.
+  pub fn filter<const M: usize>(self, v: impl DeclareInto<Filter, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_filter_widget, |m, v| m.filter = v)
+  }
+
+  /// Initializes the filter applied to the content behind the widget.
+  pub fn backdrop_filter<const M: usize>(self, v: impl DeclareInto<Filter, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_backdrop_filter_widget, |m, v| m.backdrop_filter = v)
+  }
+
+  /// Initializes how the widget and its descendants composite with the content
+  /// behind them.
+  pub fn blend_mode<const M: usize>(self, v: impl DeclareInto<BlendMode, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_blend_mode_widget, |m, v| m.blend_mode = v)
+  }
+
   /// Initializes the tooltips of the widget.
   pub fn tooltips<const M: usize>(self, v: impl DeclareInto<CowArc<str>, M>) -> Self {
     self.declare_builtin_init(v, Self::get_tooltips_widget, |m, v| m.tooltips = v)
   }
 
+  /// Initializes the semantics that describe the widget to the assistive
+  /// technologies.
+  pub fn semantics<const M: usize>(self, v: impl DeclareInto<Semantics, M>) -> Self {
+    self.declare_builtin_init(v, Self::get_semantics_widget, |m, v| m.semantics = v)
+  }
+
   /// Initializes the clip_boundary of the widget.
   pub fn clip_boundary<const M: usize>(self, v: impl DeclareInto<bool, M>) -> Self {
     self.declare_builtin_init(v, Self::get_clip_boundary_widget, |m, v| m.clip_boundary = v)
@@ -1074,7 +1246,9 @@ impl<'a> FatObj<Widget<'a>> {
           foreground,
           border,
           background,
+          backdrop_filter,
           clip_boundary,
+          box_shadow,
           radius,
           scrollable,
           layout_box
@@ -1090,11 +1264,14 @@ impl<'a> FatObj<Widget<'a>> {
           class,
           constrained_box,
           tooltips,
+          semantics,
           margin,
           cursor,
           mix_builtin,
           request_focus,
           transform,
+          filter,
+          blend_mode,
           opacity,
           visibility,
           h_align,
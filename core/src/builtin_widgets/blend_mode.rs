@@ -0,0 +1,49 @@
+use wrap_render::WrapRender;
+
+use super::*;
+
+/// This widget composites the paths painted by the host widget and its
+/// descendants with the content behind them by the blend mode, like the
+/// `mix-blend-mode` of CSS. Every path is blended individually.
+#[derive(Default, Clone)]
+pub struct BlendModeWidget {
+  pub blend_mode: BlendMode,
+}
+
+impl Declare for BlendModeWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl_compose_child_for_wrap_render!(BlendModeWidget, DirtyPhase::Paint);
+
+impl WrapRender for BlendModeWidget {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    ctx.painter().set_blend_mode(self.blend_mode);
+    host.paint(ctx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
+  use ribir_dev_helper::*;
+
+  widget_image_tests!(
+    blend_mode,
+    WidgetTester::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        background: Color::RED,
+        @MockBox {
+          size: Size::new(60., 60.),
+          anchor: Anchor::left_top(20., 20.),
+          background: Color::BLUE,
+          blend_mode: BlendMode::Screen,
+        }
+      }
+    })
+    .with_wnd_size(Size::new(100., 100.))
+  );
+}
@@ -0,0 +1,195 @@
+use wrap_render::WrapRender;
+
+use super::*;
+
+/// This widget paints a shadow of the host widget box based on the layout size
+/// and utilizes the provided `Radius` to round the corners.
+#[derive(Default, Clone)]
+pub struct BoxShadowWidget {
+  pub box_shadow: BoxShadow,
+}
+
+/// Describe a shadow cast by a box, the same as the `box-shadow` of CSS.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Lerp)]
+pub struct BoxShadow {
+  /// The offset of the shadow relative to the box.
+  pub offset: Vector,
+  /// The blur radius of the shadow, the larger the value, the bigger and
+  /// lighter the shadow is.
+  pub blur_radius: f32,
+  /// The distance that the shadow expands before blurring, a negative value
+  /// shrinks the shadow.
+  pub spread: f32,
+  /// The color of the shadow.
+  pub color: Color,
+  /// If true, the shadow is drawn inside the box, above the background and
+  /// below the content.
+  pub inset: bool,
+}
+
+impl Declare for BoxShadowWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl BoxShadow {
+  #[inline]
+  pub fn new(offset: Vector, blur_radius: f32, spread: f32, color: Color) -> Self {
+    Self { offset, blur_radius, spread, color, inset: false }
+  }
+
+  /// Create an inner shadow.
+  #[inline]
+  pub fn inset(offset: Vector, blur_radius: f32, spread: f32, color: Color) -> Self {
+    Self { offset, blur_radius, spread, color, inset: true }
+  }
+
+  /// Return true if the shadow can't be seen.
+  pub fn is_invisible(&self) -> bool {
+    self.color.alpha == 0
+      || (self.blur_radius <= 0. && self.spread <= 0. && self.offset == Vector::zero())
+  }
+
+  fn outer_rect(&self, size: Size) -> Rect {
+    Rect::from_size(size)
+      .inflate(self.spread, self.spread)
+      .translate(self.offset)
+  }
+
+  fn paint_outer(&self, size: Size, radius: Option<Radius>, painter: &mut Painter) {
+    let rect = self.outer_rect(size);
+    if rect.is_empty() {
+      return;
+    }
+    let path = if let Some(radius) = radius {
+      Path::rect_round(&rect, &map_radius(radius, |r| if r > 0. { r + self.spread } else { r }))
+    } else {
+      Path::rect(&rect)
+    };
+    painter.draw_shadow(path.into(), self.color, self.blur_radius);
+  }
+
+  fn paint_inset(&self, size: Size, radius: Option<Radius>, painter: &mut Painter) {
+    let box_rect = Rect::from_size(size);
+    let hole = box_rect
+      .inflate(-self.spread, -self.spread)
+      .translate(self.offset);
+    let extent = ShadowCommand::blur_extent(self.blur_radius);
+    let outer = box_rect
+      .union(&hole)
+      .inflate(extent + 1., extent + 1.);
+
+    let mut builder = Path::builder();
+    // The outer rect winds opposite to the hole, so the hole is excluded.
+    builder
+      .begin_path(outer.min())
+      .line_to(Point::new(outer.min_x(), outer.max_y()))
+      .line_to(outer.max())
+      .line_to(Point::new(outer.max_x(), outer.min_y()))
+      .end_path(true);
+    if !hole.is_empty() {
+      match radius {
+        Some(radius) => {
+          builder.rect_round(&hole, &map_radius(radius, |r| (r - self.spread).max(0.)))
+        }
+        None => builder.rect(&hole),
+      };
+    }
+
+    let mut painter = painter.save_guard();
+    let clip = match radius {
+      Some(radius) => Path::rect_round(&box_rect, &radius),
+      None => Path::rect(&box_rect),
+    };
+    painter
+      .clip(clip.into())
+      .draw_shadow(builder.build().into(), self.color, self.blur_radius);
+  }
+}
+
+fn map_radius(radius: Radius, f: impl Fn(f32) -> f32) -> Radius {
+  let Radius { top_left, top_right, bottom_left, bottom_right } = radius;
+  Radius::new(f(top_left), f(top_right), f(bottom_left), f(bottom_right))
+}
+
+impl_compose_child_for_wrap_render!(BoxShadowWidget, DirtyPhase::Paint);
+
+impl WrapRender for BoxShadowWidget {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    let shadow = &self.box_shadow;
+    let size = ctx.box_size().unwrap();
+    if shadow.is_invisible() || size.is_empty() {
+      host.paint(ctx);
+      return;
+    }
+
+    if shadow.inset {
+      host.paint(ctx);
+      let (provider_ctx, mut painter) = ctx.provider_ctx_and_box_painter();
+      let radius = Provider::of::<Radius>(provider_ctx).map(|r| *r);
+      shadow.paint_inset(size, radius, &mut painter);
+    } else {
+      {
+        let (provider_ctx, mut painter) = ctx.provider_ctx_and_box_painter();
+        let radius = Provider::of::<Radius>(provider_ctx).map(|r| *r);
+        shadow.paint_outer(size, radius, &mut painter);
+      }
+      host.paint(ctx);
+    }
+  }
+
+  fn visual_box(&self, host: &dyn Render, ctx: &mut VisualCtx) -> Option<Rect> {
+    let visual_box = host.visual_box(ctx);
+    let size = ctx.box_size().unwrap();
+    let shadow = &self.box_shadow;
+    let mut rect = Rect::from_size(size);
+    if !shadow.inset && !shadow.is_invisible() {
+      let extent = ShadowCommand::blur_extent(shadow.blur_radius);
+      rect = rect.union(&shadow.outer_rect(size).inflate(extent, extent));
+    }
+    Some(visual_box.map_or(rect, |v| v.union(&rect)))
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
+  use ribir_dev_helper::*;
+
+  const WND_SIZE: Size = Size::new(200., 200.);
+
+  widget_layout_test!(
+    outer_shadow_visual_box,
+    WidgetTester::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        box_shadow: BoxShadow::new(Vector::new(10., 10.), 0., 5., Color::BLACK),
+      }
+    })
+    .with_wnd_size(WND_SIZE),
+    LayoutCase::default().with_visual_rect(Rect::new(Point::new(0., 0.), Size::new(115., 115.)))
+  );
+
+  widget_image_tests!(
+    box_shadow,
+    WidgetTester::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(60., 60.),
+          margin: EdgeInsets::all(20.),
+          radius: Radius::all(8.),
+          background: Color::WHITE,
+          box_shadow: BoxShadow::new(Vector::new(0., 4.), 12., 2., Color::BLACK.with_alpha(0.5)),
+        }
+        @MockBox {
+          size: Size::new(60., 60.),
+          margin: EdgeInsets::all(20.),
+          background: Color::WHITE,
+          box_shadow: BoxShadow::inset(Vector::new(4., 4.), 8., 0., Color::BLACK.with_alpha(0.5)),
+        }
+      }
+    })
+    .with_wnd_size(WND_SIZE)
+  );
+}
@@ -0,0 +1,132 @@
+use wrap_render::WrapRender;
+
+use super::*;
+
+/// This widget paints the host widget and its descendants to an offscreen
+/// layer, and then paints the layer back with the filter applied, like the
+/// `filter` of CSS.
+#[derive(Default, Clone)]
+pub struct FilterWidget {
+  pub filter: Filter,
+}
+
+/// This widget applies the filter to the content behind the host widget box
+/// before the host painted, like the `backdrop-filter` of CSS. The filter
+/// area is rounded by the `Radius` provided.
+#[derive(Default, Clone)]
+pub struct BackdropFilterWidget {
+  pub backdrop_filter: Filter,
+}
+
+impl Declare for FilterWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl Declare for BackdropFilterWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl_compose_child_for_wrap_render!(FilterWidget, DirtyPhase::Paint);
+impl_compose_child_for_wrap_render!(BackdropFilterWidget, DirtyPhase::Paint);
+
+impl WrapRender for FilterWidget {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if !self.filter.is_empty() {
+      // The layer should cover the whole subtree, not only the box.
+      let id = ctx.id();
+      let area = ctx
+        .tree()
+        .store
+        .layout_info(id)
+        .and_then(|info| info.visual_box.bounds_rect())
+        .unwrap_or_else(|| Rect::from_size(ctx.box_size().unwrap()));
+      ctx
+        .painter()
+        .filter(Path::rect(&area).into(), self.filter.clone());
+    }
+    host.paint(ctx)
+  }
+
+  fn visual_box(&self, host: &dyn Render, ctx: &mut VisualCtx) -> Option<Rect> {
+    let visual_box = host.visual_box(ctx);
+    let extent = self.filter.blur_extent();
+    if extent > 0. {
+      let rect = Rect::from_size(ctx.box_size().unwrap());
+      let rect = visual_box.map_or(rect, |v| v.union(&rect));
+      Some(rect.inflate(extent, extent))
+    } else {
+      visual_box
+    }
+  }
+}
+
+impl WrapRender for BackdropFilterWidget {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    let size = ctx.box_size().unwrap();
+    if !self.backdrop_filter.is_empty() && !size.is_empty() {
+      let (provider_ctx, mut painter) = ctx.provider_ctx_and_box_painter();
+      let rect = Rect::from_size(size);
+      let path = match Provider::of::<Radius>(provider_ctx) {
+        Some(radius) => Path::rect_round(&rect, &radius),
+        None => Path::rect(&rect),
+      };
+      painter.backdrop_filter(path.into(), self.backdrop_filter.clone());
+    }
+    host.paint(ctx)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir::{core::test_helper::*, material as ribir_material, prelude::*};
+  use ribir_dev_helper::*;
+
+  const WND_SIZE: Size = Size::new(200., 100.);
+
+  widget_layout_test!(
+    blur_visual_box,
+    WidgetTester::new(fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        filter: Filter::blur(2.),
+      }
+    })
+    .with_wnd_size(WND_SIZE),
+    LayoutCase::default().with_visual_rect(Rect::new(Point::new(-6., -6.), Size::new(112., 112.)))
+  );
+
+  widget_image_tests!(
+    filter,
+    WidgetTester::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(60., 60.),
+          margin: EdgeInsets::all(20.),
+          background: Color::RED,
+          filter: Filter::blur(3.).then(Filter::grayscale(1.)),
+          @MockBox {
+            size: Size::new(30., 30.),
+            background: Color::BLUE,
+          }
+        }
+        @MockBox {
+          size: Size::new(60., 60.),
+          margin: EdgeInsets::all(20.),
+          background: Color::RED,
+          @MockBox {
+            size: Size::new(40., 40.),
+            anchor: Anchor::left_top(35., 35.),
+            radius: Radius::all(8.),
+            background: Color::WHITE.with_alpha(0.3),
+            backdrop_filter: Filter::blur(4.),
+          }
+        }
+      }
+    })
+    .with_wnd_size(WND_SIZE)
+  );
+}
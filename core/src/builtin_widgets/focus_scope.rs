@@ -12,6 +12,12 @@ pub struct FocusScope {
   /// If false, then the host widget can be focused.
   #[declare(default = true)]
   pub skip_host: bool,
+
+  /// If true, the `Tab` and `Shift+Tab` navigation cycles within the scope and
+  /// never moves out of it, it's useful for the modal widgets like a dialog.
+  /// A focused host steps into its descendants.
+  #[declare(default)]
+  pub trap_focus: bool,
 }
 
 impl<'c> ComposeChild<'c> for FocusScope {
@@ -147,6 +153,63 @@ mod tests {
     }
   }
 
+  #[test]
+  fn trap_focus() {
+    reset_test_env!();
+
+    let size = Size::zero();
+    let widget = fn_widget! {
+      @MockMulti {
+        @MockBox { size, tab_index: 0i16 }
+        @FocusScope {
+          skip_host: false,
+          trap_focus: true,
+          tab_index: -1i16,
+          @MockMulti {
+            @MockBox { size, tab_index: 0i16 }
+            @MockBox { size, tab_index: 0i16 }
+          }
+        }
+        @MockBox { size, tab_index: 0i16 }
+      }
+    };
+
+    let wnd = TestWindow::new(widget);
+    let mut focus_mgr = wnd.focus_mgr.borrow_mut();
+    let tree = wnd.tree();
+
+    let scope = tree
+      .content_root()
+      .first_child(tree)
+      .unwrap()
+      .next_sibling(tree)
+      .unwrap();
+    let inner1 = scope.first_child(tree).unwrap();
+    let inner2 = inner1.next_sibling(tree).unwrap();
+
+    // The scope is not in the tab order of the outside.
+    focus_mgr.focus_next_widget(tree);
+    focus_mgr.focus_next_widget(tree);
+    assert_ne!(focus_mgr.focusing(), Some(scope));
+    assert_ne!(focus_mgr.focusing(), Some(inner1));
+
+    focus_mgr.focus(scope, tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(inner1));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(inner2));
+    focus_mgr.focus_next_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope));
+
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(inner2));
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(inner1));
+    focus_mgr.focus_prev_widget(tree);
+    assert_eq!(focus_mgr.focusing(), Some(scope));
+  }
+
   #[test]
   fn focus_scope() {
     reset_test_env!();
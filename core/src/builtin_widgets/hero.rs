@@ -0,0 +1,399 @@
+//! Shared-element transitions between two widget subtrees.
+//!
+//! A `Hero` tags its content with a key. When a `pipe!` rebuild disposes a
+//! hero and mounts another one with the same key, the new hero flies from the
+//! rectangle of the old one to its own rectangle. The flight is an overlay
+//! layer above all widgets that animates the position, the size and the
+//! transform between the two layouts, the new hero is hidden until the flight
+//! lands.
+//!
+//! The content of a hero is a `GenWidget`, because it's built twice during the
+//! flight, once in its place and once in the overlay.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use ribir::prelude::*;
+//!
+//! let w = fn_widget! {
+//!   let expanded = Stateful::new(false);
+//!   @Stack {
+//!     on_tap: move |_| *$expanded.write() ^= true,
+//!     @pipe! {
+//!       let (size, anchor) = if *$expanded {
+//!         (Size::new(200., 200.), Anchor::left_top(100., 100.))
+//!       } else {
+//!         (Size::new(40., 40.), Anchor::left_top(0., 0.))
+//!       };
+//!       @Hero {
+//!         key: "avatar",
+//!         anchor,
+//!         @fn_widget! { @Container { size, background: Color::RED } }
+//!       }
+//!     }
+//!   }
+//! };
+//! App::run(w);
+//! ```
+use std::cell::Cell;
+
+use crate::{prelude::*, window::WindowFlags, wrap_render::*};
+
+/// A widget that flies from the place of the disposed hero with the same key
+/// when it's mounted. See the [module-level documentation](self) for more.
+pub struct Hero {
+  key: Key,
+  flight: Stateful<Animate<Stateful<HeroFrame>>>,
+  /// The frame where the hero is painted last time, in its place or in the
+  /// flight.
+  painted: Sc<Cell<Option<HeroFrame>>>,
+  flying: bool,
+}
+
+/// The widget that places the flying content by the frame in the overlay, it's
+/// not hit-testable.
+struct HeroFlight {
+  frame: Stateful<HeroFrame>,
+  painted: Sc<Cell<Option<HeroFrame>>>,
+}
+
+/// The global place of a hero, its origin, its layout size and the transform
+/// applied to it without the translation.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct HeroFrame {
+  pub pos: Point,
+  pub size: Size,
+  pub transform: Transform,
+}
+
+#[derive(Default)]
+pub struct HeroDeclarer {
+  key: Option<Key>,
+  transition: Option<Box<dyn Transition>>,
+}
+
+pub trait HeroDeclareExtend {
+  /// The key that pairs the hero with the disposed one to fly from.
+  fn key(self, key: impl Into<Key>) -> Self;
+  /// How to transition the flight.
+  fn transition(self, transition: impl Transition + 'static) -> Self;
+}
+
+impl HeroDeclareExtend for FatObj<HeroDeclarer> {
+  fn key(mut self, key: impl Into<Key>) -> Self {
+    self.key = Some(key.into());
+    self
+  }
+
+  fn transition(mut self, transition: impl Transition + 'static) -> Self {
+    self.transition = Some(Box::new(transition));
+    self
+  }
+}
+
+impl Declare for Hero {
+  type Builder = FatObj<HeroDeclarer>;
+
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(HeroDeclarer::default()) }
+}
+
+impl FatDeclarerExtend for HeroDeclarer {
+  type Target = Hero;
+
+  fn finish(mut this: FatObj<Self>) -> FatObj<Self::Target> {
+    let key = this
+      .key
+      .take()
+      .expect("The `key` of `Hero` is required.");
+    let transition = this
+      .transition
+      .take()
+      .unwrap_or_else(|| transitions::EASE_IN_OUT.of(BuildCtx::get()));
+    let flight = Animate::declarer()
+      .transition(transition)
+      .state(Stateful::new(HeroFrame::default()))
+      .from(HeroFrame::default())
+      .finish()
+      .into_stateful();
+
+    this.map(|_| Hero { key, flight, painted: <_>::default(), flying: false })
+  }
+}
+
+impl ComposeChild<'static> for Hero {
+  /// The child should be a `GenWidget`, so we can build it again in the
+  /// overlay to fly.
+  type Child = GenWidget;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'static> {
+    let this = this.clone_writer();
+    let mut content = FatObj::new(child.gen_widget());
+    let c_this = this.clone_writer();
+    content = content
+      .on_mounted(move |e| {
+        let wnd = e.window();
+        if !wnd.flags().contains(WindowFlags::ANIMATIONS) {
+          return;
+        }
+        let id = e.current_target();
+        let (this, gen) = (c_this.clone_writer(), child.clone());
+        // Wait for the layout of the new place, the disposed heroes of this frame
+        // are collected by then.
+        e.window()
+          .once_layout_ready(move || fly(this, gen, id, wnd));
+      })
+      .on_disposed({
+        let this = this.clone_writer();
+        move |e| {
+          let (key, frame, flight) = {
+            let hero = this.read();
+            (hero.key.clone(), hero.painted.get(), hero.flight.clone_writer())
+          };
+          // The flying hero departs from where it's in the flight.
+          if let Some(frame) = frame {
+            depart(key, frame, &e.window());
+          }
+          flight.stop();
+        }
+      });
+
+    WrapRender::combine_child(this, content.into_widget(), DirtyPhase::Paint)
+  }
+}
+
+impl WrapRender for Hero {
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    if self.flying {
+      ctx.painter().apply_alpha(0.);
+    } else {
+      let wnd = ctx.window();
+      self
+        .painted
+        .set(HeroFrame::of_widget(ctx.widget_id(), &wnd));
+      host.paint(ctx);
+    }
+  }
+}
+
+fn fly(this: impl StateWriter<Value = Hero>, gen: GenWidget, id: WidgetId, wnd: Sc<Window>) {
+  if id.is_dropped(wnd.tree()) {
+    return;
+  }
+  let from = wnd
+    .departed_heroes
+    .borrow_mut()
+    .remove(&this.read().key);
+  let (Some(from), Some(to)) = (from, HeroFrame::of_widget(id, &wnd)) else { return };
+  if from == to {
+    return;
+  }
+
+  let (flight, painted) = {
+    let hero = this.read();
+    (hero.flight.clone_writer(), hero.painted.clone())
+  };
+  let frame = flight.read().state.clone_writer();
+  *frame.shallow() = to;
+  flight.write().from = from;
+  flight.run();
+  // The animation lerps from the next frame, seek to take off from the old place
+  // in this frame.
+  flight.seek(0.);
+
+  let overlay = Overlay::new(
+    move || {
+      let content = gen
+        .gen_widget()
+        .dirty_on(frame.raw_modifies(), DirtyPhase::Layout);
+      let flight = HeroFlight { frame: frame.clone_writer(), painted: painted.clone() };
+      WrapRender::combine_child(State::value(flight), content, DirtyPhase::Layout)
+    },
+    OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+  );
+  overlay.show(wnd);
+
+  this.write().flying = true;
+  flight.end_stream().take(1).subscribe(move |_| {
+    overlay.close();
+    this.write().flying = false;
+  });
+}
+
+impl HeroFrame {
+  fn of_widget(id: WidgetId, wnd: &Window) -> Option<Self> {
+    let size = wnd.widget_size(id)?;
+    let pos = wnd.map_to_global(Point::zero(), id);
+    let x = wnd.map_to_global(Point::new(1., 0.), id) - pos;
+    let y = wnd.map_to_global(Point::new(0., 1.), id) - pos;
+    let transform = Transform::new(x.x, x.y, y.x, y.y, 0., 0.);
+    Some(Self { pos, size, transform })
+  }
+}
+
+impl WrapRender for HeroFlight {
+  fn perform_layout(&self, _: BoxClamp, host: &dyn Render, ctx: &mut LayoutCtx) -> Size {
+    let HeroFrame { pos, size, .. } = *self.frame.read();
+    let size = host.perform_layout(BoxClamp::fixed_size(size), ctx);
+    ctx.update_position(ctx.widget_id(), pos);
+    size
+  }
+
+  fn paint(&self, host: &dyn Render, ctx: &mut PaintingCtx) {
+    let frame = *self.frame.read();
+    self.painted.set(Some(frame));
+    ctx.painter().apply_transform(&frame.transform);
+    host.paint(ctx)
+  }
+
+  fn hit_test(&self, _: &dyn Render, _: &mut HitTestCtx, _: Point) -> HitTest {
+    HitTest { hit: false, can_hit_child: false }
+  }
+
+  fn get_transform(&self, host: &dyn Render) -> Option<Transform> {
+    let transform = self.frame.read().transform;
+    Some(
+      host
+        .get_transform()
+        .map_or(transform, |t| transform.then(&t)),
+    )
+  }
+}
+
+impl Lerp for HeroFrame {
+  fn lerp(&self, to: &Self, factor: f32) -> Self {
+    Self {
+      pos: Lerp::lerp(&self.pos, &to.pos, factor),
+      size: Lerp::lerp(&self.size, &to.size, factor),
+      transform: Lerp::lerp(&self.transform, &to.transform, factor),
+    }
+  }
+}
+
+fn depart(key: Key, frame: HeroFrame, wnd: &Sc<Window>) {
+  wnd
+    .departed_heroes
+    .borrow_mut()
+    .insert(key.clone(), frame);
+  // Only the heroes mounted in the same frame can fly from it.
+  let c_wnd = wnd.clone();
+  wnd.once_frame_finished(move || {
+    c_wnd.departed_heroes.borrow_mut().remove(&key);
+  });
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn expandable_hero(expanded: Stateful<bool>) -> TestWindow {
+    let wnd = TestWindow::new(fn_widget! {
+      @MockStack {
+        @pipe! {
+          let (pos, size) = if *$expanded {
+            (Point::new(100., 100.), Size::new(80., 80.))
+          } else {
+            (Point::new(0., 0.), Size::new(20., 20.))
+          };
+          @Hero {
+            key: "avatar",
+            transition: EasingTransition {
+              easing: easing::LinearEasing,
+              duration: Duration::from_millis(100),
+            },
+            anchor: Anchor::from_point(pos),
+            @fn_widget! { @MockBox { size } }
+          }
+        }
+      }
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    wnd
+  }
+
+  #[test]
+  fn fly_between_rebuilds() {
+    reset_test_env!();
+
+    let expanded = Stateful::new(false);
+    let mut wnd = expandable_hero(expanded.clone_writer());
+    wnd.draw_frame();
+
+    *expanded.write() = true;
+    wnd.draw_frame();
+    // The flight takes off from the old place, the new hero is hidden.
+    let flight = wnd.layout_info_by_path(&[1]).unwrap();
+    assert_eq!(flight.pos, Point::new(0., 0.));
+    assert_eq!(flight.size, Some(Size::new(20., 20.)));
+
+    wnd.advance_time(Duration::from_millis(50));
+    wnd.draw_frame();
+    let flight = wnd.layout_info_by_path(&[1]).unwrap();
+    assert_eq!(flight.pos, Point::new(50., 50.));
+    assert_eq!(flight.size, Some(Size::new(50., 50.)));
+
+    wnd.advance_time(Duration::from_millis(50));
+    wnd.draw_frame();
+    // Landed, the overlay is removed in the next frame.
+    wnd.draw_frame();
+    let root = wnd.tree().root();
+    assert_eq!(wnd.tree().count(root), 3);
+  }
+
+  #[test]
+  fn fly_back_in_flight() {
+    reset_test_env!();
+
+    let expanded = Stateful::new(false);
+    let mut wnd = expandable_hero(expanded.clone_writer());
+    wnd.draw_frame();
+
+    *expanded.write() = true;
+    wnd.draw_frame();
+    wnd.advance_time(Duration::from_millis(50));
+    wnd.draw_frame();
+
+    *expanded.write() = false;
+    wnd.draw_frame();
+    // The previous flight is removed in the next frame.
+    wnd.draw_frame();
+    // Flies back from where it's in the previous flight.
+    let flight = wnd.layout_info_by_path(&[1]).unwrap();
+    assert_eq!(flight.pos, Point::new(50., 50.));
+    assert_eq!(flight.size, Some(Size::new(50., 50.)));
+
+    wnd.advance_time(Duration::from_millis(50));
+    wnd.draw_frame();
+    let flight = wnd.layout_info_by_path(&[1]).unwrap();
+    assert_eq!(flight.pos, Point::new(25., 25.));
+    assert_eq!(flight.size, Some(Size::new(35., 35.)));
+  }
+
+  #[test]
+  fn no_flight_without_pair() {
+    reset_test_env!();
+
+    let trigger = Stateful::new(0);
+    let c_trigger = trigger.clone_writer();
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @pipe! {
+          let key = *$trigger;
+          @Hero {
+            key,
+            @fn_widget! { @MockBox { size: Size::new(10., 10.) } }
+          }
+        }
+      }
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    wnd.draw_frame();
+    let root = wnd.tree().root();
+    let count = wnd.tree().count(root);
+
+    *c_trigger.write() += 1;
+    wnd.draw_frame();
+    assert_eq!(wnd.tree().count(root), count);
+  }
+}
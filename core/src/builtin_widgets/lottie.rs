@@ -0,0 +1,312 @@
+use std::ops::Range;
+
+use crate::{
+  prelude::*,
+  ticker::FrameMsg,
+  window::{WindowFlags, WindowId},
+};
+
+/// A widget that plays a [`Lottie`] animation on the frame ticker of the
+/// window.
+///
+/// It plays the frames of the `segment` by the `speed`, and starts over from
+/// the beginning of the segment if it `repeat`s, otherwise it pauses at the
+/// end. Like the other animations, it only plays when the animations of the
+/// window are enabled.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let player = @LottiePlayer {
+///     lottie: Lottie::open("./loading.json").unwrap(),
+///     segment: 0. ..30.,
+///     speed: 2.,
+///   };
+///   let c_player = player.clone_writer();
+///   @Stack {
+///     on_tap: move |_| {
+///       let mut player = c_player.write();
+///       if player.is_playing() { player.pause() } else { player.play() }
+///     },
+///     @ { player }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[simple_declare]
+pub struct LottiePlayer {
+  pub lottie: Lottie,
+  /// Whether to start over when it plays to the end of the segment.
+  #[declare(default = true)]
+  pub repeat: bool,
+  /// The frames to play, all the frames of the lottie if it's `None`.
+  #[declare(default)]
+  pub segment: Option<Range<f32>>,
+  /// The playback rate of the frames, plays backward if it's negative.
+  #[declare(default = 1.)]
+  pub speed: f32,
+  /// Whether to play once it's mounted.
+  #[declare(default = true)]
+  pub autoplay: bool,
+  #[declare(skip)]
+  frame: f32,
+  #[declare(skip)]
+  playing: bool,
+  #[declare(skip, default = AppCtx::now())]
+  last_at: Instant,
+  #[declare(skip, default = BuildCtx::get().window().id())]
+  window_id: WindowId,
+}
+
+impl LottiePlayer {
+  /// Play from the current frame, it starts over if it has played to the end
+  /// of the segment.
+  pub fn play(&mut self) {
+    let Some(wnd) = AppCtx::get_window(self.window_id) else { return };
+    if self.playing || !wnd.flags().contains(WindowFlags::ANIMATIONS) {
+      return;
+    }
+    let (start, last) = self.ends();
+    if self.frame() == last {
+      self.frame = start;
+    }
+    self.playing = true;
+    self.last_at = AppCtx::now();
+    wnd.inc_running_animate();
+  }
+
+  /// Pause at the current frame.
+  pub fn pause(&mut self) {
+    if !self.playing {
+      return;
+    }
+    self.playing = false;
+    if let Some(wnd) = AppCtx::get_window(self.window_id) {
+      wnd.dec_running_animate();
+    }
+  }
+
+  pub fn is_playing(&self) -> bool { self.playing }
+
+  /// Jump to the `frame`, it's clamped to the segment.
+  pub fn seek(&mut self, frame: f32) { self.frame = frame; }
+
+  /// The current frame of the lottie.
+  pub fn frame(&self) -> f32 {
+    let Range { start, end } = self.play_range();
+    self.frame.clamp(start, (end - 1.).max(start))
+  }
+
+  /// The frames to play, the segment clamped to the frames of the lottie.
+  pub fn play_range(&self) -> Range<f32> {
+    let frames = self.lottie.frames();
+    let Some(segment) = self.segment.clone() else { return frames };
+    let clamp = |f: f32| f.clamp(frames.start, frames.end);
+    let (start, end) = (clamp(segment.start), clamp(segment.end));
+    start..end.max(start)
+  }
+
+  /// The first and the last frame to play in the direction of the speed, the
+  /// end of the segment is not included.
+  fn ends(&self) -> (f32, f32) {
+    let Range { start, end } = self.play_range();
+    let last = (end - 1.).max(start);
+    if self.speed < 0. { (last, start) } else { (start, last) }
+  }
+
+  fn advance(&mut self, at: Instant) {
+    let elapsed = at.saturating_duration_since(self.last_at);
+    self.last_at = at;
+    let Range { start, end } = self.play_range();
+    let frame = self.frame() + elapsed.as_secs_f32() * self.lottie.frame_rate() * self.speed;
+    let len = end - start;
+    if start <= frame && frame < end {
+      self.frame = frame;
+    } else if self.repeat && len > 0. {
+      self.frame = start + (frame - start).rem_euclid(len);
+    } else {
+      self.frame = self.ends().1;
+      self.pause();
+    }
+  }
+}
+
+/// The render paints the current frame of the player.
+struct LottieFrame<R>(R);
+
+impl Compose for LottiePlayer {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let frame = LottieFrame(this.clone_reader())
+      .into_widget()
+      .dirty_on(this.raw_modifies(), DirtyPhase::Layout);
+
+    let wnd = BuildCtx::get().window();
+    let player = this.clone_writer();
+    let tick = wnd
+      .frame_tick_stream()
+      .subscribe(move |msg| {
+        if let FrameMsg::BeforeLayout(at) = msg {
+          if player.read().is_playing() {
+            player.write().advance(at);
+          }
+        }
+      })
+      .unsubscribe_when_dropped();
+    let mut tick = Some(tick);
+
+    let player = this.clone_writer();
+    FatObj::new(frame)
+      .on_mounted(move |_| {
+        if player.read().autoplay {
+          player.write().play();
+        }
+      })
+      .on_disposed(move |_| {
+        tick.take();
+        this.silent().pause();
+      })
+      .into_widget()
+  }
+}
+
+impl<R: StateReader<Value = LottiePlayer>> Render for LottieFrame<R> {
+  fn perform_layout(&self, clamp: BoxClamp, _: &mut LayoutCtx) -> Size {
+    clamp.clamp(self.0.read().lottie.size())
+  }
+
+  fn visual_box(&self, ctx: &mut VisualCtx) -> Option<Rect> {
+    Some(Rect::from_size(ctx.box_size().unwrap()))
+  }
+
+  fn paint(&self, ctx: &mut PaintingCtx) {
+    let player = self.0.read();
+    let lottie_size = player.lottie.size();
+    let size = ctx.box_size().unwrap();
+    let painter = ctx.painter();
+    if lottie_size.greater_than(size).any() {
+      painter.clip(Path::rect(&Rect::from_size(size)).into());
+    }
+
+    let commands = player.lottie.frame_commands(player.frame());
+    painter.draw_bundle_commands(Rect::from_size(lottie_size), commands);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::rc::Rc;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  // One second of 10 frames, a 10x10 square moves from the left to the right.
+  const MOVING_SQUARE: &str = r#"{
+    "fr": 10, "ip": 0, "op": 10, "w": 100, "h": 10,
+    "layers": [{
+      "ty": 4, "ip": 0, "op": 10,
+      "ks": { "p": { "a": 1, "k": [{ "t": 0, "s": [0, 0] }, { "t": 10, "s": [100, 0] }] } },
+      "shapes": [
+        { "ty": "rc", "p": { "a": 0, "k": [5, 5] }, "s": { "a": 0, "k": [10, 10] } },
+        { "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] } }
+      ]
+    }]
+  }"#;
+
+  fn player_window(
+    init: impl Fn(LottiePlayerDeclarer) -> LottiePlayerDeclarer + 'static,
+  ) -> (TestWindow, Stateful<LottiePlayer>) {
+    let holder = Stateful::new(None);
+    let mounted = Stateful::new(false);
+    let (c_holder, c_mounted) = (holder.clone_writer(), mounted.clone_writer());
+    let init = Rc::new(init);
+    // Mount the player after the animations are enabled.
+    let mut wnd = TestWindow::new(fn_widget! {
+      let (init, holder) = (init.clone(), c_holder.clone_writer());
+      @pipe! {
+        if *$c_mounted {
+          let lottie = Lottie::parse_from_bytes(MOVING_SQUARE.as_bytes()).unwrap();
+          let player = init(LottiePlayer::declarer().lottie(lottie)).finish();
+          *holder.write() = Some(player.clone_writer().into_stateful());
+          player.into_widget()
+        } else {
+          Void.into_widget()
+        }
+      }
+    });
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    *mounted.write() = true;
+    wnd.draw_frame();
+    let player = holder.write().take().unwrap();
+    (wnd, player)
+  }
+
+  #[test]
+  fn play_on_ticker() {
+    reset_test_env!();
+
+    let (mut wnd, player) = player_window(|p| p);
+    wnd.draw_frame();
+    assert!(player.read().is_playing());
+    assert_eq!(wnd.layout_info_by_path(&[0]).unwrap().size, Some(Size::new(100., 10.)));
+
+    wnd.advance_time(Duration::from_millis(300));
+    wnd.draw_frame();
+    assert!((player.read().frame() - 3.).abs() < 1e-3);
+
+    // Start over from the beginning.
+    wnd.advance_time(Duration::from_millis(800));
+    wnd.draw_frame();
+    assert!((player.read().frame() - 1.).abs() < 1e-3);
+
+    player.write().pause();
+    wnd.advance_time(Duration::from_millis(300));
+    wnd.draw_frame();
+    assert!((player.read().frame() - 1.).abs() < 1e-3);
+  }
+
+  #[test]
+  fn segment_and_speed() {
+    reset_test_env!();
+
+    let (mut wnd, player) = player_window(|p| p.repeat(false).segment(2. ..6.).speed(2.));
+    wnd.draw_frame();
+    assert_eq!(player.read().frame(), 2.);
+
+    wnd.advance_time(Duration::from_millis(100));
+    wnd.draw_frame();
+    assert!((player.read().frame() - 4.).abs() < 1e-3);
+
+    // Pause at the last frame of the segment.
+    wnd.advance_time(Duration::from_millis(500));
+    wnd.draw_frame();
+    assert!(!player.read().is_playing());
+    assert_eq!(player.read().frame(), 5.);
+
+    // Play backward from the end.
+    {
+      let mut player = player.write();
+      player.speed = -1.;
+      player.play();
+    }
+    wnd.advance_time(Duration::from_millis(200));
+    wnd.draw_frame();
+    assert!((player.read().frame() - 3.).abs() < 1e-3);
+  }
+
+  #[test]
+  fn no_autoplay() {
+    reset_test_env!();
+
+    let (mut wnd, player) = player_window(|p| p.autoplay(false));
+    wnd.draw_frame();
+    assert!(!player.read().is_playing());
+
+    player.write().seek(7.);
+    wnd.advance_time(Duration::from_millis(300));
+    wnd.draw_frame();
+    assert_eq!(player.read().frame(), 7.);
+  }
+}
@@ -29,6 +29,25 @@ bitflags! {
     #[doc="Bubble custom event listener flag, hint the widget is listening to \
      custom events"]
     const Customs = 1 << 6;
+    #[doc="Drag and drop listener flag, hint the widget is listening to drag \
+     and drop events"]
+    const Drag = 1 << 7;
+    #[doc="Pan gesture listener flag, hint the widget is listening to pan events"]
+    const Pan = 1 << 8;
+    #[doc="Pinch gesture listener flag, hint the widget is listening to pinch \
+     events"]
+    const Pinch = 1 << 9;
+    #[doc="Rotate gesture listener flag, hint the widget is listening to rotate \
+     events"]
+    const Rotate = 1 << 10;
+    #[doc="Long press listener flag, hint the widget is listening to long press \
+     events"]
+    const LongPress = 1 << 11;
+    #[doc="Command listener flag, hint the widget is listening to command events"]
+    const Command = 1 << 12;
+    #[doc="Context menu listener flag, hint the widget is listening to context \
+     menu events"]
+    const ContextMenu = 1 << 13;
 
     const AllListeners = Self::Lifecycle.bits()
       | Self::Pointer.bits()
@@ -36,7 +55,14 @@ bitflags! {
       | Self::KeyBoard.bits()
       | Self::Focus.bits()
       | Self::FocusInOut.bits()
-      | Self::Customs.bits();
+      | Self::Customs.bits()
+      | Self::Drag.bits()
+      | Self::Pan.bits()
+      | Self::Pinch.bits()
+      | Self::Rotate.bits()
+      | Self::LongPress.bits()
+      | Self::Command.bits()
+      | Self::ContextMenu.bits();
     // listener end
 
     #[doc="Indicates whether this widget is tracing its focus status."]
@@ -330,6 +356,54 @@ impl MixBuiltin {
     impl_event_callback!(self, FocusInOut, FocusOutCapture, FocusEvent, f)
   }
 
+  pub fn on_drag_start(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, DragStart, DragEvent, f)
+  }
+
+  pub fn on_drag_enter(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, DragEnter, DragEvent, f)
+  }
+
+  pub fn on_drag_over(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, DragOver, DragEvent, f)
+  }
+
+  pub fn on_drag_leave(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, DragLeave, DragEvent, f)
+  }
+
+  pub fn on_drop(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, Drop, DragEvent, f)
+  }
+
+  pub fn on_drag_end(&self, f: impl FnMut(&mut DragEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Drag, DragEnd, DragEvent, f)
+  }
+
+  pub fn on_pan(&self, f: impl FnMut(&mut PanEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pan, Pan, PanEvent, f)
+  }
+
+  pub fn on_pinch(&self, f: impl FnMut(&mut PinchEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Pinch, Pinch, PinchEvent, f)
+  }
+
+  pub fn on_rotate(&self, f: impl FnMut(&mut RotateEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Rotate, Rotate, RotateEvent, f)
+  }
+
+  pub fn on_long_press(&self, f: impl FnMut(&mut LongPressEvent) + 'static) -> &Self {
+    impl_event_callback!(self, LongPress, LongPress, LongPressEvent, f)
+  }
+
+  pub fn on_command(&self, f: impl FnMut(&mut CommandEvent) + 'static) -> &Self {
+    impl_event_callback!(self, Command, Command, CommandEvent, f)
+  }
+
+  pub fn on_context_menu(&self, f: impl FnMut(&mut ContextMenuEvent) + 'static) -> &Self {
+    impl_event_callback!(self, ContextMenu, ContextMenu, ContextMenuEvent, f)
+  }
+
   pub fn on_custom_concrete_event<E: 'static, F: FnMut(&mut CustomEvent<E>) + 'static>(
     &self, mut f: F,
   ) -> &Self {
@@ -375,6 +449,8 @@ impl MixBuiltin {
       self.on_pointer_down(move |_| flags.write().insert(MixFlags::PointerPressed));
       let flags = self.flags.clone_writer();
       self.on_pointer_up(move |_| flags.write().remove(MixFlags::PointerPressed));
+      let flags = self.flags.clone_writer();
+      self.on_pointer_cancel(move |_| flags.write().remove(MixFlags::PointerPressed));
     }
   }
 
@@ -472,7 +548,7 @@ fn x_times_tap_map_filter(
       Event::TapCapture(e) if capture => e,
       _ => return None,
     };
-    let now = Instant::now();
+    let now = AppCtx::now();
     match &mut type_info {
       Some(info) if info.pointer_id == e.id => {
         if info.stamps.len() + 1 == x {
@@ -701,6 +701,8 @@ impl Render for ProvidersRender {
   fn only_sized_by_parent(&self) -> bool { self.render.only_sized_by_parent() }
 
   fn get_transform(&self) -> Option<Transform> { self.render.get_transform() }
+
+  fn semantics(&self) -> Option<Semantics> { self.render.semantics() }
 }
 
 pub(crate) struct Setup {
@@ -1,6 +1,19 @@
-use std::cell::Cell;
+use std::{any::Any, cell::Cell, collections::VecDeque, time::Duration};
+
+use crate::{
+  prelude::*,
+  ticker::FrameMsg,
+  window::{WindowFlags, WindowId},
+};
+
+mod physics;
+pub use physics::*;
+
+/// The time window to calculate the velocity of the drag scrolling.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+/// The max velocity in pixels per second that the content can be flung.
+const MAX_FLING_VELOCITY: f32 = 8000.;
 
-use crate::prelude::*;
 /// Enumerate to describe which direction allow widget to scroll.
 #[derive(Debug, Clone, Copy, Default, PartialEq, PartialOrd, Hash)]
 pub enum Scrollable {
@@ -17,16 +30,44 @@ pub enum Scrollable {
 }
 
 /// Helper struct for builtin scrollable field.
-#[derive(Default)]
+///
+/// Besides the wheel, the content can be dragged by the touch, and keeps
+/// moving after the release as its [`ScrollPhysics`] describes. Use
+/// [`ScrollableWidget::set_physics`] to change the physics, the default is
+/// [`ClampingScrollPhysics`].
 pub struct ScrollableWidget {
   pub scrollable: Scrollable,
   scroll_pos: Point,
   page: Size,
   content_size: Size,
+  overscroll: Vector,
+  physics: Box<dyn ScrollPhysics>,
+  drag: Option<ScrollDrag>,
+  fling: Option<Fling>,
+  animate_from: Option<Point>,
 
   view_id: Option<TrackId>,
 }
 
+struct ScrollDrag {
+  pointer: PointerId,
+  /// The global position of the pointer in the last event.
+  last: Point,
+  /// The distance that the pointer moved before the drag scrolling starts.
+  moved: Vector,
+  started: bool,
+  /// The scroll position the pointer pulls to, without the resistance of the
+  /// physics.
+  pulled: Point,
+  samples: VecDeque<(Instant, Point)>,
+}
+
+/// The running motion after the drag is released.
+struct Fling {
+  _tick: Box<dyn Any>,
+  wnd_id: WindowId,
+}
+
 /// The provider of `ScrollableWidget` providers the descendant widgets to use
 /// it.
 ///
@@ -44,19 +85,75 @@ impl Declare for ScrollableWidget {
   fn declarer() -> Self::Builder { FatObj::new(()) }
 }
 
+impl Default for ScrollableWidget {
+  fn default() -> Self {
+    Self {
+      scrollable: Scrollable::default(),
+      scroll_pos: Point::zero(),
+      page: Size::zero(),
+      content_size: Size::zero(),
+      overscroll: Vector::zero(),
+      physics: Box::new(ClampingScrollPhysics::default()),
+      drag: None,
+      fling: None,
+      animate_from: None,
+      view_id: None,
+    }
+  }
+}
+
 impl<'c> ComposeChild<'c> for ScrollableWidget {
   type Child = Widget<'c>;
   fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
     fn_widget! {
+      let mut child = FatObj::new(child);
+      // The animation of `animate_to` transitions the anchor of the content.
+      let relative_anchor = child.get_relative_anchor_widget().clone_writer();
+      let animate = @Animate {
+        state: relative_anchor.map_writer(|v| PartMut::new(&mut v.anchor)),
+        from: Anchor::default(),
+        transition: transitions::EASE_OUT.of(BuildCtx::get()),
+      };
+      let wheel_animate = animate.clone_writer();
+      let drag_animate = animate.clone_writer();
+      let fling_this = this.clone_writer();
       let mut view = @Viewport {
         scroll_dir: distinct_pipe!{
           let this = $this;
           this.scrollable
         },
-        on_wheel: move |e| $this.write().scroll(-e.delta_x, -e.delta_y),
+        on_wheel: move |e| {
+          wheel_animate.stop();
+          let mut this = $this.write();
+          this.stop_fling();
+          this.scroll(-e.delta_x, -e.delta_y);
+        },
+        on_pointer_down: move |e| if e.point_type == PointerType::Touch && e.is_primary {
+          drag_animate.stop();
+          $this.write().drag_start(e.id, e.global_pos());
+        },
+        on_pointer_move: move |e| if $this.is_dragging(e.id)
+          && $this.write().drag_move(e.global_pos())
+        {
+          // The drag scrolling takes over the pointer, the tap is not fired and
+          // the outer scrollable widgets not scroll.
+          e.window().claim_gesture();
+          e.stop_propagation();
+        },
+        on_pointer_up: move |e| if $this.is_dragging(e.id) {
+          let velocity = $this.write().drag_end();
+          if let Some(velocity) = velocity {
+            ScrollableWidget::fling(&fling_this, velocity, &e.window());
+          }
+        },
+        on_pointer_cancel: move |e| if $this.is_dragging(e.id) {
+          let mut this = $this.write();
+          this.drag_end();
+          let pos = this.scroll_pos;
+          this.jump_to(pos);
+        },
       };
 
-      let child = FatObj::new(child);
       let child = @ $child {
         anchor: distinct_pipe!{
           let this = $this;
@@ -73,6 +170,14 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
 
       $this.write().view_id = Some($view.track_id());
 
+      let u = watch!($this.animate_from)
+        .filter_map(|from| from)
+        .subscribe(move |from| {
+          $this.silent().animate_from = None;
+          $animate.write().from = Anchor::left_top(-from.x, -from.y);
+          animate.run();
+        });
+
       @ $view {
         on_performed_layout: move |_| {
           let view_size = $view.size.get();
@@ -80,6 +185,7 @@ impl<'c> ComposeChild<'c> for ScrollableWidget {
             $this.write().set_page(view_size);
           }
         },
+        on_disposed: move |_| u.unsubscribe(),
         providers: [Provider::value_of_writer(this.clone_boxed_writer(), None)],
         @ { child }
       }
@@ -115,6 +221,18 @@ impl ScrollableWidget {
   /// If Anchor.y is None, it will anchor the widget to the closest edge of the
   /// view in vertical direction, when the widget is out of the view.
   pub fn visible_content_box(&mut self, rect: Rect, anchor: Anchor) {
+    let pos = self.visible_content_pos(rect, anchor);
+    self.jump_to(pos);
+  }
+
+  /// Like [`ScrollableWidget::visible_content_box`], but scroll to the content
+  /// box with an animation.
+  pub fn animate_visible_content_box(&mut self, rect: Rect, anchor: Anchor) {
+    let pos = self.visible_content_pos(rect, anchor);
+    self.animate_to(pos);
+  }
+
+  fn visible_content_pos(&self, rect: Rect, anchor: Anchor) -> Point {
     let view_size = self.scroll_view_size();
 
     let offset_x = anchor
@@ -143,7 +261,7 @@ impl ScrollableWidget {
       })
       .map_or(self.scroll_pos.y, |y| rect.min_y() - y.into_pixel(rect.height(), view_size.height));
 
-    self.jump_to(Point::new(offset_x, offset_y));
+    Point::new(offset_x, offset_y)
   }
 
   /// Ensure the given child is visible in the scroll view with the given anchor
@@ -159,6 +277,14 @@ impl ScrollableWidget {
     self.visible_content_box(show_box, anchor);
   }
 
+  /// Like [`ScrollableWidget::visible_widget`], but scroll to the child with an
+  /// animation.
+  pub fn animate_visible_widget(&mut self, child: WidgetId, anchor: Anchor, wnd: &Window) {
+    let Some(pos) = self.map_to_content(Point::zero(), child, wnd) else { return };
+    let Some(size) = wnd.widget_size(child) else { return };
+    self.animate_visible_content_box(Rect::new(pos, size), anchor);
+  }
+
   pub fn scroll(&mut self, x: f32, y: f32) {
     let mut new = self.scroll_pos;
     if self.scrollable != Scrollable::X {
@@ -173,8 +299,34 @@ impl ScrollableWidget {
   pub fn jump_to(&mut self, top_left: Point) {
     let max = self.max_scrollable();
     self.scroll_pos = top_left.clamp(Point::zero(), max.to_vector().to_point());
+    self.overscroll = Vector::zero();
   }
 
+  /// Scroll to the `top_left` with an animation.
+  pub fn animate_to(&mut self, top_left: Point) {
+    self.stop_fling();
+    let from = self.scroll_pos;
+    self.jump_to(top_left);
+    if from != self.scroll_pos {
+      self.animate_from = Some(from);
+    }
+  }
+
+  /// Set the physics that describes how the content responds to the drag and
+  /// moves after the release.
+  pub fn set_physics(&mut self, physics: impl ScrollPhysics + 'static) {
+    self.physics = Box::new(physics);
+  }
+
+  /// The distance that the content is pulled beyond its edges. It's negative
+  /// beyond the start edge and positive beyond the end edge.
+  ///
+  /// Themes can watch it to draw the overscroll indicators.
+  pub fn overscroll(&self) -> Vector { self.overscroll }
+
+  /// Whether the content is moving after the drag is released.
+  pub fn is_flinging(&self) -> bool { self.fling.is_some() }
+
   #[inline]
   pub fn scroll_view_size(&self) -> Size { self.page }
 
@@ -200,15 +352,176 @@ impl ScrollableWidget {
 
   pub fn get_x_scroll_rate(&self) -> f32 {
     let pos = self.scroll_pos.x;
-    if pos.is_normal() { pos / self.max_scrollable().x } else { 0. }
+    if pos.is_normal() { (pos / self.max_scrollable().x).clamp(0., 1.) } else { 0. }
   }
 
   pub fn get_y_scroll_rate(&self) -> f32 {
     let pos = self.scroll_pos.y;
-    if pos.is_normal() { pos / self.max_scrollable().y } else { 0. }
+    if pos.is_normal() { (pos / self.max_scrollable().y).clamp(0., 1.) } else { 0. }
+  }
+
+  fn sync_pos(&mut self) {
+    // The content may be beyond its edges during the drag or the fling.
+    if self.drag.is_none() && self.fling.is_none() {
+      self.jump_to(self.scroll_pos)
+    }
+  }
+
+  fn is_dragging(&self, pointer: PointerId) -> bool {
+    self
+      .drag
+      .as_ref()
+      .is_some_and(|d| d.pointer == pointer)
+  }
+
+  fn drag_start(&mut self, pointer: PointerId, pos: Point) {
+    self.stop_fling();
+    let drag = ScrollDrag {
+      pointer,
+      last: pos,
+      moved: Vector::zero(),
+      started: false,
+      pulled: self.scroll_pos,
+      samples: VecDeque::new(),
+    };
+    self.drag = Some(drag);
   }
 
-  fn sync_pos(&mut self) { self.jump_to(self.scroll_pos) }
+  /// Drag the content to the pointer at `pos`, return if the drag scrolling is
+  /// started.
+  fn drag_move(&mut self, pos: Point) -> bool {
+    let max = self.max_scrollable();
+    let Some(drag) = self.drag.as_mut() else { return false };
+    // The content moves with the pointer, so it scrolls in the opposite
+    // direction.
+    let mut delta = drag.last - pos;
+    drag.last = pos;
+    if self.scrollable == Scrollable::X {
+      delta.y = 0.;
+    }
+    if self.scrollable == Scrollable::Y {
+      delta.x = 0.;
+    }
+
+    if !drag.started {
+      drag.moved += delta;
+      if drag.moved.length() <= GESTURE_SLOP {
+        return false;
+      }
+      drag.started = true;
+      delta = drag.moved;
+    }
+
+    let pos = self.scroll_pos;
+    let physics = &self.physics;
+    let drag_axis = |pos: f32, pulled: &mut f32, delta: f32, max: f32| {
+      // The pulled position follows the content if it's in the scroll range.
+      if (0. ..=max).contains(&pos) {
+        *pulled = pos;
+      }
+      *pulled += delta;
+      physics.apply_user_offset(pos, delta, max)
+    };
+    let x = drag_axis(pos.x, &mut drag.pulled.x, delta.x, max.x);
+    let y = drag_axis(pos.y, &mut drag.pulled.y, delta.y, max.y);
+    self.scroll_pos = Point::new(x, y);
+    let pulled = drag.pulled;
+    self.overscroll = pulled - pulled.clamp(Point::zero(), max);
+
+    let now = AppCtx::now();
+    drag.samples.push_back((now, pulled));
+    while drag
+      .samples
+      .front()
+      .is_some_and(|(at, _)| now.duration_since(*at) > VELOCITY_WINDOW)
+    {
+      drag.samples.pop_front();
+    }
+    true
+  }
+
+  /// End the drag and return the velocity of the pointer in pixels per second
+  /// if the drag scrolling was started.
+  fn drag_end(&mut self) -> Option<Vector> {
+    let drag = self.drag.take()?;
+    let pos = self.scroll_pos;
+    self.overscroll = pos - pos.clamp(Point::zero(), self.max_scrollable());
+    if !drag.started {
+      return None;
+    }
+    let velocity = match (drag.samples.front(), drag.samples.back()) {
+      (Some((start, from)), Some((end, to))) if start != end => {
+        (*to - *from) / end.duration_since(*start).as_secs_f32()
+      }
+      _ => Vector::zero(),
+    };
+    Some(velocity)
+  }
+
+  /// Keep the content moving with the `velocity` by the physics until it's
+  /// settled. If the animations of the window are disabled, the content
+  /// settles at once.
+  fn fling(this: &(impl StateWriter<Value = Self> + 'static), velocity: Vector, wnd: &Window) {
+    let max = Vector::splat(MAX_FLING_VELOCITY);
+    let mut velocity = velocity.clamp(-max, max);
+    if !wnd.flags().contains(WindowFlags::ANIMATIONS) {
+      let mut this = this.write();
+      // Ten seconds is long enough for any physics to settle.
+      for _ in 0..600 {
+        if !this.step_motion(&mut velocity, 1. / 60.) {
+          break;
+        }
+      }
+      let pos = this.scroll_pos;
+      this.jump_to(pos);
+      return;
+    }
+
+    let writer = this.clone_writer();
+    let wnd_id = wnd.id();
+    let mut last = AppCtx::now();
+    let tick = wnd
+      .frame_tick_stream()
+      .subscribe(move |msg| {
+        let FrameMsg::BeforeLayout(time) = msg else { return };
+        let dt = time.duration_since(last).as_secs_f32().min(0.1);
+        last = time;
+        let mut this = writer.write();
+        if this.fling.is_some() && !this.step_motion(&mut velocity, dt) {
+          // Stop the motion after the frame, not in its own callback.
+          let writer = writer.clone_writer();
+          if let Some(wnd) = AppCtx::get_window(wnd_id) {
+            let _ = wnd.frame_spawn(async move { writer.write().stop_fling() });
+          }
+        }
+      })
+      .unsubscribe_when_dropped();
+    wnd.inc_running_animate();
+    this.write().fling = Some(Fling { _tick: Box::new(tick), wnd_id });
+  }
+
+  /// Advance the motion of the content by `dt` seconds, return `false` if it's
+  /// settled.
+  fn step_motion(&mut self, velocity: &mut Vector, dt: f32) -> bool {
+    let max = self.max_scrollable();
+    let pos = self.scroll_pos;
+    let mut x = ScrollMotion { pos: pos.x, velocity: velocity.x };
+    let mut y = ScrollMotion { pos: pos.y, velocity: velocity.y };
+    let moving_x = self.physics.step(&mut x, max.x, dt);
+    let moving_y = self.physics.step(&mut y, max.y, dt);
+    *velocity = Vector::new(x.velocity, y.velocity);
+    self.scroll_pos = Point::new(x.pos, y.pos);
+    self.overscroll = self.scroll_pos - self.scroll_pos.clamp(Point::zero(), max);
+    moving_x || moving_y
+  }
+
+  fn stop_fling(&mut self) {
+    if self.fling.take().is_some() {
+      let max = self.max_scrollable();
+      self.scroll_pos = self.scroll_pos.clamp(Point::zero(), max);
+      self.overscroll = Vector::zero();
+    }
+  }
 
   fn set_content_size(&mut self, content_size: Size) {
     self.content_size = content_size;
@@ -221,6 +534,14 @@ impl ScrollableWidget {
   }
 }
 
+impl Drop for Fling {
+  fn drop(&mut self) {
+    if let Some(wnd) = AppCtx::get_window(self.wnd_id) {
+      wnd.dec_running_animate();
+    }
+  }
+}
+
 #[derive(SingleChild, Declare)]
 struct Viewport {
   scroll_dir: Scrollable,
@@ -365,4 +686,93 @@ mod tests {
     let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
     wnd.draw_frame();
   }
+
+  fn scroll_window(
+    physics: impl ScrollPhysics + 'static,
+  ) -> (TestWindow, Box<dyn StateWriter<Value = ScrollableWidget>>) {
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let mut content = @MockBox {
+        size: Size::new(100., 1000.),
+        scrollable: Scrollable::Y,
+      };
+      *$w_holder.write() = Some(content.get_scrollable_widget().clone_boxed_writer());
+      content
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    let scroll = holder.write().take().unwrap();
+    scroll.write().set_physics(physics);
+    (wnd, scroll)
+  }
+
+  #[test]
+  fn touch_drag_and_fling() {
+    reset_test_env!();
+
+    let (mut wnd, scroll) = scroll_window(ClampingScrollPhysics::default());
+    wnd.touch_start(0, Point::new(50., 80.));
+    // Not scroll before the pointer moves beyond the slop.
+    wnd.touch_move(0, Point::new(50., 75.));
+    assert_eq!(scroll.read().get_scroll_pos(), Point::zero());
+
+    wnd.touch_move(0, Point::new(50., 40.));
+    wnd.advance_time(Duration::from_millis(16));
+    wnd.touch_move(0, Point::new(50., 20.));
+    wnd.draw_frame();
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., 60.));
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., -60.));
+
+    // The content keeps moving after the release.
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    wnd.touch_end(0, Point::new(50., 20.));
+    assert!(scroll.read().is_flinging());
+    let mut frames = 0;
+    while scroll.read().is_flinging() {
+      wnd.advance_time(Duration::from_millis(16));
+      wnd.draw_frame();
+      frames += 1;
+      assert!(frames < 300, "the fling is not settled");
+    }
+    let pos = scroll.read().get_scroll_pos();
+    assert!(60. < pos.y && pos.y <= 900.);
+  }
+
+  #[test]
+  fn bouncing_overscroll() {
+    reset_test_env!();
+
+    let (mut wnd, scroll) = scroll_window(BouncingScrollPhysics::default());
+    wnd.touch_start(0, Point::new(50., 20.));
+    wnd.touch_move(0, Point::new(50., 30.));
+    wnd.touch_move(0, Point::new(50., 60.));
+    wnd.draw_frame();
+    // The content moves half of the drag beyond the edge.
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., -20.));
+    assert_eq!(scroll.read().overscroll(), Vector::new(0., -40.));
+
+    wnd.touch_end(0, Point::new(50., 60.));
+    wnd.draw_frame();
+    assert_eq!(scroll.read().get_scroll_pos(), Point::zero());
+    assert_eq!(scroll.read().overscroll(), Vector::zero());
+  }
+
+  #[test]
+  fn animate_to() {
+    reset_test_env!();
+
+    let (mut wnd, scroll) = scroll_window(ClampingScrollPhysics::default());
+    wnd.set_flags(WindowFlags::ANIMATIONS);
+    scroll.write().animate_to(Point::new(0., 500.));
+    wnd.draw_frame();
+    let y = wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y;
+    assert!(-500. < y && y <= 0.);
+    assert_eq!(scroll.read().get_scroll_pos(), Point::new(0., 500.));
+
+    wnd.advance_time(Duration::from_millis(250));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos, Point::new(0., -500.));
+  }
 }
@@ -0,0 +1,166 @@
+/// The velocity in pixels per second that a scroll motion considers settled.
+const MIN_VELOCITY: f32 = 10.;
+/// The longest step to advance a spring at once, to keep it stable.
+const MAX_SPRING_STEP: f32 = 1. / 120.;
+
+/// The position and the velocity of a scroll motion along one axis.
+///
+/// The position may be out of the scroll range if the physics allows the
+/// overscroll.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScrollMotion {
+  /// The scroll position in pixels.
+  pub pos: f32,
+  /// The velocity in pixels per second.
+  pub velocity: f32,
+}
+
+/// Describe how the scroll position responds to the user and moves after the
+/// user releases it.
+///
+/// The physics works on one axis, the scroll range of the axis is `0..=max`.
+pub trait ScrollPhysics {
+  /// Return the new scroll position after the user drags the content by
+  /// `delta` pixels from `pos`.
+  fn apply_user_offset(&self, pos: f32, delta: f32, max: f32) -> f32;
+
+  /// Advance the `motion` by `dt` seconds after the user released the content.
+  /// Return `false` if the motion is settled.
+  fn step(&self, motion: &mut ScrollMotion, max: f32, dt: f32) -> bool;
+}
+
+/// The physics that stops the scroll at the edges, and the fling decelerates
+/// by the friction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClampingScrollPhysics {
+  /// The exponential decay rate of the fling velocity per second.
+  pub friction: f32,
+}
+
+/// The physics that lets the content be pulled beyond the edges with the
+/// resistance and bounces back by a spring, the fling decelerates by the
+/// friction.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BouncingScrollPhysics {
+  /// The exponential decay rate of the fling velocity per second.
+  pub friction: f32,
+  /// The rate of the drag distance that moves the content beyond the edges.
+  pub resistance: f32,
+  /// The stiffness of the spring that pulls the content back to the edge.
+  pub stiffness: f32,
+  /// The damping of the spring.
+  pub damping: f32,
+}
+
+impl Default for ClampingScrollPhysics {
+  fn default() -> Self { Self { friction: 3. } }
+}
+
+impl Default for BouncingScrollPhysics {
+  fn default() -> Self { Self { friction: 3., resistance: 0.5, stiffness: 200., damping: 28. } }
+}
+
+/// Decelerate the motion by the exponential `friction`.
+fn decelerate(motion: &mut ScrollMotion, friction: f32, dt: f32) {
+  let decay = (-friction * dt).exp();
+  motion.pos += motion.velocity / friction * (1. - decay);
+  motion.velocity *= decay;
+}
+
+impl ScrollPhysics for ClampingScrollPhysics {
+  fn apply_user_offset(&self, pos: f32, delta: f32, max: f32) -> f32 {
+    (pos + delta).clamp(0., max.max(0.))
+  }
+
+  fn step(&self, motion: &mut ScrollMotion, max: f32, dt: f32) -> bool {
+    let max = max.max(0.);
+    decelerate(motion, self.friction, dt);
+    if motion.pos <= 0. || max <= motion.pos {
+      motion.pos = motion.pos.clamp(0., max);
+      motion.velocity = 0.;
+    }
+    MIN_VELOCITY <= motion.velocity.abs()
+  }
+}
+
+impl ScrollPhysics for BouncingScrollPhysics {
+  fn apply_user_offset(&self, pos: f32, delta: f32, max: f32) -> f32 {
+    let max = max.max(0.);
+    let new = pos + delta;
+    let before = pos - pos.clamp(0., max);
+    let after = new - new.clamp(0., max);
+    // Only resist the part that pulls the content farther beyond the edge.
+    if before * after >= 0. && before.abs() < after.abs() {
+      new - (after - before) * (1. - self.resistance)
+    } else {
+      new
+    }
+  }
+
+  fn step(&self, motion: &mut ScrollMotion, max: f32, mut dt: f32) -> bool {
+    let max = max.max(0.);
+    while dt > 0. {
+      let step = dt.min(MAX_SPRING_STEP);
+      dt -= step;
+      let edge = motion.pos.clamp(0., max);
+      let overscroll = motion.pos - edge;
+      if overscroll == 0. {
+        decelerate(motion, self.friction, step);
+      } else {
+        let force = -self.stiffness * overscroll - self.damping * motion.velocity;
+        motion.velocity += force * step;
+        motion.pos += motion.velocity * step;
+        // Snap to the edge once the spring is at rest.
+        let overscroll = motion.pos - motion.pos.clamp(0., max);
+        if overscroll.abs() < 0.5 && motion.velocity.abs() < MIN_VELOCITY {
+          motion.pos = edge;
+          motion.velocity = 0.;
+          return false;
+        }
+      }
+    }
+    let overscroll = motion.pos - motion.pos.clamp(0., max);
+    overscroll != 0. || MIN_VELOCITY <= motion.velocity.abs()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn settle(physics: &impl ScrollPhysics, mut motion: ScrollMotion, max: f32) -> f32 {
+    let mut frames = 0;
+    while physics.step(&mut motion, max, 1. / 60.) {
+      frames += 1;
+      assert!(frames < 600, "the motion is not settled");
+    }
+    motion.pos
+  }
+
+  #[test]
+  fn clamping_fling() {
+    let physics = ClampingScrollPhysics::default();
+    assert_eq!(physics.apply_user_offset(10., -20., 100.), 0.);
+    assert_eq!(physics.apply_user_offset(90., 20., 100.), 100.);
+
+    // The fling travels about `velocity / friction`.
+    let pos = settle(&physics, ScrollMotion { pos: 0., velocity: 300. }, 1000.);
+    assert!((90. ..100.).contains(&pos));
+    // The fling stops at the edge.
+    let pos = settle(&physics, ScrollMotion { pos: 0., velocity: 3000. }, 100.);
+    assert_eq!(pos, 100.);
+  }
+
+  #[test]
+  fn bouncing_back() {
+    let physics = BouncingScrollPhysics::default();
+    assert_eq!(physics.apply_user_offset(10., -20., 100.), -5.);
+    assert_eq!(physics.apply_user_offset(-5., 10., 100.), 5.);
+
+    let pos = settle(&physics, ScrollMotion { pos: -50., velocity: 0. }, 100.);
+    assert_eq!(pos, 0.);
+    // The fling passes the edge and bounces back.
+    let pos = settle(&physics, ScrollMotion { pos: 90., velocity: 1000. }, 100.);
+    assert_eq!(pos, 100.);
+  }
+}
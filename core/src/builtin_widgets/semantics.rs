@@ -0,0 +1,204 @@
+use smallvec::SmallVec;
+
+use crate::prelude::*;
+
+/// The role of a widget tells the assistive technologies, such as the screen
+/// readers, what kind of element the widget is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Role {
+  /// A widget without a specific meaning, it only groups its descendants.
+  #[default]
+  Generic,
+  Window,
+  Button,
+  Checkbox,
+  Radio,
+  Switch,
+  Slider,
+  ProgressIndicator,
+  TextInput,
+  MultilineTextInput,
+  ComboBox,
+  StaticText,
+  Link,
+  Image,
+  List,
+  ListItem,
+  ListBox,
+  ListBoxOption,
+  Tab,
+  TabList,
+  TabPanel,
+  Menu,
+  MenuItem,
+  Dialog,
+  ScrollView,
+}
+
+/// The checked state of a checkable widget.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Toggled {
+  False,
+  True,
+  /// The widget is partially checked, like an indeterminate checkbox.
+  Mixed,
+}
+
+/// The actions that the assistive technologies can ask a widget to perform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SemanticsAction {
+  /// Activate the widget, the window dispatches a tap event from the widget.
+  Click,
+  /// Move the focus to the widget, or the nearest focusable ancestor of it.
+  Focus,
+  Increment,
+  Decrement,
+  Expand,
+  Collapse,
+  ScrollIntoView,
+}
+
+/// The semantics describe a widget to the assistive technologies. All fields
+/// are optional, a `None` field keeps the value described by the inner
+/// semantics of the same widget.
+///
+/// The builtin widgets already fill in their semantics, you can use the
+/// `semantics` builtin attribute to describe your own widgets or overwrite
+/// some fields.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::prelude::*;
+///
+/// let _w = fn_widget! {
+///   @Container {
+///     size: Size::new(24., 24.),
+///     semantics: Semantics::new(Role::Button).with_label("Close"),
+///   }
+/// };
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Semantics {
+  pub role: Option<Role>,
+  /// The name of the widget. If a button-like widget has no label, the text of
+  /// its descendants is used.
+  pub label: Option<CowArc<str>>,
+  /// The current value of the widget, like the text of an input or the value
+  /// of a slider.
+  pub value: Option<CowArc<str>>,
+  pub checked: Option<Toggled>,
+  pub expanded: Option<bool>,
+  pub selected: Option<bool>,
+  pub disabled: Option<bool>,
+  /// Exclude the widget and its descendants from the accessibility tree, for
+  /// the decorative content.
+  pub hidden: Option<bool>,
+  /// The actions the widget supports besides the `Focus` action of the
+  /// focusable widgets.
+  pub actions: SmallVec<[SemanticsAction; 1]>,
+}
+
+/// The builtin widget that attaches the [`Semantics`] to its host.
+#[derive(Default)]
+pub struct SemanticsWidget {
+  pub semantics: Semantics,
+}
+
+impl Semantics {
+  pub fn new(role: Role) -> Self { Self { role: Some(role), ..Default::default() } }
+
+  /// The semantics that excludes the widget and its descendants from the
+  /// accessibility tree.
+  pub fn hidden() -> Self { Self { hidden: Some(true), ..Default::default() } }
+
+  pub fn with_label(mut self, label: impl Into<CowArc<str>>) -> Self {
+    self.label = Some(label.into());
+    self
+  }
+
+  pub fn with_value(mut self, value: impl Into<CowArc<str>>) -> Self {
+    self.value = Some(value.into());
+    self
+  }
+
+  pub fn with_checked(mut self, checked: Toggled) -> Self {
+    self.checked = Some(checked);
+    self
+  }
+
+  pub fn with_expanded(mut self, expanded: bool) -> Self {
+    self.expanded = Some(expanded);
+    self
+  }
+
+  pub fn with_selected(mut self, selected: bool) -> Self {
+    self.selected = Some(selected);
+    self
+  }
+
+  pub fn with_disabled(mut self, disabled: bool) -> Self {
+    self.disabled = Some(disabled);
+    self
+  }
+
+  pub fn with_action(mut self, action: SemanticsAction) -> Self {
+    if !self.actions.contains(&action) {
+      self.actions.push(action);
+    }
+    self
+  }
+
+  /// Overwrite the fields of this semantics with the fields that `outer`
+  /// specifies, the actions are merged.
+  pub(crate) fn merge(&mut self, outer: &Semantics) {
+    macro_rules! overwrite {
+      ($($field: ident),*) => {
+        $(
+          if outer.$field.is_some() {
+            self.$field = outer.$field.clone();
+          }
+        )*
+      };
+    }
+    overwrite!(role, label, value, checked, expanded, selected, disabled, hidden);
+    for a in outer.actions.iter() {
+      if !self.actions.contains(a) {
+        self.actions.push(*a);
+      }
+    }
+  }
+}
+
+impl From<Role> for Semantics {
+  fn from(role: Role) -> Self { Semantics::new(role) }
+}
+
+impl Declare for SemanticsWidget {
+  type Builder = FatObj<()>;
+  #[inline]
+  fn declarer() -> Self::Builder { FatObj::new(()) }
+}
+
+impl<'c> ComposeChild<'c> for SemanticsWidget {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    match this.try_into_value() {
+      Ok(this) => child.attach_data(Box::new(Queryable(this))),
+      Err(this) => {
+        // The semantics not change the pixels, but the window need a new frame to
+        // update the accessibility tree.
+        let wnd_id = BuildCtx::get().window().id();
+        let modifies = this.raw_modifies();
+        let _ = modifies.clone().subscribe(move |_| {
+          if let Some(wnd) = AppCtx::get_window(wnd_id) {
+            wnd.mark_accessibility_dirty();
+          }
+        });
+        child
+          .dirty_on(modifies, DirtyPhase::Paint)
+          .attach_data(Box::new(this))
+      }
+    }
+  }
+}
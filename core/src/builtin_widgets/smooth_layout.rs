@@ -13,7 +13,9 @@
 //! `init_value`.
 //!
 //! - **transition**: specifies the animated transition for the layout
-//!   information.
+//!   information. Use a physics-based transition, such as `SpringTransition`,
+//!   to keep the velocity when the layout changes again before the animation
+//!   finishes.
 //! - **init_value**: specifies the initial value of the layout information
 //!   before the first layout.
 //!
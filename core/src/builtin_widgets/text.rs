@@ -6,23 +6,36 @@ use typography::PlaceLineDirection;
 use crate::prelude::*;
 
 pub type TextInit = DeclareInit<CowArc<str>>;
-/// The text widget display text with a single style.
+/// The text widget display text with a single style, or with the styles of
+/// the `spans` that cover parts of the text.
 #[derive(Declare)]
 pub struct Text {
   pub text: CowArc<str>,
   #[declare(default = TextAlign::Start)]
   pub text_align: TextAlign,
+  /// The sorted spans with the styles of the parts of the text, see
+  /// [`AttributedText`].
+  #[declare(default)]
+  pub spans: Vec<TextSpan>,
   #[declare(skip)]
   glyphs: RefCell<Option<VisualGlyphs>>,
 }
 
 pub fn text_glyph(
   text: Substr, text_style: &TextStyle, text_align: TextAlign, bounds: Size,
+) -> VisualGlyphs {
+  rich_text_glyph(text, &[], text_style, text_align, bounds)
+}
+
+/// Typography the text with the styles of the spans as one paragraph.
+pub fn rich_text_glyph(
+  text: Substr, spans: &[TextSpan], text_style: &TextStyle, text_align: TextAlign, bounds: Size,
 ) -> VisualGlyphs {
   AppCtx::typography_store()
     .borrow_mut()
-    .typography(
+    .typography_with_spans(
       text,
+      spans,
       text_style,
       bounds,
       text_align,
@@ -33,6 +46,15 @@ pub fn text_glyph(
 
 pub fn paint_text(
   painter: &mut Painter, glyphs: &VisualGlyphs, style: PaintingStyle, box_rect: Rect,
+) {
+  paint_rich_text(painter, glyphs, &[], style, box_rect);
+}
+
+/// Paint the glyphs with the styles of the spans, the glyphs that no span
+/// covers use the current brush of the painter.
+pub fn paint_rich_text(
+  painter: &mut Painter, glyphs: &VisualGlyphs, spans: &[TextSpan], style: PaintingStyle,
+  box_rect: Rect,
 ) {
   if let Some(rect) = painter.intersection_paint_bounds(&box_rect) {
     if let PaintingStyle::Stroke(options) = style {
@@ -44,7 +66,11 @@ pub fn paint_text(
     }
 
     let font_db = AppCtx::font_db().clone();
-    painter.draw_glyphs_in_rect(glyphs, rect, &font_db.borrow());
+    if spans.is_empty() {
+      painter.draw_glyphs_in_rect(glyphs, rect, &font_db.borrow());
+    } else {
+      painter.draw_glyphs_with_spans_in_rect(glyphs, rect, spans, &font_db.borrow());
+    }
   }
 }
 
@@ -52,7 +78,8 @@ impl Render for Text {
   fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
     let style = Provider::of::<TextStyle>(ctx).unwrap();
 
-    let glyphs = text_glyph(self.text.substr(..), &style, self.text_align, clamp.max);
+    let glyphs =
+      rich_text_glyph(self.text.substr(..), &self.spans, &style, self.text_align, clamp.max);
 
     let size = glyphs.visual_rect().size;
     *self.glyphs.borrow_mut() = Some(glyphs);
@@ -87,14 +114,43 @@ impl Render for Text {
     let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
     let visual_glyphs = self.glyphs().unwrap();
     let rect = visual_glyphs.visual_rect();
-    paint_text(ctx.painter(), &visual_glyphs, style.unwrap_or(PaintingStyle::Fill), rect);
+    let style = style.unwrap_or(PaintingStyle::Fill);
+    paint_rich_text(ctx.painter(), &visual_glyphs, &self.spans, style, rect);
+  }
+
+  fn semantics(&self) -> Option<Semantics> {
+    Some(Semantics::new(Role::StaticText).with_label(self.text.clone()))
   }
 }
 
 impl Text {
   pub fn new<const M: u8>(text: impl Into<CowArc<str>>) -> Self {
-    Self { text: text.into(), text_align: TextAlign::Start, glyphs: Default::default() }
+    Self {
+      text: text.into(),
+      text_align: TextAlign::Start,
+      spans: vec![],
+      glyphs: Default::default(),
+    }
   }
+
+  /// Create a text widget from the attributed text.
+  pub fn rich(text: AttributedText) -> Self {
+    Self {
+      text: text.text().clone(),
+      text_align: TextAlign::Start,
+      spans: text.spans().to_vec(),
+      glyphs: Default::default(),
+    }
+  }
+
+  /// Return the span under the position that relative to the text widget, it's
+  /// useful to handle the inline links.
+  pub fn span_at(&self, pos: Point) -> Option<&TextSpan> {
+    let glyphs = self.glyphs()?;
+    let cluster = glyphs.glyph_cluster_at(pos.x, pos.y)?;
+    TextSpan::find(&self.spans, cluster)
+  }
+
   pub fn glyphs(&self) -> Option<Ref<VisualGlyphs>> {
     Ref::filter_map(self.glyphs.borrow(), |v| v.as_ref()).ok()
   }
@@ -187,4 +243,35 @@ mod tests {
     .with_wnd_size(WND_SIZE)
     .with_comparison(0.0001)
   );
+
+  #[test]
+  fn span_under_position() {
+    reset_test_env!();
+    let text = AttributedText::default()
+      .push("Visit ", SpanStyle::default())
+      .push_link("ribir", "https://ribir.org", SpanStyle::default().with_font_size(24.));
+    let (link, w_link) = split_value(None);
+    let w = fn_widget! {
+      let text = @Text { text: text.text().clone(), spans: text.spans().to_vec() };
+      @ $text {
+        on_tap: move |e| {
+          *$w_link.write() = $text.span_at(e.position()).and_then(|s| s.link.clone());
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+
+    let tap = |wnd: &mut TestWindow, pos: Point| {
+      wnd.mouse_move_to(pos);
+      wnd.mouse_press();
+      wnd.mouse_release();
+      wnd.draw_frame();
+    };
+    tap(&mut wnd, Point::new(2., 12.));
+    assert_eq!(*link.read(), None);
+
+    tap(&mut wnd, Point::new(70., 12.));
+    assert_eq!(link.read().as_deref(), Some("https://ribir.org"));
+  }
 }
@@ -1,5 +1,8 @@
 use super::*;
 
+// `TextDecoration` is moved to the painter, keep its old path.
+pub use ribir_painter::TextDecoration;
+
 /// Use typography to present your design and content as clearly and efficiently
 /// as possible.
 ///
@@ -39,20 +42,6 @@ pub struct TextDecorationStyle {
   pub decoration_color: Brush,
 }
 
-bitflags! {
-  /// A linear decoration to draw near the text.
-  #[derive(Default, PartialEq, Eq, Clone, Copy, Debug)]
-  pub struct  TextDecoration: u8 {
-    const NONE = 0b0001;
-    /// Draw a line underneath each line of text
-    const UNDERLINE =  0b0010;
-    /// Draw a line above each line of text
-    const OVERLINE = 0b0100;
-    /// Draw a line through each line of text
-    const THROUGHLINE = 0b1000;
-  }
-}
-
 impl TypographyTheme {
   /// Retrieve the nearest `TypographyTheme` from the context among its
   /// ancestors
@@ -58,6 +58,10 @@ impl WrapRender for VisibilityRender {
     }
   }
 
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> {
+    if self.display { host.semantics() } else { Some(Semantics::hidden()) }
+  }
+
   fn hit_test(&self, host: &dyn Render, ctx: &mut HitTestCtx, pos: Point) -> HitTest {
     if self.display {
       host.hit_test(ctx, pos)
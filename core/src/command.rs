@@ -0,0 +1,664 @@
+//! Named commands and the key bindings that trigger them.
+//!
+//! A [`Command`] is a named action, it's registered in the application wide
+//! [`CommandRegistry`] with its default key bindings. When the user presses a
+//! key binding of a command, the window fires a
+//! [`CommandEvent`](crate::events::CommandEvent) from the focused widget to
+//! its ancestors before the keyboard events, so a command is handled by the
+//! focused subtree that listens to it by `on_command`.
+//!
+//! The bindings can be overridden by the user, see
+//! [`CommandRegistry::load_bindings`].
+//!
+//! # Example
+//!
+//! ```
+//! use ribir_core::{prelude::*, test_helper::*};
+//!
+//! const SAVE: CommandId = CommandId::new("save");
+//!
+//! AppCtx::commands()
+//!   .borrow_mut()
+//!   .register(Command::new(SAVE, "Save", ["Cmd+S"]));
+//!
+//! let _w = fn_widget! {
+//!   @MockBox {
+//!     size: Size::new(100., 100.),
+//!     on_command: move |e| if e.command() == &SAVE {
+//!       // save the document.
+//!       e.handle();
+//!     }
+//!   }
+//! };
+//! ```
+
+use std::{fmt, path::Path, str::FromStr};
+
+use ribir_algo::CowArc;
+use smallvec::SmallVec;
+pub use winit::keyboard::{KeyCode, ModifiersState};
+
+/// The identity of a command.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CommandId(CowArc<str>);
+
+impl CommandId {
+  pub const UNDO: CommandId = CommandId::new("undo");
+  pub const REDO: CommandId = CommandId::new("redo");
+  pub const CUT: CommandId = CommandId::new("cut");
+  pub const COPY: CommandId = CommandId::new("copy");
+  pub const PASTE: CommandId = CommandId::new("paste");
+  pub const SELECT_ALL: CommandId = CommandId::new("select_all");
+
+  pub const fn new(id: &'static str) -> Self { Self(CowArc::Borrowed(id)) }
+
+  pub fn as_str(&self) -> &str { &self.0 }
+}
+
+impl From<String> for CommandId {
+  fn from(id: String) -> Self { Self(id.into()) }
+}
+
+impl fmt::Display for CommandId {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+/// A key pressed with the modifiers.
+///
+/// A chord is written as the modifiers and the key joined by `+`, e.g.
+/// `Ctrl+Shift+Z`. The modifiers are `Ctrl`, `Shift`, `Alt` (or `Option`),
+/// `Super` (or `Meta`) and `Cmd`, the `Cmd` is the main modifier of the
+/// current platform, the command key (⌘) on macOS and the control key on
+/// others. So `Cmd+Z` means `⌘Z` on macOS and `Ctrl+Z` on others.
+///
+/// The key is the physical key, so the bindings work the same on the keyboards
+/// with different layouts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct KeyChord {
+  pub modifiers: ModifiersState,
+  pub key: KeyCode,
+}
+
+/// A key binding is a sequence of the chords, the chords are pressed one by
+/// one, e.g. `Cmd+K Cmd+C`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyBinding(pub SmallVec<[KeyChord; 1]>);
+
+/// An error that the key binding text is invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseBindingError(String);
+
+/// An error when loading the key bindings.
+#[derive(Debug)]
+pub enum LoadBindingsError {
+  /// Fail to read the config file.
+  Io(std::io::Error),
+  /// The config line is invalid, the line number starts from 1.
+  Parse { line: usize, error: ParseBindingError },
+}
+
+/// A command that can be triggered by the key bindings.
+#[derive(Debug, Clone)]
+pub struct Command {
+  pub id: CommandId,
+  /// The human readable name of the command, for the shortcut cheat sheet and
+  /// the menus.
+  pub title: CowArc<str>,
+  pub default_bindings: Vec<KeyBinding>,
+}
+
+/// The result of matching the pressed chords to the key bindings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChordMatch {
+  /// No key binding starts with the chords.
+  None,
+  /// Some key bindings start with the chords, wait for the next chord.
+  Partial,
+  /// The commands bound to the chords, in the order of the registration.
+  Exact(SmallVec<[CommandId; 1]>),
+}
+
+/// The registry of all the commands of the application, get it by
+/// `AppCtx::commands`.
+///
+/// The standard editing commands of the associated constants of [`CommandId`]
+/// are registered by default.
+pub struct CommandRegistry {
+  commands: Vec<Command>,
+  overrides: ahash::HashMap<CommandId, Vec<KeyBinding>>,
+}
+
+impl KeyChord {
+  /// The main modifier of the current platform, the command key (⌘) on macOS
+  /// and the control key on others.
+  pub const CMD: ModifiersState =
+    if cfg!(target_os = "macos") { ModifiersState::SUPER } else { ModifiersState::CONTROL };
+
+  pub fn new(modifiers: ModifiersState, key: KeyCode) -> Self { Self { modifiers, key } }
+
+  /// Whether the key is a modifier key, a modifier key is not a chord by
+  /// itself.
+  pub fn is_modifier_key(key: KeyCode) -> bool {
+    matches!(
+      key,
+      KeyCode::ShiftLeft
+        | KeyCode::ShiftRight
+        | KeyCode::ControlLeft
+        | KeyCode::ControlRight
+        | KeyCode::AltLeft
+        | KeyCode::AltRight
+        | KeyCode::SuperLeft
+        | KeyCode::SuperRight
+        | KeyCode::Meta
+        | KeyCode::Hyper
+        | KeyCode::Fn
+        | KeyCode::FnLock
+    )
+  }
+}
+
+impl KeyBinding {
+  pub fn chords(&self) -> &[KeyChord] { &self.0 }
+}
+
+impl From<KeyChord> for KeyBinding {
+  fn from(chord: KeyChord) -> Self { Self(SmallVec::from_elem(chord, 1)) }
+}
+
+impl Command {
+  /// Create a command with its default key bindings.
+  ///
+  /// # Panics
+  ///
+  /// Panics if a binding is invalid, the default bindings are written by the
+  /// developer, so they are checked early.
+  pub fn new<const N: usize>(
+    id: CommandId, title: impl Into<CowArc<str>>, bindings: [&str; N],
+  ) -> Self {
+    let default_bindings = bindings
+      .iter()
+      .map(|b| {
+        b.parse()
+          .unwrap_or_else(|e| panic!("Invalid binding of `{id}`: {e}"))
+      })
+      .collect();
+    Self { id, title: title.into(), default_bindings }
+  }
+}
+
+impl CommandRegistry {
+  /// Register a command, the command with the same id is replaced.
+  pub fn register(&mut self, command: Command) {
+    if let Some(c) = self
+      .commands
+      .iter_mut()
+      .find(|c| c.id == command.id)
+    {
+      *c = command;
+    } else {
+      self.commands.push(command);
+    }
+  }
+
+  /// Remove the command, return it if it's registered.
+  pub fn unregister(&mut self, id: &CommandId) -> Option<Command> {
+    let idx = self.commands.iter().position(|c| &c.id == id)?;
+    Some(self.commands.remove(idx))
+  }
+
+  pub fn command(&self, id: &CommandId) -> Option<&Command> {
+    self.commands.iter().find(|c| &c.id == id)
+  }
+
+  /// Iterate the registered commands in the order of the registration, with
+  /// their effective bindings. Use it to render a shortcut cheat sheet.
+  pub fn iter(&self) -> impl Iterator<Item = (&Command, &[KeyBinding])> {
+    self
+      .commands
+      .iter()
+      .map(|c| (c, self.bindings(&c.id)))
+  }
+
+  /// The effective key bindings of the command, the user bindings if they are
+  /// set, otherwise the default bindings.
+  pub fn bindings(&self, id: &CommandId) -> &[KeyBinding] {
+    if let Some(bindings) = self.overrides.get(id) {
+      bindings
+    } else {
+      self
+        .command(id)
+        .map_or(&[], |c| &c.default_bindings)
+    }
+  }
+
+  /// Override the bindings of the command by the user bindings, an empty
+  /// bindings unbinds the command.
+  pub fn set_bindings(&mut self, id: CommandId, bindings: Vec<KeyBinding>) {
+    self.overrides.insert(id, bindings);
+  }
+
+  /// Restore the default bindings of the command.
+  pub fn reset_bindings(&mut self, id: &CommandId) { self.overrides.remove(id); }
+
+  /// Restore the default bindings of all the commands.
+  pub fn reset_all_bindings(&mut self) { self.overrides.clear(); }
+
+  /// Load the user bindings from the config text. Every line binds a command
+  /// to the comma separated bindings, leave the bindings empty to unbind the
+  /// command. A line starts with `#` is a comment.
+  ///
+  /// ```text
+  /// # The user bindings
+  /// redo = Cmd+Shift+Z, Cmd+Y
+  /// format_document = Cmd+K Cmd+F
+  /// select_all =
+  /// ```
+  ///
+  /// The config is applied only if all lines are valid. The commands are not
+  /// required to be registered before loading.
+  pub fn load_bindings(&mut self, config: &str) -> Result<(), LoadBindingsError> {
+    let mut overrides = vec![];
+    for (idx, line) in config.lines().enumerate() {
+      let line = line.trim();
+      if line.is_empty() || line.starts_with('#') {
+        continue;
+      }
+      let parse_line = || {
+        let (id, bindings) = line
+          .split_once('=')
+          .ok_or_else(|| ParseBindingError(format!("expect `=` in `{line}`")))?;
+        let id = id.trim();
+        if id.is_empty() {
+          return Err(ParseBindingError(format!("missing the command of `{line}`")));
+        }
+        let bindings = bindings
+          .split(',')
+          .map(str::trim)
+          .filter(|b| !b.is_empty())
+          .map(KeyBinding::from_str)
+          .collect::<Result<Vec<_>, _>>()?;
+        Ok((CommandId::from(id.to_string()), bindings))
+      };
+      let item = parse_line().map_err(|error| LoadBindingsError::Parse { line: idx + 1, error })?;
+      overrides.push(item);
+    }
+    self.overrides.extend(overrides);
+    Ok(())
+  }
+
+  /// Load the user bindings from the config file, see
+  /// [`CommandRegistry::load_bindings`] for the format.
+  pub fn load_bindings_file(&mut self, path: impl AsRef<Path>) -> Result<(), LoadBindingsError> {
+    let config = std::fs::read_to_string(path).map_err(LoadBindingsError::Io)?;
+    self.load_bindings(&config)
+  }
+
+  /// Match the pressed chords to the bindings of the commands.
+  ///
+  /// A binding that matches exactly shadows the longer bindings that start
+  /// with it.
+  pub fn match_chords(&self, chords: &[KeyChord]) -> ChordMatch {
+    let mut exact = SmallVec::new();
+    let mut partial = false;
+    for (c, bindings) in self.iter() {
+      for b in bindings {
+        let b = b.chords();
+        if b == chords {
+          exact.push(c.id.clone());
+          break;
+        } else if chords.len() < b.len() && b.starts_with(chords) {
+          partial = true;
+        }
+      }
+    }
+
+    if !exact.is_empty() {
+      ChordMatch::Exact(exact)
+    } else if partial {
+      ChordMatch::Partial
+    } else {
+      ChordMatch::None
+    }
+  }
+}
+
+impl Default for CommandRegistry {
+  fn default() -> Self {
+    let mut registry = Self { commands: vec![], overrides: <_>::default() };
+    registry.register(Command::new(CommandId::UNDO, "Undo", ["Cmd+Z"]));
+    registry.register(Command::new(CommandId::REDO, "Redo", ["Cmd+Shift+Z", "Cmd+Y"]));
+    registry.register(Command::new(CommandId::CUT, "Cut", ["Cmd+X"]));
+    registry.register(Command::new(CommandId::COPY, "Copy", ["Cmd+C"]));
+    registry.register(Command::new(CommandId::PASTE, "Paste", ["Cmd+V"]));
+    registry.register(Command::new(CommandId::SELECT_ALL, "Select All", ["Cmd+A"]));
+    registry
+  }
+}
+
+/// The names of the keys, the first name of a key is used to display it.
+const KEY_NAMES: &[(&str, KeyCode)] = &[
+  ("A", KeyCode::KeyA),
+  ("B", KeyCode::KeyB),
+  ("C", KeyCode::KeyC),
+  ("D", KeyCode::KeyD),
+  ("E", KeyCode::KeyE),
+  ("F", KeyCode::KeyF),
+  ("G", KeyCode::KeyG),
+  ("H", KeyCode::KeyH),
+  ("I", KeyCode::KeyI),
+  ("J", KeyCode::KeyJ),
+  ("K", KeyCode::KeyK),
+  ("L", KeyCode::KeyL),
+  ("M", KeyCode::KeyM),
+  ("N", KeyCode::KeyN),
+  ("O", KeyCode::KeyO),
+  ("P", KeyCode::KeyP),
+  ("Q", KeyCode::KeyQ),
+  ("R", KeyCode::KeyR),
+  ("S", KeyCode::KeyS),
+  ("T", KeyCode::KeyT),
+  ("U", KeyCode::KeyU),
+  ("V", KeyCode::KeyV),
+  ("W", KeyCode::KeyW),
+  ("X", KeyCode::KeyX),
+  ("Y", KeyCode::KeyY),
+  ("Z", KeyCode::KeyZ),
+  ("0", KeyCode::Digit0),
+  ("1", KeyCode::Digit1),
+  ("2", KeyCode::Digit2),
+  ("3", KeyCode::Digit3),
+  ("4", KeyCode::Digit4),
+  ("5", KeyCode::Digit5),
+  ("6", KeyCode::Digit6),
+  ("7", KeyCode::Digit7),
+  ("8", KeyCode::Digit8),
+  ("9", KeyCode::Digit9),
+  ("F1", KeyCode::F1),
+  ("F2", KeyCode::F2),
+  ("F3", KeyCode::F3),
+  ("F4", KeyCode::F4),
+  ("F5", KeyCode::F5),
+  ("F6", KeyCode::F6),
+  ("F7", KeyCode::F7),
+  ("F8", KeyCode::F8),
+  ("F9", KeyCode::F9),
+  ("F10", KeyCode::F10),
+  ("F11", KeyCode::F11),
+  ("F12", KeyCode::F12),
+  ("Enter", KeyCode::Enter),
+  ("Return", KeyCode::Enter),
+  ("Escape", KeyCode::Escape),
+  ("Esc", KeyCode::Escape),
+  ("Tab", KeyCode::Tab),
+  ("Space", KeyCode::Space),
+  ("Backspace", KeyCode::Backspace),
+  ("Delete", KeyCode::Delete),
+  ("Insert", KeyCode::Insert),
+  ("Home", KeyCode::Home),
+  ("End", KeyCode::End),
+  ("PageUp", KeyCode::PageUp),
+  ("PageDown", KeyCode::PageDown),
+  ("Up", KeyCode::ArrowUp),
+  ("Down", KeyCode::ArrowDown),
+  ("Left", KeyCode::ArrowLeft),
+  ("Right", KeyCode::ArrowRight),
+  ("-", KeyCode::Minus),
+  ("=", KeyCode::Equal),
+  ("[", KeyCode::BracketLeft),
+  ("]", KeyCode::BracketRight),
+  ("\\", KeyCode::Backslash),
+  (";", KeyCode::Semicolon),
+  ("'", KeyCode::Quote),
+  ("`", KeyCode::Backquote),
+  (",", KeyCode::Comma),
+  (".", KeyCode::Period),
+  ("/", KeyCode::Slash),
+];
+
+fn parse_modifier(name: &str) -> Option<ModifiersState> {
+  let m = match name.to_ascii_lowercase().as_str() {
+    "cmd" | "cmdorctrl" | "mod" => KeyChord::CMD,
+    "ctrl" | "control" => ModifiersState::CONTROL,
+    "shift" => ModifiersState::SHIFT,
+    "alt" | "option" => ModifiersState::ALT,
+    "super" | "meta" | "win" => ModifiersState::SUPER,
+    _ => return None,
+  };
+  Some(m)
+}
+
+impl FromStr for KeyChord {
+  type Err = ParseBindingError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let s = s.trim();
+    // The `+` key is the last part that is empty after split.
+    let (mods, key) = match s.strip_suffix("++") {
+      Some(mods) => (mods, "+"),
+      None => s.rsplit_once('+').unwrap_or(("", s)),
+    };
+    let mut modifiers = ModifiersState::empty();
+    for m in mods.split('+').filter(|m| !m.is_empty()) {
+      let m = parse_modifier(m.trim())
+        .ok_or_else(|| ParseBindingError(format!("unknown modifier `{m}` in `{s}`")))?;
+      modifiers |= m;
+    }
+    let key = key.trim();
+    let key = if key == "+" {
+      KeyCode::Equal
+    } else {
+      KEY_NAMES
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(key))
+        .map(|(_, code)| *code)
+        .ok_or_else(|| ParseBindingError(format!("unknown key `{key}` in `{s}`")))?
+    };
+    Ok(KeyChord { modifiers, key })
+  }
+}
+
+impl FromStr for KeyBinding {
+  type Err = ParseBindingError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let chords = s
+      .split_whitespace()
+      .map(KeyChord::from_str)
+      .collect::<Result<SmallVec<_>, _>>()?;
+    if chords.is_empty() {
+      return Err(ParseBindingError("empty key binding".into()));
+    }
+    Ok(KeyBinding(chords))
+  }
+}
+
+impl fmt::Display for KeyChord {
+  /// Display the chord by the modifier names of the current platform, e.g.
+  /// `Cmd+Shift+Z` on macOS and `Ctrl+Shift+Z` on others.
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    let mac = cfg!(target_os = "macos");
+    let names = [
+      (ModifiersState::CONTROL, "Ctrl"),
+      (ModifiersState::ALT, if mac { "Option" } else { "Alt" }),
+      (ModifiersState::SHIFT, "Shift"),
+      (ModifiersState::SUPER, if mac { "Cmd" } else { "Super" }),
+    ];
+    for (m, name) in names {
+      if self.modifiers.contains(m) {
+        write!(f, "{name}+")?;
+      }
+    }
+    match KEY_NAMES
+      .iter()
+      .find(|(_, code)| *code == self.key)
+    {
+      Some((name, _)) => f.write_str(name),
+      None => write!(f, "{:?}", self.key),
+    }
+  }
+}
+
+impl fmt::Display for KeyBinding {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    for (i, chord) in self.0.iter().enumerate() {
+      if i > 0 {
+        f.write_str(" ")?;
+      }
+      write!(f, "{chord}")?;
+    }
+    Ok(())
+  }
+}
+
+impl fmt::Display for ParseBindingError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result { f.write_str(&self.0) }
+}
+
+impl std::error::Error for ParseBindingError {}
+
+impl fmt::Display for LoadBindingsError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      LoadBindingsError::Io(e) => write!(f, "fail to read the key bindings: {e}"),
+      LoadBindingsError::Parse { line, error } => write!(f, "line {line}: {error}"),
+    }
+  }
+}
+
+impl std::error::Error for LoadBindingsError {}
+
+#[cfg(test)]
+mod tests {
+  use winit::{
+    event::{ElementState, Modifiers, WindowEvent},
+    keyboard::{KeyLocation, PhysicalKey},
+  };
+
+  use super::*;
+  use crate::{prelude::*, reset_test_env, test_helper::*};
+
+  fn chord(s: &str) -> KeyChord { s.parse().unwrap() }
+
+  #[test]
+  fn parse_binding() {
+    assert_eq!(
+      chord("Cmd+Shift+Z"),
+      KeyChord::new(KeyChord::CMD | ModifiersState::SHIFT, KeyCode::KeyZ)
+    );
+    assert_eq!(
+      chord("ctrl + alt + delete"),
+      KeyChord::new(ModifiersState::CONTROL | ModifiersState::ALT, KeyCode::Delete)
+    );
+    assert_eq!(chord("Ctrl++"), KeyChord::new(ModifiersState::CONTROL, KeyCode::Equal));
+    assert_eq!(chord("F5"), KeyChord::new(ModifiersState::empty(), KeyCode::F5));
+    assert!("Hyper+K".parse::<KeyChord>().is_err());
+    assert!("Ctrl+Foo".parse::<KeyChord>().is_err());
+
+    let binding: KeyBinding = "Ctrl+K Ctrl+C".parse().unwrap();
+    assert_eq!(binding.chords(), &[chord("Ctrl+K"), chord("Ctrl+C")]);
+    assert_eq!(binding.to_string(), "Ctrl+K Ctrl+C");
+    assert_eq!(chord("Shift+Ctrl+/").to_string(), "Ctrl+Shift+/");
+  }
+
+  #[test]
+  fn override_bindings() {
+    let mut registry = CommandRegistry::default();
+    let redo = |r: &CommandRegistry| {
+      r.bindings(&CommandId::REDO)
+        .iter()
+        .map(|b| b.to_string())
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(redo(&registry).len(), 2);
+
+    let config = "# user bindings\n redo = Ctrl+R\n\nselect_all =\n";
+    registry.load_bindings(config).unwrap();
+    assert_eq!(redo(&registry), ["Ctrl+R"]);
+    assert!(
+      registry
+        .bindings(&CommandId::SELECT_ALL)
+        .is_empty()
+    );
+    assert_eq!(registry.match_chords(&[chord("Cmd+A")]), ChordMatch::None);
+
+    // An invalid config is not applied.
+    let err = registry.load_bindings("undo = Ctrl+U\nredo Ctrl+Y");
+    assert!(matches!(err, Err(LoadBindingsError::Parse { line: 2, .. })));
+    assert_eq!(registry.bindings(&CommandId::UNDO)[0].chords(), &[chord("Cmd+Z")]);
+
+    registry.reset_bindings(&CommandId::REDO);
+    assert_eq!(redo(&registry).len(), 2);
+    let titles = registry
+      .iter()
+      .map(|(c, _)| c.title.to_string())
+      .collect::<Vec<_>>();
+    assert_eq!(titles, ["Undo", "Redo", "Cut", "Copy", "Paste", "Select All"]);
+  }
+
+  fn press(wnd: &TestWindow, modifiers: ModifiersState, key: KeyCode) {
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(Modifiers::from(modifiers)));
+    let state = ElementState::Pressed;
+    let vkey = VirtualKey::Character("".into());
+    wnd.processes_keyboard_event(PhysicalKey::Code(key), vkey, false, KeyLocation::Standard, state);
+    wnd.run_frame_tasks();
+  }
+
+  #[test]
+  fn dispatch_to_focus_scope() {
+    reset_test_env!();
+
+    const FORMAT: CommandId = CommandId::new("format");
+    AppCtx::commands()
+      .borrow_mut()
+      .register(Command::new(FORMAT, "Format", ["Ctrl+K Ctrl+F"]));
+
+    let log = Stateful::new(vec![]);
+    let c_log = log.clone_writer();
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_command: move |e| {
+          $c_log.write().push(format!("outer {}", e.command()));
+        },
+        on_key_down: move |_| $c_log.write().push("key".into()),
+        @MockBox {
+          size: Size::new(50., 50.),
+          auto_focus: true,
+          on_command: move |e| if e.command() == &CommandId::COPY {
+            $c_log.write().push("inner copy".into());
+            e.handle();
+          },
+        }
+      }
+    };
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    // The inner widget handles the copy, the outer does not receive it.
+    press(&wnd, KeyChord::CMD, KeyCode::KeyC);
+    assert_eq!(&*log.read(), &["inner copy"]);
+
+    // Not handled command bubbles to the root, then the key event fires.
+    log.write().clear();
+    press(&wnd, KeyChord::CMD, KeyCode::KeyV);
+    assert_eq!(&*log.read(), &["outer paste", "key"]);
+
+    // The first chord of a sequence waits for the next one.
+    log.write().clear();
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyK);
+    assert!(log.read().is_empty());
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyF);
+    assert_eq!(&*log.read(), &["outer format", "key"]);
+
+    // A broken sequence tries the chord alone.
+    log.write().clear();
+    press(&wnd, ModifiersState::CONTROL, KeyCode::KeyK);
+    press(&wnd, KeyChord::CMD, KeyCode::KeyC);
+    assert_eq!(&*log.read(), &["inner copy"]);
+
+    log.write().clear();
+    assert!(!wnd.execute_command(&CommandId::UNDO));
+    assert_eq!(&*log.read(), &["outer undo"]);
+  }
+}
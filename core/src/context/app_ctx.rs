@@ -1,7 +1,7 @@
 use std::{
   cell::RefCell,
   convert::Infallible,
-  sync::{LazyLock, Mutex, MutexGuard},
+  sync::{Arc, LazyLock, Mutex, MutexGuard, RwLock},
   task::{Context, RawWaker, RawWakerVTable, Waker},
 };
 
@@ -19,9 +19,11 @@ use rxrust::{
 use crate::{
   builtin_widgets::Theme,
   clipboard::{Clipboard, MockClipboard},
+  command::CommandRegistry,
   local_sender::LocalSender,
   prelude::{FuturesLocalScheduler, Instant},
   state::{StateWriter, Stateful},
+  ticker::{Clock, SystemClock},
   timer::Timer,
   widget::GenWidget,
   window::{ShellWindow, Window, WindowId},
@@ -46,6 +48,7 @@ pub struct AppCtx {
   font_db: Sc<RefCell<FontDB>>,
   typography_store: RefCell<TypographyStore>,
   clipboard: RefCell<Box<dyn Clipboard>>,
+  commands: RefCell<CommandRegistry>,
   runtime_waker: RefCell<Box<dyn RuntimeWaker + Send>>,
   scheduler: FuturesLocalScheduler,
   executor: RefCell<LocalPool>,
@@ -63,6 +66,11 @@ static APP_CTX: LazyLock<LocalSender<AppCtx>> = LazyLock::new(|| {
   LocalSender::new(AppCtx::default())
 });
 
+// The clock is not a part of `AppCtx`, because the timers may be polled in
+// other threads.
+static CLOCK: LazyLock<RwLock<Arc<dyn Clock>>> =
+  LazyLock::new(|| RwLock::new(Arc::new(SystemClock)));
+
 impl AppCtx {
   /// Obtain the global application context. Please note that it is not
   /// thread-safe and should only be accessed in the initial thread that
@@ -135,6 +143,11 @@ impl AppCtx {
   #[track_caller]
   pub fn clipboard() -> &'static RefCell<Box<dyn Clipboard>> { &Self::shared().clipboard }
 
+  /// Get the command registry of the application, register the commands and
+  /// load the user key bindings by it.
+  #[track_caller]
+  pub fn commands() -> &'static RefCell<CommandRegistry> { &Self::shared().commands }
+
   /// Get the typography store of the application.
   #[track_caller]
   pub fn typography_store() -> &'static RefCell<TypographyStore> {
@@ -151,6 +164,13 @@ impl AppCtx {
     &Self::shared().frame_ticks
   }
 
+  /// Return the current time of the application clock. The framework reads the
+  /// time from it rather than `Instant::now()`, so the time can be controlled.
+  pub fn now() -> Instant { CLOCK.read().unwrap().now() }
+
+  /// Return the clock of the application.
+  pub fn clock() -> Arc<dyn Clock> { CLOCK.read().unwrap().clone() }
+
   /// Execute the callback at the beginning of the next frame.
   pub fn once_next_frame<F: FnMut(Instant) + 'static>(f: F) {
     AppCtx::frame_ticks().clone().take(1).subscribe(f);
@@ -197,6 +217,14 @@ impl AppCtx {
     *Self::shared().runtime_waker.borrow_mut() = waker;
   }
 
+  /// Set the clock of the application, the frame ticks, the timers and the
+  /// animations go by it. The default clock is the system time.
+  ///
+  /// # Safety
+  /// This should be only called before application startup. The behavior is
+  /// undefined if you call it in a running application.
+  pub fn set_clock(clock: impl Clock) { *CLOCK.write().unwrap() = Arc::new(clock); }
+
   /// Start a new scope to mock a new application startup for `AppCtx`, this
   /// will force reset the application context and return a lock guard. The
   /// lock guard prevents two scope have intersecting lifetime.
@@ -226,6 +254,7 @@ impl AppCtx {
     });
 
     APP_CTX.reset();
+    *CLOCK.write().unwrap() = Arc::new(SystemClock);
 
     AppCtxScopeGuard(locker)
   }
@@ -471,6 +500,7 @@ impl Default for AppCtx {
       app_theme,
       typography_store,
       clipboard: RefCell::new(Box::new(MockClipboard {})),
+      commands: <_>::default(),
       executor: RefCell::new(executor),
       scheduler,
       runtime_waker: RefCell::new(Box::new(MockWaker)),
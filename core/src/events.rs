@@ -27,6 +27,17 @@ mod ime_pre_edit;
 pub use ime_pre_edit::*;
 mod lifecycle;
 pub use lifecycle::*;
+mod drag_drop;
+pub use drag_drop::*;
+mod command;
+pub use command::*;
+mod context_menu;
+pub use context_menu::*;
+mod gesture;
+pub(crate) use gesture::{GESTURE_SLOP, GestureArena};
+pub use gesture::{
+  GesturePhase, LONG_PRESS_DURATION, LongPressEvent, PanEvent, PinchEvent, RotateEvent,
+};
 
 pub(crate) mod focus_mgr;
 mod listener_impl_helper;
@@ -177,6 +188,37 @@ pub enum Event {
   /// The main difference between this event and focusout is that focusout emit
   /// in bubbles phase but this event emit in capture phase.
   FocusOutCapture(FocusEvent),
+  /// The drag start event fires when the user starts dragging a widget, a
+  /// handler sets the data to begin the drag.
+  DragStart(DragEvent),
+  /// The drag enter event fires when the dragged data enters a widget.
+  DragEnter(DragEvent),
+  /// The drag over event fires when the dragged data moves over a widget.
+  DragOver(DragEvent),
+  /// The drag leave event fires when the dragged data leaves a widget.
+  DragLeave(DragEvent),
+  /// The drop event fires when the data is dropped on a widget that accepts
+  /// it.
+  Drop(DragEvent),
+  /// The drag end event fires on the drag source when the drag is finished.
+  DragEnd(DragEvent),
+  /// The pan gesture event fires on the widget that wins the gesture
+  /// arbitration, see [`PanEvent`].
+  Pan(PanEvent),
+  /// The pinch gesture event fires on the widget that wins the gesture
+  /// arbitration.
+  Pinch(PinchEvent),
+  /// The rotate gesture event fires on the widget that wins the gesture
+  /// arbitration.
+  Rotate(RotateEvent),
+  /// The long press event fires when a pointer is held on the widget.
+  LongPress(LongPressEvent),
+  /// The command event fires when a key binding of a command is pressed, see
+  /// [`CommandEvent`].
+  Command(CommandEvent),
+  /// The context menu event fires when the user requests a context menu, see
+  /// [`ContextMenuEvent`].
+  ContextMenu(ContextMenuEvent),
   /// Custom event.
   CustomEvent(CustomEvent<dyn Any>),
 }
@@ -211,6 +253,18 @@ impl std::ops::Deref for Event {
       Event::Wheel(e) | Event::WheelCapture(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
       Event::KeyDown(e) | Event::KeyDownCapture(e) | Event::KeyUp(e) | Event::KeyUpCapture(e) => e,
+      Event::DragStart(e)
+      | Event::DragEnter(e)
+      | Event::DragOver(e)
+      | Event::DragLeave(e)
+      | Event::Drop(e)
+      | Event::DragEnd(e) => e,
+      Event::Pan(e) => e,
+      Event::Pinch(e) => e,
+      Event::Rotate(e) => e,
+      Event::LongPress(e) => e,
+      Event::Command(e) => e,
+      Event::ContextMenu(e) => e,
       Event::CustomEvent(e) => e,
     }
   }
@@ -244,6 +298,18 @@ impl std::ops::DerefMut for Event {
       Event::Wheel(e) | Event::WheelCapture(e) => e,
       Event::Chars(e) | Event::CharsCapture(e) => e,
       Event::KeyDown(e) | Event::KeyDownCapture(e) | Event::KeyUp(e) | Event::KeyUpCapture(e) => e,
+      Event::DragStart(e)
+      | Event::DragEnter(e)
+      | Event::DragOver(e)
+      | Event::DragLeave(e)
+      | Event::Drop(e)
+      | Event::DragEnd(e) => e,
+      Event::Pan(e) => e,
+      Event::Pinch(e) => e,
+      Event::Rotate(e) => e,
+      Event::LongPress(e) => e,
+      Event::Command(e) => e,
+      Event::ContextMenu(e) => e,
       Event::CustomEvent(e) => e,
     }
   }
@@ -279,6 +345,18 @@ impl Event {
       | Event::FocusInCapture(_)
       | Event::FocusOut(_)
       | Event::FocusOutCapture(_) => MixFlags::FocusInOut,
+      Event::DragStart(_)
+      | Event::DragEnter(_)
+      | Event::DragOver(_)
+      | Event::DragLeave(_)
+      | Event::Drop(_)
+      | Event::DragEnd(_) => MixFlags::Drag,
+      Event::Pan(_) => MixFlags::Pan,
+      Event::Pinch(_) => MixFlags::Pinch,
+      Event::Rotate(_) => MixFlags::Rotate,
+      Event::LongPress(_) => MixFlags::LongPress,
+      Event::Command(_) => MixFlags::Command,
+      Event::ContextMenu(_) => MixFlags::ContextMenu,
       Event::CustomEvent(_) => MixFlags::Customs,
     }
   }
@@ -0,0 +1,35 @@
+use crate::{command::CommandId, impl_common_event_deref, prelude::*};
+
+/// The event fired when a key binding of a command is pressed, or the command
+/// is executed by `Window::execute_command`.
+///
+/// The event starts from the focused widget and bubbles to its ancestors, the
+/// widgets of the same level all receive it, and it stops bubbling after a
+/// widget handles it. If no widget handles it, the keyboard events of the key
+/// are fired as usual.
+#[derive(Debug)]
+pub struct CommandEvent {
+  command: CommandId,
+  handled: bool,
+  pub common: CommonEvent,
+}
+
+impl CommandEvent {
+  pub(crate) fn new(command: CommandId, id: WidgetId, wnd: &Window) -> Self {
+    Self { command, handled: false, common: CommonEvent::new(id, wnd.tree) }
+  }
+
+  #[inline]
+  pub fn command(&self) -> &CommandId { &self.command }
+
+  /// Mark the command as handled, the event stops bubbling and the keyboard
+  /// events of the key are not fired.
+  #[inline]
+  pub fn handle(&mut self) { self.handled = true; }
+
+  /// Whether the command is handled by a widget before.
+  #[inline]
+  pub fn is_handled(&self) -> bool { self.handled }
+}
+
+impl_common_event_deref!(CommandEvent);
@@ -0,0 +1,82 @@
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The event fired when the user requests a context menu on the widget: a
+/// secondary button press of the mouse, a long press of the touch, or the
+/// `ContextMenu` key and `Shift+F10` on the focused widget.
+///
+/// The event bubbles from the target widget to its ancestors.
+#[derive(Debug)]
+pub struct ContextMenuEvent {
+  pub(crate) global_pos: Point,
+  pub common: CommonEvent,
+}
+
+impl_common_event_deref!(ContextMenuEvent);
+
+impl ContextMenuEvent {
+  pub(crate) fn new(global_pos: Point, id: WidgetId, wnd: &Window) -> Self {
+    Self { global_pos, common: CommonEvent::new(id, wnd.tree) }
+  }
+
+  /// The position in the window the menu is requested at, for the keyboard it
+  /// is the left bottom corner of the focused widget.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.global_pos }
+
+  /// The position the menu is requested at in the current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos) }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::{DeviceId, ElementState, MouseButton};
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn context_menu_triggers() {
+    reset_test_env!();
+
+    let (positions, w_positions) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_context_menu: move |e| $w_positions.write().push(e.global_pos()),
+        @MockBox {
+          size: Size::new(50., 20.),
+          auto_focus: true,
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    // The primary button does not request the context menu.
+    wnd.mouse_move_to(Point::new(10., 10.));
+    wnd.mouse_press();
+    wnd.mouse_release();
+    assert!(positions.read().is_empty());
+
+    let device_id = unsafe { DeviceId::dummy() };
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Right);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Right);
+    wnd.run_frame_tasks();
+    assert_eq!(*positions.read(), [Point::new(10., 10.)]);
+
+    // The keyboard requests it below the focused widget.
+    let key = VirtualKey::Named(NamedKey::ContextMenu);
+    let physical = PhysicalKey::Code(KeyCode::ContextMenu);
+    let (repeat, location) = (false, KeyLocation::Standard);
+    wnd.processes_keyboard_event(physical, key, repeat, location, ElementState::Pressed);
+    wnd.run_frame_tasks();
+    assert_eq!(positions.read()[1], Point::new(0., 20.));
+
+    wnd.touch_start(0, Point::new(30., 40.));
+    wnd.advance_time(LONG_PRESS_DURATION + Duration::from_millis(20));
+    wnd.draw_frame();
+    wnd.touch_end(0, Point::new(30., 40.));
+    assert_eq!(positions.read()[2], Point::new(30., 40.));
+  }
+}
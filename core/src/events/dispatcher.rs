@@ -3,6 +3,7 @@ use std::cell::RefCell;
 use winit::event::{DeviceId, ElementState, MouseButton, MouseScrollDelta, WindowEvent};
 
 use crate::{
+  events::{TouchKind, TouchPoint, drag_drop::DragSession},
   prelude::*,
   window::{DelayEvent, WindowId},
 };
@@ -38,6 +39,23 @@ pub(crate) struct Dispatcher {
   pub(crate) entered_widgets: Vec<WidgetId>,
   grab_mouse_wid: Sc<RefCell<Option<WidgetId>>>,
   pointer_down_wid: Option<WidgetId>,
+  /// The position where the pointer is pressed, and whether a drag may start
+  /// from it.
+  drag_origin: Option<Point>,
+  /// The drag session that is in progress.
+  pub(crate) drag: Option<Sc<RefCell<DragSession>>>,
+  /// The touch points on the window, the index is the slot of the pointer id.
+  touches: Vec<Option<ActiveTouch>>,
+}
+
+struct ActiveTouch {
+  /// The id of the finger reported by the platform.
+  finger: u64,
+  pos: Point,
+  /// The widget where the touch starts, it receives the following events of
+  /// the touch.
+  down_wid: Option<WidgetId>,
+  is_primary: bool,
 }
 
 impl Dispatcher {
@@ -48,6 +66,9 @@ impl Dispatcher {
       entered_widgets: vec![],
       grab_mouse_wid: Sc::new(RefCell::new(None)),
       pointer_down_wid: None,
+      drag_origin: None,
+      drag: None,
+      touches: vec![],
     }
   }
 
@@ -84,6 +105,10 @@ impl Dispatcher {
       }
       WindowEvent::CursorLeft { .. } => self.on_cursor_left(),
       WindowEvent::MouseWheel { delta, .. } => self.dispatch_wheel(delta, wnd_factor),
+      WindowEvent::Touch(touch) => {
+        let pos = touch.location.to_logical::<f32>(wnd_factor);
+        self.dispatch_touch(touch.id, touch.phase, Point::new(pos.x, pos.y))
+      }
       _ => log::info!("not processed event {:?}", event),
     }
   }
@@ -93,7 +118,12 @@ impl Dispatcher {
     state: ElementState,
   ) {
     let wnd = self.window();
-    if let Some(id) = wnd.focusing() {
+    if self.drag.is_some() {
+      // The keyboard only cancels the drag during a drag.
+      if key == VirtualKey::Named(NamedKey::Escape) && state == ElementState::Pressed {
+        wnd.add_delay_event(DelayEvent::DragFinish { cancel: true });
+      }
+    } else if let Some(id) = wnd.focusing() {
       let e = DelayEvent::KeyBoard { key, state, physical_key, is_repeat, location, id };
       wnd.add_delay_event(e);
     } else if key == VirtualKey::Named(NamedKey::Tab) && state == ElementState::Pressed {
@@ -117,6 +147,50 @@ impl Dispatcher {
     }
   }
 
+  pub fn dispatch_external_drag(&mut self, data: ExternalData) {
+    if let Some(session) = &self.drag {
+      session.borrow_mut().hover_external(data);
+    } else {
+      self.start_external_drag(data);
+    }
+  }
+
+  pub fn dispatch_external_drop(&mut self, data: ExternalData) {
+    if self.drag.is_none() {
+      // Some platforms drop the data without hovering it first.
+      self.start_external_drag(data.clone());
+    }
+    let finish = self
+      .drag
+      .as_ref()
+      .is_some_and(|s| s.borrow_mut().drop_external(data));
+    if finish {
+      self
+        .window()
+        .add_delay_event(DelayEvent::DragFinish { cancel: false });
+    }
+  }
+
+  pub fn dispatch_external_drag_cancel(&mut self) {
+    if self
+      .drag
+      .as_ref()
+      .is_some_and(|s| s.borrow().is_external_hovering())
+    {
+      self
+        .window()
+        .add_delay_event(DelayEvent::DragFinish { cancel: true });
+    }
+  }
+
+  fn start_external_drag(&mut self, data: ExternalData) {
+    let wnd = self.window();
+    let session = DragSession::external(data, wnd.get_cursor());
+    self.drag = Some(Sc::new(RefCell::new(session)));
+    self.drag_origin = None;
+    wnd.add_delay_event(DelayEvent::DragMove(self.hit_widget()));
+  }
+
   fn cursor_press_down(&mut self, hit: Option<WidgetId>) {
     let grab_pointer = *self.grab_mouse_wid.borrow();
     if let Some(grab_pointer) = grab_pointer {
@@ -125,8 +199,10 @@ impl Dispatcher {
         .add_delay_event(DelayEvent::GrabPointerDown(grab_pointer));
     } else {
       self.pointer_down_wid = None;
+      self.drag_origin = None;
       if let Some(hit) = hit {
         self.pointer_down_wid = Some(hit);
+        self.drag_origin = Some(self.info.cursor_pos);
         self
           .window()
           .add_delay_event(DelayEvent::PointerDown(hit));
@@ -137,7 +213,11 @@ impl Dispatcher {
   fn cursor_press_up(&mut self, hit: Option<WidgetId>) {
     let wnd = self.window();
     let grab_pointer = *self.grab_mouse_wid.borrow();
-    if let Some(grab_pointer) = grab_pointer {
+    self.drag_origin = None;
+    if self.drag.is_some() {
+      wnd.add_delay_event(DelayEvent::DragFinish { cancel: false });
+      self.pointer_down_wid = None;
+    } else if let Some(grab_pointer) = grab_pointer {
       wnd.add_delay_event(DelayEvent::GrabPointerUp(grab_pointer));
     } else {
       if let Some(hit) = hit {
@@ -155,11 +235,27 @@ impl Dispatcher {
   pub fn cursor_move_to(&mut self, position: Point) {
     self.info.cursor_pos = position;
     let grab_pointer = *self.grab_mouse_wid.borrow();
-    if let Some(grab_pointer) = grab_pointer {
+    if self.drag.is_some() {
+      let hit = self.hit_widget();
+      self
+        .window()
+        .add_delay_event(DelayEvent::DragMove(hit));
+    } else if let Some(grab_pointer) = grab_pointer {
       self
         .window()
         .add_delay_event(DelayEvent::GrabPointerMove(grab_pointer));
     } else {
+      let origin = self
+        .drag_origin
+        .filter(|origin| (position - *origin).length() >= DRAG_THRESHOLD);
+      if let Some(origin) = origin {
+        self.drag_origin = None;
+        if let Some(id) = self.pointer_down_wid {
+          self
+            .window()
+            .add_delay_event(DelayEvent::DragStart { id, origin });
+        }
+      }
       let new_hit = self.hit_widget();
       self.pointer_enter_leave_dispatch(new_hit);
       if let Some(hit) = new_hit {
@@ -171,7 +267,7 @@ impl Dispatcher {
   }
 
   pub fn on_cursor_left(&mut self) {
-    if self.grab_mouse_wid.borrow().is_none() {
+    if self.grab_mouse_wid.borrow().is_none() && self.drag.is_none() {
       self.info.cursor_pos = Point::new(-1., -1.);
       self.pointer_enter_leave_dispatch(self.hit_widget());
     }
@@ -188,22 +284,13 @@ impl Dispatcher {
           // only the first button press emit event.
           if self.info.mouse_button.1 == button.into() {
             let hit = self.hit_widget();
-            let wnd = self.window();
-            let tree = wnd.tree();
-
-            let nearest_focus = hit.and_then(|wid| {
-              wid.ancestors(tree).find(|id| {
-                id.query_all_iter::<MixBuiltin>(tree)
-                  .any(|m| m.contain_flag(MixFlags::Focus))
-              })
-            });
-            if let Some(focus_id) = nearest_focus {
-              wnd.focus_mgr.borrow_mut().focus(focus_id, tree);
-            } else {
-              wnd.focus_mgr.borrow_mut().blur(tree);
-            }
-
+            self.focus_pressed(hit);
             self.cursor_press_down(hit);
+            if let (MouseButton::Right, Some(id)) = (button, hit) {
+              let pos = self.info.cursor_pos;
+              let e = DelayEvent::ContextMenu { id, pos };
+              self.window().add_delay_event(e);
+            }
           }
         }
         ElementState::Released => {
@@ -220,6 +307,87 @@ impl Dispatcher {
     }
   }
 
+  pub fn dispatch_touch(&mut self, finger: u64, phase: TouchPhase, pos: Point) {
+    let wnd = self.window();
+    let slot = self
+      .touches
+      .iter()
+      .position(|t| t.as_ref().is_some_and(|t| t.finger == finger));
+    match (phase, slot) {
+      (TouchPhase::Started, None) => {
+        let is_primary = self.touches.iter().all(Option::is_none);
+        let hit = self.hit_widget_at(pos);
+        if is_primary {
+          self.focus_pressed(hit);
+        }
+        let touch = ActiveTouch { finger, pos, down_wid: hit, is_primary };
+        let slot = match self.touches.iter().position(Option::is_none) {
+          Some(slot) => {
+            self.touches[slot] = Some(touch);
+            slot
+          }
+          None => {
+            self.touches.push(Some(touch));
+            self.touches.len() - 1
+          }
+        };
+        if let Some(hit) = hit {
+          let touch = TouchPoint { pointer: PointerId::touch(slot), pos, is_primary };
+          wnd.add_delay_event(DelayEvent::Touch { kind: TouchKind::Down, id: hit, touch });
+        }
+      }
+      (TouchPhase::Moved, Some(slot)) => {
+        let touch = self.touches[slot].as_mut().unwrap();
+        touch.pos = pos;
+        if let Some(id) = touch.down_wid {
+          let touch =
+            TouchPoint { pointer: PointerId::touch(slot), pos, is_primary: touch.is_primary };
+          wnd.add_delay_event(DelayEvent::Touch { kind: TouchKind::Move, id, touch });
+        }
+      }
+      (TouchPhase::Ended | TouchPhase::Cancelled, Some(slot)) => {
+        let touch = self.touches[slot].take().unwrap();
+        if let Some(id) = touch.down_wid {
+          let is_primary = touch.is_primary;
+          let touch = TouchPoint { pointer: PointerId::touch(slot), pos, is_primary };
+          if phase == TouchPhase::Cancelled {
+            wnd.add_delay_event(DelayEvent::Touch { kind: TouchKind::Cancel, id, touch });
+          } else {
+            wnd.add_delay_event(DelayEvent::Touch { kind: TouchKind::Up, id, touch });
+            let tap = self
+              .hit_widget_at(pos)
+              .and_then(|hit| id.lowest_common_ancestor(hit, wnd.tree()));
+            if let Some(tap) = tap {
+              wnd.add_delay_event(DelayEvent::Touch { kind: TouchKind::Tap, id: tap, touch });
+            }
+          }
+        }
+        while self.touches.last().is_some_and(Option::is_none) {
+          self.touches.pop();
+        }
+      }
+      _ => log::warn!("Unexpected touch phase {phase:?} of the finger {finger}"),
+    }
+  }
+
+  /// Focus the nearest focus node of the pressed widget, or blur the focus if
+  /// there is no such node.
+  fn focus_pressed(&self, hit: Option<WidgetId>) {
+    let wnd = self.window();
+    let tree = wnd.tree();
+    let nearest_focus = hit.and_then(|wid| {
+      wid.ancestors(tree).find(|id| {
+        id.query_all_iter::<MixBuiltin>(tree)
+          .any(|m| m.contain_flag(MixFlags::Focus))
+      })
+    });
+    if let Some(focus_id) = nearest_focus {
+      wnd.focus_mgr.borrow_mut().focus(focus_id, tree);
+    } else {
+      wnd.focus_mgr.borrow_mut().blur(tree);
+    }
+  }
+
   pub fn dispatch_wheel(&mut self, delta: MouseScrollDelta, wnd_factor: f64) {
     if let Some(wid) = self.hit_widget() {
       let (delta_x, delta_y) = match delta {
@@ -259,7 +427,10 @@ impl Dispatcher {
     self.entered_widgets = new_hit.map_or(vec![], |wid| wid.ancestors(tree).collect::<Vec<_>>());
   }
 
-  fn hit_widget(&self) -> Option<WidgetId> {
+  pub(crate) fn hit_widget(&self) -> Option<WidgetId> { self.hit_widget_at(self.info.cursor_pos) }
+
+  /// The deepest widget at the global position.
+  pub(crate) fn hit_widget_at(&self, mut pos: Point) -> Option<WidgetId> {
     fn deepest_test(ctx: &mut HitTestCtx, pos: &mut Point) -> Option<WidgetId> {
       // Safety: The widget tree remains read-only throughout the entire hit testing
       // process.
@@ -289,7 +460,6 @@ impl Dispatcher {
     }
 
     let mut ctx = HitTestCtx::new(self.window().tree);
-    let mut pos = self.info.cursor_pos;
     let mut hit_target = deepest_test(&mut ctx, &mut pos);
 
     let (ctx, tree) = ctx.split_tree();
@@ -0,0 +1,730 @@
+use std::{
+  any::Any,
+  cell::{Ref, RefCell},
+  path::PathBuf,
+};
+
+use crate::{impl_common_event_deref, prelude::*, window::DelayEvent};
+
+/// The distance in logical pixels that the pointer must move after it is
+/// pressed before a drag starts.
+pub(crate) const DRAG_THRESHOLD: f32 = 4.;
+/// The width of the area along the edges of a scroll view that scrolls the
+/// view automatically when dragging over it.
+const AUTO_SCROLL_EDGE: f32 = 24.;
+/// The maximum distance a scroll view scrolls every time the pointer moves or
+/// a frame is drawn.
+const AUTO_SCROLL_MAX_STEP: f32 = 16.;
+
+bitflags! {
+  /// The effects of a drop. The drag source specifies the effects it allows,
+  /// and the drop target picks one of them by
+  /// [`DragEvent::set_drop_effect`] when the data is dragged over it.
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+  pub struct DropEffect: u8 {
+    #[doc="The drop is not allowed."]
+    const NONE = 0;
+    #[doc="The data is copied to the drop target."]
+    const COPY = 1 << 0;
+    #[doc="The data is moved to the drop target."]
+    const MOVE = 1 << 1;
+    #[doc="The drop target links to the data."]
+    const LINK = 1 << 2;
+    const ALL = Self::COPY.bits() | Self::MOVE.bits() | Self::LINK.bits();
+  }
+}
+
+/// The data dragged from other applications into the window.
+///
+/// It's the dragged data of the drag events that have no drag source, see
+/// [`DragEvent::files`] and [`DragEvent::text`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExternalData {
+  /// The paths of the files.
+  Files(Vec<PathBuf>),
+  Text(String),
+}
+
+/// The event of the drag and drop between the widgets.
+///
+/// A drag starts when the pointer presses on a widget and moves a few pixels.
+/// The `DragStart` event bubbles from the pressed widget, and a handler must
+/// call [`DragEvent::set_data`] to begin the drag, otherwise the pointer keeps
+/// working as usual.
+///
+/// During the drag, the pointer events are not dispatched. Instead, the
+/// `DragEnter`, `DragLeave` and `DragOver` events are dispatched to the widget
+/// under the pointer. The `DragOver` handlers decide whether the data can be
+/// dropped by [`DragEvent::set_drop_effect`]. When the pointer is released on
+/// a widget that accepts the data, the `Drop` event bubbles from it. Finally,
+/// the `DragEnd` event bubbles from the drag source with the effect of the
+/// drop, even if the drag is canceled by the `Escape` key.
+///
+/// The files or text dragged from other applications are dispatched as the
+/// same events with the [`ExternalData`], except there are no `DragStart` and
+/// `DragEnd` events since the drag source is outside the window.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::{prelude::*, test_helper::*};
+///
+/// let _w = fn_widget! {
+///   @MockMulti {
+///     @Container {
+///       size: Size::new(50., 50.),
+///       on_drag_start: move |e| e.set_data("Hello"),
+///     }
+///     @Container {
+///       size: Size::new(50., 50.),
+///       on_drag_over: move |e| {
+///         if e.has_data::<&str>() {
+///           e.set_drop_effect(DropEffect::COPY);
+///         }
+///       },
+///       on_drop: move |e| println!("{}", *e.data::<&str>().unwrap()),
+///     }
+///   }
+/// };
+/// ```
+pub struct DragEvent {
+  pub common: CommonEvent,
+  session: Sc<RefCell<DragSession>>,
+}
+
+impl_common_event_deref!(DragEvent);
+
+pub(crate) struct DragSession {
+  data: Option<Box<dyn Any>>,
+  source: Option<WidgetId>,
+  allowed_effects: DropEffect,
+  drop_effect: DropEffect,
+  preview: Option<(GenWidget, Vector)>,
+  preview_pos: Option<Stateful<Point>>,
+  overlay: Option<Overlay>,
+  /// The widget that the data is dragged over.
+  target: Option<WidgetId>,
+  /// The cursor before the drag starts.
+  cursor: CursorIcon,
+  /// The position where the pointer is pressed to start the drag.
+  origin: Point,
+  started: bool,
+  /// The external data is dropped, the data dropped later in the same batch
+  /// joins it.
+  dropping: bool,
+}
+
+impl DragEvent {
+  pub(crate) fn new(target: WidgetId, wnd: &Window, session: Sc<RefCell<DragSession>>) -> Self {
+    Self { common: CommonEvent::new(target, wnd.tree), session }
+  }
+
+  /// Set the data to drag, only works in the `DragStart` event. The widget
+  /// that sets the data becomes the drag source.
+  pub fn set_data<T: 'static>(&mut self, data: T) {
+    let id = self.current_target();
+    let mut session = self.session.borrow_mut();
+    if !session.started {
+      session.data = Some(Box::new(data));
+      session.source = Some(id);
+    }
+  }
+
+  /// Return the dragged data if it's the type `T`.
+  pub fn data<T: 'static>(&self) -> Option<Ref<'_, T>> {
+    Ref::filter_map(self.session.borrow(), |s| s.data.as_ref()?.downcast_ref::<T>()).ok()
+  }
+
+  /// Whether the dragged data is the type `T`.
+  pub fn has_data<T: 'static>(&self) -> bool { self.data::<T>().is_some() }
+
+  /// The paths of the files dragged from other applications.
+  pub fn files(&self) -> Option<Ref<'_, [PathBuf]>> {
+    Ref::filter_map(self.session.borrow(), |s| match s.data.as_ref()?.downcast_ref()? {
+      ExternalData::Files(files) => Some(&files[..]),
+      ExternalData::Text(_) => None,
+    })
+    .ok()
+  }
+
+  /// The text dragged from other applications.
+  pub fn text(&self) -> Option<Ref<'_, str>> {
+    Ref::filter_map(self.session.borrow(), |s| match s.data.as_ref()?.downcast_ref()? {
+      ExternalData::Text(text) => Some(&text[..]),
+      ExternalData::Files(_) => None,
+    })
+    .ok()
+  }
+
+  /// The widget that sets the dragged data.
+  pub fn source(&self) -> Option<WidgetId> { self.session.borrow().source }
+
+  /// Specify the effects that the drag source allows, `DropEffect::ALL` by
+  /// default.
+  pub fn set_allowed_effects(&mut self, effects: DropEffect) {
+    self.session.borrow_mut().allowed_effects = effects;
+  }
+
+  pub fn allowed_effects(&self) -> DropEffect { self.session.borrow().allowed_effects }
+
+  /// Accept the drop with one of `COPY`, `MOVE` and `LINK`. The effect is reset
+  /// to `NONE` before every `DragOver` event, so the drop target should call
+  /// this method in the `DragOver` handler every time.
+  ///
+  /// If the drag source not allows the effect, the drop is not allowed.
+  pub fn set_drop_effect(&mut self, effect: DropEffect) {
+    let mut session = self.session.borrow_mut();
+    session.drop_effect =
+      if session.allowed_effects.contains(effect) { effect } else { DropEffect::NONE };
+  }
+
+  /// The effect that the drop target picks. In the `DragEnd` event, it's the
+  /// effect of the drop, `NONE` means the drag is canceled or the data is not
+  /// dropped.
+  pub fn drop_effect(&self) -> DropEffect { self.session.borrow().drop_effect }
+
+  /// Set the widget that follows the pointer during the drag, only works in
+  /// the `DragStart` event. The preview keeps the offset between the pressed
+  /// position and the current target, so it looks like the current target is
+  /// dragged.
+  pub fn set_drag_preview(&mut self, preview: impl Into<GenWidget>) {
+    let origin = self.session.borrow().origin;
+    let offset = self.map_from_global(origin).to_vector();
+    let mut session = self.session.borrow_mut();
+    if !session.started {
+      session.preview = Some((preview.into(), offset));
+    }
+  }
+}
+
+impl Default for DragSession {
+  fn default() -> Self {
+    Self {
+      data: None,
+      source: None,
+      allowed_effects: DropEffect::ALL,
+      drop_effect: DropEffect::NONE,
+      preview: None,
+      preview_pos: None,
+      overlay: None,
+      target: None,
+      cursor: CursorIcon::Default,
+      origin: Point::zero(),
+      started: false,
+      dropping: false,
+    }
+  }
+}
+
+impl DragSession {
+  /// Create a session of the data dragged from other applications.
+  pub(crate) fn external(data: ExternalData, cursor: CursorIcon) -> Self {
+    Self { data: Some(Box::new(data)), cursor, started: true, ..Default::default() }
+  }
+
+  /// Whether the data is dragged from other applications and not dropped yet.
+  pub(crate) fn is_external_hovering(&self) -> bool { self.source.is_none() && !self.dropping }
+
+  /// More data is dragged into the window. The platform reports the files one
+  /// by one, so they are collected together.
+  pub(crate) fn hover_external(&mut self, data: ExternalData) {
+    if self.is_external_hovering() {
+      self.join_external(data);
+    }
+  }
+
+  /// The external data is dropped, return whether it's the first data of the
+  /// drop, then the drag should finish.
+  pub(crate) fn drop_external(&mut self, data: ExternalData) -> bool {
+    if self.source.is_some() {
+      false
+    } else if self.dropping {
+      self.join_external(data);
+      false
+    } else {
+      // The dropped data replaces the hovered data, they may be different.
+      self.data = Some(Box::new(data));
+      self.dropping = true;
+      true
+    }
+  }
+
+  fn join_external(&mut self, data: ExternalData) {
+    let old = self
+      .data
+      .as_mut()
+      .and_then(|d| d.downcast_mut::<ExternalData>());
+    match (old, data) {
+      (Some(ExternalData::Files(files)), ExternalData::Files(new)) => files.extend(new),
+      (_, data) => self.data = Some(Box::new(data)),
+    }
+  }
+}
+
+impl Window {
+  /// Try to start a drag from the pressed widget, the drag starts if a
+  /// `DragStart` handler sets the data.
+  pub(crate) fn start_drag(&self, id: WidgetId, origin: Point) {
+    let pressed = {
+      let dispatcher = self.dispatcher.borrow();
+      dispatcher.drag.is_none()
+        && dispatcher
+          .info
+          .mouse_buttons()
+          .contains(MouseButtons::PRIMARY)
+    };
+    if !pressed || id.is_dropped(self.tree()) {
+      return;
+    }
+
+    let session = Sc::new(RefCell::new(DragSession { origin, ..Default::default() }));
+    let mut e = Event::DragStart(DragEvent::new(id, self, session.clone()));
+    self.bottom_up_emit(&mut e, None);
+    if session.borrow().data.is_none() {
+      return;
+    }
+
+    // The pressed widget will not receive the pointer up event.
+    self.add_delay_event(DelayEvent::PointerCancel(id));
+    let mut session_ref = session.borrow_mut();
+    session_ref.started = true;
+    session_ref.cursor = self.get_cursor();
+    drop(session_ref);
+    self.show_drag_preview(&session);
+    let hit = {
+      let mut dispatcher = self.dispatcher.borrow_mut();
+      dispatcher.drag = Some(session);
+      dispatcher.hit_widget()
+    };
+    self.add_delay_event(DelayEvent::DragMove(hit));
+  }
+
+  /// The pointer moves to the `hit` widget during the drag.
+  pub(crate) fn drag_move(&self, hit: Option<WidgetId>) {
+    let Some(session) = self.dispatcher.borrow().drag.clone() else { return };
+    let tree = self.tree();
+    let hit = hit.filter(|id| !id.is_dropped(tree));
+    let old = session
+      .borrow()
+      .target
+      .filter(|id| !id.is_dropped(tree));
+
+    if old != hit {
+      let ancestor = old
+        .zip(hit)
+        .and_then(|(o, h)| o.lowest_common_ancestor(h, tree));
+      if let Some(old) = old {
+        let mut e = Event::DragLeave(DragEvent::new(old, self, session.clone()));
+        self.bottom_up_emit(&mut e, ancestor);
+      }
+      if let Some(hit) = hit {
+        let top = ancestor.unwrap_or_else(|| tree.root());
+        let mut e = Event::DragEnter(DragEvent::new(top, self, session.clone()));
+        self.top_down_emit(&mut e, hit);
+      }
+    }
+
+    let pos = self.dispatcher.borrow().info.global_pos();
+    let preview = session
+      .borrow()
+      .preview_pos
+      .as_ref()
+      .map(|p| p.clone_writer());
+    if let Some(preview) = preview {
+      *preview.write() = pos;
+    }
+
+    let mut session_ref = session.borrow_mut();
+    session_ref.target = hit;
+    session_ref.drop_effect = DropEffect::NONE;
+    drop(session_ref);
+
+    if let Some(hit) = hit {
+      let mut e = Event::DragOver(DragEvent::new(hit, self, session.clone()));
+      self.bottom_up_emit(&mut e, None);
+      if self.auto_scroll(hit, pos) {
+        // Keep scrolling while the pointer stays near the edge.
+        let wnd_id = self.id();
+        self.once_frame_finished(move || {
+          if let Some(wnd) = AppCtx::get_window(wnd_id) {
+            let hit = wnd.dispatcher.borrow().hit_widget();
+            wnd.add_delay_event(DelayEvent::DragMove(hit));
+          }
+        });
+      }
+    }
+
+    let cursor = match session.borrow().drop_effect {
+      DropEffect::COPY => CursorIcon::Copy,
+      DropEffect::MOVE => CursorIcon::Move,
+      DropEffect::LINK => CursorIcon::Alias,
+      _ => CursorIcon::NoDrop,
+    };
+    self.set_cursor(cursor);
+  }
+
+  /// Drop the data on the widget under the pointer, or cancel the drag.
+  pub(crate) fn finish_drag(&self, cancel: bool) {
+    let Some(session) = self.dispatcher.borrow_mut().drag.take() else { return };
+    let tree = self.tree();
+    let target = session
+      .borrow()
+      .target
+      .filter(|id| !id.is_dropped(tree));
+    if cancel {
+      session.borrow_mut().drop_effect = DropEffect::NONE;
+    }
+
+    if let Some(target) = target {
+      let accepted = session.borrow().drop_effect != DropEffect::NONE;
+      let e = DragEvent::new(target, self, session.clone());
+      let mut e = if accepted { Event::Drop(e) } else { Event::DragLeave(e) };
+      self.bottom_up_emit(&mut e, None);
+    }
+
+    let (source, overlay, cursor) = {
+      let mut s = session.borrow_mut();
+      (s.source, s.overlay.take(), s.cursor)
+    };
+    if let Some(overlay) = overlay {
+      overlay.close();
+    }
+    self.set_cursor(cursor);
+    if let Some(source) = source.filter(|id| !id.is_dropped(tree)) {
+      let mut e = Event::DragEnd(DragEvent::new(source, self, session));
+      self.bottom_up_emit(&mut e, None);
+    }
+  }
+
+  fn show_drag_preview(&self, session: &Sc<RefCell<DragSession>>) {
+    let Some((preview, offset)) = session.borrow().preview.clone() else { return };
+    let Some(wnd) = AppCtx::get_window(self.id()) else { return };
+
+    let pos = Stateful::new(self.dispatcher.borrow().info.global_pos());
+    let pos_writer = pos.clone_writer();
+    let overlay = Overlay::new(
+      fn_widget! {
+        @IgnorePointer {
+          anchor: pipe!(Anchor::from_point(*$pos - offset)),
+          @ { preview.gen_widget() }
+        }
+      },
+      OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+    );
+    overlay.show(wnd);
+
+    let mut session = session.borrow_mut();
+    session.preview_pos = Some(pos_writer);
+    session.overlay = Some(overlay);
+  }
+
+  /// Scroll the nearest scroll view of the `hit` widget if the position is
+  /// near its edges, return whether it scrolled.
+  fn auto_scroll(&self, hit: WidgetId, pos: Point) -> bool {
+    let tree = self.tree();
+    let ctx = ProviderCtx::collect_from(hit, tree);
+    let Some(scrollable) = Provider::of::<ScrollableWidget>(&ctx) else { return false };
+    let Some(pos) = scrollable.map_to_view(pos, tree.root(), self) else { return false };
+
+    let size = scrollable.scroll_view_size();
+    if !Rect::from_size(size).contains(pos) {
+      return false;
+    }
+    let step = |pos: f32, len: f32| {
+      if pos < AUTO_SCROLL_EDGE {
+        -(AUTO_SCROLL_EDGE - pos) / AUTO_SCROLL_EDGE * AUTO_SCROLL_MAX_STEP
+      } else if pos > len - AUTO_SCROLL_EDGE {
+        (pos - len + AUTO_SCROLL_EDGE) / AUTO_SCROLL_EDGE * AUTO_SCROLL_MAX_STEP
+      } else {
+        0.
+      }
+    };
+    let x = if scrollable.is_x_scrollable() { step(pos.x, size.width) } else { 0. };
+    let y = if scrollable.is_y_scrollable() { step(pos.y, size.height) } else { 0. };
+
+    let old = scrollable.get_scroll_pos();
+    let max = scrollable.max_scrollable();
+    let new = (old + Vector::new(x, y)).clamp(Point::zero(), max);
+    drop(scrollable);
+
+    let scroll = new != old;
+    if scroll {
+      if let Some(mut scrollable) = Provider::write_of::<ScrollableWidget>(&ctx) {
+        scrollable.jump_to(new);
+      }
+    }
+    scroll
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use winit::event::ElementState;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  fn drag_widget(
+    allowed: DropEffect, effect: DropEffect, log: Stateful<Vec<String>>,
+  ) -> impl Fn() -> Widget<'static> {
+    move || {
+      let log = log.clone_writer();
+      fn_widget! {
+        @MockMulti {
+          @MockBox {
+            size: Size::new(50., 50.),
+            on_tap: move |_| $log.write().push("tap".into()),
+            on_pointer_cancel: move |_| $log.write().push("pointer cancel".into()),
+            on_drag_start: move |e| {
+              e.set_data("Hello");
+              e.set_allowed_effects(allowed);
+              $log.write().push("start".into());
+            },
+            on_drag_end: move |e| $log.write().push(format!("end {:?}", e.drop_effect())),
+          }
+          @MockBox {
+            size: Size::new(50., 50.),
+            on_drag_enter: move |_| $log.write().push("enter".into()),
+            on_drag_leave: move |_| $log.write().push("leave".into()),
+            on_drag_over: move |e| {
+              if e.has_data::<&str>() {
+                e.set_drop_effect(effect);
+              }
+            },
+            on_drop: move |e| $log.write().push(format!("drop {}", *e.data::<&str>().unwrap())),
+          }
+        }
+      }
+      .into_widget()
+    }
+  }
+
+  #[test]
+  fn drag_and_drop() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let w = drag_widget(DropEffect::ALL, DropEffect::COPY, log.clone_writer());
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    wnd.drag_and_drop(Point::new(25., 25.), Point::new(75., 25.));
+    assert_eq!(
+      &*log.read(),
+      &["start", "pointer cancel", "enter", "drop Hello", "end DropEffect(COPY)"]
+    );
+
+    // A tap not starts a drag.
+    log.write().clear();
+    wnd.mouse_move_to(Point::new(25., 25.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(26., 26.));
+    wnd.mouse_release();
+    assert_eq!(&*log.read(), &["tap"]);
+  }
+
+  #[test]
+  fn drop_effect_not_allowed() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let w = drag_widget(DropEffect::MOVE, DropEffect::COPY, log.clone_writer());
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    wnd.mouse_move_to(Point::new(25., 25.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(75., 25.));
+    assert_eq!(wnd.get_cursor(), CursorIcon::NoDrop);
+
+    wnd.mouse_release();
+    assert_eq!(wnd.get_cursor(), CursorIcon::Default);
+    assert_eq!(&*log.read(), &["start", "pointer cancel", "enter", "leave", "end DropEffect(0x0)"]);
+  }
+
+  #[test]
+  fn cancel_drag_by_escape() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let w = drag_widget(DropEffect::ALL, DropEffect::MOVE, log.clone_writer());
+    let mut wnd = TestWindow::new(w);
+    wnd.draw_frame();
+
+    wnd.mouse_move_to(Point::new(25., 25.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(75., 25.));
+    assert_eq!(wnd.get_cursor(), CursorIcon::Move);
+
+    wnd.processes_keyboard_event(
+      PhysicalKey::Code(KeyCode::Escape),
+      VirtualKey::Named(NamedKey::Escape),
+      false,
+      KeyLocation::Standard,
+      ElementState::Pressed,
+    );
+    wnd.run_frame_tasks();
+    wnd.mouse_release();
+    assert_eq!(&*log.read(), &["start", "pointer cancel", "enter", "leave", "end DropEffect(0x0)"]);
+  }
+
+  #[test]
+  fn drag_preview() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockBox {
+        size: Size::new(50., 50.),
+        on_drag_start: move |e| {
+          e.set_data(1);
+          e.set_drag_preview(fn_widget! { @MockBox { size: Size::new(20., 20.) } });
+        },
+      }
+    });
+    wnd.draw_frame();
+    let root = wnd.tree().root();
+    assert_eq!(root.children(wnd.tree()).count(), 1);
+
+    wnd.mouse_move_to(Point::new(10., 10.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(30., 40.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(root.children(wnd.tree()).count(), 2);
+    assert_eq!(wnd.layout_info_by_path(&[1]).unwrap().pos, Point::new(20., 30.));
+
+    wnd.mouse_move_to(Point::new(60., 60.));
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[1]).unwrap().pos, Point::new(50., 50.));
+
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(root.children(wnd.tree()).count(), 1);
+  }
+
+  #[test]
+  fn auto_scroll() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(
+      fn_widget! {
+        @MockBox {
+          size: Size::new(100., 1000.),
+          scrollable: Scrollable::Y,
+          on_drag_start: move |e| e.set_data(1),
+        }
+      },
+      Size::new(100., 100.),
+    );
+    wnd.draw_frame();
+
+    wnd.mouse_move_to(Point::new(50., 50.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(50., 60.));
+    wnd.mouse_move_to(Point::new(50., 94.));
+    wnd.draw_frame();
+    let y1 = wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y;
+    assert!(y1 < 0.);
+
+    // Keep scrolling while the pointer stays near the edge.
+    wnd.draw_frame();
+    let y2 = wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y;
+    assert!(y2 < y1);
+
+    wnd.mouse_release();
+    wnd.draw_frame();
+    let y3 = wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y;
+    wnd.draw_frame();
+    assert_eq!(wnd.layout_info_by_path(&[0, 0]).unwrap().pos.y, y3);
+  }
+
+  fn external_drop_widget(log: Stateful<Vec<String>>) -> impl Fn() -> Widget<'static> {
+    move || {
+      let log = log.clone_writer();
+      fn_widget! {
+        @MockMulti {
+          @MockBox { size: Size::new(50., 50.) }
+          @MockBox {
+            size: Size::new(50., 50.),
+            on_drag_enter: move |_| $log.write().push("enter".into()),
+            on_drag_leave: move |_| $log.write().push("leave".into()),
+            on_drag_over: move |e| {
+              if e.files().is_some() {
+                e.set_drop_effect(DropEffect::COPY);
+              }
+            },
+            on_drop: move |e| {
+              let msg = match (e.files(), e.text()) {
+                (Some(files), _) => format!("drop {files:?}"),
+                (_, Some(text)) => format!("drop {text}"),
+                _ => unreachable!(),
+              };
+              $log.write().push(msg);
+            },
+          }
+        }
+      }
+      .into_widget()
+    }
+  }
+
+  #[test]
+  fn external_files_drop() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let mut wnd = TestWindow::new(external_drop_widget(log.clone_writer()));
+    wnd.draw_frame();
+
+    wnd.mouse_move_to(Point::new(10., 10.));
+    wnd.process_external_drag(ExternalData::Files(vec!["a.txt".into()]));
+    wnd.process_external_drag(ExternalData::Files(vec!["b.txt".into()]));
+    wnd.run_frame_tasks();
+    assert!(log.read().is_empty());
+
+    wnd.mouse_move_to(Point::new(60., 10.));
+    assert_eq!(wnd.get_cursor(), CursorIcon::Copy);
+
+    wnd.process_external_drop(ExternalData::Files(vec!["a.txt".into()]));
+    wnd.process_external_drop(ExternalData::Files(vec!["b.txt".into()]));
+    wnd.run_frame_tasks();
+    assert_eq!(&*log.read(), &["enter", "drop [\"a.txt\", \"b.txt\"]"]);
+    assert_eq!(wnd.get_cursor(), CursorIcon::Default);
+  }
+
+  #[test]
+  fn external_drag_cancel() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let mut wnd = TestWindow::new(external_drop_widget(log.clone_writer()));
+    wnd.draw_frame();
+
+    wnd.mouse_move_to(Point::new(60., 10.));
+    wnd.process_external_drag(ExternalData::Files(vec!["a.txt".into()]));
+    wnd.run_frame_tasks();
+    wnd.process_external_drag_cancel();
+    wnd.run_frame_tasks();
+    assert_eq!(&*log.read(), &["enter", "leave"]);
+  }
+
+  #[test]
+  fn external_text_drop_without_hover() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let mut wnd = TestWindow::new(external_drop_widget(log.clone_writer()));
+    wnd.draw_frame();
+
+    // The widget only accepts files.
+    wnd.mouse_move_to(Point::new(60., 10.));
+    wnd.process_external_drop(ExternalData::Text("Hello".into()));
+    wnd.run_frame_tasks();
+    assert_eq!(&*log.read(), &["enter", "leave"]);
+
+    log.write().clear();
+    wnd.process_external_drop(ExternalData::Files(vec!["a.txt".into()]));
+    wnd.run_frame_tasks();
+    assert_eq!(&*log.read(), &["enter", "drop [\"a.txt\"]"]);
+  }
+}
@@ -192,21 +192,38 @@ impl FocusManager {
     let mut node_id = focusing
       .and_then(|id| self.node_ids.get(&id))
       .copied();
-    let mut scope_id = node_id
-      .and_then(|id| self.scope_id(id))
-      .or(Some(self.root));
+    let mut scope_id = match node_id {
+      // The focused host of a trap scope steps into its descendants.
+      Some(id) if self.is_trap_scope(id) => node_id.take(),
+      Some(id) => self.scope_id(id),
+      None => Some(self.root),
+    };
     loop {
-      scope_id?;
-      let next = self.focus_step_in_scope(scope_id.unwrap(), node_id, backward);
+      let scope = scope_id?;
+      let next = self.focus_step_in_scope(scope, node_id, backward);
       if let Some(id) = next {
         return self.get(id).and_then(|n| n.wid);
+      } else if self.is_trap_scope(scope) {
+        // Cycle back to the host if it's focusable, or the first one in the scope.
+        let node = self.assert_get(scope);
+        if node.has_focus_node() && !self.scope_property(node.wid).skip_host {
+          return node.wid;
+        }
+        let first = self.focus_step_in_scope(scope, None, backward);
+        return first.and_then(|id| self.get(id).and_then(|n| n.wid));
       } else {
         node_id = scope_id;
-        scope_id = self.scope_id(node_id.unwrap());
+        scope_id = self.scope_id(scope);
       }
     }
   }
 
+  fn is_trap_scope(&self, node_id: NodeId) -> bool {
+    self
+      .get(node_id)
+      .is_some_and(|n| n.has_focus_scope() && self.scope_property(n.wid).trap_focus)
+  }
+
   fn collect_tab_index_in_scope(
     &self, scope_id: NodeId, backward: bool,
   ) -> Vec<(i16, NodeId, FocusType)> {
@@ -508,7 +525,7 @@ impl FocusManager {
   // to None and the focused widget requests blur, it will refresh focus
   // immediately because the widget may be in a disposed state and the widget
   // will be removed soon.
-  fn request_focus_to(&mut self, focus_to: Option<WidgetId>) {
+  pub(crate) fn request_focus_to(&mut self, focus_to: Option<WidgetId>) {
     self.request_focusing = Some(focus_to);
   }
 
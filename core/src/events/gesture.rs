@@ -0,0 +1,866 @@
+use std::collections::VecDeque;
+
+use rxrust::prelude::*;
+
+use crate::{impl_common_event_deref, prelude::*};
+
+/// The distance in logical pixels that the pointers must move before a pan,
+/// pinch or rotate gesture is recognized.
+pub(crate) const GESTURE_SLOP: f32 = 8.;
+/// The duration that a pointer must be held without moving to trigger a long
+/// press.
+pub const LONG_PRESS_DURATION: Duration = Duration::from_millis(500);
+/// The movement in this duration before the latest pointer event is used to
+/// compute the velocity of the gestures.
+const VELOCITY_WINDOW: Duration = Duration::from_millis(100);
+
+/// The phase of a continuous gesture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GesturePhase {
+  /// The gesture is recognized.
+  Start,
+  /// The pointers move during the gesture.
+  Update,
+  /// The pointers are lifted and the gesture finishes.
+  End,
+  /// The pointers are canceled, the gesture should be reverted.
+  Cancel,
+}
+
+/// The event of a pan gesture, fired when the pointers are pressed and moved
+/// a few pixels. It tracks the center of all the pressed pointers.
+///
+/// # Gesture arbitration
+///
+/// All the widgets from the pressed widget to the root that listen to the
+/// gestures compete for a pointer sequence. The first widget whose gesture is
+/// recognized wins, from the innermost widget to the outermost, and only the
+/// winner receives the gesture events until all the pointers are lifted. The
+/// tap is not fired if a gesture is recognized in the sequence.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::{prelude::*, test_helper::*};
+///
+/// let _w = fn_widget! {
+///   let offset = Stateful::new(Vector::zero());
+///   @MockBox {
+///     size: Size::new(100., 100.),
+///     anchor: pipe!(Anchor::from_point($offset.to_point())),
+///     on_pan: move |e| *$offset.write() += e.delta,
+///   }
+/// };
+/// ```
+pub struct PanEvent {
+  pub phase: GesturePhase,
+  /// The movement since the last pan event.
+  pub delta: Vector,
+  /// The movement since the pointers are pressed.
+  pub offset: Vector,
+  /// The velocity in logical pixels per second.
+  pub velocity: Vector,
+  /// The number of the pressed pointers.
+  pub pointer_count: usize,
+  pub common: CommonEvent,
+  focal: Point,
+}
+
+/// The event of a pinch gesture, fired when two or more pointers move toward
+/// or away from each other. See [`PanEvent`] for the arbitration of the
+/// gestures.
+pub struct PinchEvent {
+  pub phase: GesturePhase,
+  /// The scale since the gesture starts, `1.` is the original size.
+  pub scale: f32,
+  /// The scale factor since the last pinch event.
+  pub delta: f32,
+  /// The change of the scale per second.
+  pub velocity: f32,
+  pub common: CommonEvent,
+  focal: Point,
+}
+
+/// The event of a rotate gesture, fired when two or more pointers rotate
+/// around their center. The angles are in radians and clockwise. See
+/// [`PanEvent`] for the arbitration of the gestures.
+pub struct RotateEvent {
+  pub phase: GesturePhase,
+  /// The rotation since the gesture starts.
+  pub rotation: f32,
+  /// The rotation since the last rotate event.
+  pub delta: f32,
+  /// The angular velocity in radians per second.
+  pub velocity: f32,
+  pub common: CommonEvent,
+  focal: Point,
+}
+
+/// The event fired when a pointer is held on the widget for
+/// [`LONG_PRESS_DURATION`] without moving. See [`PanEvent`] for the
+/// arbitration of the gestures.
+pub struct LongPressEvent {
+  /// The pointer that is held.
+  pub pointer: PointerId,
+  pub common: CommonEvent,
+  focal: Point,
+}
+
+impl_common_event_deref!(PanEvent);
+impl_common_event_deref!(PinchEvent);
+impl_common_event_deref!(RotateEvent);
+impl_common_event_deref!(LongPressEvent);
+
+macro_rules! impl_gesture_focal {
+  ($($ty:ty),*) => {
+    $(
+      impl $ty {
+        /// The center of the pressed pointers in global (window) coordinates.
+        #[inline]
+        pub fn global_pos(&self) -> Point { self.focal }
+
+        /// The center of the pressed pointers in current target widget.
+        #[inline]
+        pub fn position(&self) -> Point { self.map_from_global(self.focal) }
+      }
+    )*
+  };
+}
+
+impl_gesture_focal!(PanEvent, PinchEvent, RotateEvent, LongPressEvent);
+
+/// A gesture event waiting to be emitted to the widget. The event is created
+/// just before it's emitted, since only one event can collect the providers of
+/// the widget at a time.
+type PendingGesture = (WidgetId, Box<dyn FnOnce(CommonEvent) -> Event>);
+
+/// Recognize the gestures from the pointer events and decide which widget
+/// receives them.
+#[derive(Default)]
+pub(crate) struct GestureArena {
+  pointers: Vec<(PointerId, Point)>,
+  /// The widgets that listen to the gestures, from the pressed widget to the
+  /// root.
+  members: Vec<WidgetId>,
+  winner: Option<WidgetId>,
+  /// Whether a gesture is recognized in the current or the last pointer
+  /// sequence, the tap of the sequence is not fired.
+  claimed: bool,
+  /// The id of the pointer sequence, to ignore the outdated long press timer.
+  sequence: usize,
+  long_press: Option<TaskHandle<NormalReturn<()>>>,
+  track: GestureTrack,
+  samples: VecDeque<GestureSample>,
+  pan_started: bool,
+  pinch_started: bool,
+  rotate_started: bool,
+}
+
+#[derive(Clone, Copy)]
+struct GestureTrack {
+  centroid: Point,
+  /// The average distance from the pointers to their center.
+  spread: f32,
+  /// The angle from the first pointer to the second pointer.
+  angle: f32,
+  offset: Vector,
+  scale: f32,
+  rotation: f32,
+  /// The accumulated change of the spread in pixels.
+  spread_moved: f32,
+}
+
+struct GestureSample {
+  at: Instant,
+  offset: Vector,
+  scale: f32,
+  rotation: f32,
+}
+
+impl Default for GestureTrack {
+  fn default() -> Self {
+    Self {
+      centroid: Point::zero(),
+      spread: 0.,
+      angle: 0.,
+      offset: Vector::zero(),
+      scale: 1.,
+      rotation: 0.,
+      spread_moved: 0.,
+    }
+  }
+}
+
+fn gesture_flags(id: WidgetId, tree: &WidgetTree) -> MixFlags {
+  let gestures =
+    [MixFlags::Pan, MixFlags::Pinch, MixFlags::Rotate, MixFlags::LongPress, MixFlags::ContextMenu];
+  id.query_all_iter::<MixBuiltin>(tree)
+    .fold(MixFlags::empty(), |mut flags, m| {
+      gestures
+        .iter()
+        .filter(|g| m.contain_flag(**g))
+        .for_each(|g| flags |= *g);
+      flags
+    })
+}
+
+/// The listeners that a long press of the pointer fires.
+fn long_press_flags(pointer: PointerId) -> MixFlags {
+  if pointer == PointerId::MOUSE {
+    MixFlags::LongPress
+  } else {
+    MixFlags::LongPress | MixFlags::ContextMenu
+  }
+}
+
+/// Normalize the angle to the range of `-PI` to `PI`.
+fn normalize_angle(angle: f32) -> f32 {
+  use std::f32::consts::PI;
+  let angle = angle % (2. * PI);
+  if angle > PI {
+    angle - 2. * PI
+  } else if angle < -PI {
+    angle + 2. * PI
+  } else {
+    angle
+  }
+}
+
+impl GestureArena {
+  fn pointer_down(&mut self, wnd: &Window, hit: WidgetId, pointer: PointerId, pos: Point) {
+    // The pointer up may be lost if it's released outside all the widgets.
+    self.pointers.retain(|(id, _)| *id != pointer);
+    if self.pointers.is_empty() {
+      self.reset();
+      self.sequence += 1;
+      self.claimed = false;
+      let tree = wnd.tree();
+      self.members = hit
+        .ancestors(tree)
+        .filter(|id| !gesture_flags(*id, tree).is_empty())
+        .collect();
+      let long_press = self
+        .members
+        .iter()
+        .any(|id| long_press_flags(pointer).intersects(gesture_flags(*id, tree)));
+      if long_press {
+        let wnd_id = wnd.id();
+        let sequence = self.sequence;
+        let handle =
+          observable::timer((), LONG_PRESS_DURATION, AppCtx::scheduler()).subscribe(move |_| {
+            if let Some(wnd) = AppCtx::get_window(wnd_id) {
+              wnd.gesture_long_press(sequence);
+            }
+          });
+        self.long_press = Some(handle);
+      }
+    } else {
+      self.cancel_long_press();
+    }
+    self.pointers.push((pointer, pos));
+    self.rebase();
+  }
+
+  fn pointer_move(&mut self, wnd: &Window, pointer: PointerId, pos: Point) -> Vec<PendingGesture> {
+    let Some(p) = self
+      .pointers
+      .iter_mut()
+      .find(|(id, _)| *id == pointer)
+    else {
+      return vec![];
+    };
+    if p.1 == pos {
+      return vec![];
+    }
+    p.1 = pos;
+
+    let (centroid, spread, angle) = self.geometry();
+    let track = &mut self.track;
+    let delta = centroid - track.centroid;
+    track.centroid = centroid;
+    track.offset += delta;
+    let mut scale_delta = 1.;
+    let mut rotate_delta = 0.;
+    if self.pointers.len() >= 2 {
+      if track.spread > 0. {
+        scale_delta = spread / track.spread;
+      }
+      rotate_delta = normalize_angle(angle - track.angle);
+      track.spread_moved += (spread - track.spread).abs();
+      track.scale *= scale_delta;
+      track.rotation += rotate_delta;
+      track.spread = spread;
+      track.angle = angle;
+    }
+    self.record_sample();
+    if self.track.offset.length() > GESTURE_SLOP {
+      self.cancel_long_press();
+    }
+
+    let tree = wnd.tree();
+    if self.winner.is_none() {
+      self.winner = self
+        .members
+        .iter()
+        .copied()
+        .filter(|id| !id.is_dropped(tree))
+        .find(|id| self.recognized(gesture_flags(*id, tree)));
+      if self.winner.is_some() {
+        self.claimed = true;
+        self.cancel_long_press();
+      }
+    }
+    let Some(winner) = self.winner.filter(|id| !id.is_dropped(tree)) else { return vec![] };
+
+    let flags = gesture_flags(winner, tree);
+    let multi = self.pointers.len() >= 2;
+    let mut events = vec![];
+    if flags.contains(MixFlags::Pan) {
+      if self.pan_started {
+        events.push(self.pan_event(winner, GesturePhase::Update, delta));
+      } else if self.track.offset.length() > GESTURE_SLOP {
+        self.pan_started = true;
+        let offset = self.track.offset;
+        events.push(self.pan_event(winner, GesturePhase::Start, offset));
+      }
+    }
+    if flags.contains(MixFlags::Pinch) && multi {
+      if self.pinch_started {
+        events.push(self.pinch_event(winner, GesturePhase::Update, scale_delta));
+      } else if self.pinch_recognized() {
+        self.pinch_started = true;
+        let scale = self.track.scale;
+        events.push(self.pinch_event(winner, GesturePhase::Start, scale));
+      }
+    }
+    if flags.contains(MixFlags::Rotate) && multi {
+      if self.rotate_started {
+        events.push(self.rotate_event(winner, GesturePhase::Update, rotate_delta));
+      } else if self.rotate_recognized() {
+        self.rotate_started = true;
+        let rotation = self.track.rotation;
+        events.push(self.rotate_event(winner, GesturePhase::Start, rotation));
+      }
+    }
+    events
+  }
+
+  fn pointer_up(&mut self, wnd: &Window, pointer: PointerId) -> Vec<PendingGesture> {
+    let len = self.pointers.len();
+    self.pointers.retain(|(id, _)| *id != pointer);
+    if len == self.pointers.len() {
+      return vec![];
+    }
+
+    let mut events = vec![];
+    let winner = self
+      .winner
+      .filter(|id| !id.is_dropped(wnd.tree()));
+    if let Some(winner) = winner {
+      if self.pointers.len() < 2 {
+        if self.pinch_started {
+          self.pinch_started = false;
+          events.push(self.pinch_event(winner, GesturePhase::End, 1.));
+        }
+        if self.rotate_started {
+          self.rotate_started = false;
+          events.push(self.rotate_event(winner, GesturePhase::End, 0.));
+        }
+      }
+      if self.pointers.is_empty() && self.pan_started {
+        self.pan_started = false;
+        events.push(self.pan_event(winner, GesturePhase::End, Vector::zero()));
+      }
+    }
+
+    if self.pointers.is_empty() {
+      self.reset();
+    } else {
+      self.rebase();
+    }
+    events
+  }
+
+  fn pointer_cancel(&mut self, wnd: &Window, pointer: PointerId) -> Vec<PendingGesture> {
+    if self.pointers.iter().all(|(id, _)| *id != pointer) {
+      return vec![];
+    }
+
+    let mut events = vec![];
+    let winner = self
+      .winner
+      .filter(|id| !id.is_dropped(wnd.tree()));
+    if let Some(winner) = winner {
+      let phase = GesturePhase::Cancel;
+      if self.pan_started {
+        events.push(self.pan_event(winner, phase, Vector::zero()));
+      }
+      if self.pinch_started {
+        events.push(self.pinch_event(winner, phase, 1.));
+      }
+      if self.rotate_started {
+        events.push(self.rotate_event(winner, phase, 0.));
+      }
+    }
+    // A canceled pointer cancels the whole sequence.
+    self.pointers.clear();
+    self.reset();
+    events
+  }
+
+  fn long_press(&mut self, wnd: &Window, sequence: usize) -> Option<PendingGesture> {
+    self.long_press = None;
+    if sequence != self.sequence
+      || self.winner.is_some()
+      || self.pointers.len() != 1
+      || self.track.offset.length() > GESTURE_SLOP
+    {
+      return None;
+    }
+
+    let tree = wnd.tree();
+    let (pointer, focal) = self.pointers[0];
+    let (winner, flags) = self
+      .members
+      .iter()
+      .copied()
+      .filter(|id| !id.is_dropped(tree))
+      .map(|id| (id, gesture_flags(id, tree)))
+      .find(|(_, flags)| long_press_flags(pointer).intersects(*flags))?;
+    self.winner = Some(winner);
+    self.claimed = true;
+    if flags.contains(MixFlags::LongPress) {
+      let event = move |common| Event::LongPress(LongPressEvent { pointer, common, focal });
+      Some((winner, Box::new(event)))
+    } else {
+      // A long press of the touch requests the context menu.
+      let event = move |common| Event::ContextMenu(ContextMenuEvent { global_pos: focal, common });
+      Some((winner, Box::new(event)))
+    }
+  }
+
+  fn recognized(&self, flags: MixFlags) -> bool {
+    let multi = self.pointers.len() >= 2;
+    (flags.contains(MixFlags::Pan) && self.track.offset.length() > GESTURE_SLOP)
+      || (multi && flags.contains(MixFlags::Pinch) && self.pinch_recognized())
+      || (multi && flags.contains(MixFlags::Rotate) && self.rotate_recognized())
+  }
+
+  fn pinch_recognized(&self) -> bool { self.track.spread_moved > GESTURE_SLOP }
+
+  fn rotate_recognized(&self) -> bool {
+    // The distance that the pointers move along the circle.
+    self.track.rotation.abs() * self.track.spread > GESTURE_SLOP
+  }
+
+  /// The center, the spread and the angle of the pressed pointers.
+  fn geometry(&self) -> (Point, f32, f32) {
+    let count = self.pointers.len().max(1) as f32;
+    let sum = self
+      .pointers
+      .iter()
+      .fold(Vector::zero(), |sum, (_, p)| sum + p.to_vector());
+    let centroid = (sum / count).to_point();
+    let spread = self
+      .pointers
+      .iter()
+      .map(|(_, p)| (*p - centroid).length())
+      .sum::<f32>()
+      / count;
+    let angle = match &self.pointers[..] {
+      [(_, a), (_, b), ..] => (*b - *a).angle_from_x_axis().radians,
+      _ => 0.,
+    };
+    (centroid, spread, angle)
+  }
+
+  /// Reset the tracking after the pointers changed, so the gestures continue
+  /// without jumping.
+  fn rebase(&mut self) {
+    let (centroid, spread, angle) = self.geometry();
+    let track = &mut self.track;
+    track.centroid = centroid;
+    if self.pointers.len() >= 2 {
+      track.spread = spread;
+      track.angle = angle;
+    } else {
+      track.spread = 0.;
+      track.scale = 1.;
+      track.rotation = 0.;
+      track.spread_moved = 0.;
+    }
+    self.samples.clear();
+    self.record_sample();
+  }
+
+  fn reset(&mut self) {
+    self.cancel_long_press();
+    self.members.clear();
+    self.winner = None;
+    self.track = GestureTrack::default();
+    self.samples.clear();
+    self.pan_started = false;
+    self.pinch_started = false;
+    self.rotate_started = false;
+  }
+
+  fn cancel_long_press(&mut self) {
+    if let Some(handle) = self.long_press.take() {
+      handle.unsubscribe();
+    }
+  }
+
+  fn record_sample(&mut self) {
+    let at = AppCtx::now();
+    let GestureTrack { offset, scale, rotation, .. } = self.track;
+    self
+      .samples
+      .push_back(GestureSample { at, offset, scale, rotation });
+    while self
+      .samples
+      .front()
+      .is_some_and(|s| at.duration_since(s.at) > VELOCITY_WINDOW)
+    {
+      self.samples.pop_front();
+    }
+  }
+
+  /// The velocity of the offset, the scale and the rotation per second.
+  fn velocity(&self) -> (Vector, f32, f32) {
+    let (Some(first), Some(last)) = (self.samples.front(), self.samples.back()) else {
+      return (Vector::zero(), 0., 0.);
+    };
+    let secs = last.at.duration_since(first.at).as_secs_f32();
+    if secs <= 0. {
+      return (Vector::zero(), 0., 0.);
+    }
+    (
+      (last.offset - first.offset) / secs,
+      (last.scale - first.scale) / secs,
+      (last.rotation - first.rotation) / secs,
+    )
+  }
+
+  fn pan_event(&self, id: WidgetId, phase: GesturePhase, delta: Vector) -> PendingGesture {
+    let offset = self.track.offset;
+    let velocity = self.velocity().0;
+    let pointer_count = self.pointers.len();
+    let focal = self.track.centroid;
+    let event = move |common| {
+      Event::Pan(PanEvent { phase, delta, offset, velocity, pointer_count, common, focal })
+    };
+    (id, Box::new(event))
+  }
+
+  fn pinch_event(&self, id: WidgetId, phase: GesturePhase, delta: f32) -> PendingGesture {
+    let scale = self.track.scale;
+    let velocity = self.velocity().1;
+    let focal = self.track.centroid;
+    let event =
+      move |common| Event::Pinch(PinchEvent { phase, scale, delta, velocity, common, focal });
+    (id, Box::new(event))
+  }
+
+  fn rotate_event(&self, id: WidgetId, phase: GesturePhase, delta: f32) -> PendingGesture {
+    let rotation = self.track.rotation;
+    let velocity = self.velocity().2;
+    let focal = self.track.centroid;
+    let event =
+      move |common| Event::Rotate(RotateEvent { phase, rotation, delta, velocity, common, focal });
+    (id, Box::new(event))
+  }
+}
+
+impl Window {
+  pub(crate) fn gesture_pointer_down(&self, hit: WidgetId, pointer: PointerId, pos: Point) {
+    self
+      .gesture_arena
+      .borrow_mut()
+      .pointer_down(self, hit, pointer, pos);
+  }
+
+  pub(crate) fn gesture_pointer_move(&self, pointer: PointerId, pos: Point) {
+    let events = self
+      .gesture_arena
+      .borrow_mut()
+      .pointer_move(self, pointer, pos);
+    self.emit_gesture_events(events);
+  }
+
+  pub(crate) fn gesture_pointer_up(&self, pointer: PointerId, pos: Point) {
+    // Track the last position before the pointer is lifted.
+    self.gesture_pointer_move(pointer, pos);
+    let events = self
+      .gesture_arena
+      .borrow_mut()
+      .pointer_up(self, pointer);
+    self.emit_gesture_events(events);
+  }
+
+  pub(crate) fn gesture_pointer_cancel(&self, pointer: PointerId) {
+    let events = self
+      .gesture_arena
+      .borrow_mut()
+      .pointer_cancel(self, pointer);
+    self.emit_gesture_events(events);
+  }
+
+  /// Whether a gesture is recognized in the last pointer sequence, then its
+  /// tap should not be fired.
+  pub(crate) fn is_gesture_claimed(&self) -> bool { self.gesture_arena.borrow().claimed }
+
+  /// Claim the current pointer sequence for a widget that tracks the pointers
+  /// by itself, like the drag scrolling, so the tap of the sequence is not
+  /// fired.
+  pub(crate) fn claim_gesture(&self) { self.gesture_arena.borrow_mut().claimed = true; }
+
+  fn gesture_long_press(&self, sequence: usize) {
+    let event = self
+      .gesture_arena
+      .borrow_mut()
+      .long_press(self, sequence);
+    self.emit_gesture_events(event);
+  }
+
+  fn emit_gesture_events(&self, events: impl IntoIterator<Item = PendingGesture>) {
+    for (id, event) in events {
+      let mut e = event(CommonEvent::new(id, self.tree));
+      self.emit(id, &mut e);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use std::f32::consts::FRAC_PI_2;
+
+  use super::*;
+  use crate::{reset_test_env, test_helper::*};
+
+  #[test]
+  fn touch_pointers() {
+    reset_test_env!();
+
+    let (downs, w_downs) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pointer_down: move |e| {
+          assert_eq!(e.point_type, PointerType::Touch);
+          $w_downs.write().push((e.id, e.is_primary, e.position()));
+        },
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    wnd.touch_start(7, Point::new(10., 10.));
+    wnd.touch_start(9, Point::new(50., 60.));
+    let downs = downs.read();
+    assert_eq!(downs.len(), 2);
+    assert_ne!(downs[0].0, downs[1].0);
+    assert_eq!((downs[0].1, downs[0].2), (true, Point::new(10., 10.)));
+    assert_eq!((downs[1].1, downs[1].2), (false, Point::new(50., 60.)));
+  }
+
+  #[test]
+  fn pan_with_velocity() {
+    reset_test_env!();
+
+    let (pans, w_pans) = split_value(vec![]);
+    let (taps, w_taps) = split_value(0);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(200., 200.),
+        on_pan: move |e| $w_pans.write().push((e.phase, e.offset, e.velocity)),
+        on_tap: move |_| *$w_taps.write() += 1,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    wnd.touch_start(0, Point::new(10., 10.));
+    // Not recognized in the slop.
+    wnd.touch_move(0, Point::new(14., 10.));
+    assert!(pans.read().is_empty());
+    for x in [30., 50., 70.] {
+      wnd.advance_time(Duration::from_millis(5));
+      wnd.touch_move(0, Point::new(x, 10.));
+    }
+    wnd.touch_end(0, Point::new(70., 10.));
+
+    let pans = pans.read();
+    let phases = pans.iter().map(|p| p.0).collect::<Vec<_>>();
+    use GesturePhase::*;
+    assert_eq!(phases, [Start, Update, Update, End]);
+    assert_eq!(pans[0].1, Vector::new(20., 0.));
+    let (_, offset, velocity) = pans[3];
+    assert_eq!(offset, Vector::new(60., 0.));
+    assert!(velocity.x > 0.);
+    assert_eq!(velocity.y, 0.);
+    // The tap is not fired after the pan.
+    assert_eq!(*taps.read(), 0);
+  }
+
+  #[test]
+  fn pinch_and_rotate() {
+    reset_test_env!();
+
+    let (scale, w_scale) = split_value(1.);
+    let (rotation, w_rotation) = split_value(0.);
+    let (offset, w_offset) = split_value(Vector::zero());
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(200., 200.),
+        on_pinch: move |e| *$w_scale.write() = e.scale,
+        on_rotate: move |e| *$w_rotation.write() = e.rotation,
+        on_pan: move |e| *$w_offset.write() = e.offset,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+
+    // Spread the fingers around the center (100, 100).
+    wnd.touch_start(0, Point::new(90., 100.));
+    wnd.touch_start(1, Point::new(110., 100.));
+    wnd.touch_move(0, Point::new(80., 100.));
+    wnd.touch_move(1, Point::new(120., 100.));
+    assert!((*scale.read() - 2.).abs() < 1e-4);
+
+    // Rotate the fingers a quarter turn clockwise.
+    wnd.touch_move(0, Point::new(100., 80.));
+    wnd.touch_move(1, Point::new(100., 120.));
+    assert!((*rotation.read() - FRAC_PI_2).abs() < 1e-4);
+    // The center is back to where it started.
+    assert!(offset.read().length() < 1e-4);
+
+    wnd.touch_end(0, Point::new(100., 80.));
+    wnd.touch_end(1, Point::new(100., 120.));
+  }
+
+  #[test]
+  fn inner_gesture_wins() {
+    reset_test_env!();
+
+    let (log, w_log) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockMulti {
+        on_pan: move |e| if e.phase == GesturePhase::Start {
+          $w_log.write().push("outer pan");
+        },
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pan: move |e| if e.phase == GesturePhase::Start {
+            $w_log.write().push("inner pan");
+          },
+        }
+        @MockBox {
+          size: Size::new(100., 100.),
+          on_pinch: move |e| if e.phase == GesturePhase::Start {
+            $w_log.write().push("inner pinch");
+          },
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 100.));
+    wnd.draw_frame();
+
+    wnd.touch_start(0, Point::new(10., 10.));
+    wnd.touch_move(0, Point::new(50., 10.));
+    wnd.touch_end(0, Point::new(50., 10.));
+    assert_eq!(*log.read(), ["inner pan"]);
+
+    // A single finger can't pinch, the outer pan wins.
+    wnd.touch_start(0, Point::new(110., 10.));
+    wnd.touch_move(0, Point::new(150., 10.));
+    wnd.touch_end(0, Point::new(150., 10.));
+    assert_eq!(*log.read(), ["inner pan", "outer pan"]);
+
+    // Two fingers pinch on the inner widget.
+    wnd.touch_start(0, Point::new(140., 50.));
+    wnd.touch_start(1, Point::new(160., 50.));
+    wnd.touch_move(0, Point::new(120., 50.));
+    wnd.touch_move(1, Point::new(180., 50.));
+    wnd.touch_end(0, Point::new(120., 50.));
+    wnd.touch_end(1, Point::new(180., 50.));
+    assert_eq!(*log.read(), ["inner pan", "outer pan", "inner pinch"]);
+  }
+
+  #[test]
+  fn cancel_gesture() {
+    reset_test_env!();
+
+    let (phases, w_phases) = split_value(vec![]);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_pan: move |e| $w_phases.write().push(e.phase),
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    wnd.touch_start(0, Point::new(10., 10.));
+    wnd.touch_move(0, Point::new(50., 10.));
+    wnd.touch_cancel(0, Point::new(50., 10.));
+    assert_eq!(*phases.read(), [GesturePhase::Start, GesturePhase::Cancel]);
+  }
+
+  #[test]
+  fn long_press() {
+    reset_test_env!();
+
+    let (presses, w_presses) = split_value(vec![]);
+    let (taps, w_taps) = split_value(0);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_long_press: move |e| $w_presses.write().push(e.position()),
+        on_tap: move |_| *$w_taps.write() += 1,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    // Released before the long press.
+    wnd.touch_start(0, Point::new(20., 30.));
+    wnd.touch_end(0, Point::new(20., 30.));
+    assert_eq!(*taps.read(), 1);
+
+    wnd.touch_start(0, Point::new(20., 30.));
+    wnd.advance_time(LONG_PRESS_DURATION + Duration::from_millis(20));
+    wnd.draw_frame();
+    assert_eq!(*presses.read(), [Point::new(20., 30.)]);
+    wnd.touch_end(0, Point::new(20., 30.));
+    assert_eq!(*taps.read(), 1);
+
+    // Moving cancels the long press.
+    wnd.touch_start(0, Point::new(20., 30.));
+    wnd.touch_move(0, Point::new(60., 30.));
+    wnd.advance_time(LONG_PRESS_DURATION + Duration::from_millis(20));
+    wnd.draw_frame();
+    wnd.touch_end(0, Point::new(60., 30.));
+    assert_eq!(presses.read().len(), 1);
+  }
+
+  #[test]
+  fn touch_double_tap() {
+    reset_test_env!();
+
+    let (taps, w_taps) = split_value(0);
+    let w = fn_widget! {
+      @MockBox {
+        size: Size::new(100., 100.),
+        on_double_tap: move |_| *$w_taps.write() += 1,
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    wnd.touch_start(0, Point::new(20., 30.));
+    wnd.touch_end(0, Point::new(20., 30.));
+    wnd.touch_start(3, Point::new(22., 30.));
+    wnd.touch_end(3, Point::new(22., 30.));
+    assert_eq!(*taps.read(), 1);
+  }
+}
@@ -1,6 +1,11 @@
+use ribir_geom::Point;
+pub use winit::event::TouchPhase;
+
 use super::CommonEvent;
-use crate::impl_common_event_deref;
+use crate::{context::WidgetCtx, impl_common_event_deref};
 mod from_mouse;
+mod from_touch;
+pub(crate) use from_touch::*;
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct PointerId(usize);
 
@@ -47,6 +52,17 @@ pub struct PointerEvent {
   pub is_primary: bool,
 
   pub common: CommonEvent,
+  global_pos: Point,
+}
+
+impl PointerEvent {
+  /// The X, Y coordinate of the pointer in global (window) coordinates.
+  #[inline]
+  pub fn global_pos(&self) -> Point { self.global_pos }
+
+  /// The X, Y coordinate of the pointer in current target widget.
+  #[inline]
+  pub fn position(&self) -> Point { self.map_from_global(self.global_pos) }
 }
 
 bitflags! {
@@ -5,15 +5,11 @@ use crate::prelude::*;
 
 impl PointerEvent {
   pub(crate) fn from_mouse(target: WidgetId, wnd: &Window) -> Self {
-    let no_button = wnd
-      .dispatcher
-      .borrow()
-      .info
-      .mouse_buttons()
-      .is_empty();
+    let dispatcher = wnd.dispatcher.borrow();
+    let no_button = dispatcher.info.mouse_buttons().is_empty();
     PointerEvent {
       // todo: we need to trace the pressed pointer, how to generate pointer id, by device + button?
-      id: PointerId(0),
+      id: PointerId::MOUSE,
       width: 1.0,
       height: 1.0,
       pressure: if no_button { 0. } else { 0.5 },
@@ -23,6 +19,7 @@ impl PointerEvent {
       point_type: PointerType::Mouse,
       is_primary: true,
       common: CommonEvent::new(target, wnd.tree),
+      global_pos: dispatcher.info.global_pos(),
     }
   }
 }
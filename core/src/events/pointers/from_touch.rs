@@ -0,0 +1,48 @@
+use super::PointerId;
+use crate::prelude::*;
+
+/// The information of a touch point to create the pointer events.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TouchPoint {
+  pub(crate) pointer: PointerId,
+  /// The position in global (window) coordinates.
+  pub(crate) pos: Point,
+  pub(crate) is_primary: bool,
+}
+
+/// The kind of the pointer event emitted by a touch point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum TouchKind {
+  Down,
+  Move,
+  Up,
+  Cancel,
+  Tap,
+}
+
+impl PointerId {
+  /// The pointer id of the mouse.
+  pub(crate) const MOUSE: PointerId = PointerId(0);
+
+  /// The pointer id of the touch point in the `slot`, the slots are reused
+  /// after the fingers are lifted.
+  pub(crate) fn touch(slot: usize) -> Self { PointerId(slot + 1) }
+}
+
+impl PointerEvent {
+  pub(crate) fn from_touch(target: WidgetId, touch: TouchPoint, wnd: &Window) -> Self {
+    PointerEvent {
+      id: touch.pointer,
+      width: 1.0,
+      height: 1.0,
+      pressure: 0.5,
+      tilt_x: 90.,
+      tilt_y: 90.,
+      twist: 0.,
+      point_type: PointerType::Touch,
+      is_primary: touch.is_primary,
+      common: CommonEvent::new(target, wnd.tree),
+      global_pos: touch.pos,
+    }
+  }
+}
@@ -3,9 +3,11 @@
 #[macro_use]
 extern crate bitflags;
 
+pub mod accessibility;
 pub mod animation;
 pub mod builtin_widgets;
 pub mod clipboard;
+pub mod command;
 mod context;
 pub mod data_widget;
 pub mod declare;
@@ -52,6 +54,7 @@ pub mod prelude {
     animation::*,
     builtin_widgets::*,
     class_names,
+    command::*,
     context::*,
     declare::*,
     events::*,
@@ -33,6 +33,10 @@ use crate::{prelude::*, window::WindowId};
 #[derive(Clone)]
 pub struct Overlay(Sc<RefCell<InnerOverlay>>);
 
+impl PartialEq for Overlay {
+  fn eq(&self, other: &Self) -> bool { Sc::ptr_eq(&self.0, &other.0) }
+}
+
 bitflags! {
   #[derive(Clone, Copy)]
   pub struct AutoClosePolicy: u8 {
@@ -177,9 +181,12 @@ impl Overlay {
     if let Some(showing) = showing {
       let ShowingInfo { wnd_id, .. } = showing;
       if let Some(wnd) = AppCtx::get_window(wnd_id) {
-        let _guard = BuildCtx::init_for(wnd.tree().root(), wnd.tree);
-        let showing_overlays = Provider::of::<ShowingOverlays>(BuildCtx::get()).unwrap();
-        showing_overlays.remove(self);
+        // Not collect the providers here, the overlay may be closed in an event
+        // callback that already set up the providers of the root.
+        let tree = wnd.tree();
+        if let Some(showing_overlays) = tree.root().query_ref::<ShowingOverlays>(tree) {
+          showing_overlays.remove(self);
+        }
 
         if let Some(wid) = track_id.and_then(|track_id| track_id.get()) {
           AppCtx::once_next_frame(move |_| {
@@ -868,6 +868,8 @@ impl Render for PipeNode {
   }
 
   fn get_transform(&self) -> Option<Transform> { self.as_ref().data.get_transform() }
+
+  fn semantics(&self) -> Option<Semantics> { self.as_ref().data.semantics() }
 }
 
 #[derive(Clone)]
@@ -52,6 +52,9 @@ where
 
   #[inline]
   fn get_transform(&self) -> Option<Transform> { self.proxy().get_transform() }
+
+  #[inline]
+  fn semantics(&self) -> Option<Semantics> { self.proxy().semantics() }
 }
 
 impl<R: Render> RenderProxy for RefCell<R> {
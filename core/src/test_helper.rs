@@ -1,4 +1,7 @@
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{
+  Arc,
+  atomic::{AtomicU64, Ordering},
+};
 
 #[cfg(test)]
 #[cfg(target_family = "wasm")]
@@ -8,9 +11,13 @@ pub use wasm_bindgen_test::wasm_bindgen_test;
 #[cfg(target_family = "wasm")]
 wasm_bindgen_test::wasm_bindgen_test_configure!(run_in_browser);
 
+use winit::event::{DeviceId, ElementState, MouseButton};
+
 pub use crate::timer::Timer;
 use crate::{
+  accessibility::AccessibilityTree,
   prelude::*,
+  ticker::ManualClock,
   window::{ShellWindow, WindowFlags, WindowId},
 };
 
@@ -27,6 +34,10 @@ pub fn split_value<T: 'static>(v: T) -> (Watcher<Reader<T>>, Stateful<T>) {
 
 /// The Window assists in writing unit tests; animations are disabled by
 /// default.
+///
+/// The test windows go by a [`ManualClock`] that shared by them, the time only
+/// goes forward by [`TestWindow::advance_time`], so the timers and the
+/// animations are deterministic.
 #[derive(Clone)]
 pub struct TestWindow(pub Sc<Window>);
 
@@ -54,6 +65,7 @@ impl TestWindow {
 
   fn new_wnd(root: impl Into<GenWidget>, size: Option<Size>) -> Self {
     let _ = NEW_TIMER_FN.set(Timer::new_timer_future);
+    test_clock();
     AppCtx::run_until_stalled();
 
     let wnd = AppCtx::new_window(Box::new(TestShellWindow::new(size)), root.into());
@@ -97,6 +109,71 @@ impl TestWindow {
     tree.count(content)
   }
 
+  /// Move the mouse cursor to the position in the window and emit the events.
+  pub fn mouse_move_to(&self, pos: Point) {
+    self.dispatcher.borrow_mut().cursor_move_to(pos);
+    self.run_frame_tasks();
+  }
+
+  /// Press the primary button of the mouse and emit the events.
+  pub fn mouse_press(&self) {
+    let device_id = unsafe { DeviceId::dummy() };
+    self.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    self.run_frame_tasks();
+  }
+
+  /// Release the primary button of the mouse and emit the events.
+  pub fn mouse_release(&self) {
+    let device_id = unsafe { DeviceId::dummy() };
+    self.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    self.run_frame_tasks();
+  }
+
+  /// Press the mouse at `from`, drag it to `to` and release it.
+  pub fn drag_and_drop(&self, from: Point, to: Point) {
+    self.mouse_move_to(from);
+    self.mouse_press();
+    self.mouse_move_to(from.lerp(to, 0.5));
+    self.mouse_move_to(to);
+    self.mouse_release();
+  }
+
+  /// Press the finger on the window at `pos` and emit the events.
+  pub fn touch_start(&self, finger: u64, pos: Point) {
+    self.process_touch(finger, TouchPhase::Started, pos);
+    self.run_frame_tasks();
+  }
+
+  /// Move the pressed finger to `pos` and emit the events.
+  pub fn touch_move(&self, finger: u64, pos: Point) {
+    self.process_touch(finger, TouchPhase::Moved, pos);
+    self.run_frame_tasks();
+  }
+
+  /// Lift the finger at `pos` and emit the events.
+  pub fn touch_end(&self, finger: u64, pos: Point) {
+    self.process_touch(finger, TouchPhase::Ended, pos);
+    self.run_frame_tasks();
+  }
+
+  /// Cancel the touch of the finger and emit the events.
+  pub fn touch_cancel(&self, finger: u64, pos: Point) {
+    self.process_touch(finger, TouchPhase::Cancelled, pos);
+    self.run_frame_tasks();
+  }
+
+  /// Move the clock of the test windows forward by `dur`, and run the timers
+  /// that are due. It doesn't draw the frame, call `draw_frame` to advance the
+  /// animations to the time.
+  pub fn advance_time(&self, dur: Duration) {
+    // The pending tasks start their timers before the time goes.
+    AppCtx::run_until_stalled();
+    test_clock().advance(dur);
+    Timer::wake_timeout_futures();
+    AppCtx::run_until_stalled();
+    self.run_frame_tasks();
+  }
+
   #[track_caller]
   pub fn draw_frame(&mut self) {
     // Test window not have a eventloop, manually wake-up every frame.
@@ -104,11 +181,25 @@ impl TestWindow {
     AppCtx::run_until_stalled();
     self.run_frame_tasks();
 
-    AppCtx::frame_ticks().clone().next(Instant::now());
+    AppCtx::frame_ticks().clone().next(AppCtx::now());
     self.0.draw_frame(false);
   }
 }
 
+/// Return the manual clock of the application, install one if the application
+/// is not using it.
+fn test_clock() -> ManualClock {
+  let clock: Arc<dyn Any + Send + Sync> = AppCtx::clock();
+  clock
+    .downcast::<ManualClock>()
+    .map(|clock| (*clock).clone())
+    .unwrap_or_else(|_| {
+      let clock = ManualClock::default();
+      AppCtx::set_clock(clock.clone());
+      clock
+    })
+}
+
 impl std::ops::Deref for TestWindow {
   type Target = Window;
 
@@ -121,6 +212,9 @@ pub struct TestShellWindow {
   pub id: WindowId,
   pub surface_color: Color,
   pub last_frame: Option<Frame>,
+  /// How many times the accessibility tree is updated, the accessibility is
+  /// enabled if it's not `None`.
+  pub accessibility_updates: Option<usize>,
 }
 
 impl ShellWindow for TestShellWindow {
@@ -178,6 +272,14 @@ impl ShellWindow for TestShellWindow {
 
   fn end_frame(&mut self) {}
 
+  fn is_accessibility_enabled(&self) -> bool { self.accessibility_updates.is_some() }
+
+  fn update_accessibility(&mut self, _: &AccessibilityTree) {
+    if let Some(updates) = self.accessibility_updates.as_mut() {
+      *updates += 1;
+    }
+  }
+
   fn id(&self) -> WindowId { self.id }
 
   fn device_pixel_ratio(&self) -> f32 { 1. }
@@ -193,6 +295,7 @@ impl TestShellWindow {
       id: ID.fetch_add(1, Ordering::Relaxed).into(),
       last_frame: None,
       surface_color: Color::WHITE,
+      accessibility_updates: None,
     }
   }
 }
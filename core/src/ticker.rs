@@ -1,6 +1,10 @@
-use std::convert::Infallible;
 #[cfg(not(target_family = "wasm"))]
 pub use std::time::{Duration, Instant};
+use std::{
+  any::Any,
+  convert::Infallible,
+  sync::{Arc, Mutex},
+};
 
 use rxrust::prelude::Subject;
 #[cfg(target_family = "wasm")]
@@ -36,3 +40,57 @@ pub enum FrameMsg {
   /// receive this message.
   Finish(Instant),
 }
+
+/// The source of the current time, the frame ticks, the timers and the
+/// animations of the application read the time from it. See
+/// [`AppCtx::set_clock`](crate::prelude::AppCtx::set_clock).
+pub trait Clock: Any + Send + Sync {
+  /// Return the current time.
+  fn now(&self) -> Instant;
+}
+
+/// The clock of the system time, the default clock of the application.
+#[derive(Clone, Copy, Default)]
+pub struct SystemClock;
+
+/// A clock that only goes forward when it's advanced, so the time is
+/// deterministic. The clones of it share the same time.
+///
+/// # Example
+///
+/// ```
+/// use ribir_core::{
+///   prelude::*,
+///   ticker::{Clock, ManualClock},
+/// };
+///
+/// let clock = ManualClock::default();
+/// let start = clock.now();
+/// clock.advance(Duration::from_millis(16));
+/// assert_eq!(clock.now() - start, Duration::from_millis(16));
+/// ```
+#[derive(Clone)]
+pub struct ManualClock(Arc<Mutex<Instant>>);
+
+impl Clock for SystemClock {
+  fn now(&self) -> Instant { Instant::now() }
+}
+
+impl ManualClock {
+  /// Create a clock that starts at `at`.
+  pub fn new(at: Instant) -> Self { Self(Arc::new(Mutex::new(at))) }
+
+  /// Move the time forward by `dur`.
+  pub fn advance(&self, dur: Duration) { *self.0.lock().unwrap() += dur; }
+
+  /// Set the time to `at`.
+  pub fn set(&self, at: Instant) { *self.0.lock().unwrap() = at; }
+}
+
+impl Default for ManualClock {
+  fn default() -> Self { Self::new(Instant::now()) }
+}
+
+impl Clock for ManualClock {
+  fn now(&self) -> Instant { *self.0.lock().unwrap() }
+}
@@ -11,7 +11,10 @@ use std::{
 
 use rxrust::scheduler::BoxFuture;
 
-use crate::ticker::{Duration, Instant};
+use crate::{
+  prelude::AppCtx,
+  ticker::{Duration, Instant},
+};
 
 #[derive(Default)]
 pub(crate) struct TimeReactor {
@@ -66,14 +69,14 @@ impl Timer {
   pub fn recently_timeout() -> Option<Instant> { TIME_REACTOR.lock().unwrap().recently_timeout() }
 
   pub fn new_timer_future(dur: Duration) -> BoxFuture<'static, ()> {
-    Box::pin(Timer::new(Instant::now() + dur))
+    Box::pin(Timer::new(AppCtx::now() + dur))
   }
 
   pub fn wake_timeout_futures() {
     let notifies = TIME_REACTOR
       .lock()
       .unwrap()
-      .timeout_wakers(Instant::now());
+      .timeout_wakers(AppCtx::now());
     notifies.for_each(|waker| waker.wake());
   }
 }
@@ -83,7 +86,7 @@ impl Future for Timer {
   fn poll(
     mut self: std::pin::Pin<&mut Self>, cx: &mut std::task::Context<'_>,
   ) -> std::task::Poll<Self::Output> {
-    let now = Instant::now();
+    let now = AppCtx::now();
     let when = self.as_ref().when;
     if let Some(id) = self.as_mut().id.take() {
       TIME_REACTOR
@@ -104,3 +107,43 @@ impl Future for Timer {
     Poll::Pending
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use std::{cell::RefCell, convert::Infallible, rc::Rc};
+
+  use super::*;
+  use crate::{prelude::*, reset_test_env, test_helper::TestWindow};
+
+  #[test]
+  fn timers_go_by_clock() {
+    reset_test_env!();
+
+    let wnd = TestWindow::new(fn_widget! { @Void {} });
+    let fired = Rc::new(RefCell::new(vec![]));
+    let c_fired = fired.clone();
+    AppCtx::spawn_local(async move {
+      Timer::new_timer_future(Duration::from_millis(100)).await;
+      c_fired.borrow_mut().push("timer");
+    })
+    .unwrap();
+    let mut subject = Subject::<'static, (), Infallible>::default();
+    let c_fired = fired.clone();
+    subject
+      .clone()
+      .debounce(Duration::from_millis(50), AppCtx::scheduler())
+      .subscribe(move |_| c_fired.borrow_mut().push("debounce"));
+
+    subject.next(());
+    wnd.advance_time(Duration::from_millis(40));
+    subject.next(());
+    wnd.advance_time(Duration::from_millis(40));
+    assert!(fired.borrow().is_empty());
+
+    wnd.advance_time(Duration::from_millis(10));
+    assert_eq!(*fired.borrow(), ["debounce"]);
+
+    wnd.advance_time(Duration::from_millis(10));
+    assert_eq!(*fired.borrow(), ["debounce", "timer"]);
+  }
+}
@@ -72,6 +72,11 @@ pub trait Render: 'static {
   /// Return a transform to map the coordinate to parent coordinate.
   fn get_transform(&self) -> Option<Transform> { None }
 
+  /// The semantics that the render widget describes by itself, such as the
+  /// text of a `Text`. The `semantics` builtin attribute of the widget can
+  /// overwrite it.
+  fn semantics(&self) -> Option<Semantics> { None }
+
   /// Computes the visual bounding box of the widget relative to self.
   /// The method is called by framework after the layout is done.
   /// Usually if you paint something, you should return the bounding box of the
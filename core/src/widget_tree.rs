@@ -82,7 +82,9 @@ impl WidgetTree {
 
   /// Do the work of computing the layout for all node which need, Return if any
   /// node has really computing the layout.
-  pub(crate) fn layout(&mut self, win_size: Size) {
+  /// Layout the dirty widgets, return if any widget performs the layout.
+  pub(crate) fn layout(&mut self, win_size: Size) -> bool {
+    let mut laid_out = false;
     loop {
       let Some((mut needs_layout, mut needs_paint)) = self.layout_list() else {
         break;
@@ -99,6 +101,7 @@ impl WidgetTree {
             .map(|info| info.clamp)
             .unwrap_or_else(|| BoxClamp { min: Size::zero(), max: win_size });
 
+          laid_out = true;
           let mut ctx = LayoutCtx::new(wid, self);
           let visual_rect = ctx.visual_box(wid);
           ctx.perform_layout(clamp);
@@ -131,6 +134,7 @@ impl WidgetTree {
         }
       }
     }
+    laid_out
   }
 
   pub(crate) fn alloc_node(&mut self, node: Box<dyn RenderQueryable>) -> WidgetId {
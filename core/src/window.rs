@@ -13,7 +13,10 @@ use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
 pub use winit::window::CursorIcon;
 
 use crate::{
+  accessibility::AccessibilityTree,
+  command::{ChordMatch, CommandId, KeyChord},
   events::{
+    GestureArena, TouchKind, TouchPoint,
     dispatcher::Dispatcher,
     focus_mgr::{FocusManager, FocusType},
   },
@@ -34,6 +37,9 @@ pub struct Window {
   pub(crate) dispatcher: RefCell<Dispatcher>,
   pub(crate) frame_ticker: FrameTicker,
   pub(crate) focus_mgr: RefCell<FocusManager>,
+  pub(crate) gesture_arena: RefCell<GestureArena>,
+  /// The chords pressed for a key binding sequence that is not finished.
+  pending_chords: RefCell<Vec<KeyChord>>,
   pub(crate) running_animates: Sc<Cell<u32>>,
   pre_edit: RefCell<Option<String>>,
   /// This vector store the task to emit events. When perform layout, dispatch
@@ -55,6 +61,14 @@ pub struct Window {
   ///
   /// This widgets it's detached from its parent, but still need to paint.
   pub(crate) delay_drop_widgets: RefCell<Vec<(Option<WidgetId>, TrackId)>>,
+  /// The frames of the heroes disposed in the current frame, waiting for the
+  /// heroes with the same key to fly from.
+  pub(crate) departed_heroes: RefCell<ahash::HashMap<Key, HeroFrame>>,
+  /// Whether the layout, the semantics or the focus changed since the last
+  /// accessibility tree sent to the shell window.
+  accessibility_dirty: Cell<bool>,
+  /// The focus of the last accessibility tree sent to the shell window.
+  accessibility_focus: Cell<Option<WidgetId>>,
 
   flags: Cell<WindowFlags>,
 }
@@ -105,6 +119,12 @@ pub trait ShellWindow {
   fn begin_frame(&mut self, surface_color: Color);
   fn draw_commands(&mut self, viewport: Rect, commands: &[PaintCommand]);
   fn end_frame(&mut self);
+  /// Whether the shell window needs the accessibility tree. If it returns
+  /// true, the window sends the tree to `update_accessibility` after the drawn
+  /// frames that change the layout, the semantics or the focus.
+  fn is_accessibility_enabled(&self) -> bool { false }
+  /// Receive the latest accessibility tree of the window.
+  fn update_accessibility(&mut self, _tree: &AccessibilityTree) {}
 }
 
 impl Window {
@@ -150,6 +170,48 @@ impl Window {
       .dispatch_mouse_input(device_id, state, button);
   }
 
+  /// Process a touch point on the window, `finger` is the id of the touch
+  /// point reported by the platform and `pos` is in logical pixels.
+  ///
+  /// Every touch point emits the pointer events with a different
+  /// [`PointerId`]. The widget where a touch point starts receives all the
+  /// following pointer events of it, even if the touch point moves out of the
+  /// widget.
+  pub fn process_touch(&self, finger: u64, phase: TouchPhase, pos: Point) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_touch(finger, phase, pos);
+  }
+
+  /// Process the data dragged from other applications over the window, the
+  /// widget under the cursor receives the drag events. The shell may call it
+  /// many times for a drag, such as once for every file, the files are
+  /// collected together.
+  pub fn process_external_drag(&self, data: ExternalData) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_external_drag(data);
+  }
+
+  /// Process the data dropped from other applications, it works even if the
+  /// data is not dragged over the window before.
+  pub fn process_external_drop(&self, data: ExternalData) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_external_drop(data);
+  }
+
+  /// The data dragged from other applications leaves the window.
+  pub fn process_external_drag_cancel(&self) {
+    self
+      .dispatcher
+      .borrow_mut()
+      .dispatch_external_drag_cancel();
+  }
+
   /// Request switch the focus to next widget.
   pub fn request_next_focus(&self) {
     self
@@ -220,7 +282,7 @@ impl Window {
   pub fn draw_frame(&self, force: bool) -> bool {
     AppCtx::run_until_stalled();
     let mut ticker = self.frame_ticker.clone();
-    ticker.next(FrameMsg::NewFrame(Instant::now()));
+    ticker.next(FrameMsg::NewFrame(AppCtx::now()));
     self.run_frame_tasks();
 
     self.update_painter_viewport();
@@ -234,7 +296,7 @@ impl Window {
       };
       self.shell_wnd.borrow_mut().begin_frame(surface);
 
-      ticker.next(FrameMsg::BeforeLayout(Instant::now()));
+      ticker.next(FrameMsg::BeforeLayout(AppCtx::now()));
       self.layout();
 
       self.tree().draw();
@@ -246,10 +308,18 @@ impl Window {
       shell.draw_commands(Rect::from_size(inner_size), &painter.finish());
 
       shell.end_frame();
+      let focus = self.focusing();
+      if shell.is_accessibility_enabled()
+        && (self.accessibility_dirty.get() || self.accessibility_focus.get() != focus)
+      {
+        self.accessibility_dirty.set(false);
+        self.accessibility_focus.set(focus);
+        shell.update_accessibility(&self.accessibility_tree());
+      }
     }
 
     AppCtx::end_frame();
-    ticker.next(FrameMsg::Finish(Instant::now()));
+    ticker.next(FrameMsg::Finish(AppCtx::now()));
     ticker.retain();
 
     draw
@@ -260,7 +330,9 @@ impl Window {
       self.run_frame_tasks();
 
       let tree = self.tree_mut();
-      tree.layout(self.shell_wnd.borrow().inner_size());
+      if tree.layout(self.shell_wnd.borrow().inner_size()) {
+        self.mark_accessibility_dirty();
+      }
       self.run_frame_tasks();
 
       if !tree.is_dirty() {
@@ -269,7 +341,7 @@ impl Window {
       }
 
       if !tree.is_dirty() {
-        let ready = FrameMsg::LayoutReady(Instant::now());
+        let ready = FrameMsg::LayoutReady(AppCtx::now());
         self.frame_ticker.clone().next(ready);
         self.run_frame_tasks();
       }
@@ -307,6 +379,8 @@ impl Window {
       dispatcher,
       painter: RefCell::new(painter),
       focus_mgr,
+      gesture_arena: <_>::default(),
+      pending_chords: <_>::default(),
       delay_emitter: <_>::default(),
       frame_ticker: FrameTicker::default(),
       running_animates: <_>::default(),
@@ -314,6 +388,9 @@ impl Window {
       priority_task_queue: PriorityTaskQueue::default(),
       shell_wnd: RefCell::new(shell_wnd),
       delay_drop_widgets: <_>::default(),
+      departed_heroes: <_>::default(),
+      accessibility_dirty: Cell::new(true),
+      accessibility_focus: <_>::default(),
       flags: Cell::new(WindowFlags::DEFAULT),
       pre_edit: <_>::default(),
     };
@@ -345,6 +422,46 @@ impl Window {
     self.add_delay_event(DelayEvent::BubbleCustomEvent { from, data: Box::new(e) as Box<dyn Any> });
   }
 
+  /// Mark the accessibility tree changed, the shell window receives a new tree
+  /// after the next drawn frame.
+  pub(crate) fn mark_accessibility_dirty(&self) { self.accessibility_dirty.set(true); }
+
+  /// Take a snapshot of the accessibility tree of the window.
+  pub fn accessibility_tree(&self) -> AccessibilityTree {
+    AccessibilityTree::new(self.tree(), self.focusing())
+  }
+
+  /// Perform an action that the assistive technologies request on the widget.
+  ///
+  /// The `Click` action dispatches a tap event from the widget and the `Focus`
+  /// action moves the focus to the focusable widget nearest to it. The other
+  /// actions bubble from the widget as a `CustomEvent<SemanticsAction>`.
+  pub fn perform_semantics_action(&self, id: WidgetId, action: SemanticsAction) {
+    let tree = self.tree();
+    if id.is_dropped(tree) {
+      return;
+    }
+    match action {
+      SemanticsAction::Click => self.add_delay_event(DelayEvent::Tap(id)),
+      SemanticsAction::Focus => {
+        let focus_node = id
+          .descendants(tree)
+          .chain(id.ancestors(tree).skip(1))
+          .find(|id| {
+            id.query_all_iter::<MixBuiltin>(tree)
+              .any(|m| m.contain_flag(MixFlags::Focus))
+          });
+        if let Some(focus_node) = focus_node {
+          self
+            .focus_mgr
+            .borrow_mut()
+            .focus(focus_node, tree);
+        }
+      }
+      _ => self.bubble_custom_event(id, action),
+    }
+  }
+
   pub(crate) fn add_focus_node(&self, wid: WidgetId, auto_focus: bool, focus_type: FocusType) {
     self
       .focus_mgr
@@ -478,6 +595,8 @@ impl Window {
           self.top_down_emit(&mut Event::FocusOutCapture(FocusEvent::new(top, self.tree)), bottom);
           self.bottom_up_emit(&mut Event::FocusOut(FocusEvent::new(bottom, self.tree)), up);
         }
+        DelayEvent::KeyBoard { id, physical_key, state: ElementState::Pressed, .. }
+          if self.resolve_command_key(id, physical_key) => {}
         DelayEvent::KeyBoard { id, physical_key, key, is_repeat, location, state } => {
           let root = self.tree().root();
           let event =
@@ -495,8 +614,14 @@ impl Window {
           };
           self.bottom_up_emit(&mut event, None);
           if let Event::KeyDown(e) = event {
-            if !e.is_prevent_default() && *e.key() == VirtualKey::Named(NamedKey::Tab) {
-              self.add_delay_event(DelayEvent::TabFocusMove);
+            if !e.is_prevent_default() {
+              if *e.key() == VirtualKey::Named(NamedKey::Tab) {
+                self.add_delay_event(DelayEvent::TabFocusMove);
+              } else if is_context_menu_key(e.key(), e.with_shift_key()) {
+                let height = self.widget_size(id).unwrap_or_default().height;
+                let pos = self.map_to_global(Point::new(0., height), id);
+                self.emit_context_menu(id, pos);
+              }
             }
           }
         }
@@ -536,24 +661,30 @@ impl Window {
             .focus_mgr
             .borrow_mut()
             .refresh_focus(self.tree());
+          self.gesture_pointer_down(id, PointerId::MOUSE, self.cursor_pos());
         }
+        DelayEvent::ContextMenu { id, pos } => self.emit_context_menu(id, pos),
         DelayEvent::PointerMove(id) => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
           self.top_down_emit(&mut Event::PointerMoveCapture(event), id);
           self.bottom_up_emit(&mut Event::PointerMove(PointerEvent::from_mouse(id, self)), None);
+          self.gesture_pointer_move(PointerId::MOUSE, self.cursor_pos());
         }
         DelayEvent::PointerUp(id) => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
           self.top_down_emit(&mut Event::PointerUpCapture(event), id);
           let event = PointerEvent::from_mouse(id, self);
           self.bottom_up_emit(&mut Event::PointerUp(event), None);
+          self.gesture_pointer_up(PointerId::MOUSE, self.cursor_pos());
         }
-        DelayEvent::_PointerCancel(id) => {
+        DelayEvent::PointerCancel(id) => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
           self.top_down_emit(&mut Event::PointerCancelCapture(event), id);
           let event = PointerEvent::from_mouse(id, self);
           self.bottom_up_emit(&mut Event::PointerCancel(event), None);
+          self.gesture_pointer_cancel(PointerId::MOUSE);
         }
+        DelayEvent::Touch { kind, id, touch } => self.emit_touch(kind, id, touch),
         DelayEvent::PointerEnter { bottom, up } => {
           let top = up.unwrap_or_else(|| self.tree().root());
           self.top_down_emit(&mut Event::PointerEnter(PointerEvent::from_mouse(top, self)), bottom);
@@ -561,7 +692,7 @@ impl Window {
         DelayEvent::PointerLeave { bottom, up } => {
           self.bottom_up_emit(&mut Event::PointerLeave(PointerEvent::from_mouse(bottom, self)), up);
         }
-        DelayEvent::Tap(wid) => {
+        DelayEvent::Tap(wid) if !self.is_gesture_claimed() => {
           let event = PointerEvent::from_mouse(self.tree().root(), self);
           self.top_down_emit(&mut Event::TapCapture(event), wid);
           let event = PointerEvent::from_mouse(wid, self);
@@ -581,20 +712,133 @@ impl Window {
         DelayEvent::GrabPointerMove(wid) => {
           let mut e = Event::PointerMove(PointerEvent::from_mouse(wid, self));
           self.emit(wid, &mut e);
+          self.gesture_pointer_move(PointerId::MOUSE, self.cursor_pos());
         }
         DelayEvent::GrabPointerUp(wid) => {
           let mut e = Event::PointerUp(PointerEvent::from_mouse(wid, self));
           self.emit(wid, &mut e);
+          self.gesture_pointer_up(PointerId::MOUSE, self.cursor_pos());
         }
+        DelayEvent::DragStart { id, origin } => self.start_drag(id, origin),
+        DelayEvent::DragMove(hit) => self.drag_move(hit),
+        DelayEvent::DragFinish { cancel } => self.finish_drag(cancel),
         DelayEvent::BubbleCustomEvent { from: id, data } => {
           let mut e = Event::CustomEvent(new_custom_event(CommonEvent::new(id, self.tree), data));
           self.bottom_up_emit(&mut e, None);
         }
+        // The tap of a recognized gesture is not fired.
+        DelayEvent::Tap(_) => {}
+      }
+    }
+  }
+
+  fn emit_touch(&self, kind: TouchKind, id: WidgetId, touch: TouchPoint) {
+    if kind == TouchKind::Tap && self.is_gesture_claimed() {
+      return;
+    }
+    type EventCtor = fn(PointerEvent) -> Event;
+    let (capture, bubble): (EventCtor, EventCtor) = match kind {
+      TouchKind::Down => (Event::PointerDownCapture, Event::PointerDown),
+      TouchKind::Move => (Event::PointerMoveCapture, Event::PointerMove),
+      TouchKind::Up => (Event::PointerUpCapture, Event::PointerUp),
+      TouchKind::Cancel => (Event::PointerCancelCapture, Event::PointerCancel),
+      TouchKind::Tap => (Event::TapCapture, Event::Tap),
+    };
+    let event = PointerEvent::from_touch(self.tree().root(), touch, self);
+    self.top_down_emit(&mut capture(event), id);
+    self.bottom_up_emit(&mut bubble(PointerEvent::from_touch(id, touch, self)), None);
+
+    match kind {
+      TouchKind::Down => {
+        self
+          .focus_mgr
+          .borrow_mut()
+          .refresh_focus(self.tree());
+        self.gesture_pointer_down(id, touch.pointer, touch.pos);
+      }
+      TouchKind::Move => self.gesture_pointer_move(touch.pointer, touch.pos),
+      TouchKind::Up => self.gesture_pointer_up(touch.pointer, touch.pos),
+      TouchKind::Cancel => self.gesture_pointer_cancel(touch.pointer),
+      TouchKind::Tap => {}
+    }
+  }
+
+  /// Match the pressed key to the key bindings of the commands, return `true`
+  /// if the key is consumed by a command or a pending key sequence.
+  fn resolve_command_key(&self, focus: WidgetId, key: PhysicalKey) -> bool {
+    let PhysicalKey::Code(code) = key else { return false };
+    if KeyChord::is_modifier_key(code) {
+      return false;
+    }
+    let modifiers = self.dispatcher.borrow().info.modifiers();
+    let mut pending = self.pending_chords.borrow_mut();
+    pending.push(KeyChord::new(modifiers, code));
+    let matched = AppCtx::commands().borrow().match_chords(&pending);
+    match matched {
+      ChordMatch::Partial => true,
+      ChordMatch::None if pending.len() > 1 => {
+        // The sequence is broken, try the key as a new start.
+        pending.clear();
+        drop(pending);
+        self.resolve_command_key(focus, key)
+      }
+      ChordMatch::None => {
+        pending.clear();
+        false
+      }
+      ChordMatch::Exact(commands) => {
+        pending.clear();
+        drop(pending);
+        commands
+          .into_iter()
+          .any(|c| self.emit_command(focus, c))
+      }
+    }
+  }
+
+  /// Fire the command from the focused widget, or the root if no widget is
+  /// focused. Return whether the command is handled.
+  pub fn execute_command(&self, command: &CommandId) -> bool {
+    let focus = self.focus_mgr.borrow().focusing();
+    let target = focus.unwrap_or_else(|| self.tree().root());
+    self.emit_command(target, command.clone())
+  }
+
+  /// Fire the command from the `target` widget, return whether the command is
+  /// handled.
+  pub fn execute_command_from(&self, command: &CommandId, target: WidgetId) -> bool {
+    if target.is_dropped(self.tree()) {
+      return false;
+    }
+    self.emit_command(target, command.clone())
+  }
+
+  fn emit_command(&self, target: WidgetId, command: CommandId) -> bool {
+    let tree = self.tree();
+    let mut e = Event::Command(CommandEvent::new(command, target, self));
+    for id in target.ancestors(tree) {
+      e.bubble_to_parent(id);
+      for m in id.query_all_iter::<MixBuiltin>(tree) {
+        if m.contain_flag(MixFlags::Command) {
+          m.dispatch(&mut e);
+        }
+      }
+      let Event::Command(c) = &e else { unreachable!() };
+      if c.is_handled() || !c.is_propagation() {
+        break;
       }
     }
+    matches!(e, Event::Command(c) if c.is_handled())
+  }
+
+  pub(crate) fn emit_context_menu(&self, target: WidgetId, pos: Point) {
+    let mut e = Event::ContextMenu(ContextMenuEvent::new(pos, target, self));
+    self.bottom_up_emit(&mut e, None);
   }
 
-  fn emit(&self, id: WidgetId, e: &mut Event) {
+  fn cursor_pos(&self) -> Point { self.dispatcher.borrow().info.global_pos() }
+
+  pub(crate) fn emit(&self, id: WidgetId, e: &mut Event) {
     id.query_all_iter::<MixBuiltin>(self.tree())
       .for_each(|m| {
         if m.contain_flag(e.flags()) {
@@ -603,7 +847,7 @@ impl Window {
       })
   }
 
-  fn top_down_emit(&self, e: &mut Event, bottom: WidgetId) {
+  pub(crate) fn top_down_emit(&self, e: &mut Event, bottom: WidgetId) {
     let tree = self.tree();
     let path = bottom
       .ancestors(tree)
@@ -624,7 +868,7 @@ impl Window {
     });
   }
 
-  fn bottom_up_emit(&self, e: &mut Event, up: Option<WidgetId>) {
+  pub(crate) fn bottom_up_emit(&self, e: &mut Event, up: Option<WidgetId>) {
     if !e.is_propagation() {
       return;
     }
@@ -696,6 +940,15 @@ impl Window {
   /// Return the current focused widget id.
   pub fn focusing(&self) -> Option<WidgetId> { self.focus_mgr.borrow().focusing() }
 
+  /// Request to move the focus to the widget, the focus changes before the
+  /// next frame.
+  pub fn request_focus(&self, id: WidgetId) {
+    self
+      .focus_mgr
+      .borrow_mut()
+      .request_focus_to(Some(id));
+  }
+
   /// The device pixel ratio of Window interface returns the ratio of the
   /// resolution in physical pixels to the logic pixels for the current display
   /// device.
@@ -842,9 +1095,18 @@ pub(crate) enum DelayEvent {
     delta_y: f32,
   },
   PointerDown(WidgetId),
+  ContextMenu {
+    id: WidgetId,
+    pos: Point,
+  },
   PointerMove(WidgetId),
   PointerUp(WidgetId),
-  _PointerCancel(WidgetId),
+  PointerCancel(WidgetId),
+  Touch {
+    kind: TouchKind,
+    id: WidgetId,
+    touch: TouchPoint,
+  },
   PointerEnter {
     bottom: WidgetId,
     up: Option<WidgetId>,
@@ -861,6 +1123,14 @@ pub(crate) enum DelayEvent {
   GrabPointerDown(WidgetId),
   GrabPointerMove(WidgetId),
   GrabPointerUp(WidgetId),
+  DragStart {
+    id: WidgetId,
+    origin: Point,
+  },
+  DragMove(Option<WidgetId>),
+  DragFinish {
+    cancel: bool,
+  },
   BubbleCustomEvent {
     from: WidgetId,
     data: Box<dyn Any>,
@@ -876,6 +1146,15 @@ impl From<WindowId> for u64 {
   #[inline]
   fn from(value: WindowId) -> Self { value.0 }
 }
+
+fn is_context_menu_key(key: &VirtualKey, shift: bool) -> bool {
+  match key {
+    VirtualKey::Named(NamedKey::ContextMenu) => true,
+    VirtualKey::Named(NamedKey::F10) => shift,
+    _ => false,
+  }
+}
+
 #[cfg(test)]
 mod tests {
 
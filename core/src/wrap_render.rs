@@ -35,6 +35,8 @@ pub trait WrapRender {
     host.visual_box(ctx)
   }
 
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> { host.semantics() }
+
   fn combine_child(
     this: impl StateWriter<Value = Self>, mut child: Widget, dirty: DirtyPhase,
   ) -> Widget
@@ -116,6 +118,8 @@ impl Render for RenderPair {
   fn dirty_phase(&self) -> DirtyPhase { self.host.dirty_phase() }
 
   fn get_transform(&self) -> Option<Transform> { self.wrapper.get_transform(self.host.as_render()) }
+
+  fn semantics(&self) -> Option<Semantics> { self.wrapper.semantics(self.host.as_render()) }
 }
 
 impl<R> WrapRender for R
@@ -144,6 +148,8 @@ where
   fn visual_box(&self, host: &dyn Render, ctx: &mut VisualCtx) -> Option<Rect> {
     self.read().visual_box(host, ctx)
   }
+
+  fn semantics(&self, host: &dyn Render) -> Option<Semantics> { self.read().semantics(host) }
 }
 
 #[macro_export]
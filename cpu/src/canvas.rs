@@ -0,0 +1,102 @@
+use ribir_geom::{DevicePoint, DeviceRect};
+use ribir_painter::{BlendMode, ColorMatrix, PixelImage, image::ColorFormat};
+
+use crate::{
+  raster::Mask,
+  shader::{Rgba, blend},
+};
+
+/// The premultiplied pixels of a device area.
+pub(crate) struct Canvas {
+  pub rect: DeviceRect,
+  pub pixels: Vec<Rgba>,
+}
+
+impl Canvas {
+  /// Create a transparent canvas.
+  pub fn new(rect: DeviceRect) -> Self {
+    let len = rect.area().max(0) as usize;
+    Self { rect, pixels: vec![[0.; 4]; len] }
+  }
+
+  /// Read the `rect` area of the image as a canvas.
+  pub fn from_image(img: &PixelImage, rect: DeviceRect) -> Self {
+    assert_eq!(img.color_format(), ColorFormat::Rgba8);
+    let data = img.pixel_bytes();
+    let width = img.width() as usize;
+    let mut canvas = Self::new(rect);
+    let mut idx = 0;
+    for y in rect.y_range() {
+      for x in rect.x_range() {
+        let i = (y as usize * width + x as usize) * 4;
+        let a = data[i + 3] as f32 / 255.;
+        canvas.pixels[idx] = [
+          data[i] as f32 / 255. * a,
+          data[i + 1] as f32 / 255. * a,
+          data[i + 2] as f32 / 255. * a,
+          a,
+        ];
+        idx += 1;
+      }
+    }
+    canvas
+  }
+
+  /// Write the canvas back to the image, the image stores the colors not
+  /// premultiplied.
+  pub fn write_to_image(&self, img: &mut PixelImage) {
+    let width = img.width() as usize;
+    let data = img.pixel_bytes_mut();
+    let mut idx = 0;
+    for y in self.rect.y_range() {
+      for x in self.rect.x_range() {
+        let i = (y as usize * width + x as usize) * 4;
+        let [r, g, b, a] = self.pixels[idx];
+        let to_u8 = |c: f32| (c.clamp(0., 1.) * 255.).round() as u8;
+        let un = |c: f32| if a > 0. { c / a } else { 0. };
+        data[i..i + 4].copy_from_slice(&[to_u8(un(r)), to_u8(un(g)), to_u8(un(b)), to_u8(a)]);
+        idx += 1;
+      }
+    }
+  }
+
+  fn index(&self, x: i32, y: i32) -> usize {
+    ((y - self.rect.min_y()) * self.rect.width() + x - self.rect.min_x()) as usize
+  }
+
+  pub fn pixel(&self, x: i32, y: i32) -> Rgba {
+    if self.rect.contains(DevicePoint::new(x, y)) { self.pixels[self.index(x, y)] } else { [0.; 4] }
+  }
+
+  /// Paint the color returned by `color_at` to the pixels covered by the
+  /// `mask`.
+  pub fn paint(&mut self, mask: &Mask, mode: BlendMode, color_at: impl Fn(i32, i32) -> Rgba) {
+    let Some(rect) = mask.rect.intersection(&self.rect) else { return };
+    for y in rect.y_range() {
+      for x in rect.x_range() {
+        let coverage = mask.get(x, y);
+        if coverage <= 0. {
+          continue;
+        }
+        let src = color_at(x, y).map(|c| c * coverage);
+        let idx = self.index(x, y);
+        self.pixels[idx] = blend(src, self.pixels[idx], mode);
+      }
+    }
+  }
+
+  /// Composite the `layer` over this canvas with the `opacity`.
+  pub fn draw_layer(&mut self, layer: &Canvas, opacity: f32, mask: &Mask) {
+    self.paint(mask, BlendMode::Normal, |x, y| layer.pixel(x, y).map(|c| c * opacity));
+  }
+
+  /// Transform the colors of the canvas by the color matrix.
+  pub fn apply_color_matrix(&mut self, matrix: &ColorMatrix) {
+    for p in self.pixels.iter_mut() {
+      let a = p[3];
+      let color = if a > 0. { [p[0] / a, p[1] / a, p[2] / a, a] } else { [0.; 4] };
+      let [r, g, b, a] = matrix.apply(color);
+      *p = [r * a, g * a, b * a, a];
+    }
+  }
+}
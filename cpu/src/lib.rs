@@ -0,0 +1,352 @@
+//! A software painter backend of Ribir, it rasterizes the paint commands to a
+//! [`PixelImage`] on the CPU. It doesn't require any GPU device, so it can be
+//! used by the headless tests and the offscreen rendering.
+//!
+//! The `cpu` feature of `ribir` rasterizes the window frames by it and blits
+//! them to the window by `softbuffer`, so the applications run on the machines
+//! without a GPU.
+use ribir_geom::{DeviceRect, Transform, transform_to_device_rect};
+use ribir_painter::{
+  BlendMode, Color, FilterCommand, FilterOp, PaintCommand, PaintPathAction, PainterBackend,
+  PaintingStyle, PathCommand, PixelImage, ShadowCommand, image::ColorFormat,
+};
+
+mod canvas;
+mod raster;
+mod shader;
+
+use canvas::Canvas;
+use raster::{Mask, gaussian_blur, matrix_scale, path_mask};
+use shader::{Shader, premultiplied};
+
+const MAX_BLUR_SIGMA: f32 = 64.;
+
+/// A painter backend that paints the commands to a [`PixelImage`] in the
+/// `Rgba8` format.
+///
+/// # Example
+///
+/// ```
+/// use ribir_cpu::CpuBackend;
+/// use ribir_geom::{DeviceRect, DeviceSize, Transform};
+/// use ribir_painter::{Color, PainterBackend, PixelImage, image::ColorFormat};
+///
+/// let mut backend = CpuBackend::default();
+/// let size = DeviceSize::new(4, 4);
+/// let mut img = PixelImage::new(vec![0; 64].into(), 4, 4, ColorFormat::Rgba8);
+/// backend.begin_frame(Color::RED);
+/// backend.draw_commands(DeviceRect::from_size(size), &[], &Transform::identity(), &mut img);
+/// backend.end_frame();
+/// assert_eq!(&img.pixel_bytes()[..4], &[255, 0, 0, 255]);
+/// ```
+#[derive(Default)]
+pub struct CpuBackend {
+  surface: Option<Color>,
+  clips: Vec<Clip>,
+}
+
+/// The clip area, the mask is the coverage of the clip paths in the `rect`.
+struct Clip {
+  rect: DeviceRect,
+  mask: Option<Mask>,
+}
+
+impl CpuBackend {
+  pub fn new() -> Self { Self::default() }
+
+  fn draw(&mut self, commands: &[PaintCommand], global_matrix: &Transform, canvas: &mut Canvas) {
+    let mut idx = 0;
+    while idx < commands.len() {
+      match &commands[idx] {
+        PaintCommand::PushFilter(filter) if !filter.backdrop => {
+          let end = pair_pop_filter(commands, idx);
+          let cmds = &commands[idx + 1..end];
+          self.draw_filter_layer(filter, cmds, global_matrix, canvas);
+          idx = end + 1;
+        }
+        cmd => {
+          self.draw_command(cmd, global_matrix, canvas);
+          idx += 1;
+        }
+      }
+    }
+  }
+
+  fn draw_command(&mut self, cmd: &PaintCommand, global_matrix: &Transform, canvas: &mut Canvas) {
+    match cmd {
+      PaintCommand::Path(PathCommand { path, paint_bounds, transform, action }) => {
+        let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+        let rect = self.viewport().intersection(&bounds);
+        let matrix = transform.then(global_matrix);
+        match action {
+          PaintPathAction::Paint { brush, painting_style, blend_mode } => {
+            let Some(rect) = rect else { return };
+            let Some(shader) = Shader::new(brush, &matrix) else { return };
+            let mask = self.clipped(path_mask(path, painting_style, &matrix, &rect));
+            canvas.paint(&mask, *blend_mode, |x, y| shader.color_at(x, y));
+          }
+          PaintPathAction::Clip => {
+            // An invisible clip still needs to be popped by its `PopClip`.
+            let clip = match rect {
+              Some(rect) => {
+                let mask = path_mask(path, &PaintingStyle::Fill, &matrix, &rect);
+                Clip { rect, mask: Some(self.clipped(mask)) }
+              }
+              None => Clip { rect: DeviceRect::zero(), mask: None },
+            };
+            self.clips.push(clip);
+          }
+        }
+      }
+      PaintCommand::Shadow(ShadowCommand { path, paint_bounds, transform, color, blur_radius }) => {
+        let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+        let Some(rect) = self.viewport().intersection(&bounds) else { return };
+        let matrix = transform.then(global_matrix);
+        let sigma = (blur_radius * matrix_scale(&matrix) / 2.).min(MAX_BLUR_SIGMA);
+        // Rasterize the path with the content out of the view that blurred in.
+        let extent = (sigma * 3.).ceil() as i32;
+        let area = rect.inflate(extent, extent);
+        let mask = path_mask(path, &PaintingStyle::Fill, &matrix, &area);
+        let mut alpha: Vec<[f32; 1]> = mask.alpha.iter().map(|a| [*a]).collect();
+        let (width, height) = (area.width() as usize, area.height() as usize);
+        gaussian_blur(&mut alpha, width, height, sigma);
+        let blurred = Mask { rect: area, alpha: alpha.into_iter().map(|[a]| a).collect() };
+        let mask = self.clipped(blurred.crop(&rect));
+        let color = premultiplied(*color);
+        canvas.paint(&mask, BlendMode::Normal, |_, _| color);
+      }
+      PaintCommand::PopClip => {
+        self.clips.pop();
+      }
+      PaintCommand::PushFilter(filter) => {
+        // The filter layer is drawn by `draw` with its commands together, only
+        // the backdrop filter reaches here.
+        if filter.backdrop {
+          self.draw_backdrop_filter(filter, global_matrix, canvas);
+        }
+      }
+      // The backdrop filter has no content to composite.
+      PaintCommand::PopFilter => {}
+      PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
+        let matrix = transform.then(global_matrix);
+        let bounds = transform_to_device_rect(bounds, &matrix);
+        let Some(rect) = self.viewport().intersection(&bounds) else { return };
+        let layer = self.draw_layer(rect, cmds, &matrix);
+        let mask = self.clip_mask(rect);
+        canvas.draw_layer(&layer, *opacity, &mask);
+      }
+    }
+  }
+
+  fn draw_filter_layer(
+    &mut self, filter: &FilterCommand, cmds: &[PaintCommand], global_matrix: &Transform,
+    canvas: &mut Canvas,
+  ) {
+    let FilterCommand { path, transform, filter, .. } = filter;
+    let matrix = transform.then(global_matrix);
+    let scale = matrix_scale(&matrix);
+    let extent = (filter.blur_extent() * scale).ceil() as i32;
+    let path_rect = transform_to_device_rect(&path.bounds(None), &matrix);
+    // The content out of the viewport only affects the visible part by the blur.
+    let view = self.viewport().inflate(extent, extent);
+    let Some(content) = path_rect.intersection(&view) else { return };
+    let layer_rect = content.inflate(extent, extent);
+
+    // The clips of the canvas are not used in the layer, they are applied when
+    // the layer is composited.
+    let clips = std::mem::replace(&mut self.clips, vec![Clip { rect: content, mask: None }]);
+    let mut layer = Canvas::new(layer_rect);
+    self.draw(cmds, global_matrix, &mut layer);
+    self.clips = clips;
+
+    apply_filter(filter.ops(), scale, &mut layer);
+    let Some(rect) = self.viewport().intersection(&layer_rect) else { return };
+    canvas.draw_layer(&layer, 1., &self.clip_mask(rect));
+  }
+
+  /// Apply the filter to the content already drawn behind the filter path.
+  fn draw_backdrop_filter(
+    &mut self, filter: &FilterCommand, global_matrix: &Transform, canvas: &mut Canvas,
+  ) {
+    let FilterCommand { path, paint_bounds, transform, filter, .. } = filter;
+    let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+    let Some(rect) = self.viewport().intersection(&bounds) else { return };
+    let matrix = transform.then(global_matrix);
+    let scale = matrix_scale(&matrix);
+    let extent = (filter.blur_extent() * scale).ceil() as i32;
+    let Some(src) = rect
+      .inflate(extent, extent)
+      .intersection(&canvas.rect)
+    else {
+      return;
+    };
+
+    let mut layer = Canvas::new(src);
+    let mut idx = 0;
+    for y in src.y_range() {
+      for x in src.x_range() {
+        layer.pixels[idx] = canvas.pixel(x, y);
+        idx += 1;
+      }
+    }
+    apply_filter(filter.ops(), scale, &mut layer);
+    let mask = self.clipped(path_mask(path, &PaintingStyle::Fill, &matrix, &rect));
+    canvas.draw_layer(&layer, 1., &mask);
+  }
+
+  /// Draw the commands to a new transparent layer of the `rect` area.
+  fn draw_layer(&mut self, rect: DeviceRect, cmds: &[PaintCommand], matrix: &Transform) -> Canvas {
+    let clips = std::mem::replace(&mut self.clips, vec![Clip { rect, mask: None }]);
+    let mut layer = Canvas::new(rect);
+    self.draw(cmds, matrix, &mut layer);
+    self.clips = clips;
+    layer
+  }
+
+  fn viewport(&self) -> DeviceRect {
+    self
+      .clips
+      .last()
+      .map_or(DeviceRect::zero(), |c| c.rect)
+  }
+
+  /// The coverage of the current clip in the `rect` area.
+  fn clip_mask(&self, rect: DeviceRect) -> Mask {
+    match self.clips.last() {
+      Some(Clip { mask: Some(mask), .. }) => mask.crop(&rect),
+      Some(Clip { rect: clip, mask: None }) => {
+        Mask::new(rect, 1.).crop(&clip.intersection(&rect).unwrap_or_default())
+      }
+      None => Mask::new(DeviceRect::zero(), 0.),
+    }
+  }
+
+  /// Limit the coverage of the `mask` by the current clip.
+  fn clipped(&self, mut mask: Mask) -> Mask {
+    if let Some(Clip { mask: Some(clip), .. }) = self.clips.last() {
+      let rect = mask.rect;
+      let mut idx = 0;
+      for y in rect.y_range() {
+        for x in rect.x_range() {
+          mask.alpha[idx] *= clip.get(x, y);
+          idx += 1;
+        }
+      }
+    }
+    mask
+  }
+}
+
+impl PainterBackend for CpuBackend {
+  type Texture = PixelImage;
+
+  fn begin_frame(&mut self, surface: Color) { self.surface = Some(surface); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, commands: &[PaintCommand], global_matrix: &Transform,
+    output: &mut Self::Texture,
+  ) {
+    assert_eq!(output.color_format(), ColorFormat::Rgba8);
+    if let Some(surface) = self.surface.take() {
+      let color = surface.into_components();
+      for p in output.pixel_bytes_mut().chunks_exact_mut(4) {
+        p.copy_from_slice(&color);
+      }
+    }
+
+    let img_rect = DeviceRect::from_size((output.width() as i32, output.height() as i32).into());
+    let Some(rect) = viewport.intersection(&img_rect) else { return };
+    let mut canvas = Canvas::from_image(output, rect);
+    self.clips.push(Clip { rect, mask: None });
+    self.draw(commands, global_matrix, &mut canvas);
+    self.clips.clear();
+    canvas.write_to_image(output);
+  }
+
+  fn end_frame(&mut self) {}
+}
+
+fn apply_filter(ops: &[FilterOp], scale: f32, layer: &mut Canvas) {
+  let (width, height) = (layer.rect.width() as usize, layer.rect.height() as usize);
+  for op in ops {
+    match op {
+      FilterOp::Blur(radius) => {
+        let sigma = (radius * scale).min(MAX_BLUR_SIGMA);
+        gaussian_blur(&mut layer.pixels, width, height, sigma);
+      }
+      FilterOp::ColorMatrix(matrix) => layer.apply_color_matrix(matrix),
+    }
+  }
+}
+
+fn pair_pop_filter(commands: &[PaintCommand], start: usize) -> usize {
+  let mut depth = 0;
+  for (idx, cmd) in commands.iter().enumerate().skip(start) {
+    match cmd {
+      PaintCommand::PushFilter(_) => depth += 1,
+      PaintCommand::PopFilter => {
+        depth -= 1;
+        if depth == 0 {
+          return idx;
+        }
+      }
+      _ => {}
+    }
+  }
+  commands.len()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::{Point, Rect, Size};
+  use ribir_painter::{Painter, Path};
+
+  use super::*;
+
+  fn render(painter: &mut Painter, size: (u32, u32)) -> PixelImage {
+    let (w, h) = size;
+    let data = vec![0; (w * h * 4) as usize];
+    let mut img = PixelImage::new(data.into(), w, h, ColorFormat::Rgba8);
+    let mut backend = CpuBackend::new();
+    backend.begin_frame(Color::WHITE);
+    let viewport = DeviceRect::from_size((w as i32, h as i32).into());
+    backend.draw_commands(viewport, &painter.finish(), &Transform::identity(), &mut img);
+    backend.end_frame();
+    img
+  }
+
+  fn pixel(img: &PixelImage, x: u32, y: u32) -> [u8; 4] {
+    let idx = ((y * img.width() + x) * 4) as usize;
+    img.pixel_bytes()[idx..idx + 4]
+      .try_into()
+      .unwrap()
+  }
+
+  #[test]
+  fn clip_rect() {
+    let mut painter = Painter::new(Rect::from_size(Size::new(8., 8.)));
+    painter
+      .clip(Path::rect(&Rect::new(Point::new(2., 2.), Size::new(4., 4.))).into())
+      .rect(&Rect::from_size(Size::new(8., 8.)))
+      .set_fill_brush(Color::RED)
+      .fill();
+
+    let img = render(&mut painter, (8, 8));
+    assert_eq!(pixel(&img, 0, 0), [255, 255, 255, 255]);
+    assert_eq!(pixel(&img, 3, 3), [255, 0, 0, 255]);
+    assert_eq!(pixel(&img, 6, 6), [255, 255, 255, 255]);
+  }
+
+  #[test]
+  fn alpha_over_surface() {
+    let mut painter = Painter::new(Rect::from_size(Size::new(4., 4.)));
+    painter
+      .apply_alpha(0.5)
+      .rect(&Rect::from_size(Size::new(4., 4.)))
+      .set_fill_brush(Color::BLACK)
+      .fill();
+
+    let img = render(&mut painter, (4, 4));
+    assert_eq!(pixel(&img, 1, 1), [127, 127, 127, 255]);
+  }
+}
@@ -0,0 +1,250 @@
+use ribir_geom::{DeviceRect, Point, Transform};
+use ribir_painter::{PaintingStyle, Path, Vertex, VertexBuffers};
+
+const TOLERANCE: f32 = 0.1_f32;
+
+/// The coverage of a path in a device area, every value is in `[0, 1]`.
+pub(crate) struct Mask {
+  pub rect: DeviceRect,
+  pub alpha: Vec<f32>,
+}
+
+impl Mask {
+  pub fn new(rect: DeviceRect, alpha: f32) -> Self {
+    let len = rect.area().max(0) as usize;
+    Self { rect, alpha: vec![alpha; len] }
+  }
+
+  /// Return the coverage at the device pixel, zero if it's out of the mask.
+  pub fn get(&self, x: i32, y: i32) -> f32 {
+    if self
+      .rect
+      .contains(ribir_geom::DevicePoint::new(x, y))
+    {
+      let idx = (y - self.rect.min_y()) * self.rect.width() + x - self.rect.min_x();
+      self.alpha[idx as usize]
+    } else {
+      0.
+    }
+  }
+
+  /// Return a mask of the `rect` area, the pixels out of this mask are zero.
+  pub fn crop(&self, rect: &DeviceRect) -> Mask {
+    let mut mask = Mask::new(*rect, 0.);
+    let mut idx = 0;
+    for y in rect.y_range() {
+      for x in rect.x_range() {
+        mask.alpha[idx] = self.get(x, y);
+        idx += 1;
+      }
+    }
+    mask
+  }
+}
+
+/// Rasterize the path transformed by the `matrix` to the `rect` area of the
+/// device with anti-aliasing.
+///
+/// The path is tessellated to triangles first, and then every triangle
+/// accumulates its signed area to the pixels it covers. The edges shared by two
+/// adjacent triangles cancel each other out, so there is no seam between them.
+pub(crate) fn path_mask(
+  path: &Path, style: &PaintingStyle, matrix: &Transform, rect: &DeviceRect,
+) -> Mask {
+  let origin = rect.origin.to_f32();
+  let vertex_ctor = |pos| {
+    let pos = matrix.transform_point(pos);
+    Vertex::new([pos.x - origin.x, pos.y - origin.y], ())
+  };
+  let tolerance = TOLERANCE / matrix_scale(matrix).max(f32::EPSILON);
+  let mut buffer = VertexBuffers::<()>::new();
+  match style {
+    PaintingStyle::Fill => path.fill_tessellate(tolerance, &mut buffer, vertex_ctor),
+    PaintingStyle::Stroke(options) => {
+      path.stroke_tessellate(tolerance, options.clone(), &mut buffer, vertex_ctor)
+    }
+  }
+
+  let mut acc = Accumulator::new(rect.width() as usize, rect.height() as usize);
+  let point = |idx: &u32| {
+    let [x, y] = buffer.vertices[*idx as usize].pos;
+    Point::new(x, y)
+  };
+  for tri in buffer.indices.chunks_exact(3) {
+    let [a, b, c] = [point(&tri[0]), point(&tri[1]), point(&tri[2])];
+    // Make every triangle has the same orientation.
+    let area = (b - a).cross(c - a);
+    if area > 0. {
+      acc.line(a, b);
+      acc.line(b, c);
+      acc.line(c, a);
+    } else if area < 0. {
+      acc.line(a, c);
+      acc.line(c, b);
+      acc.line(b, a);
+    }
+  }
+
+  Mask { rect: *rect, alpha: acc.into_alpha() }
+}
+
+/// The scale of the transform, the max length a unit vector can be scaled to.
+pub(crate) fn matrix_scale(matrix: &Transform) -> f32 {
+  let Transform { m11, m12, m21, m22, .. } = matrix;
+  (m11.abs() + m12.abs()).max(m21.abs() + m22.abs())
+}
+
+/// Accumulate the signed area of lines to the cells, the coverage of a pixel
+/// is the sum of the cells from the row start to it.
+struct Accumulator {
+  width: usize,
+  height: usize,
+  /// Two extra cells for every row, the lines at the right edge write to them.
+  stride: usize,
+  cells: Vec<f32>,
+}
+
+impl Accumulator {
+  fn new(width: usize, height: usize) -> Self {
+    let stride = width + 2;
+    Self { width, height, stride, cells: vec![0.; stride * height] }
+  }
+
+  fn line(&mut self, p0: Point, p1: Point) {
+    if (p0.y - p1.y).abs() <= f32::EPSILON {
+      return;
+    }
+    let (dir, p0, p1) = if p0.y < p1.y { (1., p0, p1) } else { (-1., p1, p0) };
+    let y_start = p0.y.max(0.);
+    let y_end = p1.y.min(self.height as f32);
+    if y_start >= y_end {
+      return;
+    }
+
+    let dxdy = (p1.x - p0.x) / (p1.y - p0.y);
+    let max_x = self.width as f32;
+    let mut x = p0.x + (y_start - p0.y) * dxdy;
+    for y in (y_start as usize)..(y_end.ceil() as usize) {
+      let row = y * self.stride;
+      let dy = ((y + 1) as f32).min(y_end) - (y as f32).max(y_start);
+      let x_next = x + dxdy * dy;
+      let d = dy * dir;
+      // The part out of the left or right edge is treated as a vertical line on
+      // the edge, it keeps the coverage of the pixels inside.
+      let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+      let (x0, x1) = (x0.clamp(0., max_x), x1.clamp(0., max_x));
+      let x0_floor = x0.floor();
+      let x0i = x0_floor as usize;
+      let x1_ceil = x1.ceil();
+      let x1i = x1_ceil as usize;
+      let cells = &mut self.cells[row..row + self.stride];
+      if x1i <= x0i + 1 {
+        let xmf = 0.5 * (x0 + x1) - x0_floor;
+        cells[x0i] += d - d * xmf;
+        cells[x0i + 1] += d * xmf;
+      } else {
+        let s = (x1 - x0).recip();
+        let x0f = x0 - x0_floor;
+        let a0 = 0.5 * s * (1. - x0f) * (1. - x0f);
+        let x1f = x1 - x1_ceil + 1.;
+        let am = 0.5 * s * x1f * x1f;
+        cells[x0i] += d * a0;
+        if x1i == x0i + 2 {
+          cells[x0i + 1] += d * (1. - a0 - am);
+        } else {
+          let a1 = s * (1.5 - x0f);
+          cells[x0i + 1] += d * (a1 - a0);
+          for cell in &mut cells[x0i + 2..x1i - 1] {
+            *cell += d * s;
+          }
+          let a2 = a1 + (x1i - x0i - 3) as f32 * s;
+          cells[x1i - 1] += d * (1. - a2 - am);
+        }
+        cells[x1i] += d * am;
+      }
+      x = x_next;
+    }
+  }
+
+  fn into_alpha(self) -> Vec<f32> {
+    let mut alpha = Vec::with_capacity(self.width * self.height);
+    for row in self.cells.chunks_exact(self.stride) {
+      let mut sum = 0.;
+      for cell in &row[..self.width] {
+        sum += cell;
+        alpha.push(sum.abs().min(1.));
+      }
+    }
+    alpha
+  }
+}
+
+/// Blur the pixels in place by a Gaussian blur of the standard deviation
+/// `sigma`, the pixels out of the area are treated as transparent.
+pub(crate) fn gaussian_blur<const N: usize>(
+  pixels: &mut [[f32; N]], width: usize, height: usize, sigma: f32,
+) {
+  if sigma <= 0. || width == 0 || height == 0 {
+    return;
+  }
+  let radius = (sigma * 3.).ceil() as isize;
+  let kernel: Vec<f32> = (-radius..=radius)
+    .map(|i| (-(i * i) as f32 / (2. * sigma * sigma)).exp())
+    .collect();
+  let total: f32 = kernel.iter().sum();
+  let kernel: Vec<f32> = kernel.into_iter().map(|k| k / total).collect();
+
+  let mut line = vec![[0.; N]; width.max(height)];
+  let mut blur_line = |pixels: &mut [[f32; N]], len: usize, idx: &dyn Fn(usize) -> usize| {
+    for (i, p) in line[..len].iter_mut().enumerate() {
+      *p = pixels[idx(i)];
+    }
+    for i in 0..len {
+      let mut v = [0.; N];
+      for (k, w) in kernel.iter().enumerate() {
+        let j = i as isize + k as isize - radius;
+        if j >= 0 && (j as usize) < len {
+          let p = &line[j as usize];
+          for c in 0..N {
+            v[c] += p[c] * w;
+          }
+        }
+      }
+      pixels[idx(i)] = v;
+    }
+  };
+
+  for y in 0..height {
+    blur_line(pixels, width, &|x| y * width + x);
+  }
+  for x in 0..width {
+    blur_line(pixels, height, &|y| y * width + x);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::{Rect, Size};
+
+  use super::*;
+
+  #[test]
+  fn rect_coverage() {
+    let path = Path::rect(&Rect::new(Point::new(1.5, 1.), Size::new(2., 2.)));
+    let rect = DeviceRect::from_size((5, 4).into());
+    let mask = path_mask(&path, &PaintingStyle::Fill, &Transform::identity(), &rect);
+    let row = |y: i32| (0..5).map(|x| mask.get(x, y)).collect::<Vec<_>>();
+    assert_eq!(row(0), [0.; 5]);
+    assert_eq!(row(1), [0., 0.5, 1., 0.5, 0.]);
+    assert_eq!(row(2), [0., 0.5, 1., 0.5, 0.]);
+    assert_eq!(row(3), [0.; 5]);
+  }
+
+  #[test]
+  fn coverage_out_of_area() {
+    let path = Path::rect(&Rect::new(Point::new(-10., -10.), Size::new(20., 20.)));
+    let rect = DeviceRect::new((2, 2).into(), (4, 4).into());
+    let mask = path_mask(&path, &PaintingStyle::Fill, &Transform::identity(), &rect);
+    assert!(mask.alpha.iter().all(|a| *a == 1.));
+  }
+}
@@ -0,0 +1,242 @@
+use ribir_geom::{Point, Transform};
+use ribir_painter::{
+  BlendMode, Color, CommandBrush, GradientStop, PixelImage, SpreadMethod, image::ColorFormat,
+};
+
+/// A premultiplied color with the `f32` components.
+pub(crate) type Rgba = [f32; 4];
+
+pub(crate) fn premultiplied(color: Color) -> Rgba {
+  let [r, g, b, a] = color.into_f32_components();
+  [r * a, g * a, b * a, a]
+}
+
+/// Compute the color of every pixel painted by a brush.
+pub(crate) enum Shader<'a> {
+  Color(Rgba),
+  Image {
+    img: &'a PixelImage,
+    opacity: f32,
+    to_brush: Transform,
+  },
+  Linear {
+    start: Point,
+    end: Point,
+    stops: &'a [GradientStop],
+    spread: SpreadMethod,
+    to_brush: Transform,
+  },
+  Radial {
+    start: Point,
+    start_radius: f32,
+    end: Point,
+    end_radius: f32,
+    stops: &'a [GradientStop],
+    spread: SpreadMethod,
+    to_brush: Transform,
+  },
+}
+
+impl<'a> Shader<'a> {
+  /// Create a shader of the brush that painted with the `matrix`.
+  pub fn new(brush: &'a CommandBrush, matrix: &Transform) -> Option<Self> {
+    let to_brush = matrix.inverse()?;
+    let shader = match brush {
+      CommandBrush::Color(color) => Shader::Color(premultiplied(*color)),
+      CommandBrush::Image { img, opacity } => {
+        assert_eq!(img.color_format(), ColorFormat::Rgba8);
+        Shader::Image { img, opacity: *opacity, to_brush }
+      }
+      CommandBrush::Linear(linear) => Shader::Linear {
+        start: linear.start,
+        end: linear.end,
+        stops: &linear.stops,
+        spread: linear.spread_method,
+        to_brush,
+      },
+      CommandBrush::Radial(radial) => Shader::Radial {
+        start: radial.start_center,
+        start_radius: radial.start_radius,
+        end: radial.end_center,
+        end_radius: radial.end_radius,
+        stops: &radial.stops,
+        spread: radial.spread_method,
+        to_brush,
+      },
+    };
+    Some(shader)
+  }
+
+  /// The premultiplied color at the center of the device pixel.
+  pub fn color_at(&self, x: i32, y: i32) -> Rgba {
+    let pos = Point::new(x as f32 + 0.5, y as f32 + 0.5);
+    match self {
+      Shader::Color(color) => *color,
+      Shader::Image { img, opacity, to_brush } => {
+        let pos = to_brush.transform_point(pos);
+        sample_image(img, pos).map(|c| c * opacity)
+      }
+      Shader::Linear { start, end, stops, spread, to_brush } => {
+        let pos = to_brush.transform_point(pos);
+        let dir = *end - *start;
+        let len = dir.square_length();
+        if len == 0. {
+          return [0.; 4];
+        }
+        let offset = (pos - *start).dot(dir) / len;
+        gradient_color(stops, spread_offset(offset, *spread))
+      }
+      Shader::Radial { start, start_radius, end, end_radius, stops, spread, to_brush } => {
+        let pos = to_brush.transform_point(pos);
+        match radial_offset(pos, *start, *start_radius, *end, *end_radius) {
+          Some(offset) => gradient_color(stops, spread_offset(offset, *spread)),
+          None => [0.; 4],
+        }
+      }
+    }
+  }
+}
+
+/// Sample the image with the bilinear filter, the image repeats in both
+/// directions.
+fn sample_image(img: &PixelImage, pos: Point) -> Rgba {
+  let (w, h) = (img.width() as i32, img.height() as i32);
+  if w == 0 || h == 0 {
+    return [0.; 4];
+  }
+  let data = img.pixel_bytes();
+  let texel = |x: i32, y: i32| {
+    let idx = (y.rem_euclid(h) * w + x.rem_euclid(w)) as usize * 4;
+    premultiplied(Color::new(data[idx], data[idx + 1], data[idx + 2], data[idx + 3]))
+  };
+
+  let x = pos.x - 0.5;
+  let y = pos.y - 0.5;
+  let (x0, y0) = (x.floor(), y.floor());
+  let (fx, fy) = (x - x0, y - y0);
+  let (x0, y0) = (x0 as i32, y0 as i32);
+  let [c00, c10, c01, c11] =
+    [texel(x0, y0), texel(x0 + 1, y0), texel(x0, y0 + 1), texel(x0 + 1, y0 + 1)];
+  let mut res = [0.; 4];
+  for i in 0..4 {
+    let top = c00[i] * (1. - fx) + c10[i] * fx;
+    let bottom = c01[i] * (1. - fx) + c11[i] * fx;
+    res[i] = top * (1. - fy) + bottom * fy;
+  }
+  res
+}
+
+fn spread_offset(offset: f32, spread: SpreadMethod) -> f32 {
+  match spread {
+    SpreadMethod::Pad => offset.clamp(0., 1.),
+    SpreadMethod::Reflect => 1. - ((offset / 2.).rem_euclid(1.) - 0.5).abs() * 2.,
+    SpreadMethod::Repeat => offset.rem_euclid(1.),
+  }
+}
+
+/// The interpolated color of the stops at the `offset`.
+fn gradient_color(stops: &[GradientStop], offset: f32) -> Rgba {
+  let (Some(first), Some(last)) = (stops.first(), stops.last()) else {
+    return [0.; 4];
+  };
+  let color = if offset <= first.offset {
+    first.color.into_f32_components()
+  } else if offset >= last.offset {
+    last.color.into_f32_components()
+  } else {
+    let next = stops
+      .iter()
+      .position(|s| s.offset >= offset)
+      .unwrap_or(stops.len() - 1)
+      .max(1);
+    let (prev, next) = (&stops[next - 1], &stops[next]);
+    let range = next.offset - prev.offset;
+    let weight = if range > 0. { (offset - prev.offset) / range } else { 1. };
+    let prev = prev.color.into_f32_components();
+    let next = next.color.into_f32_components();
+    std::array::from_fn(|i| prev[i] * (1. - weight) + next[i] * weight)
+  };
+  let [r, g, b, a] = color;
+  [r * a, g * a, b * a, a]
+}
+
+/// The offset of the two-point conical gradient at the position, see
+/// https://html.spec.whatwg.org/multipage/canvas.html#dom-context-2d-createradialgradient
+fn radial_offset(pos: Point, c0: Point, r0: f32, c1: Point, r1: f32) -> Option<f32> {
+  let d0 = pos - c0;
+  let d10 = c1 - c0;
+  let dr = r1 - r0;
+  let a = d10.square_length() - dr * dr;
+  let b = -2. * (d10.dot(d0) + dr * r0);
+  let c = d0.square_length() - r0 * r0;
+
+  let offset = if a.abs() < 0.1 {
+    if b.abs() < 0.1 {
+      return None;
+    }
+    -c / b
+  } else {
+    let delta = b * b - 4. * a * c;
+    if delta < 0. {
+      return None;
+    }
+    let sqrt_delta = delta.sqrt();
+    ((-b + sqrt_delta) / (2. * a)).max((-b - sqrt_delta) / (2. * a))
+  };
+  // The radius is negative at the offset.
+  if r0 + dr * offset < 0. {
+    return None;
+  }
+  Some(offset)
+}
+
+/// Composite the premultiplied `src` over the `backdrop` by the blend mode.
+pub(crate) fn blend(src: Rgba, backdrop: Rgba, mode: BlendMode) -> Rgba {
+  let sa = src[3];
+  let ba = backdrop[3];
+  let separable: fn(f32, f32) -> f32 = match mode {
+    BlendMode::Normal => return std::array::from_fn(|i| src[i] + backdrop[i] * (1. - sa)),
+    BlendMode::DestinationOut => return backdrop.map(|c| c * (1. - sa)),
+    BlendMode::Multiply => |cb, cs| cb * cs,
+    BlendMode::Screen => |cb, cs| cb + cs - cb * cs,
+    BlendMode::Overlay => |cb, cs| {
+      if cb <= 0.5 { 2. * cb * cs } else { 1. - 2. * (1. - cb) * (1. - cs) }
+    },
+    BlendMode::Darken => f32::min,
+    BlendMode::Lighten => f32::max,
+  };
+
+  let unpremultiply = |c: f32, a: f32| if a > 0. { c / a } else { 0. };
+  let mut res = [0.; 4];
+  for i in 0..3 {
+    let mixed = separable(unpremultiply(backdrop[i], ba), unpremultiply(src[i], sa)).clamp(0., 1.);
+    res[i] = src[i] * (1. - ba) + backdrop[i] * (1. - sa) + sa * ba * mixed;
+  }
+  res[3] = sa + ba * (1. - sa);
+  res
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn blend_opaque_colors() {
+    let red = premultiplied(Color::RED);
+    let blue = premultiplied(Color::BLUE);
+    assert_eq!(blend(blue, red, BlendMode::Normal), blue);
+    assert_eq!(blend(blue, red, BlendMode::Multiply), [0., 0., 0., 1.]);
+    assert_eq!(blend(blue, red, BlendMode::Screen), [1., 0., 1., 1.]);
+    assert_eq!(blend(blue, red, BlendMode::DestinationOut), [0.; 4]);
+  }
+
+  #[test]
+  fn gradient_stops() {
+    let stops = [GradientStop::new(Color::BLACK, 0.25), GradientStop::new(Color::WHITE, 0.75)];
+    assert_eq!(gradient_color(&stops, 0.), [0., 0., 0., 1.]);
+    assert_eq!(gradient_color(&stops, 0.5), [0.5, 0.5, 0.5, 1.]);
+    assert_eq!(gradient_color(&stops, 1.), [1.; 4]);
+    assert_eq!(spread_offset(1.25, SpreadMethod::Repeat), 0.25);
+    assert_eq!(spread_offset(1.25, SpreadMethod::Reflect), 0.75);
+  }
+}
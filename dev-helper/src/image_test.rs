@@ -13,7 +13,7 @@ use ribir_painter::{PixelImage, image::ColorFormat};
 /// name}\{function name}.{fmt}`:
 ///
 /// - the `{module path}` is where the generated test is placed.
-/// - the `{backend name}` is the painter-backend name like `wgpu`.
+/// - the `{backend name}` is the painter-backend name like `wgpu` or `cpu`.
 /// - the `{function  name}` is the function you pass to the macro.
 /// - the `{fmt}` is the file format the backend wants to check.
 ///
@@ -36,6 +36,18 @@ macro_rules! painter_backend_eq_image_test {
           $(.with_comparison($comparison))?
           .test();
       }
+
+      #[test]
+      fn [<cpu_ $painter_fn>]() {
+        let mut painter = $painter_fn();
+        let viewport = painter.viewport().to_i32().cast_unit();
+        let img = cpu_render_commands(&painter.finish(), viewport, Color::TRANSPARENT);
+        let name = format!("{}_cpu", std::stringify!($painter_fn));
+        let file_path = test_case_name!(name, "png");
+        ImageTest::new(img, &file_path)
+          $(.with_comparison($comparison))?
+          .test();
+      }
     }
   };
 }
@@ -224,3 +236,23 @@ pub fn wgpu_render_commands(
     draw_img(&mut backend)
   }
 }
+
+/// Render painter by the cpu backend, and return the image.
+pub fn cpu_render_commands(
+  commands: &[ribir_painter::PaintCommand], viewport: ribir_geom::DeviceRect,
+  surface: ribir_painter::Color,
+) -> PixelImage {
+  use ribir_cpu::CpuBackend;
+  use ribir_geom::{DeviceRect, DeviceSize};
+  use ribir_painter::PainterBackend;
+
+  let rect = DeviceRect::from_size(DeviceSize::new(viewport.max_x() + 2, viewport.max_y() + 2));
+  let (width, height) = (rect.width() as u32, rect.height() as u32);
+  let data = vec![0; (width * height * 4) as usize];
+  let mut img = PixelImage::new(data.into(), width, height, ColorFormat::Rgba8);
+  let mut backend = CpuBackend::new();
+  backend.begin_frame(surface);
+  backend.draw_commands(rect, commands, &Transform::identity(), &mut img);
+  backend.end_frame();
+  img
+}
@@ -2,11 +2,12 @@ use std::error::Error;
 
 use guillotiere::euclid::Vector2D;
 use ribir_geom::{
-  DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
+  DevicePoint, DeviceRect, DeviceSize, Point, Transform, rect_corners, transform_to_device_rect,
 };
 use ribir_painter::{
-  Color, CommandBrush, PaintCommand, PaintPath, PaintPathAction, PainterBackend, PaintingStyle,
-  PathCommand, PixelImage, Vertex, VertexBuffers, image::ColorFormat,
+  BlendMode, Color, ColorMatrix, CommandBrush, Filter, FilterCommand, FilterOp, PaintCommand,
+  PaintPath, PaintPathAction, PainterBackend, PaintingStyle, PathCommand, PixelImage,
+  ShadowCommand, Vertex, VertexBuffers, image::ColorFormat,
 };
 
 use crate::{
@@ -103,8 +104,19 @@ where
     self.viewport = viewport;
     self.begin_draw_phase();
     let output_size = output.size();
-    for cmd in commands {
-      self.draw_command(cmd, global_matrix, output_size, output);
+    let mut idx = 0;
+    while idx < commands.len() {
+      match &commands[idx] {
+        PaintCommand::PushFilter(filter) if !filter.backdrop => {
+          let end = pair_pop_filter(commands, idx);
+          self.draw_filter_layer(filter, &commands[idx + 1..end], global_matrix, output);
+          idx = end + 1;
+        }
+        cmd => {
+          self.draw_command(cmd, global_matrix, output_size, output);
+          idx += 1;
+        }
+      }
     }
     self.draw_triangles(output);
     self.end_draw_phase();
@@ -179,6 +191,13 @@ where
           return;
         };
 
+        if let PaintPathAction::Paint { blend_mode, .. } = action {
+          if *blend_mode != BlendMode::Normal {
+            self.draw_blend_path(cmd, viewport, global_matrix, output);
+            return;
+          }
+        }
+
         if !self.can_batch_path_command(cmd) {
           self.new_draw_phase(output);
         }
@@ -257,6 +276,39 @@ where
           },
         }
       }
+      PaintCommand::Shadow(ShadowCommand { path, paint_bounds, transform, color, blur_radius }) => {
+        if self.skip_clip_cnt > 0 {
+          return;
+        }
+        let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+        let Some(viewport) = self.viewport().intersection(&bounds) else {
+          return;
+        };
+
+        let tex_used = self.tex_ids_map.len();
+        let can_batch = match self.current_phase {
+          CurrentPhase::None => true,
+          CurrentPhase::Color => tex_used < self.gpu_impl.limits().max_tex_load,
+          _ => false,
+        };
+        if !can_batch {
+          self.new_draw_phase(output);
+        }
+
+        let matrix = transform.then(global_matrix);
+        let sigma = (blur_radius * matrix_scale(&matrix) / 2.).min(MAX_BLUR_SIGMA);
+        let (mask, mask_to_view) = self.tex_mgr.store_blurred_alpha_path(
+          path,
+          sigma,
+          &matrix,
+          &viewport,
+          &mut self.gpu_impl,
+        );
+        let (rect, mask_head) = self.push_mask_layer(mask, &mask_to_view);
+        let color_attr = ColorAttr { color: color.into_components(), mask_head };
+        add_rect_vertices(rect, output_tex_size, color_attr, &mut self.color_vertices_buffer);
+        self.current_phase = CurrentPhase::Color;
+      }
       PaintCommand::PopClip => {
         if self.skip_clip_cnt > 0 {
           self.skip_clip_cnt -= 1;
@@ -264,6 +316,15 @@ where
           self.clip_layer_stack.pop();
         }
       }
+      PaintCommand::PushFilter(filter) => {
+        // The filter layer is drawn by `draw_commands` with its commands
+        // together, only the backdrop filter reaches here.
+        if filter.backdrop && self.skip_clip_cnt == 0 {
+          self.draw_backdrop_filter(filter, global_matrix, output);
+        }
+      }
+      // The backdrop filter has no content to composite.
+      PaintCommand::PopFilter => {}
       PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
         let matrix = transform.then(global_matrix);
         let scale = self.tex_mgr.cache_scale(&bounds.size, &matrix);
@@ -331,6 +392,181 @@ where
     }
   }
 
+  /// Draw the `cmds` to an offscreen layer, and composite it to the `output`
+  /// after the filter applied.
+  fn draw_filter_layer(
+    &mut self, filter: &FilterCommand, cmds: &[PaintCommand], global_matrix: &Transform,
+    output: &mut Impl::Texture,
+  ) {
+    if self.skip_clip_cnt > 0 {
+      return;
+    }
+    let FilterCommand { path, transform, filter, .. } = filter;
+    let matrix = transform.then(global_matrix);
+    let scale = matrix_scale(&matrix);
+    let extent = (filter.blur_extent() * scale).ceil() as i32;
+    let path_rect = transform_to_device_rect(&path.bounds(None), &matrix);
+    // The content out of the viewport only affects the visible part by the blur.
+    let view = self.viewport().inflate(extent, extent);
+    let Some(content) = path_rect.intersection(&view) else {
+      return;
+    };
+    let layer_rect = content.inflate(extent, extent);
+
+    self.new_draw_phase(output);
+    let (layer_idx, mut layer) = self
+      .tex_mgr
+      .push_layer(layer_rect.size, &mut self.gpu_impl);
+    let rect = DeviceRect::from_size(layer_rect.size);
+    layer.clear_areas(&[rect], &mut self.gpu_impl);
+
+    // Draw the content to the layer, the clip masks of the output are not used
+    // in the layer, they are applied when the layer is composited.
+    let viewport = self.viewport;
+    let content_view =
+      DeviceRect::new((content.origin - layer_rect.origin).to_point(), content.size);
+    self
+      .clip_layer_stack
+      .push(ClipLayer { viewport: content_view, mask_head: -1 });
+    let origin = layer_rect.origin.to_f32();
+    let layer_matrix = global_matrix.then_translate(Vector2D::new(-origin.x, -origin.y));
+    self.draw_commands(rect, cmds, &layer_matrix, &mut layer);
+    self.clip_layer_stack.pop();
+    self.viewport = viewport;
+    self.begin_draw_phase();
+
+    self.apply_filter(filter, scale, &mut layer, rect);
+    self.tex_mgr.restore_layer(layer_idx, layer);
+
+    let slice = TextureSlice { tex_id: TextureID::Layer(layer_idx), rect };
+    let points = rect_corners(&layer_rect.to_f32().cast_unit());
+    let view_to_slice = Transform::translation(-origin.x, -origin.y);
+    let mask_head = self.current_clip_mask_index();
+    self.draw_img_slice(slice, &view_to_slice, mask_head, 1., output.size(), points);
+    // The layer texture can be reused after the composition submitted.
+    self.new_draw_phase(output);
+    self.tex_mgr.pop_layer();
+  }
+
+  /// Draw the path to an offscreen layer, and then composite it with the
+  /// content already drawn in the `viewport` by its blend mode.
+  fn draw_blend_path(
+    &mut self, cmd: &PathCommand, viewport: DeviceRect, global_matrix: &Transform,
+    output: &mut Impl::Texture,
+  ) {
+    let Some(rect) = viewport.intersection(&DeviceRect::from_size(output.size())) else {
+      return;
+    };
+    let PaintPathAction::Paint { brush, painting_style, blend_mode } = &cmd.action else {
+      unreachable!();
+    };
+    let action = PaintPathAction::Paint {
+      brush: brush.clone(),
+      painting_style: painting_style.clone(),
+      blend_mode: BlendMode::Normal,
+    };
+    let src_cmd = PaintCommand::Path(PathCommand { action, ..cmd.clone() });
+
+    // Submit the content drawn before, it's the backdrop of the blending.
+    self.new_draw_phase(output);
+
+    // The source layer uses the same coordinate as the output, so the clip
+    // masks of the output are applied to the source.
+    let src_size = DeviceSize::new(rect.max_x(), rect.max_y());
+    let (src_idx, mut src) = self
+      .tex_mgr
+      .push_layer(src_size, &mut self.gpu_impl);
+    src.clear_areas(&[rect], &mut self.gpu_impl);
+    let view = self.viewport;
+    self.draw_commands(view, &[src_cmd], global_matrix, &mut src);
+    self.viewport = view;
+    self.begin_draw_phase();
+
+    let (backdrop_idx, mut backdrop) = self
+      .tex_mgr
+      .push_layer(rect.size, &mut self.gpu_impl);
+    self
+      .gpu_impl
+      .copy_texture_from_texture(&mut backdrop, DevicePoint::zero(), output, &rect);
+    self
+      .gpu_impl
+      .draw_blend(output, rect, &src, &backdrop, *blend_mode);
+
+    self.tex_mgr.restore_layer(backdrop_idx, backdrop);
+    self.tex_mgr.restore_layer(src_idx, src);
+    self.tex_mgr.pop_layer();
+    self.tex_mgr.pop_layer();
+  }
+
+  /// Apply the filter to the content already drawn behind the filter path.
+  fn draw_backdrop_filter(
+    &mut self, filter: &FilterCommand, global_matrix: &Transform, output: &mut Impl::Texture,
+  ) {
+    let FilterCommand { path, paint_bounds, transform, filter, .. } = filter;
+    let bounds = transform_to_device_rect(paint_bounds, global_matrix);
+    let Some(viewport) = self.viewport().intersection(&bounds) else {
+      return;
+    };
+    let matrix = transform.then(global_matrix);
+    let scale = matrix_scale(&matrix);
+    let extent = (filter.blur_extent() * scale).ceil() as i32;
+    let Some(src) = viewport
+      .inflate(extent, extent)
+      .intersection(&DeviceRect::from_size(output.size()))
+    else {
+      return;
+    };
+
+    // Submit the content drawn before, and then read it back.
+    self.new_draw_phase(output);
+    let (layer_idx, mut layer) = self
+      .tex_mgr
+      .push_layer(src.size, &mut self.gpu_impl);
+    self
+      .gpu_impl
+      .copy_texture_from_texture(&mut layer, DevicePoint::zero(), output, &src);
+    let rect = DeviceRect::from_size(src.size);
+    self.apply_filter(filter, scale, &mut layer, rect);
+    self.tex_mgr.restore_layer(layer_idx, layer);
+
+    let (points, mask_head) = self.new_mask_layer(&viewport, &matrix, path, &PaintingStyle::Fill);
+    let slice = TextureSlice { tex_id: TextureID::Layer(layer_idx), rect };
+    let origin = src.origin.to_f32();
+    let view_to_slice = Transform::translation(-origin.x, -origin.y);
+    self.draw_img_slice(slice, &view_to_slice, mask_head, 1., output.size(), points);
+    self.new_draw_phase(output);
+    self.tex_mgr.pop_layer();
+  }
+
+  /// Apply the filter to the `rect` of the `layer` whose pixels are
+  /// premultiplied alpha, and leave the result not premultiplied to composite.
+  fn apply_filter(
+    &mut self, filter: &Filter, scale: f32, layer: &mut Impl::Texture, rect: DeviceRect,
+  ) {
+    let ops = filter.ops();
+    for (i, op) in ops.iter().enumerate() {
+      match op {
+        FilterOp::Blur(radius) => {
+          let sigma = (radius * scale).min(MAX_BLUR_SIGMA);
+          self
+            .gpu_impl
+            .draw_gaussian_blur(layer, rect, sigma);
+        }
+        FilterOp::ColorMatrix(matrix) => {
+          let last = i == ops.len() - 1;
+          self
+            .gpu_impl
+            .draw_color_matrix(layer, rect, matrix, !last);
+        }
+      }
+    }
+    if !matches!(ops.last(), Some(FilterOp::ColorMatrix(_))) {
+      self
+        .gpu_impl
+        .draw_color_matrix(layer, rect, &ColorMatrix::IDENTITY, false);
+    }
+  }
+
   fn can_batch_img_path(&self) -> bool {
     let limits = self.gpu_impl.limits();
     self.current_phase == CurrentPhase::None
@@ -477,7 +713,10 @@ where
       self
         .tex_mgr
         .store_alpha_path(path, style, matrix, view, &mut self.gpu_impl);
+    self.push_mask_layer(mask, &mask_to_view)
+  }
 
+  fn push_mask_layer(&mut self, mask: TextureSlice, mask_to_view: &Transform) -> ([Point; 4], i32) {
     let mut points = rect_corners(&mask.rect.to_f32().cast_unit());
     for p in points.iter_mut() {
       *p = mask_to_view.transform_point(*p);
@@ -582,6 +821,33 @@ impl TextureIdxMap {
   fn len(&self) -> usize { self.textures.len() }
 }
 
+/// The max standard deviation of the shadow blur in device pixels, a larger
+/// blur will be clamped to it to limit the samples of the blur pass.
+const MAX_BLUR_SIGMA: f32 = 64.;
+
+fn matrix_scale(matrix: &Transform) -> f32 {
+  let Transform { m11, m12, m21, m22, .. } = matrix;
+  (m11.abs() + m12.abs()).max(m21.abs() + m22.abs())
+}
+
+/// Return the index of the `PopFilter` paired with the `PushFilter` at `start`.
+fn pair_pop_filter(commands: &[PaintCommand], start: usize) -> usize {
+  let mut depth = 0;
+  for (idx, cmd) in commands.iter().enumerate().skip(start) {
+    match cmd {
+      PaintCommand::PushFilter(_) => depth += 1,
+      PaintCommand::PopFilter => {
+        depth -= 1;
+        if depth == 0 {
+          return idx;
+        }
+      }
+      _ => {}
+    }
+  }
+  commands.len()
+}
+
 pub fn vertices_coord(pos: Point, tex_size: DeviceSize) -> [f32; 2] {
   [pos.x / tex_size.width as f32, pos.y / tex_size.height as f32]
 }
@@ -611,7 +877,7 @@ mod tests {
   use ribir_algo::Resource;
   use ribir_dev_helper::*;
   use ribir_geom::*;
-  use ribir_painter::{Brush, Painter, Path, Svg};
+  use ribir_painter::{Brush, Filter, Painter, Path, Radius, Svg};
 
   use super::*;
 
@@ -804,6 +1070,7 @@ mod tests {
           action: PaintPathAction::Paint {
             brush: CommandBrush::Color(color),
             painting_style: PaintingStyle::Fill,
+            blend_mode: BlendMode::Normal,
           },
         })
       })
@@ -813,4 +1080,127 @@ mod tests {
     painter
   }
   painter_backend_eq_image_test!(draw_bundle_svg, comparison = 0.001);
+
+  painter_backend_eq_image_test!(draw_shadow, comparison = 0.002);
+  fn draw_shadow() -> Painter {
+    let mut painter = painter(Size::new(300., 150.));
+    let rect = Rect::new(Point::new(30., 30.), Size::new(80., 80.));
+    let path = Path::rect_round(&rect, &Radius::all(8.));
+    painter
+      .draw_shadow(path.clone().into(), Color::BLACK.with_alpha(0.6), 16.)
+      .set_fill_brush(Color::WHITE)
+      .fill_path(path.clone().into());
+
+    let circle = Path::circle(Point::new(220., 70.), 40.);
+    painter
+      .translate(0., 8.)
+      .draw_shadow(circle.clone().into(), Color::RED, 8.)
+      .translate(0., -8.)
+      .set_fill_brush(Color::YELLOW)
+      .fill_path(circle.into());
+    painter
+  }
+
+  painter_backend_eq_image_test!(filter_layers, comparison = 0.002);
+  fn filter_layers() -> Painter {
+    let mut painter = painter(Size::new(360., 120.));
+    let area = Path::rect(&Rect::from_size(Size::new(100., 100.)));
+    let draw_content = |painter: &mut Painter, filter: Filter| {
+      let mut painter = painter.save_guard();
+      painter
+        .filter(area.clone().into(), filter)
+        .set_fill_brush(Color::RED)
+        .rect(&Rect::new(Point::new(10., 10.), Size::new(50., 50.)))
+        .fill()
+        .set_fill_brush(Color::BLUE)
+        .circle(Point::new(60., 60.), 30.)
+        .fill();
+    };
+
+    painter.translate(10., 10.);
+    draw_content(&mut painter, Filter::blur(4.));
+    painter.translate(120., 0.);
+    draw_content(&mut painter, Filter::grayscale(1.));
+    painter.translate(120., 0.);
+    draw_content(&mut painter, Filter::brightness(0.5).then(Filter::blur(2.)));
+    painter
+  }
+
+  painter_backend_eq_image_test!(backdrop_filter, comparison = 0.002);
+  fn backdrop_filter() -> Painter {
+    let mut painter = painter(Size::new(200., 120.));
+    painter
+      .set_fill_brush(Color::RED)
+      .rect(&Rect::new(Point::new(20., 20.), Size::new(80., 80.)))
+      .fill()
+      .set_fill_brush(Color::BLUE)
+      .circle(Point::new(120., 60.), 40.)
+      .fill();
+
+    let glass =
+      Path::rect_round(&Rect::new(Point::new(60., 30.), Size::new(100., 60.)), &Radius::all(10.));
+    painter
+      .save()
+      .backdrop_filter(glass.clone().into(), Filter::blur(6.))
+      .set_fill_brush(Color::WHITE.with_alpha(0.3))
+      .fill_path(glass.into())
+      .restore();
+    painter
+  }
+
+  painter_backend_eq_image_test!(blend_modes, comparison = 0.002);
+  fn blend_modes() -> Painter {
+    let mut painter = painter(Size::new(490., 150.));
+    let draw_backdrop = |painter: &mut Painter| {
+      painter
+        .set_fill_brush(Color::RED)
+        .rect(&Rect::new(Point::new(5., 5.), Size::new(40., 40.)))
+        .fill()
+        .set_fill_brush(Color::YELLOW.with_alpha(0.5))
+        .rect(&Rect::new(Point::new(25., 25.), Size::new(40., 40.)))
+        .fill();
+    };
+
+    let modes = [
+      BlendMode::Normal,
+      BlendMode::Multiply,
+      BlendMode::Screen,
+      BlendMode::Overlay,
+      BlendMode::Darken,
+      BlendMode::Lighten,
+      BlendMode::DestinationOut,
+    ];
+    for (i, mode) in modes.into_iter().enumerate() {
+      let mut painter = painter.save_guard();
+      painter.translate(i as f32 * 70., 0.);
+      draw_backdrop(&mut painter);
+      painter
+        .set_blend_mode(mode)
+        .set_fill_brush(Color::BLUE.with_alpha(0.8))
+        .circle(Point::new(35., 35.), 22.)
+        .fill();
+    }
+
+    // The image brush, and the blending clipped.
+    let img = Resource::new(PixelImage::from_png(include_bytes!("../imgs/leaves.png")));
+    let brushes: [(Brush, BlendMode); 3] = [
+      (img.clone().into(), BlendMode::Multiply),
+      (img.into(), BlendMode::Screen),
+      (Color::BLUE.into(), BlendMode::Overlay),
+    ];
+    for (i, (brush, mode)) in brushes.into_iter().enumerate() {
+      let mut painter = painter.save_guard();
+      painter.translate(i as f32 * 70., 75.);
+      draw_backdrop(&mut painter);
+      if i == 2 {
+        painter.clip(Path::rect(&Rect::from_size(Size::new(35., 70.))).into());
+      }
+      painter
+        .set_blend_mode(mode)
+        .set_fill_brush(brush)
+        .rect(&Rect::new(Point::new(15., 15.), Size::new(40., 40.)))
+        .fill();
+    }
+    painter
+  }
 }
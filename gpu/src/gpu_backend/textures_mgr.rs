@@ -22,6 +22,7 @@ pub(super) enum TextureID {
   Alpha(usize),
   Rgba(usize),
   Bundle(usize),
+  Layer(usize),
 }
 
 #[derive(PartialEq, Clone)]
@@ -42,6 +43,13 @@ pub(super) struct TexturesMgr<T: Texture> {
   target_atlas: Atlas<Resource<dyn Any>, T>,
   tess_task: Vec<TessTask>,
   tess_task_buffer: VertexBuffers<()>,
+  /// The slices need to blur after their paths tessellated, and the standard
+  /// deviation of the blur.
+  blur_task: Vec<(TextureSlice, f32)>,
+  /// The offscreen textures of the filter layers. The layers are used as a
+  /// stack, a nested layer always uses the texture after its parent's.
+  layers: Vec<Option<T>>,
+  layers_used: usize,
   need_clear_areas: Vec<DeviceRect>,
 }
 
@@ -66,6 +74,7 @@ macro_rules! id_to_texture_mut {
       TextureID::Alpha(id) => $mgr.alpha_atlas.get_texture_mut(id),
       TextureID::Rgba(id) => $mgr.rgba_atlas.get_texture_mut(id),
       TextureID::Bundle(id) => $mgr.target_atlas.get_texture_mut(id),
+      TextureID::Layer(id) => $mgr.layers[id].as_mut().unwrap(),
     }
   };
 }
@@ -76,6 +85,7 @@ macro_rules! id_to_texture {
       TextureID::Alpha(id) => $mgr.alpha_atlas.get_texture(id),
       TextureID::Rgba(id) => $mgr.rgba_atlas.get_texture(id),
       TextureID::Bundle(id) => $mgr.target_atlas.get_texture(id),
+      TextureID::Layer(id) => $mgr.layers[id].as_ref().unwrap(),
     }
   };
 }
@@ -106,6 +116,9 @@ where
       ),
       tess_task: <_>::default(),
       tess_task_buffer: <_>::default(),
+      blur_task: vec![],
+      layers: vec![],
+      layers_used: 0,
       need_clear_areas: vec![],
     }
   }
@@ -187,6 +200,46 @@ where
     }
   }
 
+  /// Store a blurred alpha path of fill style in texture and return the
+  /// texture and a transform that can transform the mask to viewport. The
+  /// `sigma` is the standard deviation of the blur in device pixels.
+  ///
+  /// The blurred path is not cached, because its content is depended on the
+  /// scale of the matrix.
+  pub(super) fn store_blurred_alpha_path(
+    &mut self, path: &PaintPath, sigma: f32, matrix: &Transform, viewport: &DeviceRect,
+    gpu: &mut T::Host,
+  ) -> (TextureSlice, Transform) {
+    let extent = (sigma * 3.).ceil() as i32;
+    let path_bounds = transform_to_device_rect(&path.bounds(None), matrix);
+    let mut visual_rect = path_bounds.inflate(extent, extent);
+    if !self
+      .alpha_atlas
+      .is_good_size_to_alloc(size_expand_blank(visual_rect.size))
+    {
+      // Only the pixels near the viewport can affect the visible part.
+      let view = viewport.inflate(extent, extent);
+      visual_rect = visual_rect.intersection(&view).unwrap_or(view);
+    }
+
+    let (_, slice) = self.alpha_allocate(visual_rect.size, gpu);
+    let offset = (slice.rect.origin - visual_rect.origin)
+      .to_f32()
+      .cast_unit();
+    let transform = matrix.then_translate(offset);
+    self.tess_task.push(TessTask {
+      slice,
+      transform,
+      path: path.clone(),
+      style: PaintingStyle::Fill,
+      clip_rect: Some(slice.rect),
+    });
+    self.blur_task.push((slice, sigma));
+
+    let offset = (visual_rect.origin - slice.rect.origin).to_f32();
+    (slice.expand_for_paste(), Transform::translation(offset.x, offset.y))
+  }
+
   pub(super) fn store_image(
     &mut self, img: &Resource<PixelImage>, gpu: &mut T::Host,
   ) -> TextureSlice {
@@ -225,6 +278,31 @@ where
 
   pub(super) fn texture(&self, tex_id: TextureID) -> &T { id_to_texture!(self, tex_id) }
 
+  /// Take the texture of a new layer that's at least `size` large. The texture
+  /// should be given back by `restore_layer` before it's used as a
+  /// `TextureID::Layer`, and the layer should be popped by `pop_layer` after
+  /// its drawing is submitted.
+  pub(super) fn push_layer(&mut self, size: DeviceSize, gpu: &mut T::Host) -> (usize, T) {
+    let idx = self.layers_used;
+    self.layers_used += 1;
+    if idx == self.layers.len() {
+      self.layers.push(None);
+    }
+
+    let texture = match self.layers[idx].take() {
+      Some(tex) if !size.greater_than(tex.size()).any() => tex,
+      Some(tex) => gpu.new_texture(tex.size().max(size), ColorFormat::Rgba8),
+      None => gpu.new_texture(size, ColorFormat::Rgba8),
+    };
+    (idx, texture)
+  }
+
+  pub(super) fn restore_layer(&mut self, idx: usize, texture: T) {
+    self.layers[idx] = Some(texture);
+  }
+
+  pub(super) fn pop_layer(&mut self) { self.layers_used -= 1; }
+
   fn alpha_allocate(
     &mut self, mut size: DeviceSize, gpu: &mut T::Host,
   ) -> (AtlasDist, TextureSlice) {
@@ -389,6 +467,11 @@ where
       gpu_impl.draw_alpha_triangles(&indices, texture);
     }
 
+    for (slice, sigma) in self.blur_task.drain(..) {
+      let texture = id_to_texture_mut!(self, slice.tex_id);
+      gpu_impl.draw_gaussian_blur(texture, slice.rect, sigma);
+    }
+
     self.tess_task.clear();
     self.tess_task_buffer.vertices.clear();
     self.tess_task_buffer.indices.clear();
@@ -400,6 +483,7 @@ where
     });
     self.rgba_atlas.end_frame();
     self.target_atlas.end_frame();
+    assert_eq!(self.layers_used, 0, "Filter layers not popped.");
   }
 }
 
@@ -4,7 +4,9 @@ use std::ops::Range;
 
 pub use gpu_backend::Texture;
 use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
-use ribir_painter::{Color, GradientStop, VertexBuffers, image::ColorFormat};
+use ribir_painter::{
+  BlendMode, Color, ColorMatrix, GradientStop, VertexBuffers, image::ColorFormat,
+};
 mod gpu_backend;
 use zerocopy::AsBytes;
 
@@ -35,6 +37,10 @@ pub use wgpu_impl::*;
 ///   |    ^                                      v      |
 ///   |    +----<-----------<---------------------+      |
 ///   |                                                  |
+///   | -> + draw_gaussian_blur()-----------------+      |
+///   |    ^                                      v      |
+///   |    +----<-----------<---------------------+      |
+///   |                                                  |
 ///   | -> load_textures()                               |
 ///   | -> load_mask_layers()                            |
 ///   |                                                  |    
@@ -64,6 +70,11 @@ pub use wgpu_impl::*;
 ///   |        +------------------------------------+    |
 ///   +---<----------------------------------------------+
 ///
+/// Between two draw phases, the filter layers may call
+/// `copy_texture_from_texture()`, `draw_gaussian_blur()` and
+/// `draw_color_matrix()` to process the offscreen textures, and the blended
+/// paths call `draw_blend()` to composite their offscreen textures.
+///
 /// -+ ->- end_frame()
 ///
 /// The coordinate always start from the left-top to right-bottom. Vertices
@@ -100,6 +111,28 @@ pub trait GPUBackendImpl {
   fn draw_alpha_triangles_with_scissor(
     &mut self, indices: &Range<u32>, texture: &mut Self::Texture, scissor: DeviceRect,
   );
+  /// Blur the `rect` area of the `texture` in place with a Gaussian blur of
+  /// the standard deviation `sigma` in pixels. The pixels out of the `rect` are
+  /// treated as transparent. The backend is free to implement it as two
+  /// separable passes.
+  fn draw_gaussian_blur(&mut self, texture: &mut Self::Texture, rect: DeviceRect, sigma: f32);
+  /// Transform the colors of the `rect` area of the `texture` in place by the
+  /// color `matrix`. The pixels of the texture are premultiplied alpha, they
+  /// are un-premultiplied before the matrix applied, and the result is
+  /// premultiplied again only if `premultiplied` is true.
+  fn draw_color_matrix(
+    &mut self, texture: &mut Self::Texture, rect: DeviceRect, matrix: &ColorMatrix,
+    premultiplied: bool,
+  );
+  /// Composite the `rect` area of the `src` texture with the `backdrop`
+  /// texture by the blend `mode`, and replace the `rect` area of the `texture`
+  /// with the result. The `src` is read at the same position as the
+  /// `texture`, but the `backdrop` is read from its origin. All the pixels are
+  /// premultiplied alpha.
+  fn draw_blend(
+    &mut self, texture: &mut Self::Texture, rect: DeviceRect, src: &Self::Texture,
+    backdrop: &Self::Texture, mode: BlendMode,
+  );
 
   /// load textures that will be use in this draw phase
   fn load_textures(&mut self, textures: &[&Self::Texture]);
@@ -6,9 +6,12 @@ use std::{
 
 use futures::channel::oneshot;
 use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
-use ribir_painter::{Color, PixelImage, VertexBuffers, image::ColorFormat};
+use ribir_painter::{BlendMode, Color, ColorMatrix, PixelImage, VertexBuffers, image::ColorFormat};
 
 use self::{
+  blend_pass::BlendPass,
+  blur_pass::BlurPass,
+  color_matrix_pass::ColorMatrixPass,
   draw_alpha_triangles_pass::DrawAlphaTrianglesPass,
   draw_color_triangles_pass::DrawColorTrianglesPass,
   draw_img_triangles_pass::DrawImgTrianglesPass,
@@ -26,6 +29,9 @@ mod shaders;
 mod uniform;
 mod vertex_buffer;
 
+mod blend_pass;
+mod blur_pass;
+mod color_matrix_pass;
 mod draw_alpha_triangles_pass;
 mod draw_color_triangles_pass;
 mod draw_img_triangles_pass;
@@ -46,6 +52,9 @@ pub struct WgpuImpl {
   clear_tex_pass: ClearTexturePass,
   alpha_triangles_pass: DrawAlphaTrianglesPass,
   copy_tex_pass: Option<CopyTexturePass>,
+  blend_pass: Option<BlendPass>,
+  blur_pass: Option<BlurPass>,
+  color_matrix_pass: Option<ColorMatrixPass>,
   color_triangles_pass: Option<DrawColorTrianglesPass>,
   img_triangles_pass: Option<DrawImgTrianglesPass>,
   radial_gradient_pass: Option<DrawRadialGradientTrianglesPass>,
@@ -278,6 +287,24 @@ impl GPUBackendImpl for WgpuImpl {
     );
   }
 
+  fn draw_gaussian_blur(&mut self, texture: &mut Self::Texture, rect: DeviceRect, sigma: f32) {
+    self.draw_blur(texture, rect, sigma);
+  }
+
+  fn draw_color_matrix(
+    &mut self, texture: &mut Self::Texture, rect: DeviceRect, matrix: &ColorMatrix,
+    premultiplied: bool,
+  ) {
+    self.draw_color_matrix_in_place(texture, rect, matrix, premultiplied);
+  }
+
+  fn draw_blend(
+    &mut self, texture: &mut Self::Texture, rect: DeviceRect, src: &Self::Texture,
+    backdrop: &Self::Texture, mode: BlendMode,
+  ) {
+    self.draw_blend_to(texture, rect, src, backdrop, mode);
+  }
+
   fn draw_color_triangles(
     &mut self, texture: &mut Self::Texture, indices: Range<u32>, clear: Option<Color>,
   ) {
@@ -634,6 +661,9 @@ impl WgpuImpl {
       alpha_triangles_pass,
       clear_tex_pass,
       copy_tex_pass: None,
+      blend_pass: None,
+      blur_pass: None,
+      color_matrix_pass: None,
       color_triangles_pass: None,
       img_triangles_pass: None,
       radial_gradient_pass: None,
@@ -646,15 +676,17 @@ impl WgpuImpl {
 
     let surface = surface.map(|surface| {
       use wgpu::TextureFormat::*;
-      let format = surface
-        .get_capabilities(&adapter)
+      let caps = surface.get_capabilities(&adapter);
+      let format = caps
         .formats
         .into_iter()
         .find(|&f| f == Rgba8Unorm || f == Bgra8Unorm)
         .expect("No suitable format found for the surface!");
+      // The backdrop filter needs to read back the content of the surface.
+      let read_usages = wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::TEXTURE_BINDING;
 
       let config = wgpu::SurfaceConfiguration {
-        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT | (caps.usages & read_usages),
         format,
         width: 0,
         height: 0,
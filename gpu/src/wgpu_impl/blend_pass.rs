@@ -0,0 +1,189 @@
+use ribir_geom::DeviceRect;
+use ribir_painter::BlendMode;
+use wgpu::{StoreOp, include_wgsl};
+use zerocopy::AsBytes;
+
+use super::uniform::Uniform;
+use crate::{WgpuImpl, WgpuTexture, command_encoder};
+
+/// Composite a source texture with a backdrop texture by a blend mode, and
+/// replace the area of the target texture with the result.
+pub struct BlendPass {
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  layout: wgpu::PipelineLayout,
+  tex_layout: wgpu::BindGroupLayout,
+  format: Option<wgpu::TextureFormat>,
+  uniform: Uniform<BlendInfo>,
+}
+
+#[repr(C)]
+#[derive(AsBytes, Clone, Copy)]
+struct BlendInfo {
+  /// The origin of the area to write in the target texture.
+  origin: [i32; 2],
+  size: [i32; 2],
+  target_size: [i32; 2],
+  mode: u32,
+  _dummy: u32,
+}
+
+impl BlendPass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let shader = device.create_shader_module(include_wgsl!("./shaders/blend.wgsl"));
+    let uniform = Uniform::new(device, wgpu::ShaderStages::VERTEX_FRAGMENT, 1);
+    let tex_entry = |binding| wgpu::BindGroupLayoutEntry {
+      binding,
+      visibility: wgpu::ShaderStages::FRAGMENT,
+      ty: wgpu::BindingType::Texture {
+        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+        view_dimension: wgpu::TextureViewDimension::D2,
+        multisampled: false,
+      },
+      count: None,
+    };
+    let tex_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[tex_entry(0), tex_entry(1)],
+      label: Some("Blend textures layout"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Blend pipeline layout"),
+      bind_group_layouts: &[uniform.layout(), &tex_layout],
+      push_constant_ranges: &[],
+    });
+
+    Self { pipeline: None, shader, layout, tex_layout, format: None, uniform }
+  }
+
+  fn update(&mut self, format: wgpu::TextureFormat, device: &wgpu::Device) {
+    if Some(format) != self.format {
+      self.format = Some(format);
+      self.pipeline.take();
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Blend pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_main",
+          buffers: &[],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::REPLACE),
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+          topology: wgpu::PrimitiveTopology::TriangleStrip,
+          strip_index_format: None,
+          front_face: wgpu::FrontFace::Ccw,
+          cull_mode: None,
+          unclipped_depth: false,
+          polygon_mode: wgpu::PolygonMode::Fill,
+          conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+          count: 1,
+          mask: !0,
+          alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+      });
+      self.pipeline = Some(pipeline);
+    }
+  }
+}
+
+/// The blend mode index used by the shader.
+fn mode_index(mode: BlendMode) -> u32 {
+  match mode {
+    BlendMode::Normal => 0,
+    BlendMode::Multiply => 1,
+    BlendMode::Screen => 2,
+    BlendMode::Overlay => 3,
+    BlendMode::Darken => 4,
+    BlendMode::Lighten => 5,
+    BlendMode::DestinationOut => 6,
+  }
+}
+
+impl WgpuImpl {
+  pub(crate) fn draw_blend_to(
+    &mut self, texture: &WgpuTexture, rect: DeviceRect, src: &WgpuTexture, backdrop: &WgpuTexture,
+    mode: BlendMode,
+  ) {
+    let Some(rect) = rect.intersection(&DeviceRect::from_size(texture.size())) else {
+      return;
+    };
+    let pass = self
+      .blend_pass
+      .get_or_insert_with(|| BlendPass::new(&self.device));
+    pass.update(texture.format(), &self.device);
+
+    let info = BlendInfo {
+      origin: rect.origin.to_array(),
+      size: rect.size.to_array(),
+      target_size: texture.size().to_array(),
+      mode: mode_index(mode),
+      _dummy: 0,
+    };
+    pass.uniform.write_buffer(&self.queue, &[info]);
+
+    let tex_bind = self
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &pass.tex_layout,
+        entries: &[
+          wgpu::BindGroupEntry {
+            binding: 0,
+            resource: wgpu::BindingResource::TextureView(src.view()),
+          },
+          wgpu::BindGroupEntry {
+            binding: 1,
+            resource: wgpu::BindingResource::TextureView(backdrop.view()),
+          },
+        ],
+        label: Some("Blend textures bind group"),
+      });
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: texture.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+
+    let encoder = command_encoder!(self);
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Blend pass"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    rpass.set_scissor_rect(
+      rect.min_x() as u32,
+      rect.min_y() as u32,
+      rect.width() as u32,
+      rect.height() as u32,
+    );
+    rpass.set_bind_group(0, pass.uniform.bind_group(), &[]);
+    rpass.set_bind_group(1, &tex_bind, &[]);
+    rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
+    rpass.draw(0..4, 0..1);
+    drop(rpass);
+
+    // The uniform is rewritten by the next pass, so submit the commands
+    // before it.
+    self.submit();
+  }
+}
@@ -0,0 +1,210 @@
+use ribir_geom::{DeviceRect, DeviceSize};
+use wgpu::{StoreOp, include_wgsl};
+use zerocopy::AsBytes;
+
+use super::uniform::Uniform;
+use crate::{WgpuImpl, WgpuTexture, command_encoder};
+
+/// A separable Gaussian blur, blur the horizontal direction to a scratch
+/// texture first, and then blur the vertical direction back.
+pub struct BlurPass {
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  layout: wgpu::PipelineLayout,
+  tex_layout: wgpu::BindGroupLayout,
+  format: Option<wgpu::TextureFormat>,
+  uniform: Uniform<BlurInfo>,
+  scratch: Option<WgpuTexture>,
+}
+
+#[repr(C)]
+#[derive(AsBytes, Clone, Copy)]
+struct BlurInfo {
+  /// The origin of the area to read in the source texture.
+  src_origin: [i32; 2],
+  /// The origin of the area to write in the target texture.
+  dst_origin: [i32; 2],
+  size: [i32; 2],
+  /// The blur direction, (1, 0) for horizontal and (0, 1) for vertical.
+  dir: [i32; 2],
+  target_size: [i32; 2],
+  sigma: f32,
+  /// How many pixels sampled on each side.
+  radius: i32,
+}
+
+impl BlurPass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let shader = device.create_shader_module(include_wgsl!("./shaders/blur.wgsl"));
+    let uniform = Uniform::new(device, wgpu::ShaderStages::VERTEX_FRAGMENT, 1);
+    let tex_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+          sample_type: wgpu::TextureSampleType::Float { filterable: false },
+          view_dimension: wgpu::TextureViewDimension::D2,
+          multisampled: false,
+        },
+        count: None,
+      }],
+      label: Some("Blur texture layout"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Blur pipeline layout"),
+      bind_group_layouts: &[uniform.layout(), &tex_layout],
+      push_constant_ranges: &[],
+    });
+
+    Self { pipeline: None, shader, layout, tex_layout, format: None, uniform, scratch: None }
+  }
+
+  fn update(&mut self, format: wgpu::TextureFormat, size: DeviceSize, device: &wgpu::Device) {
+    if Some(format) != self.format {
+      self.format = Some(format);
+      self.pipeline.take();
+      self.scratch.take();
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Blur pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_main",
+          buffers: &[],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::REPLACE),
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+          topology: wgpu::PrimitiveTopology::TriangleStrip,
+          strip_index_format: None,
+          front_face: wgpu::FrontFace::Ccw,
+          cull_mode: None,
+          unclipped_depth: false,
+          polygon_mode: wgpu::PolygonMode::Fill,
+          conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+          count: 1,
+          mask: !0,
+          alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+      });
+      self.pipeline = Some(pipeline);
+    }
+
+    let scratch_size = self.scratch.as_ref().map(|t| t.size());
+    if scratch_size.is_none_or(|s| s.width < size.width || s.height < size.height) {
+      let size = scratch_size.map_or(size, |s| s.max(size));
+      let tex = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Blur scratch texture"),
+        size: wgpu::Extent3d {
+          width: size.width as u32,
+          height: size.height as u32,
+          depth_or_array_layers: 1,
+        },
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        mip_level_count: 1,
+        sample_count: 1,
+        view_formats: &[],
+      });
+      self.scratch = Some(WgpuTexture::from_tex(tex));
+    }
+  }
+}
+
+impl WgpuImpl {
+  pub(crate) fn draw_blur(&mut self, texture: &WgpuTexture, rect: DeviceRect, sigma: f32) {
+    if sigma <= 0. || rect.is_empty() {
+      return;
+    }
+    let pass = self
+      .blur_pass
+      .get_or_insert_with(|| BlurPass::new(&self.device));
+    pass.update(texture.format(), rect.size, &self.device);
+    let radius = (sigma * 3.).ceil() as i32;
+
+    let scratch = pass.scratch.take().unwrap();
+    let size = rect.size.to_array();
+    let horizontal = BlurInfo {
+      src_origin: rect.origin.to_array(),
+      dst_origin: [0, 0],
+      size,
+      dir: [1, 0],
+      target_size: scratch.size().to_array(),
+      sigma,
+      radius,
+    };
+    self.blur_to(texture, &scratch, horizontal, rect.size);
+
+    let vertical = BlurInfo {
+      src_origin: [0, 0],
+      dst_origin: rect.origin.to_array(),
+      size,
+      dir: [0, 1],
+      target_size: texture.size().to_array(),
+      sigma,
+      radius,
+    };
+    self.blur_to(&scratch, texture, vertical, rect.size);
+
+    self.blur_pass.as_mut().unwrap().scratch = Some(scratch);
+  }
+
+  fn blur_to(&mut self, from: &WgpuTexture, to: &WgpuTexture, info: BlurInfo, size: DeviceSize) {
+    let pass = self.blur_pass.as_mut().unwrap();
+    pass.uniform.write_buffer(&self.queue, &[info]);
+    let tex_bind = self
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &pass.tex_layout,
+        entries: &[wgpu::BindGroupEntry {
+          binding: 0,
+          resource: wgpu::BindingResource::TextureView(from.view()),
+        }],
+        label: Some("Blur texture bind group"),
+      });
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: to.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+
+    let encoder = command_encoder!(self);
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Blur pass"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    let [x, y] = info.dst_origin;
+    rpass.set_scissor_rect(x as u32, y as u32, size.width as u32, size.height as u32);
+    rpass.set_bind_group(0, pass.uniform.bind_group(), &[]);
+    rpass.set_bind_group(1, &tex_bind, &[]);
+    rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
+    rpass.draw(0..4, 0..1);
+    drop(rpass);
+
+    // The uniform is rewritten by the next pass, so submit the commands
+    // before it.
+    self.submit();
+  }
+}
@@ -0,0 +1,218 @@
+use ribir_geom::{DevicePoint, DeviceRect, DeviceSize};
+use ribir_painter::ColorMatrix;
+use wgpu::{StoreOp, include_wgsl};
+use zerocopy::AsBytes;
+
+use super::uniform::Uniform;
+use crate::{WgpuImpl, WgpuTexture, command_encoder};
+
+/// Transform the colors of a texture area by a color matrix. The result is
+/// drawn to a scratch texture first and then copied back, because a texture
+/// can't be sampled and rendered in the same pass.
+pub struct ColorMatrixPass {
+  pipeline: Option<wgpu::RenderPipeline>,
+  shader: wgpu::ShaderModule,
+  layout: wgpu::PipelineLayout,
+  tex_layout: wgpu::BindGroupLayout,
+  format: Option<wgpu::TextureFormat>,
+  uniform: Uniform<ColorMatrixInfo>,
+  scratch: Option<WgpuTexture>,
+}
+
+#[repr(C)]
+#[derive(AsBytes, Clone, Copy)]
+struct ColorMatrixInfo {
+  /// The column-major 4x4 part of the color matrix.
+  matrix: [[f32; 4]; 4],
+  offset: [f32; 4],
+  /// The origin of the area to read in the source texture.
+  src_origin: [i32; 2],
+  size: [i32; 2],
+  target_size: [i32; 2],
+  /// Premultiply the output color if it's not zero.
+  premultiplied: u32,
+  _dummy: u32,
+}
+
+impl ColorMatrixPass {
+  pub fn new(device: &wgpu::Device) -> Self {
+    let shader = device.create_shader_module(include_wgsl!("./shaders/color_matrix.wgsl"));
+    let uniform = Uniform::new(device, wgpu::ShaderStages::VERTEX_FRAGMENT, 1);
+    let tex_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+      entries: &[wgpu::BindGroupLayoutEntry {
+        binding: 0,
+        visibility: wgpu::ShaderStages::FRAGMENT,
+        ty: wgpu::BindingType::Texture {
+          sample_type: wgpu::TextureSampleType::Float { filterable: false },
+          view_dimension: wgpu::TextureViewDimension::D2,
+          multisampled: false,
+        },
+        count: None,
+      }],
+      label: Some("Color matrix texture layout"),
+    });
+    let layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+      label: Some("Color matrix pipeline layout"),
+      bind_group_layouts: &[uniform.layout(), &tex_layout],
+      push_constant_ranges: &[],
+    });
+
+    Self { pipeline: None, shader, layout, tex_layout, format: None, uniform, scratch: None }
+  }
+
+  fn update(&mut self, format: wgpu::TextureFormat, size: DeviceSize, device: &wgpu::Device) {
+    if Some(format) != self.format {
+      self.format = Some(format);
+      self.pipeline.take();
+      self.scratch.take();
+    }
+
+    if self.pipeline.is_none() {
+      let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: Some("Color matrix pipeline"),
+        layout: Some(&self.layout),
+        vertex: wgpu::VertexState {
+          module: &self.shader,
+          entry_point: "vs_main",
+          buffers: &[],
+          compilation_options: Default::default(),
+        },
+        fragment: Some(wgpu::FragmentState {
+          module: &self.shader,
+          entry_point: "fs_main",
+          targets: &[Some(wgpu::ColorTargetState {
+            format,
+            blend: Some(wgpu::BlendState::REPLACE),
+            write_mask: wgpu::ColorWrites::all(),
+          })],
+          compilation_options: Default::default(),
+        }),
+        primitive: wgpu::PrimitiveState {
+          topology: wgpu::PrimitiveTopology::TriangleStrip,
+          strip_index_format: None,
+          front_face: wgpu::FrontFace::Ccw,
+          cull_mode: None,
+          unclipped_depth: false,
+          polygon_mode: wgpu::PolygonMode::Fill,
+          conservative: false,
+        },
+        depth_stencil: None,
+        multisample: wgpu::MultisampleState {
+          count: 1,
+          mask: !0,
+          alpha_to_coverage_enabled: false,
+        },
+        multiview: None,
+      });
+      self.pipeline = Some(pipeline);
+    }
+
+    let scratch_size = self.scratch.as_ref().map(|t| t.size());
+    if scratch_size.is_none_or(|s| s.width < size.width || s.height < size.height) {
+      let size = scratch_size.map_or(size, |s| s.max(size));
+      let tex = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("Color matrix scratch texture"),
+        size: wgpu::Extent3d {
+          width: size.width as u32,
+          height: size.height as u32,
+          depth_or_array_layers: 1,
+        },
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::COPY_SRC | wgpu::TextureUsages::RENDER_ATTACHMENT,
+        mip_level_count: 1,
+        sample_count: 1,
+        view_formats: &[],
+      });
+      self.scratch = Some(WgpuTexture::from_tex(tex));
+    }
+  }
+}
+
+impl ColorMatrixInfo {
+  fn new(
+    matrix: &ColorMatrix, src_origin: [i32; 2], size: [i32; 2], target_size: [i32; 2],
+  ) -> Self {
+    let m = &matrix.0;
+    let column = |c: usize| [m[c], m[5 + c], m[10 + c], m[15 + c]];
+    Self {
+      matrix: [column(0), column(1), column(2), column(3)],
+      offset: column(4),
+      src_origin,
+      size,
+      target_size,
+      premultiplied: 0,
+      _dummy: 0,
+    }
+  }
+}
+
+impl WgpuImpl {
+  pub(crate) fn draw_color_matrix_in_place(
+    &mut self, texture: &WgpuTexture, rect: DeviceRect, matrix: &ColorMatrix, premultiplied: bool,
+  ) {
+    if rect.is_empty() {
+      return;
+    }
+    let pass = self
+      .color_matrix_pass
+      .get_or_insert_with(|| ColorMatrixPass::new(&self.device));
+    pass.update(texture.format(), rect.size, &self.device);
+
+    let scratch = pass.scratch.take().unwrap();
+    let mut info = ColorMatrixInfo::new(
+      matrix,
+      rect.origin.to_array(),
+      rect.size.to_array(),
+      scratch.size().to_array(),
+    );
+    info.premultiplied = premultiplied as u32;
+    pass.uniform.write_buffer(&self.queue, &[info]);
+
+    let tex_bind = self
+      .device
+      .create_bind_group(&wgpu::BindGroupDescriptor {
+        layout: &pass.tex_layout,
+        entries: &[wgpu::BindGroupEntry {
+          binding: 0,
+          resource: wgpu::BindingResource::TextureView(texture.view()),
+        }],
+        label: Some("Color matrix texture bind group"),
+      });
+
+    let color_attachments = wgpu::RenderPassColorAttachment {
+      view: scratch.view(),
+      resolve_target: None,
+      ops: wgpu::Operations { load: wgpu::LoadOp::Load, store: StoreOp::Store },
+    };
+
+    let encoder = command_encoder!(self);
+    let mut rpass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+      label: Some("Color matrix pass"),
+      color_attachments: &[Some(color_attachments)],
+      depth_stencil_attachment: None,
+      timestamp_writes: None,
+      occlusion_query_set: None,
+    });
+
+    rpass.set_scissor_rect(0, 0, rect.width() as u32, rect.height() as u32);
+    rpass.set_bind_group(0, pass.uniform.bind_group(), &[]);
+    rpass.set_bind_group(1, &tex_bind, &[]);
+    rpass.set_pipeline(pass.pipeline.as_ref().unwrap());
+    rpass.draw(0..4, 0..1);
+    drop(rpass);
+
+    let scratch_rect = DeviceRect::from_size(rect.size);
+    self.copy_same_format_texture(
+      texture.inner_tex.texture(),
+      DevicePoint::new(rect.min_x(), rect.min_y()),
+      scratch.inner_tex.texture(),
+      &scratch_rect,
+    );
+    // The uniform is rewritten by the next pass, so submit the commands
+    // before it.
+    self.submit();
+
+    self.color_matrix_pass.as_mut().unwrap().scratch = Some(scratch);
+  }
+}
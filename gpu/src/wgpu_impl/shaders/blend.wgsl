@@ -0,0 +1,73 @@
+struct BlendInfo {
+  origin: vec2<i32>,
+  size: vec2<i32>,
+  target_size: vec2<i32>,
+  mode: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> info: BlendInfo;
+
+@group(1) @binding(0)
+var src_tex: texture_2d<f32>;
+@group(1) @binding(1)
+var backdrop_tex: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
+  let corner = vec2<f32>(f32(idx & 1u), f32(idx >> 1u));
+  let pos = (vec2<f32>(info.origin) + corner * vec2<f32>(info.size)) / vec2<f32>(info.target_size);
+  return vec4<f32>(pos * vec2(2., -2.) + vec2(-1., 1.), 0.0, 1.0);
+}
+
+fn unpremultiply(c: vec4<f32>) -> vec3<f32> {
+  if c.a > 0. {
+    return c.rgb / c.a;
+  }
+  return vec3(0.);
+}
+
+// The blend function of the separable blend modes, `cb` is the backdrop color
+// and `cs` is the source color, both are not premultiplied.
+fn blend(cb: vec3<f32>, cs: vec3<f32>) -> vec3<f32> {
+  switch info.mode {
+    case 1u: {
+      return cb * cs;
+    }
+    case 2u: {
+      return cb + cs - cb * cs;
+    }
+    case 3u: {
+      let multiply = 2. * cb * cs;
+      let screen = 1. - 2. * (1. - cb) * (1. - cs);
+      return select(screen, multiply, cb <= vec3(0.5));
+    }
+    case 4u: {
+      return min(cb, cs);
+    }
+    case 5u: {
+      return max(cb, cs);
+    }
+    default: {
+      return cs;
+    }
+  }
+}
+
+@fragment
+fn fs_main(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+  let pos = vec2<i32>(floor(frag.xy));
+  let src = textureLoad(src_tex, pos, 0);
+  let backdrop = textureLoad(backdrop_tex, pos - info.origin, 0);
+  let as_ = src.a;
+  let ab = backdrop.a;
+
+  // Destination out, erase the backdrop by the source alpha.
+  if info.mode == 6u {
+    return backdrop * (1. - as_);
+  }
+
+  let mixed = clamp(blend(unpremultiply(backdrop), unpremultiply(src)), vec3(0.), vec3(1.));
+  let color = src.rgb * (1. - ab) + backdrop.rgb * (1. - as_) + as_ * ab * mixed;
+  return vec4(color, as_ + ab * (1. - as_));
+}
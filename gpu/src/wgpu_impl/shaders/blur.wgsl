@@ -0,0 +1,45 @@
+struct BlurInfo {
+  src_origin: vec2<i32>,
+  dst_origin: vec2<i32>,
+  size: vec2<i32>,
+  dir: vec2<i32>,
+  target_size: vec2<i32>,
+  sigma: f32,
+  radius: i32,
+}
+
+@group(0) @binding(0)
+var<uniform> info: BlurInfo;
+
+@group(1) @binding(0)
+var src_tex: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
+  let corner = vec2<f32>(f32(idx & 1u), f32(idx >> 1u));
+  let pos = (vec2<f32>(info.dst_origin) + corner * vec2<f32>(info.size))
+    / vec2<f32>(info.target_size);
+  return vec4<f32>(pos * vec2(2., -2.) + vec2(-1., 1.), 0.0, 1.0);
+}
+
+fn load(pos: vec2<i32>) -> vec4<f32> {
+  if any(pos < vec2(0)) || any(pos >= info.size) {
+    return vec4(0.);
+  }
+  return textureLoad(src_tex, info.src_origin + pos, 0);
+}
+
+@fragment
+fn fs_main(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+  let pos = vec2<i32>(floor(frag.xy)) - info.dst_origin;
+  let factor = -0.5 / (info.sigma * info.sigma);
+  var color = load(pos);
+  var total = 1.;
+  for (var i = 1; i <= info.radius; i++) {
+    let weight = exp(f32(i * i) * factor);
+    let offset = info.dir * i;
+    color += (load(pos + offset) + load(pos - offset)) * weight;
+    total += 2. * weight;
+  }
+  return color / total;
+}
@@ -0,0 +1,36 @@
+struct ColorMatrixInfo {
+  matrix: mat4x4<f32>,
+  offset: vec4<f32>,
+  src_origin: vec2<i32>,
+  size: vec2<i32>,
+  target_size: vec2<i32>,
+  premultiplied: u32,
+}
+
+@group(0) @binding(0)
+var<uniform> info: ColorMatrixInfo;
+
+@group(1) @binding(0)
+var src_tex: texture_2d<f32>;
+
+@vertex
+fn vs_main(@builtin(vertex_index) idx: u32) -> @builtin(position) vec4<f32> {
+  let corner = vec2<f32>(f32(idx & 1u), f32(idx >> 1u));
+  let pos = corner * vec2<f32>(info.size) / vec2<f32>(info.target_size);
+  return vec4<f32>(pos * vec2(2., -2.) + vec2(-1., 1.), 0.0, 1.0);
+}
+
+@fragment
+fn fs_main(@builtin(position) frag: vec4<f32>) -> @location(0) vec4<f32> {
+  let pos = vec2<i32>(floor(frag.xy));
+  let src = textureLoad(src_tex, info.src_origin + pos, 0);
+  var color = vec4(0.);
+  if src.a > 0. {
+    color = vec4(src.rgb / src.a, src.a);
+  }
+  let res = clamp(info.matrix * color + info.offset, vec4(0.), vec4(1.));
+  if info.premultiplied != 0u {
+    return vec4(res.rgb * res.a, res.a);
+  }
+  return res;
+}
@@ -105,6 +105,18 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "on_focus_in_capture" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_focus_out" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_focus_out_capture" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_start" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_enter" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_over" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_leave" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drop" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_drag_end" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_pan" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_pinch" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_rotate" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_long_press" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_command" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
+  "on_context_menu" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_custom_concrete_event" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "on_custom_event" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
   "events_stream" => builtin_member!{"MixBuiltin", Method, "mix_builtin"},
@@ -118,6 +130,8 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "background" => builtin_member!{"Background", Field, "background"},
   // BorderWidget
   "border" => builtin_member!{"BorderWidget", Field, "border"},
+  // BoxShadowWidget
+  "box_shadow" => builtin_member!{"BoxShadowWidget", Field, "box_shadow"},
   // RadiusWidget
   "radius" => builtin_member!{"RadiusWidget", Field, "radius"},
   // Foreground
@@ -168,10 +182,18 @@ pub static BUILTIN_INFOS: phf::Map<&'static str, BuiltinMember> = phf_map! {
   "visible" => builtin_member!{"Visibility", Field, "visibility"},
   // Opacity
   "opacity" => builtin_member!{"Opacity", Field, "opacity"},
+  // FilterWidget
+  "filter" => builtin_member!{"FilterWidget", Field, "filter"},
+  // BackdropFilterWidget
+  "backdrop_filter" => builtin_member!{"BackdropFilterWidget", Field, "backdrop_filter"},
+  // BlendModeWidget
+  "blend_mode" => builtin_member!{"BlendModeWidget", Field, "blend_mode"},
   // KeepAlive
   "keep_alive" => builtin_member!{"KeepAlive", Field, "keep_alive"},
   // Tooltips
   "tooltips" => builtin_member!{"Tooltips", Field, "tooltips"},
+  // SemanticsWidget
+  "semantics" => builtin_member!{"SemanticsWidget", Field, "semantics"},
   // TrackWidgetId
   "track_id" => builtin_member!{"TrackWidgetId", Method, "track_id"},
   // ClipBoundary
@@ -0,0 +1,122 @@
+//! Export the paint commands of a frame to the vector documents. The text is
+//! exported as the outlines of the glyphs, because the painter already draws
+//! the glyphs as paths.
+use std::fmt::Write;
+
+use ribir_geom::{Point, Transform};
+
+use crate::{LineCap, LineJoin, Path, PathSegment};
+
+mod pdf;
+mod svg;
+pub use pdf::*;
+pub use svg::*;
+
+/// Format a number in a short form for the documents.
+fn num(v: f32) -> String { num_with_precision(v, 4) }
+
+fn num_with_precision(v: f32, precision: usize) -> String {
+  let v = if v.abs() < 0.1_f32.powi(precision as i32) { 0. } else { v };
+  let s = format!("{v:.precision$}");
+  let s = s.trim_end_matches('0').trim_end_matches('.');
+  s.to_string()
+}
+
+/// The six components of the matrix in the order `a b c d e f` of both SVG and
+/// PDF. The components keep more precision than the coordinates, because the
+/// glyphs are scaled down from the font units by a tiny factor.
+fn matrix_components(m: &Transform) -> [String; 6] {
+  [m.m11, m.m12, m.m21, m.m22, m.m31, m.m32].map(|v| num_with_precision(v, 6))
+}
+
+fn matrix_scale(matrix: &Transform) -> f32 {
+  let Transform { m11, m12, m21, m22, .. } = matrix;
+  (m11.abs() + m12.abs()).max(m21.abs() + m22.abs())
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum PathFormat {
+  /// The SVG path data, the command is before its operands.
+  Svg,
+  /// The PDF path operators, the operator is after its operands, and there is
+  /// no quadratic curve.
+  Pdf,
+}
+
+/// Write the path transformed by the `matrix` in the format.
+fn write_path_data(path: &Path, matrix: Option<&Transform>, format: PathFormat, out: &mut String) {
+  let pt = |p: Point| matrix.map_or(p, |m| m.transform_point(p));
+  let mut emit = |svg_op: &str, pdf_op: &str, operands: &[Point]| {
+    let operands = operands
+      .iter()
+      .map(|p| format!("{} {}", num(p.x), num(p.y)))
+      .collect::<Vec<_>>()
+      .join(" ");
+    if !out.is_empty() {
+      out.push(' ');
+    }
+    match format {
+      PathFormat::Svg if operands.is_empty() => out.push_str(svg_op),
+      PathFormat::Svg => {
+        let _ = write!(out, "{svg_op}{operands}");
+      }
+      PathFormat::Pdf if operands.is_empty() => out.push_str(pdf_op),
+      PathFormat::Pdf => {
+        let _ = write!(out, "{operands} {pdf_op}");
+      }
+    }
+  };
+
+  let mut current = Point::zero();
+  let mut start = current;
+  for seg in path.segments() {
+    match seg {
+      PathSegment::MoveTo(to) => {
+        current = pt(to);
+        start = current;
+        emit("M", "m", &[current]);
+      }
+      PathSegment::LineTo(to) => {
+        current = pt(to);
+        emit("L", "l", &[current]);
+      }
+      PathSegment::QuadTo { ctrl, to } => {
+        let (ctrl, to) = (pt(ctrl), pt(to));
+        if format == PathFormat::Svg {
+          emit("Q", "", &[ctrl, to]);
+        } else {
+          let ctrl1 = current + (ctrl - current) * (2. / 3.);
+          let ctrl2 = to + (ctrl - to) * (2. / 3.);
+          emit("", "c", &[ctrl1, ctrl2, to]);
+        }
+        current = to;
+      }
+      PathSegment::CubicTo { to, ctrl1, ctrl2 } => {
+        current = pt(to);
+        emit("C", "c", &[pt(ctrl1), pt(ctrl2), current]);
+      }
+      PathSegment::Close(true) => {
+        current = start;
+        emit("Z", "h", &[]);
+      }
+      PathSegment::Close(false) => {}
+    }
+  }
+}
+
+fn line_cap_name(cap: LineCap) -> &'static str {
+  match cap {
+    LineCap::Butt => "butt",
+    LineCap::Round => "round",
+    LineCap::Square => "square",
+  }
+}
+
+fn line_join_name(join: LineJoin) -> &'static str {
+  match join {
+    LineJoin::Miter => "miter",
+    LineJoin::MiterClip => "miter-clip",
+    LineJoin::Round => "round",
+    LineJoin::Bevel => "bevel",
+  }
+}
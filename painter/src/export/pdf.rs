@@ -0,0 +1,511 @@
+use std::fmt::Write;
+
+use ribir_geom::{Size, Transform};
+
+use super::*;
+use crate::{
+  BlendMode, Color, CommandBrush, GradientStop, PaintCommand, PaintPathAction, PaintingStyle,
+  PathCommand, PixelImage, ShadowCommand, StrokeOptions, image::ColorFormat,
+};
+
+/// The number of the layers to approximate the blur of a shadow.
+const SHADOW_LAYERS: usize = 4;
+
+/// Export the paint commands to a single page PDF document of the `size`, the
+/// unit of the page is the logic pixel.
+///
+/// PDF has no blur effect, so the shadows are approximated by the layers of
+/// the translucent path that spread to the blur radius, and the filter layers
+/// only keep their content. The backdrop filters, the
+/// [`BlendMode::DestinationOut`], the alpha of the gradient stops and the
+/// repeat and reflect spread methods of the gradients are not supported
+/// either.
+///
+/// # Example
+///
+/// ```
+/// use ribir_geom::{Rect, Size};
+/// use ribir_painter::{Color, Painter, export::commands_to_pdf};
+///
+/// let size = Size::new(100., 100.);
+/// let mut painter = Painter::new(Rect::from_size(size));
+/// painter
+///   .rect(&Rect::from_size(size))
+///   .set_fill_brush(Color::RED)
+///   .fill();
+/// let pdf = commands_to_pdf(&painter.finish(), size);
+/// assert!(pdf.starts_with(b"%PDF-"));
+/// ```
+pub fn commands_to_pdf(commands: &[PaintCommand], size: Size) -> Vec<u8> {
+  // The origin of the PDF page is at the bottom-left corner.
+  let page_matrix = Transform::new(1., 0., 0., -1., 0., size.height);
+  let mut writer = PdfWriter::new(page_matrix);
+  writer.write_commands(commands, &Transform::identity(), 1.);
+  writer.finish(size)
+}
+
+/// The first objects of the document are the catalog, the page tree, the page
+/// and its content.
+const FIRST_RESOURCE_OBJ: usize = 5;
+
+struct PdfWriter {
+  page_matrix: Transform,
+  content: String,
+  /// The objects of the resources, the first one is the object
+  /// `FIRST_RESOURCE_OBJ`.
+  objects: Vec<Vec<u8>>,
+  ext_g_states: Vec<(String, usize)>,
+  patterns: Vec<usize>,
+}
+
+impl PdfWriter {
+  fn new(page_matrix: Transform) -> Self {
+    let [a, b, c, d, e, f] = matrix_components(&page_matrix);
+    Self {
+      page_matrix,
+      content: format!("{a} {b} {c} {d} {e} {f} cm\n"),
+      objects: vec![],
+      ext_g_states: vec![],
+      patterns: vec![],
+    }
+  }
+
+  /// Write the commands, the `matrix` and `opacity` are the accumulated
+  /// transform and opacity of the bundles that the commands belong to.
+  fn write_commands(&mut self, commands: &[PaintCommand], matrix: &Transform, opacity: f32) {
+    for cmd in commands {
+      match cmd {
+        PaintCommand::Path(PathCommand { path, transform, action, .. }) => {
+          let matrix = transform.then(matrix);
+          match action {
+            PaintPathAction::Paint { brush, painting_style, blend_mode } => {
+              self.paint_path(path, &matrix, brush, painting_style, *blend_mode, opacity);
+            }
+            PaintPathAction::Clip => self.push_clip(path, &matrix),
+          }
+        }
+        PaintCommand::PopClip => self.content.push_str("Q\n"),
+        // Only keep the content of the filter layer, it's clipped by the filter
+        // path.
+        PaintCommand::PushFilter(filter) => {
+          if filter.backdrop {
+            self.content.push_str("q\n");
+          } else {
+            self.push_clip(&filter.path, &filter.transform.then(matrix));
+          }
+        }
+        PaintCommand::PopFilter => self.content.push_str("Q\n"),
+        PaintCommand::Shadow(shadow) => self.shadow(shadow, matrix, opacity),
+        PaintCommand::Bundle { transform, opacity: alpha, cmds, .. } => {
+          self.write_commands(cmds, &transform.then(matrix), opacity * alpha);
+        }
+      }
+    }
+  }
+
+  fn paint_path(
+    &mut self, path: &Path, matrix: &Transform, brush: &CommandBrush, style: &PaintingStyle,
+    blend_mode: BlendMode, opacity: f32,
+  ) {
+    let Some(blend) = blend_mode_name(blend_mode) else { return };
+    let stroke = matches!(style, PaintingStyle::Stroke(_));
+    let (color_op, pattern_op, alpha_key) =
+      if stroke { ("RG", "SCN", "CA") } else { ("rg", "scn", "ca") };
+
+    let (paint, alpha) = match brush {
+      CommandBrush::Color(color) => {
+        let [r, g, b, a] = color.into_f32_components();
+        (format!("{} {} {} {color_op}", num(r), num(g), num(b)), a)
+      }
+      CommandBrush::Image { img, opacity } => {
+        let pattern = self.image_pattern(img, matrix);
+        (format!("/Pattern {} /P{pattern} {pattern_op}", &color_op[..1]), *opacity)
+      }
+      CommandBrush::Linear(linear) => {
+        let [x0, y0, x1, y1] = [linear.start.x, linear.start.y, linear.end.x, linear.end.y];
+        let shading = format!(
+          "/ShadingType 2 /Coords [{}] /Function {} /Extend [true true]",
+          [x0, y0, x1, y1].map(num).join(" "),
+          stops_function(&linear.stops)
+        );
+        let pattern = self.shading_pattern(&shading, matrix);
+        (format!("/Pattern {} /P{pattern} {pattern_op}", &color_op[..1]), 1.)
+      }
+      CommandBrush::Radial(radial) => {
+        let coords = [
+          radial.start_center.x,
+          radial.start_center.y,
+          radial.start_radius,
+          radial.end_center.x,
+          radial.end_center.y,
+          radial.end_radius,
+        ];
+        let shading = format!(
+          "/ShadingType 3 /Coords [{}] /Function {} /Extend [true true]",
+          coords.map(num).join(" "),
+          stops_function(&radial.stops)
+        );
+        let pattern = self.shading_pattern(&shading, matrix);
+        (format!("/Pattern {} /P{pattern} {pattern_op}", &color_op[..1]), 1.)
+      }
+    };
+
+    self.content.push_str("q\n");
+    let alpha = alpha * opacity;
+    if alpha < 1. || blend != "Normal" {
+      let state = self.ext_g_state(&format!("/{alpha_key} {} /BM /{blend}", num(alpha)));
+      let _ = writeln!(self.content, "/GS{state} gs");
+    }
+    let [a, b, c, d, e, f] = matrix_components(matrix);
+    let _ = writeln!(self.content, "{a} {b} {c} {d} {e} {f} cm");
+    let _ = writeln!(self.content, "{paint}");
+    if let PaintingStyle::Stroke(options) = style {
+      self.stroke_options(options);
+    }
+    let mut data = String::new();
+    write_path_data(path, None, PathFormat::Pdf, &mut data);
+    let op = if stroke { "S" } else { "f" };
+    let _ = writeln!(self.content, "{data} {op}\nQ");
+  }
+
+  /// Approximate the blurred shadow by filling the path, and stroking it by
+  /// the round strokes of the decreasing width around. Every stroke paints a
+  /// part of the alpha, so the shadow fades out to the blur radius.
+  fn shadow(&mut self, shadow: &ShadowCommand, matrix: &Transform, opacity: f32) {
+    let ShadowCommand { path, transform, color, blur_radius, .. } = shadow;
+    let matrix = transform.then(matrix);
+    let fill = CommandBrush::Color(*color);
+    self.paint_path(path, &matrix, &fill, &PaintingStyle::Fill, BlendMode::Normal, opacity);
+    if *blur_radius <= 0. {
+      return;
+    }
+
+    let layer = CommandBrush::Color(color.apply_alpha(1. / SHADOW_LAYERS as f32));
+    for i in 1..=SHADOW_LAYERS {
+      let stroke = PaintingStyle::Stroke(StrokeOptions {
+        width: 2. * blur_radius * i as f32 / SHADOW_LAYERS as f32,
+        line_cap: LineCap::Round,
+        line_join: LineJoin::Round,
+        ..Default::default()
+      });
+      self.paint_path(path, &matrix, &layer, &stroke, BlendMode::Normal, opacity);
+    }
+  }
+
+  /// Save the graphics state and clip it by the path, the state is restored by
+  /// the paired `Q`.
+  fn push_clip(&mut self, path: &Path, matrix: &Transform) {
+    // The path is transformed before written, so the transform not remains in
+    // the graphics state.
+    let mut data = String::new();
+    write_path_data(path, Some(matrix), PathFormat::Pdf, &mut data);
+    let _ = writeln!(self.content, "q\n{data} W n");
+  }
+
+  fn stroke_options(&mut self, options: &StrokeOptions) {
+    let cap = match options.line_cap {
+      LineCap::Butt => 0,
+      LineCap::Round => 1,
+      LineCap::Square => 2,
+    };
+    let join = match options.line_join {
+      LineJoin::Miter | LineJoin::MiterClip => 0,
+      LineJoin::Round => 1,
+      LineJoin::Bevel => 2,
+    };
+    let _ = writeln!(
+      self.content,
+      "{} w {cap} J {join} j {} M",
+      num(options.width),
+      num(options.miter_limit)
+    );
+  }
+
+  /// Return the index of the graphics state with the `params`.
+  fn ext_g_state(&mut self, params: &str) -> usize {
+    if let Some(idx) = self
+      .ext_g_states
+      .iter()
+      .position(|(p, _)| p == params)
+    {
+      return idx;
+    }
+    let obj = self.add_object(format!("<< /Type /ExtGState {params} >>").into_bytes());
+    self.ext_g_states.push((params.to_string(), obj));
+    self.ext_g_states.len() - 1
+  }
+
+  /// Add a pattern painting the shading, and return its index.
+  fn shading_pattern(&mut self, shading: &str, matrix: &Transform) -> usize {
+    let obj = format!(
+      "<< /Type /Pattern /PatternType 2 /Matrix [{}] /Shading << /ColorSpace /DeviceRGB {shading} \
+       >> >>",
+      self.pattern_matrix(matrix)
+    );
+    let obj = self.add_object(obj.into_bytes());
+    self.patterns.push(obj);
+    self.patterns.len() - 1
+  }
+
+  /// Add a pattern repeating the image, and return its index.
+  fn image_pattern(&mut self, img: &PixelImage, matrix: &Transform) -> usize {
+    let (w, h) = (img.width(), img.height());
+    let (rgb, alpha) = rgb_and_alpha(img);
+    let mask = self.add_stream(
+      &format!(
+        "/Type /XObject /Subtype /Image /Width {w} /Height {h} /ColorSpace /DeviceGray \
+         /BitsPerComponent 8"
+      ),
+      &alpha,
+    );
+    let image = self.add_stream(
+      &format!(
+        "/Type /XObject /Subtype /Image /Width {w} /Height {h} /ColorSpace /DeviceRGB \
+         /BitsPerComponent 8 /SMask {mask} 0 R"
+      ),
+      &rgb,
+    );
+    // The first row of the image is at the top of the unit square.
+    let content = format!("q {w} 0 0 -{h} 0 {h} cm /Im Do Q");
+    let obj = self.add_stream(
+      &format!(
+        "/Type /Pattern /PatternType 1 /PaintType 1 /TilingType 1 /BBox [0 0 {w} {h}] /XStep {w} \
+         /YStep {h} /Matrix [{}] /Resources << /XObject << /Im {image} 0 R >> >>",
+        self.pattern_matrix(matrix)
+      ),
+      content.as_bytes(),
+    );
+    self.patterns.push(obj);
+    self.patterns.len() - 1
+  }
+
+  /// The pattern is in the default space of the page, not the current
+  /// transform.
+  fn pattern_matrix(&self, matrix: &Transform) -> String {
+    matrix_components(&matrix.then(&self.page_matrix)).join(" ")
+  }
+
+  fn add_stream(&mut self, dict: &str, data: &[u8]) -> usize {
+    let mut obj = format!("<< {dict} /Length {} >>\nstream\n", data.len()).into_bytes();
+    obj.extend_from_slice(data);
+    obj.extend_from_slice(b"\nendstream");
+    self.add_object(obj)
+  }
+
+  /// Add an object and return its object number.
+  fn add_object(&mut self, obj: Vec<u8>) -> usize {
+    self.objects.push(obj);
+    FIRST_RESOURCE_OBJ + self.objects.len() - 1
+  }
+
+  fn finish(self, size: Size) -> Vec<u8> {
+    let Self { content, objects, ext_g_states, patterns, .. } = self;
+    let mut resources = String::from("<<");
+    if !ext_g_states.is_empty() {
+      resources.push_str(" /ExtGState <<");
+      for (idx, (_, obj)) in ext_g_states.iter().enumerate() {
+        let _ = write!(resources, " /GS{idx} {obj} 0 R");
+      }
+      resources.push_str(" >>");
+    }
+    if !patterns.is_empty() {
+      resources.push_str(" /Pattern <<");
+      for (idx, obj) in patterns.iter().enumerate() {
+        let _ = write!(resources, " /P{idx} {obj} 0 R");
+      }
+      resources.push_str(" >>");
+    }
+    resources.push_str(" >>");
+
+    let mut doc = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n".to_vec();
+    let mut offsets = vec![];
+    let mut write_obj = |doc: &mut Vec<u8>, obj: &[u8]| {
+      offsets.push(doc.len());
+      let _ = std::io::Write::write_all(doc, format!("{} 0 obj\n", offsets.len()).as_bytes());
+      doc.extend_from_slice(obj);
+      doc.extend_from_slice(b"\nendobj\n");
+    };
+    write_obj(&mut doc, b"<< /Type /Catalog /Pages 2 0 R >>");
+    write_obj(&mut doc, b"<< /Type /Pages /Kids [3 0 R] /Count 1 >>");
+    let page = format!(
+      "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources {resources} /Contents 4 0 R \
+       >>",
+      num(size.width),
+      num(size.height)
+    );
+    write_obj(&mut doc, page.as_bytes());
+    let mut stream = format!("<< /Length {} >>\nstream\n", content.len()).into_bytes();
+    stream.extend_from_slice(content.as_bytes());
+    stream.extend_from_slice(b"endstream");
+    write_obj(&mut doc, &stream);
+    for obj in objects.iter() {
+      write_obj(&mut doc, obj);
+    }
+
+    let xref = doc.len();
+    let mut tail = format!("xref\n0 {}\n0000000000 65535 f \n", offsets.len() + 1);
+    for offset in offsets {
+      let _ = writeln!(tail, "{offset:010} 00000 n ");
+    }
+    let _ = write!(
+      tail,
+      "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref}\n%%EOF\n",
+      FIRST_RESOURCE_OBJ + objects.len()
+    );
+    doc.extend_from_slice(tail.as_bytes());
+    doc
+  }
+}
+
+/// The function of the gradient stops, the colors are padded before the first
+/// stop and after the last stop.
+fn stops_function(stops: &[GradientStop]) -> String {
+  let rgb = |c: Color| {
+    let [r, g, b, _] = c.into_f32_components();
+    [r, g, b].map(num).join(" ")
+  };
+  let interpolate = |c0: Color, c1: Color| {
+    format!("<< /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >>", rgb(c0), rgb(c1))
+  };
+
+  let mut stops: Vec<_> = stops
+    .iter()
+    .map(|s| (s.offset.clamp(0., 1.), s.color))
+    .collect();
+  match (stops.first(), stops.last()) {
+    (Some(first), Some(last)) => {
+      let (first, last) = (*first, *last);
+      if first.0 > 0. {
+        stops.insert(0, (0., first.1));
+      }
+      if last.0 < 1. {
+        stops.push((1., last.1));
+      }
+    }
+    _ => return interpolate(Color::TRANSPARENT, Color::TRANSPARENT),
+  }
+  if stops.len() == 2 {
+    return interpolate(stops[0].1, stops[1].1);
+  }
+
+  let functions = stops
+    .windows(2)
+    .map(|w| interpolate(w[0].1, w[1].1))
+    .collect::<Vec<_>>()
+    .join(" ");
+  let bounds = stops[1..stops.len() - 1]
+    .iter()
+    .map(|s| num(s.0))
+    .collect::<Vec<_>>()
+    .join(" ");
+  let encode = vec!["0 1"; stops.len() - 1].join(" ");
+  format!(
+    "<< /FunctionType 3 /Domain [0 1] /Functions [{functions}] /Bounds [{bounds}] /Encode \
+     [{encode}] >>"
+  )
+}
+
+/// The PDF name of the blend mode, `None` if the PDF not supports it.
+fn blend_mode_name(mode: BlendMode) -> Option<&'static str> {
+  let name = match mode {
+    BlendMode::Normal => "Normal",
+    BlendMode::Multiply => "Multiply",
+    BlendMode::Screen => "Screen",
+    BlendMode::Overlay => "Overlay",
+    BlendMode::Darken => "Darken",
+    BlendMode::Lighten => "Lighten",
+    BlendMode::DestinationOut => return None,
+  };
+  Some(name)
+}
+
+/// Split the image pixels to the RGB data and the alpha data.
+fn rgb_and_alpha(img: &PixelImage) -> (Vec<u8>, Vec<u8>) {
+  let pixels = (img.width() * img.height()) as usize;
+  let mut rgb = Vec::with_capacity(pixels * 3);
+  let mut alpha = Vec::with_capacity(pixels);
+  let data = img.pixel_bytes();
+  match img.color_format() {
+    ColorFormat::Rgba8 => {
+      for p in data.chunks_exact(4) {
+        rgb.extend_from_slice(&p[..3]);
+        alpha.push(p[3]);
+      }
+    }
+    ColorFormat::Alpha8 => {
+      for a in data {
+        rgb.extend_from_slice(&[0, 0, 0]);
+        alpha.push(*a);
+      }
+    }
+  }
+  (rgb, alpha)
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::{Point, Rect};
+
+  use super::*;
+  use crate::Painter;
+
+  #[test]
+  fn page_content() {
+    let size = Size::new(10., 20.);
+    let mut painter = Painter::new(Rect::from_size(size));
+    painter
+      .clip(Path::rect(&Rect::new(Point::new(1., 1.), Size::new(5., 5.))).into())
+      .rect(&Rect::from_size(size))
+      .set_fill_brush(Color::RED.with_alpha(0.5))
+      .fill();
+    let bytes = commands_to_pdf(&painter.finish(), size);
+    // Map the bytes to chars one by one to keep the offsets.
+    let pdf: String = bytes
+      .iter()
+      .map(|b| if b.is_ascii() { *b as char } else { '?' })
+      .collect();
+
+    assert!(pdf.contains("/MediaBox [0 0 10 20]"));
+    assert!(pdf.contains(
+      "1 0 0 -1 0 20 cm\nq\n1 1 m 6 1 l 6 6 l 1 6 l h W n\nq\n/GS0 gs\n1 0 0 1 0 0 cm\n1 0 0 \
+       rg\n0 0 m 10 0 l 10 20 l 0 20 l h f\nQ\nQ\n"
+    ));
+    assert!(pdf.contains("<< /Type /ExtGState /ca 0.502 /BM /Normal >>"));
+
+    // The offsets in the cross-reference table point to the objects.
+    let xref = pdf.find("xref\n").unwrap();
+    let offsets = pdf[xref..]
+      .lines()
+      .skip(3)
+      .take_while(|l| l.ends_with(" n "))
+      .map(|l| l[..10].parse::<usize>().unwrap());
+    for (idx, offset) in offsets.enumerate() {
+      assert!(pdf[offset..].starts_with(&format!("{} 0 obj", idx + 1)));
+    }
+  }
+
+  #[test]
+  fn approximate_shadow() {
+    let size = Size::new(20., 20.);
+    let mut painter = Painter::new(Rect::from_size(size));
+    let path = Path::rect(&Rect::new(Point::new(5., 5.), Size::new(10., 10.)));
+    painter.draw_shadow(path.into(), Color::BLACK, 4.);
+    let bytes = commands_to_pdf(&painter.finish(), size);
+    let pdf = String::from_utf8_lossy(&bytes);
+
+    // The path is filled once and stroked by every layer.
+    assert_eq!(
+      pdf
+        .matches("5 5 m 15 5 l 15 15 l 5 15 l h f")
+        .count(),
+      1
+    );
+    assert_eq!(
+      pdf
+        .matches("5 5 m 15 5 l 15 15 l 5 15 l h S")
+        .count(),
+      SHADOW_LAYERS
+    );
+    assert!(pdf.contains("8 w 1 J 1 j"));
+  }
+}
@@ -0,0 +1,369 @@
+use std::fmt::Write;
+
+use ribir_geom::{Size, Transform};
+
+use super::*;
+use crate::{
+  BlendMode, Color, CommandBrush, FilterCommand, FilterOp, GradientStop, PaintCommand,
+  PaintPathAction, PaintingStyle, PathCommand, ShadowCommand, SpreadMethod,
+};
+
+/// Export the paint commands to a SVG document of the `size`.
+///
+/// The images are embedded as PNG data when the `png` feature is enabled,
+/// otherwise they are ignored. The backdrop filters and the
+/// [`BlendMode::DestinationOut`] have no equivalent in SVG, and are ignored
+/// too.
+///
+/// # Example
+///
+/// ```
+/// use ribir_geom::{Rect, Size};
+/// use ribir_painter::{Color, Painter, export::commands_to_svg};
+///
+/// let size = Size::new(100., 100.);
+/// let mut painter = Painter::new(Rect::from_size(size));
+/// painter
+///   .rect(&Rect::from_size(size))
+///   .set_fill_brush(Color::RED)
+///   .fill();
+/// let svg = commands_to_svg(&painter.finish(), size);
+/// assert!(svg.starts_with("<svg"));
+/// ```
+pub fn commands_to_svg(commands: &[PaintCommand], size: Size) -> String {
+  let mut writer = SvgWriter::default();
+  writer.write_commands(commands);
+  let SvgWriter { defs, body, .. } = writer;
+  let (w, h) = (num(size.width), num(size.height));
+  format!(
+    "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} \
+     {h}\"><defs>{defs}</defs>{body}</svg>"
+  )
+}
+
+#[derive(Default)]
+struct SvgWriter {
+  defs: String,
+  body: String,
+  next_id: usize,
+  /// The number of the groups opened by every filter layer.
+  filter_groups: Vec<usize>,
+}
+
+impl SvgWriter {
+  fn write_commands(&mut self, commands: &[PaintCommand]) {
+    for cmd in commands {
+      match cmd {
+        PaintCommand::Path(PathCommand { path, transform, action, .. }) => match action {
+          PaintPathAction::Paint { brush, painting_style, blend_mode } => {
+            let Some(blend) = blend_mode_style(*blend_mode) else { continue };
+            let d = path_data(path);
+            let ts = matrix_attr(transform);
+            let _ = write!(self.body, "<path d=\"{d}\"{ts}{blend}");
+            match painting_style {
+              PaintingStyle::Fill => {
+                let paint = self.paint(brush, "fill");
+                self.body.push_str(&paint);
+              }
+              PaintingStyle::Stroke(options) => {
+                let paint = self.paint(brush, "stroke");
+                let _ = write!(
+                  self.body,
+                  " fill=\"none\"{paint} stroke-width=\"{}\" stroke-linecap=\"{}\" \
+                   stroke-linejoin=\"{}\" stroke-miterlimit=\"{}\"",
+                  num(options.width),
+                  line_cap_name(options.line_cap),
+                  line_join_name(options.line_join),
+                  num(options.miter_limit)
+                );
+              }
+            }
+            self.body.push_str("/>");
+          }
+          PaintPathAction::Clip => {
+            let id = self.clip_path(path, transform);
+            let _ = write!(self.body, "<g clip-path=\"url(#{id})\">");
+          }
+        },
+        PaintCommand::Shadow(ShadowCommand {
+          path,
+          paint_bounds,
+          transform,
+          color,
+          blur_radius,
+        }) => {
+          let id = self.new_id("f");
+          let sigma = blur_radius / 2. * matrix_scale(transform);
+          let _ = write!(
+            self.defs,
+            "<filter id=\"{id}\" filterUnits=\"userSpaceOnUse\" x=\"{}\" y=\"{}\" width=\"{}\" \
+             height=\"{}\"><feGaussianBlur stdDeviation=\"{}\"/></filter>",
+            num(paint_bounds.min_x()),
+            num(paint_bounds.min_y()),
+            num(paint_bounds.width()),
+            num(paint_bounds.height()),
+            num(sigma)
+          );
+          let _ = write!(
+            self.body,
+            "<g filter=\"url(#{id})\"><path d=\"{}\"{}{}/></g>",
+            path_data(path),
+            matrix_attr(transform),
+            color_paint(*color, "fill")
+          );
+        }
+        PaintCommand::PopClip => self.body.push_str("</g>"),
+        PaintCommand::PushFilter(filter) => self.push_filter(filter),
+        PaintCommand::PopFilter => {
+          let groups = self.filter_groups.pop().unwrap_or_default();
+          for _ in 0..groups {
+            self.body.push_str("</g>");
+          }
+        }
+        PaintCommand::Bundle { transform, opacity, cmds, .. } => {
+          let _ = write!(self.body, "<g{}", matrix_attr(transform));
+          if *opacity < 1. {
+            let _ = write!(self.body, " opacity=\"{}\"", num(*opacity));
+          }
+          self.body.push('>');
+          self.write_commands(cmds);
+          self.body.push_str("</g>");
+        }
+      }
+    }
+  }
+
+  fn push_filter(&mut self, filter: &FilterCommand) {
+    let FilterCommand { path, transform, filter, backdrop, .. } = filter;
+    if *backdrop {
+      self.filter_groups.push(0);
+      return;
+    }
+
+    let scale = matrix_scale(transform);
+    let extent = filter.blur_extent() * scale;
+    let bounds = transform
+      .outer_transformed_rect(&path.bounds(None))
+      .inflate(extent, extent);
+    let id = self.new_id("f");
+    let _ = write!(
+      self.defs,
+      "<filter id=\"{id}\" filterUnits=\"userSpaceOnUse\" x=\"{}\" y=\"{}\" width=\"{}\" \
+       height=\"{}\" color-interpolation-filters=\"sRGB\">",
+      num(bounds.min_x()),
+      num(bounds.min_y()),
+      num(bounds.width()),
+      num(bounds.height()),
+    );
+    for op in filter.ops() {
+      match op {
+        FilterOp::Blur(radius) => {
+          let _ = write!(self.defs, "<feGaussianBlur stdDeviation=\"{}\"/>", num(radius * scale));
+        }
+        FilterOp::ColorMatrix(matrix) => {
+          let values = matrix.0.map(num).join(" ");
+          let _ = write!(self.defs, "<feColorMatrix type=\"matrix\" values=\"{values}\"/>");
+        }
+      }
+    }
+    self.defs.push_str("</filter>");
+
+    // The content out of the filter path is discarded before the effects.
+    let clip = self.clip_path(path, transform);
+    let _ = write!(self.body, "<g filter=\"url(#{id})\"><g clip-path=\"url(#{clip})\">");
+    self.filter_groups.push(2);
+  }
+
+  /// Define a clip path and return its id.
+  fn clip_path(&mut self, path: &Path, transform: &Transform) -> String {
+    let id = self.new_id("c");
+    let _ = write!(
+      self.defs,
+      "<clipPath id=\"{id}\"><path d=\"{}\"{}/></clipPath>",
+      path_data(path),
+      matrix_attr(transform)
+    );
+    id
+  }
+
+  /// Return the `attr` attribute to paint with the brush, the gradients and
+  /// images are defined in the `defs`.
+  fn paint(&mut self, brush: &CommandBrush, attr: &str) -> String {
+    let url = match brush {
+      CommandBrush::Color(color) => return color_paint(*color, attr),
+      CommandBrush::Linear(linear) => {
+        let id = self.new_id("g");
+        let [x1, y1, x2, y2] =
+          [linear.start.x, linear.start.y, linear.end.x, linear.end.y].map(num);
+        let _ = write!(
+          self.defs,
+          "<linearGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" x1=\"{x1}\" y1=\"{y1}\" \
+           x2=\"{x2}\" y2=\"{y2}\" spreadMethod=\"{}\">{}</linearGradient>",
+          spread_name(linear.spread_method),
+          stops(&linear.stops)
+        );
+        id
+      }
+      CommandBrush::Radial(radial) => {
+        let id = self.new_id("g");
+        let _ = write!(
+          self.defs,
+          "<radialGradient id=\"{id}\" gradientUnits=\"userSpaceOnUse\" cx=\"{}\" cy=\"{}\" \
+           r=\"{}\" fx=\"{}\" fy=\"{}\" fr=\"{}\" spreadMethod=\"{}\">{}</radialGradient>",
+          num(radial.end_center.x),
+          num(radial.end_center.y),
+          num(radial.end_radius),
+          num(radial.start_center.x),
+          num(radial.start_center.y),
+          num(radial.start_radius),
+          spread_name(radial.spread_method),
+          stops(&radial.stops)
+        );
+        id
+      }
+      CommandBrush::Image { img, opacity } => {
+        let Some(href) = image_href(img) else { return format!(" {attr}=\"none\"") };
+        let id = self.new_id("p");
+        let (w, h) = (img.width(), img.height());
+        let _ = write!(
+          self.defs,
+          "<pattern id=\"{id}\" patternUnits=\"userSpaceOnUse\" width=\"{w}\" \
+           height=\"{h}\"><image width=\"{w}\" height=\"{h}\" opacity=\"{}\" \
+           href=\"{href}\"/></pattern>",
+          num(*opacity)
+        );
+        id
+      }
+    };
+    format!(" {attr}=\"url(#{url})\"")
+  }
+
+  fn new_id(&mut self, prefix: &str) -> String {
+    self.next_id += 1;
+    format!("{prefix}{}", self.next_id)
+  }
+}
+
+fn path_data(path: &Path) -> String {
+  let mut d = String::new();
+  write_path_data(path, None, PathFormat::Svg, &mut d);
+  d
+}
+
+fn matrix_attr(matrix: &Transform) -> String {
+  if matrix == &Transform::identity() {
+    String::new()
+  } else {
+    let components = matrix_components(matrix).join(" ");
+    format!(" transform=\"matrix({components})\"")
+  }
+}
+
+fn color_paint(color: Color, attr: &str) -> String {
+  let [r, g, b, a] = color.into_components();
+  let mut paint = format!(" {attr}=\"rgb({r},{g},{b})\"");
+  if a < 255 {
+    let _ = write!(paint, " {attr}-opacity=\"{}\"", num(a as f32 / 255.));
+  }
+  paint
+}
+
+fn stops(stops: &[GradientStop]) -> String {
+  let mut res = String::new();
+  for stop in stops {
+    let [r, g, b, a] = stop.color.into_components();
+    let _ = write!(
+      res,
+      "<stop offset=\"{}\" stop-color=\"rgb({r},{g},{b})\" stop-opacity=\"{}\"/>",
+      num(stop.offset),
+      num(a as f32 / 255.)
+    );
+  }
+  res
+}
+
+fn spread_name(spread: SpreadMethod) -> &'static str {
+  match spread {
+    SpreadMethod::Pad => "pad",
+    SpreadMethod::Reflect => "reflect",
+    SpreadMethod::Repeat => "repeat",
+  }
+}
+
+/// The style attribute of the blend mode, `None` if the SVG not supports it.
+fn blend_mode_style(mode: BlendMode) -> Option<&'static str> {
+  let style = match mode {
+    BlendMode::Normal => "",
+    BlendMode::Multiply => " style=\"mix-blend-mode:multiply\"",
+    BlendMode::Screen => " style=\"mix-blend-mode:screen\"",
+    BlendMode::Overlay => " style=\"mix-blend-mode:overlay\"",
+    BlendMode::Darken => " style=\"mix-blend-mode:darken\"",
+    BlendMode::Lighten => " style=\"mix-blend-mode:lighten\"",
+    BlendMode::DestinationOut => return None,
+  };
+  Some(style)
+}
+
+#[cfg(feature = "png")]
+fn image_href(img: &crate::PixelImage) -> Option<String> {
+  let mut png = vec![];
+  img.write_as_png(&mut png).ok()?;
+  Some(format!("data:image/png;base64,{}", base64(&png)))
+}
+
+#[cfg(not(feature = "png"))]
+fn image_href(_: &crate::PixelImage) -> Option<String> { None }
+
+#[cfg(feature = "png")]
+fn base64(data: &[u8]) -> String {
+  const TABLE: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+  let mut res = String::with_capacity(data.len().div_ceil(3) * 4);
+  for chunk in data.chunks(3) {
+    let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+    let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+    for i in 0..4 {
+      if i <= chunk.len() {
+        res.push(TABLE[(n >> (18 - i * 6)) as usize & 63] as char);
+      } else {
+        res.push('=');
+      }
+    }
+  }
+  res
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_geom::{Point, Rect};
+
+  use super::*;
+  use crate::Painter;
+
+  #[test]
+  fn clip_and_fill() {
+    let size = Size::new(10., 10.);
+    let mut painter = Painter::new(Rect::from_size(size));
+    painter
+      .clip(Path::rect(&Rect::new(Point::new(1., 1.), Size::new(5., 5.))).into())
+      .rect(&Rect::from_size(size))
+      .set_fill_brush(Color::RED.with_alpha(0.5))
+      .fill();
+    let svg = commands_to_svg(&painter.finish(), size);
+
+    assert_eq!(
+      svg,
+      "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"10\" height=\"10\" viewBox=\"0 0 10 \
+       10\"><defs><clipPath id=\"c1\"><path d=\"M1 1 L6 1 L6 6 L1 6 Z\"/></clipPath></defs><g \
+       clip-path=\"url(#c1)\"><path d=\"M0 0 L10 0 L10 10 L0 10 Z\" fill=\"rgb(255,0,0)\" \
+       fill-opacity=\"0.502\"/></g></svg>"
+    );
+  }
+
+  #[cfg(feature = "png")]
+  #[test]
+  fn base64_padding() {
+    assert_eq!(base64(b"M"), "TQ==");
+    assert_eq!(base64(b"Ma"), "TWE=");
+    assert_eq!(base64(b"Man"), "TWFu");
+  }
+}
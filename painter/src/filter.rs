@@ -0,0 +1,214 @@
+use serde::{Deserialize, Serialize};
+
+/// A 4x5 matrix to transform the color, the same as the `feColorMatrix` of
+/// SVG. The matrix is row-major, each row computes one channel of `r, g, b, a`
+/// from the un-premultiplied source color, and the last column is the offset.
+///
+/// ```text
+/// | R' |   | m0  m1  m2  m3  m4  |   | R |
+/// | G' |   | m5  m6  m7  m8  m9  |   | G |
+/// | B' | = | m10 m11 m12 m13 m14 | * | B |
+/// | A' |   | m15 m16 m17 m18 m19 |   | A |
+/// | 1  |   | 0   0   0   0   1   |   | 1 |
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ColorMatrix(pub [f32; 20]);
+
+impl ColorMatrix {
+  pub const IDENTITY: Self = Self([
+    1., 0., 0., 0., 0., //
+    0., 1., 0., 0., 0., //
+    0., 0., 1., 0., 0., //
+    0., 0., 0., 1., 0.,
+  ]);
+
+  /// Convert the color to grayscale, `amount` is the proportion of the
+  /// conversion, `1.` is completely grayscale and `0.` leaves the color
+  /// unchanged.
+  pub fn grayscale(amount: f32) -> Self {
+    let a = 1. - amount.clamp(0., 1.);
+    #[rustfmt::skip]
+    let m = [
+      0.2126 + 0.7874 * a, 0.7152 - 0.7152 * a, 0.0722 - 0.0722 * a, 0., 0.,
+      0.2126 - 0.2126 * a, 0.7152 + 0.2848 * a, 0.0722 - 0.0722 * a, 0., 0.,
+      0.2126 - 0.2126 * a, 0.7152 - 0.7152 * a, 0.0722 + 0.9278 * a, 0., 0.,
+      0., 0., 0., 1., 0.,
+    ];
+    Self(m)
+  }
+
+  /// Multiply the color channels by `amount`, `0.` makes the color black and
+  /// `1.` leaves the color unchanged.
+  pub fn brightness(amount: f32) -> Self {
+    let a = amount.max(0.);
+    #[rustfmt::skip]
+    let m = [
+      a, 0., 0., 0., 0.,
+      0., a, 0., 0., 0.,
+      0., 0., a, 0., 0.,
+      0., 0., 0., 1., 0.,
+    ];
+    Self(m)
+  }
+
+  /// Adjust the contrast of the color, `0.` makes the color completely gray
+  /// and `1.` leaves the color unchanged.
+  pub fn contrast(amount: f32) -> Self {
+    let a = amount.max(0.);
+    let offset = 0.5 - 0.5 * a;
+    #[rustfmt::skip]
+    let m = [
+      a, 0., 0., 0., offset,
+      0., a, 0., 0., offset,
+      0., 0., a, 0., offset,
+      0., 0., 0., 1., 0.,
+    ];
+    Self(m)
+  }
+
+  /// Return a matrix that applies `self` first and then `next`.
+  pub fn then(&self, next: &ColorMatrix) -> ColorMatrix {
+    let (a, b) = (&next.0, &self.0);
+    let mut m = [0.; 20];
+    for row in 0..4 {
+      for col in 0..5 {
+        let mut v = (0..4)
+          .map(|k| a[row * 5 + k] * b[k * 5 + col])
+          .sum::<f32>();
+        if col == 4 {
+          v += a[row * 5 + 4];
+        }
+        m[row * 5 + col] = v;
+      }
+    }
+    ColorMatrix(m)
+  }
+
+  /// Apply the matrix to an un-premultiplied color in `[0, 1]`.
+  pub fn apply(&self, color: [f32; 4]) -> [f32; 4] {
+    let m = &self.0;
+    let mut res = [0.; 4];
+    for (row, c) in res.iter_mut().enumerate() {
+      let v = (0..4)
+        .map(|k| m[row * 5 + k] * color[k])
+        .sum::<f32>()
+        + m[row * 5 + 4];
+      *c = v.clamp(0., 1.);
+    }
+    res
+  }
+}
+
+/// A single effect of the [`Filter`].
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FilterOp {
+  /// A Gaussian blur, the value is the standard deviation of the Gaussian
+  /// function in logical pixels.
+  Blur(f32),
+  /// Transform every pixel by a color matrix.
+  ColorMatrix(ColorMatrix),
+}
+
+/// A sequence of graphical effects applied to the pixels of a layer, like the
+/// `filter` of CSS. The effects are applied in order.
+///
+/// # Example
+///
+/// ```
+/// use ribir_painter::Filter;
+///
+/// let filter = Filter::blur(4.).then(Filter::grayscale(1.));
+/// assert_eq!(filter.ops().len(), 2);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Filter {
+  ops: Vec<FilterOp>,
+}
+
+impl Filter {
+  /// Blur the layer by a Gaussian blur with the standard deviation `radius`.
+  pub fn blur(radius: f32) -> Self { Self::from_op(FilterOp::Blur(radius)) }
+
+  /// Convert the layer to grayscale, see [`ColorMatrix::grayscale`].
+  pub fn grayscale(amount: f32) -> Self { Self::color_matrix(ColorMatrix::grayscale(amount)) }
+
+  /// Adjust the brightness of the layer, see [`ColorMatrix::brightness`].
+  pub fn brightness(amount: f32) -> Self { Self::color_matrix(ColorMatrix::brightness(amount)) }
+
+  /// Adjust the contrast of the layer, see [`ColorMatrix::contrast`].
+  pub fn contrast(amount: f32) -> Self { Self::color_matrix(ColorMatrix::contrast(amount)) }
+
+  /// Transform the layer pixels by a color matrix.
+  pub fn color_matrix(matrix: ColorMatrix) -> Self { Self::from_op(FilterOp::ColorMatrix(matrix)) }
+
+  /// Append the effects of `next` after the effects of this filter. Adjacent
+  /// color matrices are merged into one.
+  pub fn then(mut self, next: Filter) -> Self {
+    for op in next.ops {
+      match (self.ops.last_mut(), op) {
+        (Some(FilterOp::ColorMatrix(m)), FilterOp::ColorMatrix(n)) => *m = m.then(&n),
+        _ => self.ops.push(op),
+      }
+    }
+    self
+  }
+
+  /// The effects of the filter.
+  pub fn ops(&self) -> &[FilterOp] { &self.ops }
+
+  /// Return true if the filter has no effect.
+  pub fn is_empty(&self) -> bool {
+    self.ops.iter().all(|op| match op {
+      FilterOp::Blur(r) => *r <= 0.,
+      FilterOp::ColorMatrix(m) => m == &ColorMatrix::IDENTITY,
+    })
+  }
+
+  /// The distance the filter spreads the content out of its bounds.
+  pub fn blur_extent(&self) -> f32 {
+    self
+      .ops
+      .iter()
+      .map(|op| match op {
+        FilterOp::Blur(r) => r.max(0.) * 3.,
+        FilterOp::ColorMatrix(_) => 0.,
+      })
+      .sum()
+  }
+
+  fn from_op(op: FilterOp) -> Self { Self { ops: vec![op] } }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn merge_color_matrix() {
+    let filter = Filter::brightness(0.5).then(Filter::grayscale(1.));
+    assert_eq!(filter.ops().len(), 1);
+
+    let FilterOp::ColorMatrix(m) = filter.ops()[0] else { unreachable!() };
+    let [r, g, b, a] = m.apply([1., 0., 0., 1.]);
+    assert!((r - 0.1063).abs() < 1e-4);
+    assert_eq!(r, g);
+    assert_eq!(g, b);
+    assert_eq!(a, 1.);
+
+    let filter = filter.then(Filter::blur(2.));
+    assert_eq!(filter.ops().len(), 2);
+    assert_eq!(filter.blur_extent(), 6.);
+  }
+
+  #[test]
+  fn empty_filter() {
+    assert!(Filter::default().is_empty());
+    assert!(Filter::blur(0.).is_empty());
+    assert!(
+      Filter::brightness(1.)
+        .then(Filter::contrast(1.))
+        .is_empty()
+    );
+    assert!(!Filter::grayscale(0.5).is_empty());
+  }
+}
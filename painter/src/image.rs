@@ -67,6 +67,10 @@ impl PixelImage {
   pub fn size(&self) -> DeviceSize { DeviceSize::new(self.width as i32, self.height as i32) }
   #[inline]
   pub fn pixel_bytes(&self) -> &[u8] { &self.data }
+
+  /// Return the mutable pixel bytes, the data is copied if it's borrowed.
+  #[inline]
+  pub fn pixel_bytes_mut(&mut self) -> &mut [u8] { self.data.to_mut() }
 }
 
 impl std::fmt::Debug for PixelImage {
@@ -2,6 +2,9 @@
 
 //! A 2d logic painter, generate the paint command
 pub mod color;
+pub mod export;
+mod filter;
+pub use filter::*;
 mod painter;
 pub mod path;
 pub mod path_builder;
@@ -20,3 +23,5 @@ pub use style::*;
 pub use crate::image::PixelImage;
 mod svg;
 pub use svg::Svg;
+mod lottie;
+pub use lottie::Lottie;
@@ -0,0 +1,1082 @@
+use std::{cell::RefCell, error::Error, io::Read, ops::Range, rc::Rc, time::Duration};
+
+use lyon_algorithms::{
+  geom::{CubicBezierSegment, point},
+  measure::{PathMeasurements, SampleType},
+  path::Path as LyonPath,
+};
+use ribir_algo::Resource;
+use ribir_geom::{Angle, Point, Rect, Size, Transform, Vector};
+use serde::{
+  Deserialize, Deserializer,
+  de::{DeserializeOwned, IgnoredAny},
+};
+
+use crate::{
+  Color, LineCap, LineJoin, PaintCommand, Painter, Path, Radius, StrokeOptions,
+  path_builder::PathBuilder,
+};
+
+/// A [Lottie](https://lottiefiles.github.io/lottie-docs/) animation, it
+/// generates the paint commands of any frame for the Ribir painter.
+///
+/// It supports the shape layers, the solid layers, the null layers and the
+/// precomposition layers, with their transforms, masks and the eased
+/// keyframes. In the shape layers, the groups, rectangles, ellipses, paths,
+/// fills, strokes and trim paths are supported. The other features are ignored
+/// with a warning when parsing.
+#[derive(Clone)]
+pub struct Lottie {
+  comp: Rc<Composition>,
+  last: RefCell<Option<LastFrame>>,
+}
+
+#[derive(Clone)]
+struct LastFrame {
+  frame: f32,
+  commands: Resource<Box<[PaintCommand]>>,
+}
+
+impl Lottie {
+  /// Parse Lottie from the bytes of its JSON.
+  pub fn parse_from_bytes(data: &[u8]) -> Result<Self, Box<dyn Error>> {
+    let comp: Composition = serde_json::from_slice(data)?;
+    if !comp.frame_rate.is_finite() || comp.frame_rate <= 0. {
+      return Err(format!("Invalid frame rate of the lottie: {}.", comp.frame_rate).into());
+    }
+    comp.warn_unsupported();
+    Ok(Self { comp: Rc::new(comp), last: RefCell::new(None) })
+  }
+
+  /// Parse Lottie from a JSON file.
+  pub fn open<P: AsRef<std::path::Path>>(path: P) -> Result<Self, Box<dyn Error>> {
+    let mut file = std::fs::File::open(path)?;
+    let mut bytes = vec![];
+    file.read_to_end(&mut bytes)?;
+    Self::parse_from_bytes(&bytes)
+  }
+
+  pub fn size(&self) -> Size { Size::new(self.comp.width, self.comp.height) }
+
+  /// The number of frames played per second.
+  pub fn frame_rate(&self) -> f32 { self.comp.frame_rate }
+
+  /// The range of the frames, the animation plays from its start to its end.
+  pub fn frames(&self) -> Range<f32> { self.comp.in_point..self.comp.out_point }
+
+  /// How long it takes to play all the frames once.
+  pub fn duration(&self) -> Duration {
+    let Range { start, end } = self.frames();
+    Duration::from_secs_f32(((end - start) / self.frame_rate()).max(0.))
+  }
+
+  /// The paint commands of the `frame`, the frame can be fractional to lerp
+  /// between the keyframes.
+  pub fn frame_commands(&self, frame: f32) -> Resource<Box<[PaintCommand]>> {
+    let mut last = self.last.borrow_mut();
+    if let Some(last) = last.as_ref().filter(|last| last.frame == frame) {
+      return last.commands.clone();
+    }
+
+    let bound_rect = Rect::from_size(Size::new(f32::MAX, f32::MAX));
+    let mut painter = Painter::new(bound_rect);
+    paint_layers(&self.comp.layers, frame, &self.comp, &mut vec![], &mut painter);
+    let commands = Resource::new(painter.finish().to_owned().into_boxed_slice());
+    *last = Some(LastFrame { frame, commands: commands.clone() });
+    commands
+  }
+}
+
+const PRECOMP_LAYER: u8 = 0;
+const SOLID_LAYER: u8 = 1;
+const NULL_LAYER: u8 = 3;
+const SHAPE_LAYER: u8 = 4;
+
+#[derive(Deserialize)]
+struct Composition {
+  #[serde(rename = "fr")]
+  frame_rate: f32,
+  #[serde(rename = "ip")]
+  in_point: f32,
+  #[serde(rename = "op")]
+  out_point: f32,
+  #[serde(rename = "w")]
+  width: f32,
+  #[serde(rename = "h")]
+  height: f32,
+  #[serde(default)]
+  layers: Vec<Layer>,
+  #[serde(default)]
+  assets: Vec<Asset>,
+}
+
+#[derive(Deserialize)]
+struct Asset {
+  id: String,
+  /// Only the precompositions have layers, the images are not supported.
+  layers: Option<Vec<Layer>>,
+}
+
+#[derive(Deserialize)]
+struct Layer {
+  ty: u8,
+  ind: Option<f32>,
+  parent: Option<f32>,
+  #[serde(rename = "ip")]
+  in_point: f32,
+  #[serde(rename = "op")]
+  out_point: f32,
+  #[serde(rename = "st", default)]
+  start_time: f32,
+  #[serde(rename = "sr", default = "one")]
+  stretch: f32,
+  #[serde(rename = "ks", default)]
+  transform: TransformProps,
+  #[serde(default)]
+  shapes: Vec<ShapeItem>,
+  #[serde(rename = "masksProperties", default)]
+  masks: Vec<Mask>,
+  #[serde(rename = "refId")]
+  ref_id: Option<String>,
+  #[serde(rename = "w", default)]
+  width: f32,
+  #[serde(rename = "h", default)]
+  height: f32,
+  #[serde(rename = "sc")]
+  solid_color: Option<String>,
+  #[serde(rename = "sw", default)]
+  solid_width: f32,
+  #[serde(rename = "sh", default)]
+  solid_height: f32,
+  #[serde(rename = "hd", default)]
+  hidden: bool,
+  /// The matte mode of the layer, it uses the layer above as its matte.
+  #[serde(rename = "tt")]
+  matte_mode: Option<u8>,
+  /// If the layer is the matte of the layer below.
+  #[serde(rename = "td")]
+  matte_target: Option<u8>,
+  #[serde(rename = "tm")]
+  time_remap: Option<IgnoredAny>,
+}
+
+#[derive(Deserialize, Default)]
+struct TransformProps {
+  #[serde(rename = "a")]
+  anchor: Option<Property<Vec<f32>>>,
+  #[serde(rename = "p")]
+  position: Option<Position>,
+  #[serde(rename = "s")]
+  scale: Option<Property<Vec<f32>>>,
+  #[serde(rename = "r")]
+  rotation: Option<Property<Vec<f32>>>,
+  /// The rotation of the 3d layers around the z axis.
+  #[serde(rename = "rz")]
+  rotation_z: Option<Property<Vec<f32>>>,
+  #[serde(rename = "o")]
+  opacity: Option<Property<Vec<f32>>>,
+  #[serde(rename = "sk")]
+  skew: Option<Property<Vec<f32>>>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Position {
+  Split { x: Property<Vec<f32>>, y: Property<Vec<f32>> },
+  Combined(Property<Vec<f32>>),
+}
+
+#[derive(Deserialize)]
+struct Mask {
+  #[serde(default = "add_mode")]
+  mode: String,
+  #[serde(rename = "pt")]
+  path: Property<Bezier>,
+  #[serde(rename = "inv", default)]
+  inverted: bool,
+}
+
+#[derive(Deserialize)]
+struct ShapeItem {
+  #[serde(rename = "hd", default)]
+  hidden: bool,
+  #[serde(flatten)]
+  shape: Shape,
+}
+
+#[derive(Deserialize)]
+#[serde(tag = "ty")]
+enum Shape {
+  #[serde(rename = "gr")]
+  Group {
+    #[serde(rename = "it", default)]
+    items: Vec<ShapeItem>,
+  },
+  #[serde(rename = "rc")]
+  Rect {
+    #[serde(rename = "p")]
+    center: Property<Vec<f32>>,
+    #[serde(rename = "s")]
+    size: Property<Vec<f32>>,
+    #[serde(rename = "r")]
+    roundness: Option<Property<Vec<f32>>>,
+  },
+  #[serde(rename = "el")]
+  Ellipse {
+    #[serde(rename = "p")]
+    center: Property<Vec<f32>>,
+    #[serde(rename = "s")]
+    size: Property<Vec<f32>>,
+  },
+  #[serde(rename = "sh")]
+  Path {
+    #[serde(rename = "ks")]
+    path: Property<Bezier>,
+  },
+  #[serde(rename = "fl")]
+  Fill {
+    #[serde(rename = "c")]
+    color: Property<Vec<f32>>,
+    #[serde(rename = "o")]
+    opacity: Option<Property<Vec<f32>>>,
+  },
+  #[serde(rename = "st")]
+  Stroke {
+    #[serde(rename = "c")]
+    color: Property<Vec<f32>>,
+    #[serde(rename = "o")]
+    opacity: Option<Property<Vec<f32>>>,
+    #[serde(rename = "w")]
+    width: Property<Vec<f32>>,
+    #[serde(rename = "lc")]
+    line_cap: Option<u8>,
+    #[serde(rename = "lj")]
+    line_join: Option<u8>,
+    #[serde(rename = "ml")]
+    miter_limit: Option<f32>,
+    #[serde(rename = "d")]
+    dashes: Option<IgnoredAny>,
+  },
+  #[serde(rename = "tm")]
+  Trim {
+    #[serde(rename = "s")]
+    start: Property<Vec<f32>>,
+    #[serde(rename = "e")]
+    end: Property<Vec<f32>>,
+    #[serde(rename = "o")]
+    offset: Option<Property<Vec<f32>>>,
+  },
+  #[serde(rename = "tr")]
+  Transform(TransformProps),
+  #[serde(other)]
+  Unsupported,
+}
+
+/// The cubic bezier path of the shapes and the masks, the tangents are relative
+/// to their vertices.
+#[derive(Deserialize, Clone, Default)]
+struct Bezier {
+  #[serde(rename = "c", default)]
+  closed: bool,
+  #[serde(rename = "v", default)]
+  vertices: Vec<[f32; 2]>,
+  #[serde(rename = "i", default)]
+  in_tangents: Vec<[f32; 2]>,
+  #[serde(rename = "o", default)]
+  out_tangents: Vec<[f32; 2]>,
+}
+
+/// A value that is static or animated by keyframes.
+enum Property<V> {
+  Static(V),
+  Animated(Vec<Keyframe<V>>),
+}
+
+struct Keyframe<V> {
+  time: f32,
+  start: V,
+  /// The value to end with, the start value of the next keyframe if it's
+  /// `None`.
+  end: Option<V>,
+  /// Keep the start value until the next keyframe.
+  hold: bool,
+  /// The easing from this keyframe to the next one, linear if it's `None`.
+  easing: Option<CubicBezierSegment<f32>>,
+}
+
+trait KeyValue: Clone + Default {
+  type Raw: DeserializeOwned;
+
+  fn from_raw(raw: Self::Raw) -> Self;
+
+  fn lerp(&self, to: &Self, rate: f32) -> Self;
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Numbers {
+  One(f32),
+  Many(Vec<f32>),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Beziers {
+  One(Bezier),
+  Many(Vec<Bezier>),
+}
+
+#[derive(Deserialize)]
+struct RawProperty<R> {
+  k: RawValue<R>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawValue<R> {
+  Animated(Vec<RawKeyframe<R>>),
+  Static(R),
+}
+
+#[derive(Deserialize)]
+struct RawKeyframe<R> {
+  t: f32,
+  s: Option<R>,
+  e: Option<R>,
+  i: Option<Tangent>,
+  o: Option<Tangent>,
+  #[serde(default)]
+  h: u8,
+}
+
+#[derive(Deserialize)]
+struct Tangent {
+  x: Numbers,
+  y: Numbers,
+}
+
+impl<'de, V: KeyValue> Deserialize<'de> for Property<V> {
+  fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+    let RawProperty { k } = RawProperty::<V::Raw>::deserialize(deserializer)?;
+    let frames = match k {
+      RawValue::Static(v) => return Ok(Property::Static(V::from_raw(v))),
+      RawValue::Animated(frames) if frames.is_empty() => return Ok(Property::Static(V::default())),
+      RawValue::Animated(frames) => frames,
+    };
+
+    let mut keyframes: Vec<Keyframe<V>> = Vec::with_capacity(frames.len());
+    for RawKeyframe { t, s, e, i, o, h } in frames {
+      // The last keyframe of the legacy format has no value, it ends with the
+      // end value of the previous one.
+      let start = s.map(V::from_raw).unwrap_or_else(|| {
+        keyframes
+          .last()
+          .map(|k| k.end.clone().unwrap_or_else(|| k.start.clone()))
+          .unwrap_or_default()
+      });
+      let easing = o.zip(i).map(|(o, i)| CubicBezierSegment {
+        from: point(0., 0.),
+        ctrl1: point(o.x.first(), o.y.first()),
+        ctrl2: point(i.x.first(), i.y.first()),
+        to: point(1., 1.),
+      });
+      let end = e.map(V::from_raw);
+      keyframes.push(Keyframe { time: t, start, end, hold: h == 1, easing });
+    }
+    Ok(Property::Animated(keyframes))
+  }
+}
+
+impl<V: KeyValue> Property<V> {
+  fn value(&self, frame: f32) -> V {
+    let frames = match self {
+      Property::Static(v) => return v.clone(),
+      Property::Animated(frames) => frames,
+    };
+
+    let idx = frames.partition_point(|k| k.time <= frame);
+    let Some(from) = idx.checked_sub(1).map(|i| &frames[i]) else {
+      return frames[0].start.clone();
+    };
+    let Some(next) = frames.get(idx).filter(|_| !from.hold) else {
+      return from.start.clone();
+    };
+
+    let to = from.end.as_ref().unwrap_or(&next.start);
+    let rate = (frame - from.time) / (next.time - from.time);
+    let rate = from
+      .easing
+      .as_ref()
+      .map_or(rate, |e| ease(e, rate));
+    from.start.lerp(to, rate)
+  }
+}
+
+impl Property<Vec<f32>> {
+  fn scalar(&self, frame: f32) -> f32 { self.value(frame).first().copied().unwrap_or(0.) }
+
+  fn vector(&self, frame: f32) -> Vector {
+    let v = self.value(frame);
+    let x = v.first().copied().unwrap_or(0.);
+    Vector::new(x, v.get(1).copied().unwrap_or(x))
+  }
+
+  fn color(&self, frame: f32, opacity: &Option<Property<Vec<f32>>>) -> Color {
+    let c = self.value(frame);
+    let channel = |i: usize| c.get(i).copied().unwrap_or(1.);
+    let alpha = channel(3) * percent(opacity, frame, 1.);
+    Color::from_f32_rgba(channel(0), channel(1), channel(2), alpha)
+  }
+}
+
+fn ease(curve: &CubicBezierSegment<f32>, x: f32) -> f32 {
+  let t = curve
+    .solve_t_for_x(x)
+    .into_iter()
+    .find(|t| (-1e-3..=1. + 1e-3).contains(t))
+    .unwrap_or(x);
+  curve.y(t.clamp(0., 1.))
+}
+
+fn percent(prop: &Option<Property<Vec<f32>>>, frame: f32, default: f32) -> f32 {
+  prop
+    .as_ref()
+    .map_or(default, |p| p.scalar(frame) / 100.)
+}
+
+impl Numbers {
+  fn first(&self) -> f32 {
+    match self {
+      Numbers::One(v) => *v,
+      Numbers::Many(v) => v.first().copied().unwrap_or(0.),
+    }
+  }
+}
+
+impl KeyValue for Vec<f32> {
+  type Raw = Numbers;
+
+  fn from_raw(raw: Numbers) -> Self {
+    match raw {
+      Numbers::One(v) => vec![v],
+      Numbers::Many(v) => v,
+    }
+  }
+
+  fn lerp(&self, to: &Self, rate: f32) -> Self {
+    self
+      .iter()
+      .zip(to.iter())
+      .map(|(from, to)| from + (to - from) * rate)
+      .collect()
+  }
+}
+
+impl KeyValue for Bezier {
+  type Raw = Beziers;
+
+  fn from_raw(raw: Beziers) -> Self {
+    match raw {
+      Beziers::One(b) => b,
+      Beziers::Many(b) => b.into_iter().next().unwrap_or_default(),
+    }
+  }
+
+  fn lerp(&self, to: &Self, rate: f32) -> Self {
+    // The shapes with different vertices can't be lerped.
+    if self.vertices.len() != to.vertices.len() {
+      return self.clone();
+    }
+    let lerp = |from: &[[f32; 2]], to: &[[f32; 2]]| {
+      from
+        .iter()
+        .zip(to)
+        .map(|(a, b)| [a[0] + (b[0] - a[0]) * rate, a[1] + (b[1] - a[1]) * rate])
+        .collect()
+    };
+    Bezier {
+      closed: self.closed,
+      vertices: lerp(&self.vertices, &to.vertices),
+      in_tangents: lerp(&self.in_tangents, &to.in_tangents),
+      out_tangents: lerp(&self.out_tangents, &to.out_tangents),
+    }
+  }
+}
+
+impl Bezier {
+  fn build_into(&self, builder: &mut PathBuilder) {
+    let Self { closed, vertices, in_tangents, out_tangents } = self;
+    if vertices.is_empty() {
+      return;
+    }
+    let at = |list: &[[f32; 2]], i: usize| {
+      list
+        .get(i)
+        .map_or(Point::zero(), |p| Point::new(p[0], p[1]))
+    };
+    let curve_to = |builder: &mut PathBuilder, from: usize, to: usize| {
+      let to_pt = at(vertices, to);
+      let ctrl1 = at(vertices, from) + at(out_tangents, from).to_vector();
+      let ctrl2 = to_pt + at(in_tangents, to).to_vector();
+      builder.bezier_curve_to(ctrl1, ctrl2, to_pt);
+    };
+
+    builder.begin_path(at(vertices, 0));
+    for i in 1..vertices.len() {
+      curve_to(builder, i - 1, i);
+    }
+    if *closed {
+      curve_to(builder, vertices.len() - 1, 0);
+    }
+    builder.end_path(*closed);
+  }
+
+  fn to_path(&self) -> Path {
+    let mut builder = Path::builder();
+    self.build_into(&mut builder);
+    builder.build()
+  }
+}
+
+impl TransformProps {
+  fn matrix(&self, frame: f32) -> Transform {
+    let vector = |p: &Option<Property<Vec<f32>>>, default: f32| {
+      p.as_ref()
+        .map_or(Vector::splat(default), |p| p.vector(frame))
+    };
+    let anchor = vector(&self.anchor, 0.);
+    let position = match &self.position {
+      Some(Position::Split { x, y }) => Vector::new(x.scalar(frame), y.scalar(frame)),
+      Some(Position::Combined(p)) => p.vector(frame),
+      None => Vector::zero(),
+    };
+    let scale = vector(&self.scale, 100.) / 100.;
+    let rotation = self
+      .rotation
+      .as_ref()
+      .or(self.rotation_z.as_ref())
+      .map_or(0., |r| r.scalar(frame));
+
+    Transform::translation(-anchor.x, -anchor.y)
+      .then_scale(scale.x, scale.y)
+      .then_rotate(Angle::degrees(rotation))
+      .then_translate(position)
+  }
+
+  fn opacity(&self, frame: f32) -> f32 { percent(&self.opacity, frame, 1.) }
+}
+
+impl Layer {
+  /// The frame in the time of the layer.
+  fn local_frame(&self, frame: f32) -> f32 { (frame - self.start_time) / self.stretch }
+
+  fn matrix(&self, layers: &[Layer], frame: f32) -> Transform {
+    let mut matrix = self.transform.matrix(self.local_frame(frame));
+    let mut parent = self.parent;
+    // Limit the depth in case of the parenting cycle.
+    for _ in 0..layers.len() {
+      let Some(p) = parent.and_then(|ind| layers.iter().find(|l| l.ind == Some(ind))) else {
+        break;
+      };
+      matrix = matrix.then(&p.transform.matrix(p.local_frame(frame)));
+      parent = p.parent;
+    }
+    matrix
+  }
+}
+
+/// Paint the `layers`, the `precomps` are the ids of the precompositions that
+/// are painting, a precomposition layer that refers to one of them is skipped
+/// to break the cycle.
+fn paint_layers<'a>(
+  layers: &[Layer], frame: f32, comp: &'a Composition, precomps: &mut Vec<&'a str>,
+  painter: &mut Painter,
+) {
+  // The first layer is on the top.
+  for layer in layers.iter().rev() {
+    let visible = layer.in_point <= frame && frame < layer.out_point;
+    if !visible || layer.hidden || layer.matte_target.is_some_and(|t| t != 0) {
+      continue;
+    }
+
+    let local = layer.local_frame(frame);
+    let mut painter = painter.save_guard();
+    painter
+      .apply_transform(&layer.matrix(layers, frame))
+      .apply_alpha(layer.transform.opacity(local));
+    clip_masks(&layer.masks, local, &mut painter);
+
+    match layer.ty {
+      SHAPE_LAYER => {
+        let mut draws = vec![];
+        collect_shapes(&layer.shapes, local, &[], &mut draws);
+        paint_draws(&draws, &mut painter);
+      }
+      SOLID_LAYER => {
+        let color = layer
+          .solid_color
+          .as_deref()
+          .and_then(|c| u32::from_str_radix(c.trim_start_matches('#'), 16).ok())
+          .map_or(Color::BLACK, |rgb| Color::from_u32((rgb << 8) | 0xFF));
+        let rect = Rect::from_size(Size::new(layer.solid_width, layer.solid_height));
+        painter
+          .set_fill_brush(color)
+          .fill_path(Path::rect(&rect).into());
+      }
+      PRECOMP_LAYER => {
+        let asset = comp
+          .assets
+          .iter()
+          .find(|a| Some(&a.id) == layer.ref_id.as_ref())
+          .filter(|a| !precomps.contains(&a.id.as_str()));
+        if let Some((id, layers)) = asset.and_then(|a| Some((&a.id, a.layers.as_ref()?))) {
+          let rect = Rect::from_size(Size::new(layer.width, layer.height));
+          painter.clip(Path::rect(&rect).into());
+          precomps.push(id);
+          paint_layers(layers, local, comp, precomps, &mut painter);
+          precomps.pop();
+        }
+      }
+      _ => {}
+    }
+  }
+}
+
+fn clip_masks(masks: &[Mask], frame: f32, painter: &mut Painter) {
+  let mut added = Path::builder();
+  let mut has_added = false;
+  for mask in masks.iter().filter(|m| !m.inverted) {
+    match mask.mode.as_str() {
+      "a" => {
+        mask.path.value(frame).build_into(&mut added);
+        has_added = true;
+      }
+      "i" => {
+        painter.clip(mask.path.value(frame).to_path().into());
+      }
+      _ => {}
+    }
+  }
+  if has_added {
+    painter.clip(added.build().into());
+  }
+}
+
+/// The painting of the shapes in a group, the first one is on the top.
+enum Draw {
+  Fill { path: Path, color: Color },
+  Stroke { path: Path, color: Color, options: StrokeOptions },
+  Group { transform: Transform, opacity: f32, draws: Vec<Draw> },
+}
+
+/// The trim range of the paths, normalized with the offset, its start is not
+/// greater than its end.
+#[derive(Clone, Copy)]
+struct Trim {
+  start: f32,
+  end: f32,
+}
+
+/// Collect the draws of the shapes in a group into `draws`, and return the
+/// paths of the group that the fills and strokes after the group paint.
+///
+/// The `outer_trims` are the trim paths after the group in its ancestors, they
+/// trim the paths of the group too.
+fn collect_shapes(
+  items: &[ShapeItem], frame: f32, outer_trims: &[Trim], draws: &mut Vec<Draw>,
+) -> Vec<Path> {
+  let mut paths = vec![];
+  for (idx, item) in items.iter().enumerate() {
+    if item.hidden {
+      continue;
+    }
+    // A trim path trims all the paths before it.
+    let trims = || {
+      items[idx + 1..]
+        .iter()
+        .filter(|i| !i.hidden)
+        .filter_map(|i| i.shape.trim(frame))
+        .chain(outer_trims.iter().copied())
+    };
+    match &item.shape {
+      Shape::Group { items: children } => {
+        let trims: Vec<_> = trims().collect();
+        let mut group_draws = vec![];
+        let group_paths = collect_shapes(children, frame, &trims, &mut group_draws);
+        let (transform, opacity) = children
+          .iter()
+          .rev()
+          .find_map(|i| match &i.shape {
+            Shape::Transform(t) => Some((t.matrix(frame), t.opacity(frame))),
+            _ => None,
+          })
+          .unwrap_or((Transform::identity(), 1.));
+        paths.extend(
+          group_paths
+            .into_iter()
+            .map(|p| p.transform(&transform)),
+        );
+        if !group_draws.is_empty() {
+          draws.push(Draw::Group { transform, opacity, draws: group_draws });
+        }
+      }
+      Shape::Fill { color, opacity } => {
+        if let Some(path) = merge_paths(&paths) {
+          draws.push(Draw::Fill { path, color: color.color(frame, opacity) });
+        }
+      }
+      Shape::Stroke { color, opacity, width, line_cap, line_join, miter_limit, .. } => {
+        if let Some(path) = merge_paths(&paths) {
+          let options = StrokeOptions {
+            width: width.scalar(frame),
+            line_cap: match line_cap {
+              Some(2) => LineCap::Round,
+              Some(3) => LineCap::Square,
+              _ => LineCap::Butt,
+            },
+            line_join: match line_join {
+              Some(2) => LineJoin::Round,
+              Some(3) => LineJoin::Bevel,
+              _ => LineJoin::Miter,
+            },
+            miter_limit: miter_limit.unwrap_or(4.),
+          };
+          draws.push(Draw::Stroke { path, color: color.color(frame, opacity), options });
+        }
+      }
+      shape => {
+        if let Some(path) = shape
+          .path(frame)
+          .and_then(|p| trim_path(p, trims()))
+        {
+          paths.push(path);
+        }
+      }
+    }
+  }
+  paths
+}
+
+fn paint_draws(draws: &[Draw], painter: &mut Painter) {
+  for draw in draws.iter().rev() {
+    match draw {
+      Draw::Fill { path, color } => {
+        painter
+          .set_fill_brush(*color)
+          .fill_path(path.clone().into());
+      }
+      Draw::Stroke { path, color, options } => {
+        painter
+          .set_stroke_brush(*color)
+          .set_strokes(options.clone())
+          .stroke_path(path.clone().into());
+      }
+      Draw::Group { transform, opacity, draws } => {
+        let mut painter = painter.save_guard();
+        painter
+          .apply_transform(transform)
+          .apply_alpha(*opacity);
+        paint_draws(draws, &mut painter);
+      }
+    }
+  }
+}
+
+impl Shape {
+  fn path(&self, frame: f32) -> Option<Path> {
+    let path = match self {
+      Shape::Rect { center, size, roundness } => {
+        let size = size.vector(frame);
+        let origin = center.vector(frame) - size / 2.;
+        let rect = Rect::new(origin.to_point(), size.to_size());
+        let radius = roundness
+          .as_ref()
+          .map_or(0., |r| r.scalar(frame))
+          .min(size.x / 2.)
+          .min(size.y / 2.);
+        if radius > 0. { Path::rect_round(&rect, &Radius::all(radius)) } else { Path::rect(&rect) }
+      }
+      Shape::Ellipse { center, size } => {
+        let mut builder = Path::builder();
+        builder.ellipse(center.vector(frame).to_point(), size.vector(frame) / 2., 0.);
+        builder.build()
+      }
+      Shape::Path { path } => path.value(frame).to_path(),
+      _ => return None,
+    };
+    Some(path)
+  }
+
+  fn trim(&self, frame: f32) -> Option<Trim> {
+    let Shape::Trim { start, end, offset } = self else { return None };
+    let offset = offset.as_ref().map_or(0., |o| o.scalar(frame)) / 360.;
+    let start = start.scalar(frame) / 100. + offset;
+    let end = end.scalar(frame) / 100. + offset;
+    Some(Trim { start: start.min(end), end: start.max(end) })
+  }
+}
+
+fn trim_path(mut path: Path, trims: impl Iterator<Item = Trim>) -> Option<Path> {
+  for Trim { start, end } in trims {
+    if end - start >= 1. {
+      continue;
+    }
+    if end <= start {
+      return None;
+    }
+    let measurements = PathMeasurements::from_path(&path.lyon_path, 1e-3);
+    if measurements.length() <= 0. {
+      return None;
+    }
+
+    // Wrap the range into the path, it may cross the start point of the path.
+    let shift = start.floor();
+    let (start, end) = (start - shift, end - shift);
+    let mut sampler = measurements.create_sampler(&path.lyon_path, SampleType::Normalized);
+    let mut builder = LyonPath::builder();
+    sampler.split_range(start..end.min(1.), &mut builder);
+    if end > 1. {
+      sampler.split_range(0. ..end - 1., &mut builder);
+    }
+    path = builder.build().into();
+  }
+  Some(path)
+}
+
+fn merge_paths(paths: &[Path]) -> Option<Path> {
+  match paths {
+    [] => None,
+    [path] => Some(path.clone()),
+    paths => {
+      let mut builder = LyonPath::builder();
+      let slices: Vec<_> = paths
+        .iter()
+        .map(|p| p.lyon_path.as_slice())
+        .collect();
+      builder.extend_from_paths(&slices);
+      Some(builder.build().into())
+    }
+  }
+}
+
+impl Composition {
+  fn warn_unsupported(&self) {
+    let mut warnings = ahash::AHashSet::new();
+    let mut layers: Vec<&Layer> = self.layers.iter().collect();
+    layers.extend(
+      self
+        .assets
+        .iter()
+        .filter_map(|a| a.layers.as_ref())
+        .flatten(),
+    );
+    for layer in layers {
+      if !matches!(layer.ty, PRECOMP_LAYER | SOLID_LAYER | NULL_LAYER | SHAPE_LAYER) {
+        warnings.insert("image and text layers");
+      }
+      if layer.matte_mode.is_some_and(|m| m != 0) {
+        warnings.insert("track mattes");
+      }
+      if layer.time_remap.is_some() {
+        warnings.insert("time remapping");
+      }
+      if layer.transform.skew.is_some() {
+        warnings.insert("skew");
+      }
+      for mask in &layer.masks {
+        if mask.inverted || !matches!(mask.mode.as_str(), "a" | "i" | "n") {
+          warnings.insert("masks except the add and intersect ones");
+        }
+      }
+      shapes_warnings(&layer.shapes, &mut warnings);
+    }
+
+    for w in warnings {
+      log::warn!("[painter]: not support {w} in lottie, ignored!");
+    }
+  }
+}
+
+fn shapes_warnings(items: &[ShapeItem], warnings: &mut ahash::AHashSet<&'static str>) {
+  for item in items {
+    match &item.shape {
+      Shape::Group { items } => shapes_warnings(items, warnings),
+      Shape::Stroke { dashes: Some(_), .. } => {
+        warnings.insert("stroke dashes");
+      }
+      Shape::Transform(t) if t.skew.is_some() => {
+        warnings.insert("skew");
+      }
+      Shape::Unsupported => {
+        warnings.insert("gradients, stars, repeaters and other shapes");
+      }
+      _ => {}
+    }
+  }
+}
+
+fn one() -> f32 { 1. }
+
+fn add_mode() -> String { "a".into() }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::{CommandBrush, PaintPathAction, PaintingStyle, PathCommand};
+
+  fn lottie(layers: &str) -> Lottie {
+    let json =
+      format!(r#"{{ "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 100, "layers": {layers} }}"#);
+    Lottie::parse_from_bytes(json.as_bytes()).unwrap()
+  }
+
+  fn paths(lottie: &Lottie, frame: f32) -> Vec<PathCommand> {
+    lottie
+      .frame_commands(frame)
+      .iter()
+      .filter_map(|c| match c {
+        PaintCommand::Path(p) => Some(p.clone()),
+        _ => None,
+      })
+      .collect()
+  }
+
+  const MOVING_RECT: &str = r#"[{
+    "ty": 4, "ip": 0, "op": 60,
+    "ks": {
+      "p": { "a": 1, "k": [
+        { "t": 0, "s": [0, 0], "o": { "x": [0], "y": [0] }, "i": { "x": [1], "y": [1] } },
+        { "t": 30, "s": [60, 0], "h": 1 },
+        { "t": 45, "s": [30, 30] }
+      ]},
+      "o": { "a": 0, "k": 50 }
+    },
+    "shapes": [{ "ty": "gr", "it": [
+      { "ty": "rc", "p": { "a": 0, "k": [10, 10] }, "s": { "a": 0, "k": [20, 20] } },
+      { "ty": "fl", "c": { "a": 0, "k": [1, 0, 0, 1] }, "o": { "a": 0, "k": 100 } },
+      { "ty": "tr", "p": { "a": 0, "k": [0, 0] } }
+    ]}]
+  }]"#;
+
+  #[test]
+  fn keyframes() {
+    let lottie = lottie(MOVING_RECT);
+    assert_eq!(lottie.size(), Size::new(100., 100.));
+    assert_eq!(lottie.frames(), 0. ..60.);
+    assert_eq!(lottie.duration(), Duration::from_secs(2));
+
+    let bounds = |frame| paths(&lottie, frame)[0].paint_bounds;
+    assert_eq!(bounds(0.), Rect::new(Point::zero(), Size::new(20., 20.)));
+    // The linear easing moves to the middle.
+    assert_eq!(bounds(15.).origin, Point::new(30., 0.));
+    // Hold the value until the next keyframe.
+    assert_eq!(bounds(40.).origin, Point::new(60., 0.));
+    assert_eq!(bounds(50.).origin, Point::new(30., 30.));
+
+    let PaintPathAction::Paint { brush: CommandBrush::Color(color), .. } =
+      &paths(&lottie, 0.)[0].action
+    else {
+      panic!("Expect a color fill.");
+    };
+    assert!((127..=128).contains(&color.alpha));
+  }
+
+  #[test]
+  fn cubic_easing() {
+    let curve = CubicBezierSegment {
+      from: point(0., 0.),
+      ctrl1: point(0.42, 0.),
+      ctrl2: point(0.58, 1.),
+      to: point(1., 1.),
+    };
+    assert!((ease(&curve, 0.5) - 0.5).abs() < 1e-3);
+    assert!(ease(&curve, 0.2) < 0.2);
+    assert!(ease(&curve, 0.8) > 0.8);
+  }
+
+  #[test]
+  fn trim_and_stroke() {
+    let lottie = lottie(
+      r#"[{
+        "ty": 4, "ip": 0, "op": 60,
+        "shapes": [
+          { "ty": "sh", "ks": { "a": 0, "k": {
+            "c": false, "v": [[0, 0], [100, 0]], "i": [[0, 0], [0, 0]], "o": [[0, 0], [0, 0]]
+          }}},
+          { "ty": "st", "c": { "a": 0, "k": [0, 0, 1] }, "o": { "a": 0, "k": 100 },
+            "w": { "a": 0, "k": 2 }, "lc": 2 },
+          { "ty": "tm", "s": { "a": 0, "k": 25 }, "e": { "a": 0, "k": 75 } }
+        ]
+      }]"#,
+    );
+    let paths = paths(&lottie, 0.);
+    assert_eq!(paths.len(), 1);
+    let PaintPathAction::Paint { painting_style: PaintingStyle::Stroke(options), .. } =
+      &paths[0].action
+    else {
+      panic!("Expect a stroke.");
+    };
+    assert_eq!(options.width, 2.);
+    assert_eq!(options.line_cap, LineCap::Round);
+    let bounds = paths[0].path.bounds(None);
+    assert!((bounds.min_x() - 25.).abs() < 0.1);
+    assert!((bounds.max_x() - 75.).abs() < 0.1);
+  }
+
+  #[test]
+  fn masks_and_parents() {
+    let lottie = lottie(
+      r##"[
+        { "ty": 3, "ind": 1, "ip": 0, "op": 60, "ks": { "p": { "a": 0, "k": [10, 10] } } },
+        { "ty": 1, "ind": 2, "parent": 1, "ip": 0, "op": 60, "sc": "#00ff00", "sw": 50, "sh": 50,
+          "masksProperties": [{ "mode": "a", "pt": { "a": 0, "k": {
+            "c": true, "v": [[0, 0], [20, 0], [20, 20]], "i": [[0, 0], [0, 0], [0, 0]],
+            "o": [[0, 0], [0, 0], [0, 0]]
+          }}}]
+        },
+        { "ty": 1, "ind": 3, "ip": 30, "op": 60, "sc": "#0000ff", "sw": 10, "sh": 10 }
+      ]"##,
+    );
+    let cmds = paths(&lottie, 0.);
+    assert_eq!(cmds.len(), 2);
+    assert!(matches!(cmds[0].action, PaintPathAction::Clip));
+    assert_eq!(cmds[0].paint_bounds, Rect::new(Point::new(10., 10.), Size::new(20., 20.)));
+    assert_eq!(cmds[1].paint_bounds, Rect::new(Point::new(10., 10.), Size::new(50., 50.)));
+
+    // The layer is painted only in its frames.
+    assert_eq!(paths(&lottie, 30.).len(), 3);
+  }
+
+  #[test]
+  fn reject_invalid_frame_rate() {
+    let json = r#"{ "fr": 0, "ip": 0, "op": 60, "w": 100, "h": 100, "layers": [] }"#;
+    assert!(Lottie::parse_from_bytes(json.as_bytes()).is_err());
+    let json = r#"{ "fr": -30, "ip": 0, "op": 60, "w": 100, "h": 100, "layers": [] }"#;
+    assert!(Lottie::parse_from_bytes(json.as_bytes()).is_err());
+  }
+
+  #[test]
+  fn precomp_cycle() {
+    let json = r##"{
+      "fr": 30, "ip": 0, "op": 60, "w": 100, "h": 100,
+      "assets": [
+        { "id": "a", "layers": [
+          { "ty": 1, "ip": 0, "op": 60, "sc": "#ff0000", "sw": 10, "sh": 10 },
+          { "ty": 0, "ip": 0, "op": 60, "refId": "a", "w": 100, "h": 100 },
+          { "ty": 0, "ip": 0, "op": 60, "refId": "b", "w": 100, "h": 100 }
+        ]},
+        { "id": "b", "layers": [
+          { "ty": 0, "ip": 0, "op": 60, "refId": "a", "w": 100, "h": 100 }
+        ]}
+      ],
+      "layers": [{ "ty": 0, "ip": 0, "op": 60, "refId": "a", "w": 100, "h": 100 }]
+    }"##;
+    let lottie = Lottie::parse_from_bytes(json.as_bytes()).unwrap();
+    let fills = paths(&lottie, 0.)
+      .into_iter()
+      .filter(|p| !matches!(p.action, PaintPathAction::Clip))
+      .count();
+    // The solid of `a` is painted once, `a -> a` and `a -> b -> a` are skipped.
+    assert_eq!(fills, 1);
+  }
+}
@@ -5,7 +5,7 @@ use ribir_geom::{Angle, DeviceRect, Point, Rect, Size, Transform, Vector};
 use serde::{Deserialize, Serialize};
 
 use crate::{
-  Brush, Color, Glyph, PixelImage, Svg, VisualGlyphs,
+  Brush, Color, Filter, Glyph, PixelImage, Svg, TextDecoration, TextSpan, VisualGlyphs,
   color::{LinearGradient, RadialGradient},
   font_db::FontDB,
   path::*,
@@ -21,9 +21,18 @@ pub struct Painter {
   init_state: PainterState,
   state_stack: Vec<PainterState>,
   commands: Vec<PaintCommand>,
+  /// The clip and filter layers not popped yet, the states only record how
+  /// many of them they have.
+  layers: Vec<Layer>,
   path_builder: PathBuilder,
 }
 
+#[derive(Debug, Clone, Copy)]
+enum Layer {
+  Clip,
+  Filter,
+}
+
 pub struct PainterResult<'a>(&'a mut Vec<PaintCommand>);
 
 /// `PainterBackend` use to draw textures for every frame, All `draw_commands`
@@ -76,6 +85,9 @@ pub enum PaintPathAction {
     brush: CommandBrush,
     /// The style to paint the path.
     painting_style: PaintingStyle,
+    /// How to composite the painted path with the content behind it.
+    #[serde(default)]
+    blend_mode: BlendMode,
   },
 
   Clip,
@@ -111,6 +123,42 @@ pub struct PathCommand {
   pub action: PaintPathAction,
 }
 
+/// A shadow cast by a path. The path is filled with the `color` and then
+/// blurred by a Gaussian blur.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowCommand {
+  /// The path that casts the shadow.
+  pub path: PaintPath,
+  /// The bounds after path applied transform, include the blur extent.
+  pub paint_bounds: Rect,
+  /// The transform need to apply to the path.
+  pub transform: Transform,
+  /// The color of the shadow.
+  pub color: Color,
+  /// The blur radius of the shadow in the path axis, the standard deviation
+  /// of the Gaussian blur is half of it.
+  pub blur_radius: f32,
+}
+
+/// Push a filter layer, the commands until the paired
+/// [`PaintCommand::PopFilter`] are drawn to an offscreen layer first, and then
+/// the layer is composited back after the filter applied.
+///
+/// If `backdrop` is true, the filter is applied to the content already drawn
+/// behind the `path` instead, and the commands in the layer are drawn as
+/// usual.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilterCommand {
+  /// The area of the layer, the content out of it is discarded.
+  pub path: PaintPath,
+  /// The bounds after path applied transform, include the blur extent.
+  pub paint_bounds: Rect,
+  /// The transform need to apply to the path.
+  pub transform: Transform,
+  pub filter: Filter,
+  pub backdrop: bool,
+}
+
 /// Explain the method for rendering shapes and paths, including filling or
 /// stroking them.
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
@@ -122,6 +170,31 @@ pub enum PaintingStyle {
   Stroke(StrokeOptions),
 }
 
+/// The mode to composite the painted content with the content behind it, the
+/// same as the `mix-blend-mode` of CSS. The content not covered by the path is
+/// not affected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum BlendMode {
+  /// Paint the content over the backdrop.
+  #[default]
+  Normal,
+  /// Multiply the content and backdrop colors, the result is always darker.
+  Multiply,
+  /// Multiply the complements of the content and backdrop colors, and then
+  /// complement the result, the result is always lighter.
+  Screen,
+  /// Multiply or screen the colors depending on the backdrop color, the
+  /// backdrop highlights and shadows are preserved.
+  Overlay,
+  /// Select the darker of the content and backdrop colors.
+  Darken,
+  /// Select the lighter of the content and backdrop colors.
+  Lighten,
+  /// Erase the backdrop by the alpha of the content, the content color is not
+  /// painted.
+  DestinationOut,
+}
+
 /// Define the default method for the painter to render paths, including filling
 /// or stroking them.
 #[derive(Debug, Clone, Copy, Default)]
@@ -134,7 +207,13 @@ pub enum PathStyle {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum PaintCommand {
   Path(PathCommand),
+  /// Paint a blurred shadow of a path.
+  Shadow(ShadowCommand),
   PopClip,
+  /// Begin a filter layer.
+  PushFilter(FilterCommand),
+  /// End the last filter layer.
+  PopFilter,
   /// A Bundle of paint commands that can be assumed as a single command, that
   /// means the backend can cache it.
   Bundle {
@@ -156,7 +235,9 @@ struct PainterState {
   style: PathStyle,
   transform: Transform,
   opacity: f32,
-  clip_cnt: usize,
+  blend_mode: BlendMode,
+  /// How many clip and filter layers pushed before the state restored.
+  layer_cnt: usize,
   /// The visible boundary of the painter in visual axis, not care about the
   /// transform.
   bounds: Rect,
@@ -170,8 +251,9 @@ impl PainterState {
       stroke_brush: Color::BLACK.into(),
       fill_brush: Color::GRAY.into(),
       transform: Transform::identity(),
-      clip_cnt: 0,
+      layer_cnt: 0,
       opacity: 1.,
+      blend_mode: BlendMode::Normal,
       style: PathStyle::Fill,
     }
   }
@@ -185,6 +267,7 @@ impl Painter {
       state_stack: vec![init_state.clone()],
       init_state,
       commands: vec![],
+      layers: vec![],
       path_builder: Path::builder(),
     }
   }
@@ -219,7 +302,7 @@ impl Painter {
 
   #[inline]
   pub fn finish(&mut self) -> PainterResult {
-    self.fill_all_pop_clips();
+    self.fill_all_pop_layers();
     PainterResult(&mut self.commands)
   }
 
@@ -244,13 +327,13 @@ impl Painter {
   /// nothing.
   #[inline]
   pub fn restore(&mut self) {
-    let clip_cnt = self.current_state().clip_cnt;
+    let layer_cnt = self.current_state().layer_cnt;
     self.state_stack.pop();
-    self.push_n_pop_cmd(clip_cnt - self.current_state().clip_cnt);
+    self.push_n_pop_cmd(layer_cnt - self.current_state().layer_cnt);
   }
 
   pub fn reset(&mut self) {
-    self.fill_all_pop_clips();
+    self.fill_all_pop_layers();
     self.commands.clear();
     self.state_stack.clear();
     self.state_stack.push(self.init_state.clone());
@@ -299,6 +382,16 @@ impl Painter {
     self
   }
 
+  /// Return the blend mode used to paint paths.
+  pub fn blend_mode(&self) -> BlendMode { self.current_state().blend_mode }
+
+  /// Change the blend mode to composite the subsequent painted paths with the
+  /// content behind them.
+  pub fn set_blend_mode(&mut self, mode: BlendMode) -> &mut Self {
+    self.current_state_mut().blend_mode = mode;
+    self
+  }
+
   #[inline]
   pub fn set_strokes(&mut self, strokes: StrokeOptions) -> &mut Self {
     self.current_state_mut().stroke_options = strokes;
@@ -375,13 +468,28 @@ impl Painter {
         s.bounds = s.transform.outer_transformed_rect(&bounds);
         let cmd = PathCommand::new(path, PaintPathAction::Clip, s.transform);
         self.commands.push(PaintCommand::Path(cmd));
-        self.current_state_mut().clip_cnt += 1;
+        self.push_layer(Layer::Clip);
       }
     }
 
     self
   }
 
+  /// Push a filter layer, all the drawing after it are painted to an offscreen
+  /// layer until the state is restored, then the layer is composited back with
+  /// the `filter` applied. The `path` describes the area of the layer, the
+  /// content out of its bounds is discarded.
+  pub fn filter(&mut self, path: PaintPath, filter: Filter) -> &mut Self {
+    self.push_filter(path, filter, false)
+  }
+
+  /// Apply the `filter` to the content already painted behind the `path`, like
+  /// the `backdrop-filter` of CSS. The drawing after it is painted as usual,
+  /// and the layer is popped when the state is restored.
+  pub fn backdrop_filter(&mut self, path: PaintPath, filter: Filter) -> &mut Self {
+    self.push_filter(path, filter, true)
+  }
+
   /// Fill a path with fill brush.
   pub fn fill_path(&mut self, path: PaintPath) -> &mut Self {
     self.inner_draw_path(path, PathStyle::Fill)
@@ -529,6 +637,46 @@ impl Painter {
     self
   }
 
+  /// Draws a shadow of the `path` filled with `color` and blurred by the
+  /// `blur_radius`. The shadow is not clipped by the path, so it's the caller's
+  /// responsibility to paint something over it if needed.
+  pub fn draw_shadow(&mut self, path: PaintPath, color: Color, blur_radius: f32) -> &mut Self {
+    invisible_return!(self);
+    let blur_radius = blur_radius.max(0.);
+    let p_bounds = path.bounds(None);
+    if color.alpha == 0 || p_bounds.is_empty() || !locatable_bounds(&p_bounds) {
+      return self;
+    }
+    let extent = ShadowCommand::blur_extent(blur_radius);
+    if !self.intersect_paint_bounds(&p_bounds.inflate(extent, extent)) {
+      return self;
+    }
+
+    let color = color.apply_alpha(self.alpha());
+    let cmd = ShadowCommand::new(path, color, blur_radius, *self.transform());
+    self.commands.push(PaintCommand::Shadow(cmd));
+    self
+  }
+
+  fn push_filter(&mut self, path: PaintPath, filter: Filter, backdrop: bool) -> &mut Self {
+    invisible_return!(self);
+    if filter.is_empty() {
+      return self;
+    }
+    let p_bounds = path.bounds(None);
+    let extent = filter.blur_extent();
+    if !locatable_bounds(&p_bounds)
+      || !self.intersect_paint_bounds(&p_bounds.inflate(extent, extent))
+    {
+      return self;
+    }
+
+    let cmd = FilterCommand::new(path, filter, backdrop, *self.transform());
+    self.commands.push(PaintCommand::PushFilter(cmd));
+    self.push_layer(Layer::Filter);
+    self
+  }
+
   /// Draws a bundle of paint commands that can be treated as a single command.
   /// This allows the backend to cache it.
   ///
@@ -558,7 +706,10 @@ impl Painter {
     // For a small number of path commands (less than 16), store them
     // individually as multiple resources. This means the backend doesn't
     // need to perform a single draw operation for an SVG.
-    if commands.len() <= 16 {
+    // A bundle is composited as a whole, so the commands are never bundled
+    // when a blend mode is applied to each path.
+    let blend = self.blend_mode();
+    if commands.len() <= 16 || blend != BlendMode::Normal {
       let transform = *self.transform();
       let alpha = self.alpha();
 
@@ -566,12 +717,25 @@ impl Painter {
         let cmd = match cmd.clone() {
           PaintCommand::Path(mut path) => {
             path.transform(&transform);
-            if let PaintPathAction::Paint { ref mut brush, .. } = path.action {
+            if let PaintPathAction::Paint { ref mut brush, ref mut blend_mode, .. } = path.action {
               brush.apply_alpha(alpha);
+              if blend != BlendMode::Normal {
+                *blend_mode = blend;
+              }
             }
             PaintCommand::Path(path)
           }
+          PaintCommand::Shadow(mut shadow) => {
+            shadow.transform(&transform);
+            shadow.color = shadow.color.apply_alpha(alpha);
+            PaintCommand::Shadow(shadow)
+          }
+          PaintCommand::PushFilter(mut filter) => {
+            filter.transform(&transform);
+            PaintCommand::PushFilter(filter)
+          }
           PaintCommand::PopClip => PaintCommand::PopClip,
+          PaintCommand::PopFilter => PaintCommand::PopFilter,
           PaintCommand::Bundle { transform: b_ts, opacity, bounds, cmds } => PaintCommand::Bundle {
             transform: transform.then(&b_ts),
             opacity: alpha * opacity,
@@ -690,6 +854,71 @@ impl Painter {
     self
   }
 
+  /// Draw the text glyphs within the box_rect with the styles of the spans,
+  /// the glyphs that no span covers are painted with the current brush.
+  pub fn draw_glyphs_with_spans_in_rect(
+    &mut self, visual_glyphs: &VisualGlyphs, box_rect: Rect, spans: &[TextSpan], font_db: &FontDB,
+  ) -> &mut Self {
+    let visual_rect = visual_glyphs.visual_rect();
+    let Some(paint_rect) = self.intersection_paint_bounds(&box_rect) else {
+      return self;
+    };
+    let Some(glyphs) = visual_glyphs.glyphs_in_bounds(&paint_rect) else {
+      return self;
+    };
+
+    self.translate(visual_rect.origin.x, visual_rect.origin.y);
+
+    let base_size = visual_glyphs.font_size();
+    for g in glyphs {
+      let Some(span) = TextSpan::find(spans, g.cluster as usize) else {
+        self.draw_glyph(&g, base_size, font_db);
+        continue;
+      };
+      let style = &span.style;
+      let font_size = style.font_size.unwrap_or(base_size);
+      let bounds = g.bounds();
+
+      self.save();
+      if let Some(background) = style.background {
+        self
+          .set_fill_brush(background)
+          .rect(&bounds)
+          .fill();
+      }
+      if let Some(color) = style.color {
+        self.set_fill_brush(color).set_stroke_brush(color);
+      }
+      if !(style.decoration - TextDecoration::NONE).is_empty() {
+        // The glyph is placed with its baseline at the bottom of the em box.
+        let thickness = (font_size / 14.).max(1.);
+        let lines = [
+          (TextDecoration::UNDERLINE, 1.08),
+          (TextDecoration::OVERLINE, 0.2),
+          (TextDecoration::THROUGHLINE, 0.7),
+        ];
+        for (line, pos) in lines {
+          if style.decoration.contains(line) {
+            let y = bounds.min_y() + font_size * pos - thickness / 2.;
+            self.rect(&Rect::new(
+              Point::new(bounds.min_x(), y),
+              Size::new(bounds.width(), thickness),
+            ));
+          }
+        }
+        let brush = style
+          .decoration_color
+          .map_or_else(|| self.fill_brush().clone(), Brush::from);
+        self.save();
+        self.set_fill_brush(brush).fill().restore();
+      }
+      self.draw_glyph(&g, font_size, font_db);
+      self.restore();
+    }
+
+    self
+  }
+
   fn inner_draw_path(&mut self, path: PaintPath, path_style: PathStyle) -> &mut Self {
     invisible_return!(self);
     let line_width = matches!(path_style, PathStyle::Stroke).then(|| self.line_width());
@@ -714,7 +943,8 @@ impl Painter {
       };
       brush.apply_alpha(self.alpha());
       let ts = *self.transform();
-      let action = PaintPathAction::Paint { brush, painting_style };
+      let blend_mode = self.blend_mode();
+      let action = PaintPathAction::Paint { brush, painting_style, blend_mode };
       let cmd = PathCommand::new(path, action, ts);
       self.commands.push(PaintCommand::Path(cmd));
     }
@@ -749,26 +979,42 @@ impl Painter {
 
   fn stroke_options(&self) -> &StrokeOptions { &self.current_state().stroke_options }
 
+  fn push_layer(&mut self, layer: Layer) {
+    self.layers.push(layer);
+    self.current_state_mut().layer_cnt += 1;
+  }
+
   fn push_n_pop_cmd(&mut self, n: usize) {
     for _ in 0..n {
-      if matches!(
-        self.commands.last(),
-        Some(PaintCommand::Path(PathCommand { action: PaintPathAction::Clip, .. }))
-      ) {
+      let layer = self.layers.pop().expect("Layer not paired.");
+      // A layer without any drawing can be dropped, except the backdrop filter
+      // that works on the content behind it.
+      let empty = match (layer, self.commands.last()) {
+        (Layer::Clip, Some(PaintCommand::Path(PathCommand { action, .. }))) => {
+          matches!(action, PaintPathAction::Clip)
+        }
+        (Layer::Filter, Some(PaintCommand::PushFilter(f))) => !f.backdrop,
+        _ => false,
+      };
+      if empty {
         self.commands.pop();
       } else {
-        self.commands.push(PaintCommand::PopClip)
+        let cmd = match layer {
+          Layer::Clip => PaintCommand::PopClip,
+          Layer::Filter => PaintCommand::PopFilter,
+        };
+        self.commands.push(cmd);
       }
     }
   }
 
-  fn fill_all_pop_clips(&mut self) {
-    let clip_cnt = self.current_state().clip_cnt;
+  fn fill_all_pop_layers(&mut self) {
+    let layer_cnt = self.current_state().layer_cnt;
     self
       .state_stack
       .iter_mut()
-      .for_each(|s| s.clip_cnt = 0);
-    self.push_n_pop_cmd(clip_cnt);
+      .for_each(|s| s.layer_cnt = 0);
+    self.push_n_pop_cmd(layer_cnt);
   }
 
   fn is_visible_canvas(&self) -> bool {
@@ -876,6 +1122,47 @@ impl PathCommand {
   }
 }
 
+impl ShadowCommand {
+  pub fn new(path: PaintPath, color: Color, blur_radius: f32, transform: Transform) -> Self {
+    let mut cmd = Self {
+      path,
+      paint_bounds: Rect::zero(),
+      transform: Transform::identity(),
+      color,
+      blur_radius,
+    };
+    cmd.transform(&transform);
+    cmd
+  }
+
+  /// The distance the blur spreads out of the path, beyond it the shadow is
+  /// invisible.
+  pub fn blur_extent(blur_radius: f32) -> f32 { blur_radius * 1.5 }
+
+  pub fn transform(&mut self, transform: &Transform) {
+    self.transform = self.transform.then(transform);
+    let extent = Self::blur_extent(self.blur_radius);
+    let bounds = self.path.bounds(None).inflate(extent, extent);
+    self.paint_bounds = self.transform.outer_transformed_rect(&bounds);
+  }
+}
+
+impl FilterCommand {
+  pub fn new(path: PaintPath, filter: Filter, backdrop: bool, transform: Transform) -> Self {
+    let mut cmd =
+      Self { path, paint_bounds: Rect::zero(), transform: Transform::identity(), filter, backdrop };
+    cmd.transform(&transform);
+    cmd
+  }
+
+  pub fn transform(&mut self, transform: &Transform) {
+    self.transform = self.transform.then(transform);
+    let extent = self.filter.blur_extent();
+    let bounds = self.path.bounds(None).inflate(extent, extent);
+    self.paint_bounds = self.transform.outer_transformed_rect(&bounds);
+  }
+}
+
 impl CommandBrush {
   pub fn apply_alpha(&mut self, alpha: f32) -> &mut Self {
     match self {
@@ -960,7 +1247,7 @@ mod test {
 
     std::mem::drop(commands);
 
-    assert_eq!(painter.current_state().clip_cnt, 0);
+    assert_eq!(painter.current_state().layer_cnt, 0);
   }
 
   #[test]
@@ -974,6 +1261,60 @@ mod test {
     assert_eq!(painter.commands.len(), 0);
   }
 
+  #[test]
+  fn pop_filter_and_clip_in_order() {
+    let mut painter = painter();
+    let area = Path::rect(&rect(0., 0., 100., 100.));
+    painter
+      .save()
+      .filter(area.clone().into(), Filter::blur(2.))
+      .clip(area.clone().into())
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+    let commands = painter.finish();
+    assert!(matches!(commands[0], PaintCommand::PushFilter(_)));
+    assert!(matches!(commands[commands.len() - 2], PaintCommand::PopClip));
+    assert!(matches!(commands[commands.len() - 1], PaintCommand::PopFilter));
+    std::mem::drop(commands);
+
+    // An empty filter layer is dropped, but the backdrop filter is kept.
+    painter.reset();
+    painter
+      .save()
+      .filter(area.clone().into(), Filter::grayscale(1.))
+      .restore();
+    assert_eq!(painter.commands.len(), 0);
+    painter
+      .save()
+      .backdrop_filter(area.into(), Filter::blur(2.))
+      .restore();
+    assert_eq!(painter.commands.len(), 2);
+  }
+
+  #[test]
+  fn blend_mode_follow_state() {
+    let mut painter = painter();
+    painter
+      .save()
+      .set_blend_mode(BlendMode::Multiply)
+      .rect(&rect(0., 0., 10., 10.))
+      .fill()
+      .restore();
+    assert_eq!(painter.blend_mode(), BlendMode::Normal);
+    painter.rect(&rect(0., 0., 10., 10.)).fill();
+
+    let blend_mode = |cmd: &PaintCommand| match cmd {
+      PaintCommand::Path(PathCommand {
+        action: PaintPathAction::Paint { blend_mode, .. }, ..
+      }) => *blend_mode,
+      _ => unreachable!(),
+    };
+    let commands = painter.finish();
+    assert_eq!(blend_mode(&commands[0]), BlendMode::Multiply);
+    assert_eq!(blend_mode(&commands[1]), BlendMode::Normal);
+  }
+
   #[test]
   fn filter_invalid_commands() {
     let mut painter = painter();
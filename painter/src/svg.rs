@@ -274,7 +274,10 @@ fn fallback_color_check(cmds: &[PaintCommand]) -> (bool, bool) {
           }
         }
       }
-      PaintCommand::PopClip => {}
+      PaintCommand::Shadow(_)
+      | PaintCommand::PushFilter(_)
+      | PaintCommand::PopClip
+      | PaintCommand::PopFilter => {}
       PaintCommand::Bundle { cmds, .. } => {
         let (f, s) = fallback_color_check(cmds);
         fill_fallback = f;
@@ -301,7 +304,10 @@ fn brush_replace(cmds: &[PaintCommand], fill: &Brush, stroke: &Brush) -> Box<[Pa
         }
         PaintCommand::Path(p)
       }
+      PaintCommand::Shadow(s) => PaintCommand::Shadow(s.clone()),
+      PaintCommand::PushFilter(f) => PaintCommand::PushFilter(f.clone()),
       PaintCommand::PopClip => PaintCommand::PopClip,
+      PaintCommand::PopFilter => PaintCommand::PopFilter,
       PaintCommand::Bundle { transform, opacity, bounds, cmds } => {
         let cmds = brush_replace(cmds, fill, stroke);
         let cmds = Resource::new(cmds);
@@ -17,6 +17,8 @@ pub mod typography;
 pub use text_reorder::TextReorder;
 mod typography_store;
 pub use typography_store::{TypographyStore, VisualGlyphs};
+mod attributed_text;
+pub use attributed_text::*;
 mod svg_glyph_cache;
 
 // Enum value descriptions are from the CSS spec.
@@ -90,10 +92,43 @@ pub enum TextOverflow {
   #[default]
   Overflow,
   AutoWrap,
+  /// Keep the text in one line, and replace the text that overflows the line
+  /// with an ellipsis "…".
+  Ellipsis(EllipsisMode),
+  /// Wrap the text in at most the number of lines, the text after the last
+  /// line is truncated and an ellipsis "…" ends the last line.
+  MaxLines(usize),
+}
+
+/// Where the ellipsis replaces the truncated text of a line.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub enum EllipsisMode {
+  /// Truncate the end of the text, like "Hello wo…".
+  #[default]
+  End,
+  /// Truncate the middle of the text, like "Hell…rld".
+  Middle,
+  /// Truncate the start of the text, like "…lo world".
+  Start,
 }
 
 impl TextOverflow {
-  fn is_auto_wrap(&self) -> bool { matches!(self, TextOverflow::AutoWrap) }
+  fn is_auto_wrap(&self) -> bool {
+    match self {
+      TextOverflow::AutoWrap => true,
+      TextOverflow::MaxLines(lines) => *lines > 1,
+      _ => false,
+    }
+  }
+
+  /// The maximum number of lines the text can use, `None` if not limited.
+  pub fn max_lines(&self) -> Option<usize> {
+    match self {
+      TextOverflow::Ellipsis(_) => Some(1),
+      TextOverflow::MaxLines(lines) => Some((*lines).max(1)),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Debug, Clone, PartialEq)]
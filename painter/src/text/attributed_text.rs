@@ -0,0 +1,371 @@
+use std::ops::Range;
+
+use ribir_algo::CowArc;
+
+use crate::{Color, FontFace, FontFamily, FontStyle, FontWeight, TextStyle};
+
+bitflags::bitflags! {
+  /// A linear decoration to draw near the text.
+  #[derive(Default, PartialEq, Eq, Hash, Clone, Copy, Debug)]
+  pub struct TextDecoration: u8 {
+    const NONE = 0b0001;
+    /// Draw a line underneath each line of text
+    const UNDERLINE = 0b0010;
+    /// Draw a line above each line of text
+    const OVERLINE = 0b0100;
+    /// Draw a line through each line of text
+    const THROUGHLINE = 0b1000;
+  }
+}
+
+/// The style of a span of the [`AttributedText`]. The fields that are `None`
+/// inherit from the text style of the whole text.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SpanStyle {
+  pub font_families: Option<Box<[FontFamily]>>,
+  pub font_size: Option<f32>,
+  pub font_weight: Option<FontWeight>,
+  pub font_style: Option<FontStyle>,
+  /// The space between characters in logical pixel units.
+  pub letter_space: Option<f32>,
+  /// The color to fill the glyphs.
+  pub color: Option<Color>,
+  /// The color to fill the background of the glyphs.
+  pub background: Option<Color>,
+  pub decoration: TextDecoration,
+  /// The color of the decoration lines, the color of the glyphs by default.
+  pub decoration_color: Option<Color>,
+}
+
+/// A span of the [`AttributedText`] with its style.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextSpan {
+  /// The byte range of the span in the text.
+  pub range: Range<usize>,
+  pub style: SpanStyle,
+  /// The link of the span, it's not used by the typography but helps to
+  /// handle the inline links.
+  pub link: Option<CowArc<str>>,
+}
+
+/// A text with the spans of different styles, the text is shaped and wrapped
+/// as one paragraph.
+///
+/// The spans are sorted and never overlap, the text not covered by any span
+/// uses the style of the whole text.
+///
+/// # Example
+///
+/// ```
+/// use ribir_painter::*;
+///
+/// let text = AttributedText::default()
+///   .push("Hello ", SpanStyle::default())
+///   .push("world", SpanStyle::default().with_font_weight(FontWeight::BOLD))
+///   .push_link("!", "https://ribir.org", SpanStyle::default());
+///
+/// assert_eq!(&**text.text(), "Hello world!");
+/// assert_eq!(text.spans().len(), 2);
+/// assert_eq!(text.span_at(7).map(|s| &s.range), Some(&(6..11)));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AttributedText {
+  text: CowArc<str>,
+  spans: Vec<TextSpan>,
+}
+
+impl SpanStyle {
+  pub fn with_font_families(mut self, families: impl Into<Box<[FontFamily]>>) -> Self {
+    self.font_families = Some(families.into());
+    self
+  }
+
+  pub fn with_font_size(mut self, font_size: f32) -> Self {
+    self.font_size = Some(font_size);
+    self
+  }
+
+  pub fn with_font_weight(mut self, weight: FontWeight) -> Self {
+    self.font_weight = Some(weight);
+    self
+  }
+
+  pub fn with_font_style(mut self, style: FontStyle) -> Self {
+    self.font_style = Some(style);
+    self
+  }
+
+  pub fn with_letter_space(mut self, letter_space: f32) -> Self {
+    self.letter_space = Some(letter_space);
+    self
+  }
+
+  pub fn with_color(mut self, color: Color) -> Self {
+    self.color = Some(color);
+    self
+  }
+
+  pub fn with_background(mut self, color: Color) -> Self {
+    self.background = Some(color);
+    self
+  }
+
+  pub fn with_decoration(mut self, decoration: TextDecoration) -> Self {
+    self.decoration = decoration;
+    self
+  }
+
+  pub fn with_decoration_color(mut self, color: Color) -> Self {
+    self.decoration_color = Some(color);
+    self
+  }
+
+  /// Override the fields of this style with the fields that `other` specifies.
+  pub fn merge(&mut self, other: &SpanStyle) {
+    fn over<T: Clone>(this: &mut Option<T>, other: &Option<T>) {
+      if other.is_some() {
+        this.clone_from(other);
+      }
+    }
+    over(&mut self.font_families, &other.font_families);
+    over(&mut self.font_size, &other.font_size);
+    over(&mut self.font_weight, &other.font_weight);
+    over(&mut self.font_style, &other.font_style);
+    over(&mut self.letter_space, &other.letter_space);
+    over(&mut self.color, &other.color);
+    over(&mut self.background, &other.background);
+    over(&mut self.decoration_color, &other.decoration_color);
+    self.decoration |= other.decoration;
+  }
+
+  /// The font face of the span base on the font face of the whole text.
+  pub fn font_face(&self, base: &FontFace) -> FontFace {
+    FontFace {
+      families: self
+        .font_families
+        .clone()
+        .unwrap_or_else(|| base.families.clone()),
+      stretch: base.stretch,
+      style: self.font_style.unwrap_or(base.style),
+      weight: self.font_weight.unwrap_or(base.weight),
+    }
+  }
+
+  /// Whether the style only paints differently, and shapes the same as the
+  /// text style of the whole text.
+  fn is_paint_only(&self) -> bool {
+    self.font_families.is_none()
+      && self.font_size.is_none()
+      && self.font_weight.is_none()
+      && self.font_style.is_none()
+      && self.letter_space.is_none()
+  }
+}
+
+impl TextSpan {
+  /// The font size of the span base on the text style of the whole text.
+  pub fn font_size(&self, base: &TextStyle) -> f32 {
+    self.style.font_size.unwrap_or(base.font_size)
+  }
+
+  pub(crate) fn is_paint_only(&self) -> bool { self.style.is_paint_only() }
+
+  /// Return the span that contains the byte index in the sorted spans.
+  pub fn find(spans: &[TextSpan], idx: usize) -> Option<&TextSpan> {
+    let i = spans.partition_point(|s| s.range.end <= idx);
+    spans.get(i).filter(|s| s.range.contains(&idx))
+  }
+}
+
+impl AttributedText {
+  /// Create an attributed text without any span.
+  pub fn new(text: impl Into<CowArc<str>>) -> Self { Self { text: text.into(), spans: vec![] } }
+
+  /// Append the text with the style, the style that specifies nothing doesn't
+  /// create a span.
+  pub fn push(self, text: &str, style: SpanStyle) -> Self { self.push_span(text, style, None) }
+
+  /// Append the text as a link with the style.
+  pub fn push_link(self, text: &str, link: impl Into<CowArc<str>>, style: SpanStyle) -> Self {
+    self.push_span(text, style, Some(link.into()))
+  }
+
+  fn push_span(mut self, text: &str, style: SpanStyle, link: Option<CowArc<str>>) -> Self {
+    let start = self.text.len();
+    let mut s = self.text.to_string();
+    s.push_str(text);
+    self.text = s.into();
+    if !text.is_empty() && (style != SpanStyle::default() || link.is_some()) {
+      self
+        .spans
+        .push(TextSpan { range: start..start + text.len(), style, link });
+    }
+    self
+  }
+
+  pub fn text(&self) -> &CowArc<str> { &self.text }
+
+  pub fn spans(&self) -> &[TextSpan] { &self.spans }
+
+  /// Replace the spans of the text, the spans must be sorted and not overlap.
+  pub fn set_spans(&mut self, spans: Vec<TextSpan>) { self.spans = spans; }
+
+  /// Return the span that contains the byte index of the text.
+  pub fn span_at(&self, idx: usize) -> Option<&TextSpan> { TextSpan::find(&self.spans, idx) }
+
+  /// Apply the style to the range of the text, the fields the style specifies
+  /// override the fields of the spans in the range.
+  pub fn apply_style(&mut self, rg: Range<usize>, style: &SpanStyle) {
+    let rg = rg.start.min(self.text.len())..rg.end.min(self.text.len());
+    if rg.is_empty() {
+      return;
+    }
+    self.split_at(rg.start);
+    self.split_at(rg.end);
+
+    let mut spans = Vec::with_capacity(self.spans.len() + 2);
+    let mut pos = rg.start;
+    for mut s in self.spans.drain(..) {
+      if s.range.end <= rg.start || rg.end <= s.range.start {
+        spans.push(s);
+        continue;
+      }
+      if pos < s.range.start {
+        spans.push(TextSpan { range: pos..s.range.start, style: style.clone(), link: None });
+      }
+      pos = s.range.end;
+      s.style.merge(style);
+      spans.push(s);
+    }
+    if pos < rg.end {
+      spans.push(TextSpan { range: pos..rg.end, style: style.clone(), link: None });
+    }
+    spans.sort_by_key(|s| s.range.start);
+    self.spans = spans;
+  }
+
+  /// Insert the text at the byte index, the inserted text extends the span
+  /// before it. Return the length of the inserted text.
+  pub fn insert_str(&mut self, at: usize, v: &str) -> usize {
+    if v.is_empty() {
+      return 0;
+    }
+    let mut s = self.text.to_string();
+    s.insert_str(at, v);
+    self.text = s.into();
+
+    let len = v.len();
+    for span in self.spans.iter_mut() {
+      if at < span.range.start || (at == span.range.start && at != 0) {
+        span.range.start += len;
+        span.range.end += len;
+      } else if at <= span.range.end {
+        span.range.end += len;
+      }
+    }
+    len
+  }
+
+  /// Delete the text in the byte range, and return the deleted range.
+  pub fn del_rg_str(&mut self, mut rg: Range<usize>) -> Range<usize> {
+    rg.start = rg.start.min(self.text.len());
+    rg.end = rg.end.min(self.text.len());
+    if rg.is_empty() {
+      return rg;
+    }
+    let mut s = self.text.to_string();
+    s.drain(rg.clone());
+    self.text = s.into();
+
+    let shift = |pos: usize| {
+      if pos <= rg.start {
+        pos
+      } else if pos < rg.end {
+        rg.start
+      } else {
+        pos - rg.len()
+      }
+    };
+    self.spans.retain_mut(|span| {
+      span.range = shift(span.range.start)..shift(span.range.end);
+      !span.range.is_empty()
+    });
+    rg
+  }
+
+  fn split_at(&mut self, idx: usize) {
+    let Some(i) = self
+      .spans
+      .iter()
+      .position(|s| s.range.start < idx && idx < s.range.end)
+    else {
+      return;
+    };
+    let mut right = self.spans[i].clone();
+    self.spans[i].range.end = idx;
+    right.range.start = idx;
+    self.spans.insert(i + 1, right);
+  }
+}
+
+impl From<CowArc<str>> for AttributedText {
+  fn from(text: CowArc<str>) -> Self { Self::new(text) }
+}
+
+impl From<&'static str> for AttributedText {
+  fn from(text: &'static str) -> Self { Self::new(text) }
+}
+
+impl From<String> for AttributedText {
+  fn from(text: String) -> Self { Self::new(text) }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn bold() -> SpanStyle { SpanStyle::default().with_font_weight(FontWeight::BOLD) }
+
+  fn ranges(text: &AttributedText) -> Vec<(usize, usize)> {
+    text
+      .spans()
+      .iter()
+      .map(|s| (s.range.start, s.range.end))
+      .collect()
+  }
+
+  #[test]
+  fn edit_spans() {
+    let mut text = AttributedText::default()
+      .push("Hello ", SpanStyle::default())
+      .push("world", bold());
+    assert_eq!(ranges(&text), [(6, 11)]);
+
+    // Insert at the start of a span goes before it, but inside it at the end.
+    text.insert_str(6, "big ");
+    assert_eq!(&**text.text(), "Hello big world");
+    assert_eq!(ranges(&text), [(10, 15)]);
+    text.insert_str(15, "!");
+    assert_eq!(ranges(&text), [(10, 16)]);
+
+    text.del_rg_str(8..12);
+    assert_eq!(&**text.text(), "Hello birld!");
+    assert_eq!(ranges(&text), [(8, 12)]);
+    text.del_rg_str(7..12);
+    assert!(text.spans().is_empty());
+  }
+
+  #[test]
+  fn apply_style() {
+    let red = SpanStyle::default().with_color(Color::RED);
+    let mut text = AttributedText::default()
+      .push("Hello ", SpanStyle::default())
+      .push("world", bold());
+
+    text.apply_style(3..8, &red);
+    assert_eq!(ranges(&text), [(3, 6), (6, 8), (8, 11)]);
+    assert_eq!(text.spans()[0].style, red);
+    assert_eq!(text.spans()[1].style, bold().with_color(Color::RED));
+    assert_eq!(text.spans()[2].style, bold());
+  }
+}
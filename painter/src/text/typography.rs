@@ -6,7 +6,7 @@ use smallvec::{SmallVec, smallvec};
 use unicode_script::{Script, UnicodeScript};
 use unicode_segmentation::UnicodeSegmentation;
 
-use crate::{Glyph, GlyphUnit, TextAlign, TextOverflow, shaper::ShapeResult};
+use crate::{EllipsisMode, Glyph, GlyphUnit, TextAlign, TextOverflow, shaper::ShapeResult};
 
 #[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PlaceLineDirection {
@@ -72,6 +72,10 @@ pub struct TypographyMan<Paras> {
   inline_cursor: GlyphUnit,
   visual_lines: SmallVec<[VisualLine; 1]>,
   over_bounds: bool,
+  /// The glyphs to replace the truncated text.
+  ellipsis: Option<InputRun>,
+  /// The start byte of the graphemes, the text is only truncated at them.
+  graphemes: Vec<usize>,
 }
 
 impl<Paras> TypographyMan<Paras>
@@ -92,13 +96,27 @@ where
       inline_cursor: GlyphUnit::ZERO,
       visual_lines: smallvec![],
       over_bounds: false,
+      ellipsis: None,
+      graphemes: vec![],
     }
   }
 
+  /// Use the glyphs of the run as the ellipsis that replaces the truncated
+  /// text, the text is truncated without ellipsis if not provided.
+  pub fn with_ellipsis(mut self, ellipsis: InputRun) -> Self {
+    self.ellipsis = Some(ellipsis);
+    self
+  }
+
   pub fn typography_all(mut self) -> VisualInfos {
+    let max_lines = self.overflow.max_lines();
     while let Some(p) = self.inputs.next() {
       self.consume_paragraph(p);
+      if max_lines.is_some_and(|max| self.visual_lines.len() > max) {
+        break;
+      }
     }
+    self.truncate_lines();
 
     if self.line_dir.is_reverse() {
       self.visual_lines.reverse();
@@ -212,6 +230,12 @@ where
     let base = run.range.start as u32;
     let line_offset = (self.line_height - em) / 2.;
     let is_auto_wrap = self.overflow.is_auto_wrap();
+    if self.overflow.max_lines().is_some() {
+      let graphemes = text
+        .grapheme_indices(true)
+        .map(|(i, _)| i + base as usize);
+      self.graphemes.extend(graphemes);
+    }
 
     let new_line = |this: &mut Self, cursor: &mut dyn InlineCursor| {
       this.end_line();
@@ -253,6 +277,183 @@ where
     }
   }
 
+  /// Drop the lines over the maximum lines, and replace the truncated text
+  /// with the ellipsis.
+  fn truncate_lines(&mut self) {
+    let Some(max_lines) = self.overflow.max_lines() else { return };
+    let mode =
+      if let TextOverflow::Ellipsis(mode) = self.overflow { mode } else { EllipsisMode::End };
+    self.graphemes.sort_unstable();
+    let bound = if self.line_dir.is_horizontal() { self.bounds.height } else { self.bounds.width };
+
+    if self.visual_lines.len() > max_lines {
+      // The text after the last line is truncated, so the ellipsis ends it.
+      self.visual_lines.truncate(max_lines);
+      self.over_bounds = true;
+      self.ellipsis_line(max_lines - 1, EllipsisMode::End, bound, true);
+    } else {
+      for idx in 0..self.visual_lines.len() {
+        if bound < self.visual_lines[idx].line_extent(self.line_dir) {
+          self.ellipsis_line(idx, mode, bound, false);
+        }
+      }
+    }
+  }
+
+  /// Remove the glyphs of the line in the logical order of the `mode` until the
+  /// line with the ellipsis fits the `bound`, the ellipsis takes the place of
+  /// the removed glyph next to the kept text in the visual order. If `force`,
+  /// the ellipsis is placed even if the line fits.
+  fn ellipsis_line(&mut self, idx: usize, mode: EllipsisMode, bound: GlyphUnit, force: bool) {
+    let hor_line = self.line_dir.is_horizontal();
+    let advance = |g: &Glyph| if hor_line { g.y_advance } else { g.x_advance };
+    let ellipsis = self.ellipsis_glyphs();
+    let ellipsis_width = ellipsis
+      .iter()
+      .fold(GlyphUnit::ZERO, |acc, g| acc + advance(g));
+
+    let line = &mut self.visual_lines[idx];
+    let extent = line.line_extent(self.line_dir);
+
+    // The clusters of the line in the logical order with their advances.
+    let mut clusters: Vec<(u32, GlyphUnit)> = vec![];
+    let mut sorted = line.glyphs.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|g| g.cluster);
+    for g in sorted {
+      match clusters.last_mut() {
+        Some((c, width)) if *c == g.cluster => *width += advance(g),
+        _ => clusters.push((g.cluster, advance(g))),
+      }
+    }
+
+    let n = clusters.len();
+    let (mut lo, mut hi) = match mode {
+      EllipsisMode::End => (n, n),
+      EllipsisMode::Middle => (n / 2, n / 2),
+      EllipsisMode::Start => (0, 0),
+    };
+    let mut kept = clusters
+      .iter()
+      .fold(GlyphUnit::ZERO, |acc, (_, w)| acc + *w);
+    let mut grow_end = false;
+    while bound - ellipsis_width < kept && (0 < lo || hi < n) {
+      grow_end = match mode {
+        EllipsisMode::End => false,
+        EllipsisMode::Middle => (!grow_end && hi < n) || lo == 0,
+        EllipsisMode::Start => true,
+      };
+      if grow_end {
+        kept -= clusters[hi].1;
+        hi += 1;
+      } else {
+        lo -= 1;
+        kept -= clusters[lo].1;
+      }
+    }
+    if lo == hi && !force {
+      return;
+    }
+
+    // Only truncate the text at the grapheme boundaries.
+    let graphemes = &self.graphemes;
+    let start = clusters.get(lo).map_or(u32::MAX, |(c, _)| {
+      let i = graphemes.partition_point(|g| *g <= *c as usize);
+      i.checked_sub(1)
+        .map_or(*c, |i| graphemes[i] as u32)
+    });
+    let end = clusters.get(hi).map_or(u32::MAX, |(c, _)| {
+      let i = graphemes.partition_point(|g| *g < *c as usize);
+      graphemes.get(i).map_or(u32::MAX, |g| *g as u32)
+    });
+    let removed = |g: &Glyph| start <= g.cluster && g.cluster < end;
+
+    let removed_clusters = line
+      .glyphs
+      .iter()
+      .filter(|g| removed(g))
+      .map(|g| g.cluster);
+    let designated =
+      if mode == EllipsisMode::Start { removed_clusters.max() } else { removed_clusters.min() };
+    // If nothing removed, the ellipsis follows the logical end of the line.
+    let slot = match designated {
+      Some(c) => line.glyphs.iter().position(|g| g.cluster == c),
+      None => {
+        let ltr = line
+          .glyphs
+          .first()
+          .zip(line.glyphs.last())
+          .is_none_or(|(first, last)| first.cluster <= last.cluster);
+        if ltr { None } else { Some(0) }
+      }
+    };
+    let cluster = designated
+      .or(clusters.last().map(|(c, _)| *c))
+      .unwrap_or_default();
+
+    let mut glyphs = Vec::with_capacity(line.glyphs.len() + ellipsis.len());
+    let mut shift = GlyphUnit::ZERO;
+    let place_ellipsis = |glyphs: &mut Vec<Glyph>, mut pos: GlyphUnit| {
+      for e in ellipsis.iter() {
+        let mut e = e.clone();
+        e.cluster = cluster;
+        if hor_line {
+          e.y_offset += pos
+        } else {
+          e.x_offset += pos
+        }
+        pos += advance(&e);
+        glyphs.push(e);
+      }
+    };
+    for (i, mut g) in std::mem::take(&mut line.glyphs)
+      .into_iter()
+      .enumerate()
+    {
+      let is_removed = removed(&g);
+      let advance = advance(&g);
+      let pos = if hor_line { &mut g.y_offset } else { &mut g.x_offset };
+      if slot == Some(i) {
+        place_ellipsis(&mut glyphs, *pos + shift);
+        shift += ellipsis_width;
+      }
+      if is_removed {
+        shift -= advance;
+      } else {
+        *pos += shift;
+        glyphs.push(g);
+      }
+    }
+    if slot.is_none() {
+      place_ellipsis(&mut glyphs, extent + shift);
+      shift += ellipsis_width;
+    }
+    line.glyphs = glyphs;
+    let extent = extent + shift;
+    if hor_line { line.height = extent } else { line.width = extent }
+  }
+
+  /// The ellipsis glyphs placed at the start of the line.
+  fn ellipsis_glyphs(&self) -> Vec<Glyph> {
+    let Some(run) = self.ellipsis.as_ref() else { return vec![] };
+    let font_size = run.font_size_factor * GlyphUnit::PIXELS_PER_EM as f32;
+    let line_offset = (self.line_height - GlyphUnit::from_pixel(font_size)) / 2.;
+    let hor_line = self.line_dir.is_horizontal();
+    run
+      .shape_result
+      .glyphs
+      .iter()
+      .map(|g| {
+        let mut g = g.clone().cast_to(font_size);
+        if hor_line {
+          g.x_offset += line_offset
+        } else {
+          g.y_offset += line_offset
+        }
+        g
+      })
+      .collect()
+  }
+
   fn push_glyph(&mut self, g: Glyph) {
     let line = self.visual_lines.last_mut();
     line.unwrap().glyphs.push(g)
@@ -420,6 +621,11 @@ impl VisualLine {
     if line_dir.is_horizontal() { self.width } else { self.height }
   }
 
+  /// The size of the line in the direction that the glyphs are placed.
+  pub fn line_extent(&self, line_dir: PlaceLineDirection) -> GlyphUnit {
+    if line_dir.is_horizontal() { self.height } else { self.width }
+  }
+
   pub fn glyphs_iter(&self, hor_line: bool) -> impl DoubleEndedIterator<Item = Glyph> + '_ {
     self.glyphs.iter().map(move |g| {
       let mut g = g.clone();
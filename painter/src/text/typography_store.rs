@@ -3,6 +3,7 @@ use std::{cell::RefCell, ops::Range};
 use font_db::GlyphBaseline;
 use ribir_algo::{FrameCache, Sc, Substr};
 use ribir_geom::{Point, Rect, Size};
+use smallvec::{SmallVec, smallvec};
 
 use crate::{
   font_db::FontDB,
@@ -15,6 +16,8 @@ use crate::{
 #[derive(Clone, PartialEq, Eq, Hash)]
 struct RunKey {
   pub ids: Box<[ID]>,
+  /// The font size relative to the standard font size.
+  pub font_size: GlyphUnit,
   pub line_height: GlyphUnit,
   pub letter_space: GlyphUnit,
   pub text: Substr,
@@ -92,6 +95,19 @@ impl TypographyStore {
   pub fn typography(
     &mut self, text: Substr, style: &TextStyle, bounds: Size, text_align: TextAlign,
     baseline: GlyphBaseline, line_dir: PlaceLineDirection,
+  ) -> VisualGlyphs {
+    self.typography_with_spans(text, &[], style, bounds, text_align, baseline, line_dir)
+  }
+
+  /// Do the typography of the text with the spans of different styles as one
+  /// paragraph, the `style` is the style of the text that no span covers.
+  ///
+  /// The spans that change the font, the size or the letter space are shaped
+  /// in their own runs, the other styles of the spans only affect the painting.
+  #[allow(clippy::too_many_arguments)]
+  pub fn typography_with_spans(
+    &mut self, text: Substr, spans: &[TextSpan], style: &TextStyle, bounds: Size,
+    text_align: TextAlign, baseline: GlyphBaseline, line_dir: PlaceLineDirection,
   ) -> VisualGlyphs {
     let TextStyle { font_size, ref font_face, letter_space, line_height, overflow } = *style;
     // Since we cache the result of the standard font size, we must ensure that all
@@ -101,43 +117,120 @@ impl TypographyStore {
       GlyphUnit::from_pixel(bounds.width / scale),
       GlyphUnit::from_pixel(bounds.height / scale),
     );
-    let letter_space =
-      GlyphUnit::from_pixel(letter_space / font_size * GlyphUnit::PIXELS_PER_EM as f32);
-    let line_height =
-      GlyphUnit::from_pixel(line_height / font_size * GlyphUnit::PIXELS_PER_EM as f32);
+    let to_em = |v: f32| GlyphUnit::from_pixel(v / font_size * GlyphUnit::PIXELS_PER_EM as f32);
 
     let info = self.reorder.reorder_text(&text).clone();
-    let ids = self
-      .font_db
-      .borrow_mut()
-      .select_all_match(font_face)
-      .into_boxed_slice();
-    let runs = [RunKey { ids, line_height, letter_space, text }].into();
-    let key = TypographyKey::new(runs, bounds, text_align, line_dir, overflow, baseline);
+    let (ranges, runs, base_ids) = {
+      let mut font_db = self.font_db.borrow_mut();
+      let base_ids: Box<[ID]> = font_db
+        .select_all_match(font_face)
+        .into_boxed_slice();
+      let base_run = |rg: Range<usize>| RunKey {
+        ids: base_ids.clone(),
+        font_size: GlyphUnit::STANDARD_EM,
+        line_height: to_em(line_height),
+        letter_space: to_em(letter_space),
+        text: text.substr(rg),
+      };
+
+      let mut ranges = vec![];
+      let mut runs = vec![];
+      let mut pos = 0;
+      for span in spans.iter().filter(|s| !s.is_paint_only()) {
+        let rg = span.range.start.max(pos)..span.range.end.min(text.len());
+        if rg.is_empty() {
+          continue;
+        }
+        if pos < rg.start {
+          ranges.push(pos..rg.start);
+          runs.push(base_run(pos..rg.start));
+        }
+        let span_style = &span.style;
+        let size = span_style.font_size.unwrap_or(font_size);
+        let ids = font_db
+          .select_all_match(&span_style.font_face(font_face))
+          .into_boxed_slice();
+        runs.push(RunKey {
+          ids,
+          font_size: to_em(size),
+          line_height: to_em(line_height * size / font_size),
+          letter_space: to_em(span_style.letter_space.unwrap_or(letter_space)),
+          text: text.substr(rg.clone()),
+        });
+        pos = rg.end;
+        ranges.push(rg);
+      }
+      if pos < text.len() || runs.is_empty() {
+        ranges.push(pos..text.len());
+        runs.push(base_run(pos..text.len()));
+      }
+      (ranges, runs, base_ids)
+    };
+
+    let key = TypographyKey::new(runs.into(), bounds, text_align, line_dir, overflow, baseline);
     let infos = if let Some(infos) = self.cache.get(&key).cloned() {
       infos
     } else {
-      let ids = &key.runs[0].ids;
-      let text = &key.runs[0].text;
+      let line_height = key
+        .runs
+        .iter()
+        .fold(GlyphUnit::ZERO, |h, r| h.max(r.line_height));
+      let ellipsis = overflow.max_lines().map(|_| {
+        let ellipsis: Substr = "…".into();
+        let dir = TextDirection::LeftToRight;
+        let shape_result = self
+          .shaper
+          .shape_text(&ellipsis, &base_ids, dir, baseline);
+        InputRun::new(shape_result, 1., to_em(letter_space), 0..ellipsis.len())
+      });
       let inputs = info.paras.iter().map(|p| {
         p.runs
           .iter()
-          .map(|r| {
+          .flat_map(|r| {
             let dir = if r.is_empty() || p.levels[r.start].is_ltr() {
               TextDirection::LeftToRight
             } else {
               TextDirection::RightToLeft
             };
 
-            let shape_result = self
-              .shaper
-              .shape_text(&text.substr(r.clone()), ids, dir, baseline);
-            InputRun::new(shape_result, 1., letter_space, r.clone())
+            let pieces: SmallVec<[_; 1]> = if r.is_empty() {
+              let idx = ranges
+                .partition_point(|rg| rg.end < r.start)
+                .min(ranges.len() - 1);
+              smallvec![(r.clone(), &key.runs[idx])]
+            } else {
+              ranges
+                .iter()
+                .zip(key.runs.iter())
+                .map(|(rg, run)| (rg.start.max(r.start)..rg.end.min(r.end), run))
+                .filter(|(piece, _)| !piece.is_empty())
+                .collect()
+            };
+            let mut pieces: SmallVec<[InputRun; 1]> = pieces
+              .into_iter()
+              .map(|(piece, run)| {
+                let text = text.substr(piece.clone());
+                let shape_result = self
+                  .shaper
+                  .shape_text(&text, &run.ids, dir, baseline);
+                let factor = run.font_size.into_pixel() / GlyphUnit::PIXELS_PER_EM as f32;
+                InputRun::new(shape_result, factor, run.letter_space, piece)
+              })
+              .collect();
+            // The spans in a right-to-left run are placed from right to left.
+            if dir == TextDirection::RightToLeft {
+              pieces.reverse();
+            }
+            pieces
           })
           .collect()
       });
 
-      let t_man = TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow);
+      let mut t_man =
+        TypographyMan::new(inputs, line_dir, text_align, line_height, bounds, overflow);
+      if let Some(ellipsis) = ellipsis {
+        t_man = t_man.with_ellipsis(ellipsis);
+      }
       let visual_info = t_man.typography_all();
       let infos = Sc::new(visual_info);
       self.cache.put(key, infos.clone());
@@ -272,6 +365,22 @@ impl VisualGlyphs {
     (line_para, offset)
   }
 
+  /// Return the cluster of the glyph under the position, or `None` if the
+  /// position is not on any glyph.
+  pub fn glyph_cluster_at(&self, x: f32, y: f32) -> Option<usize> {
+    let (row, col) = self.nearest_glyph(x, y);
+    let glyph = self
+      .visual_info
+      .visual_lines
+      .get(row)?
+      .glyphs
+      .get(col)?;
+    self
+      .glyph_rect(row, col)
+      .contains(Point::new(x, y))
+      .then_some(glyph.cluster as usize)
+  }
+
   pub fn position_to_cluster(&self, row: usize, col: usize) -> usize {
     let lines = &self.visual_info.visual_lines;
 
@@ -471,12 +580,8 @@ impl TypographyKey {
       // width. The wider one can use for the narrower one. S
       TextOverflow::Overflow => GlyphUnit::MAX,
 
-      TextOverflow::AutoWrap => {
-        if line_dir.is_horizontal() {
-          bounds.height
-        } else {
-          bounds.width
-        }
+      TextOverflow::AutoWrap | TextOverflow::Ellipsis(_) | TextOverflow::MaxLines(_) => {
+        if line_dir.is_horizontal() { bounds.height } else { bounds.width }
       }
     };
 
@@ -831,4 +936,156 @@ mod tests {
     );
     assert_eq!(1, store.cache.len());
   }
+
+  #[test]
+  fn mixed_style_spans() {
+    let mut store = test_store();
+    let style = zero_letter_space_style(16., TextOverflow::Overflow);
+    let text: Substr = "aaabbb".into();
+    let plain = store.typography(
+      text.clone(),
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    let spans = [TextSpan {
+      range: 3..6,
+      style: SpanStyle::default().with_font_size(32.),
+      link: Some("https://ribir.org".into()),
+    }];
+    let rich = store.typography_with_spans(
+      text,
+      &spans,
+      &style,
+      Size::new(f32::MAX, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    );
+
+    let plain_rect = plain.visual_rect();
+    let rich_rect = rich.visual_rect();
+    // The bigger span doubles its width and the line height.
+    assert!((rich_rect.width() - plain_rect.width() * 1.5).abs() < 1.);
+    assert_eq!(rich_rect.height(), plain_rect.height() * 2.);
+
+    let first = plain.glyph_rect(0, 1);
+    assert_eq!(rich.glyph_cluster_at(first.center().x, rich_rect.height() - 1.), Some(1));
+    let span_glyph = rich.glyph_rect(0, 4);
+    let cluster = rich.glyph_cluster_at(span_glyph.center().x, span_glyph.center().y);
+    assert_eq!(cluster, Some(4));
+    let link = cluster.and_then(|c| TextSpan::find(&spans, c)?.link.clone());
+    assert_eq!(link.as_deref(), Some("https://ribir.org"));
+    assert_eq!(rich.glyph_cluster_at(rich_rect.width() + 10., 1.), None);
+  }
+
+  fn line_clusters(glyphs: &VisualGlyphs, row: usize) -> Vec<u32> {
+    glyphs.visual_info.visual_lines[row]
+      .glyphs
+      .iter()
+      .map(|g| g.cluster)
+      .collect()
+  }
+
+  fn ellipsis_typography(text: &'static str, overflow: TextOverflow, width: f32) -> VisualGlyphs {
+    let mut store = test_store();
+    let style = zero_letter_space_style(16., overflow);
+    store.typography(
+      text.into(),
+      &style,
+      Size::new(width, f32::MAX),
+      TextAlign::Start,
+      GlyphBaseline::Alphabetic,
+      PlaceLineDirection::TopToBottom,
+    )
+  }
+
+  #[test]
+  fn text_ellipsis() {
+    let text = "Hello world";
+    let full = ellipsis_typography(text, TextOverflow::Overflow, 60.);
+    let full_width = full.visual_rect().width();
+    assert!(full_width > 60.);
+
+    let end = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::End), 60.);
+    assert!(end.visual_rect().width() <= 60.);
+    let clusters = line_clusters(&end, 0);
+    let ellipsis = end.visual_info.visual_lines[0]
+      .glyphs
+      .last()
+      .unwrap();
+    assert_ne!(ellipsis.glyph_id.0, 0);
+    // The ellipsis takes the cluster of the first truncated char.
+    assert_eq!(&clusters[..clusters.len() - 1], &(0..ellipsis.cluster).collect::<Vec<_>>());
+
+    let start = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::Start), 60.);
+    assert!(start.visual_rect().width() <= 60.);
+    let clusters = line_clusters(&start, 0);
+    assert_eq!(*clusters.last().unwrap(), 10);
+    assert!(clusters[1] > 0);
+    let start_ellipsis = &start.visual_info.visual_lines[0].glyphs[0];
+    assert_eq!(start_ellipsis.glyph_id, ellipsis.glyph_id);
+
+    let middle = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::Middle), 60.);
+    assert!(middle.visual_rect().width() <= 60.);
+    let clusters = line_clusters(&middle, 0);
+    assert_eq!(clusters[0], 0);
+    assert_eq!(*clusters.last().unwrap(), 10);
+    assert!(clusters.len() < text.len());
+
+    // The text fits the line is not truncated.
+    let fit = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::End), 200.);
+    assert_eq!(line_clusters(&fit, 0), (0..11).collect::<Vec<_>>());
+  }
+
+  #[test]
+  fn rtl_ellipsis() {
+    let text = "שלום עולם";
+    let end = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::End), 40.);
+    assert!(end.visual_rect().width() <= 40.);
+    let line = &end.visual_info.visual_lines[0];
+    // The logical end of a right-to-left text is on the left.
+    let left = &line.glyphs[0];
+    assert!(
+      line.glyphs[1..]
+        .iter()
+        .all(|g| g.cluster < left.cluster)
+    );
+    assert_eq!(line.glyphs.last().unwrap().cluster, 0);
+  }
+
+  #[test]
+  fn max_lines() {
+    let text = "aaa bbb ccc ddd eee";
+    let wrap = ellipsis_typography(text, TextOverflow::AutoWrap, 40.);
+    assert!(wrap.glyph_row_count() > 2);
+
+    let glyphs = ellipsis_typography(text, TextOverflow::MaxLines(2), 40.);
+    assert_eq!(glyphs.glyph_row_count(), 2);
+    assert!(glyphs.visual_rect().width() <= 40.);
+    assert_eq!(line_clusters(&glyphs, 0), line_clusters(&wrap, 0));
+    let last = glyphs.visual_info.visual_lines[1]
+      .glyphs
+      .last()
+      .unwrap();
+    let end = ellipsis_typography(text, TextOverflow::Ellipsis(EllipsisMode::End), 40.);
+    let ellipsis = end.visual_info.visual_lines[0]
+      .glyphs
+      .last()
+      .unwrap();
+    assert_eq!(last.glyph_id, ellipsis.glyph_id);
+
+    let paragraphs =
+      ellipsis_typography("aaa\nbbb", TextOverflow::Ellipsis(EllipsisMode::End), 200.);
+    assert_eq!(paragraphs.glyph_row_count(), 1);
+    assert!(
+      paragraphs.visual_rect().width()
+        > ellipsis_typography("aaa", TextOverflow::Overflow, 200.)
+          .visual_rect()
+          .width()
+    );
+  }
 }
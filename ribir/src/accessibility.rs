@@ -0,0 +1,362 @@
+//! Bridge the accessibility tree of the windows to the accessibility API of the
+//! platform.
+use std::{
+  collections::HashMap,
+  sync::{Arc, Mutex},
+};
+
+use accesskit::{
+  Action, ActionRequest, Affine, Checked, Node, NodeBuilder, NodeClassSet, NodeId, Tree, TreeUpdate,
+};
+use ribir_core::{
+  accessibility::{AccessNode, AccessibilityTree},
+  prelude::{Role, SemanticsAction, Toggled, WidgetId},
+  window::WindowId,
+};
+use winit::{event::WindowEvent, window::Window};
+
+use crate::prelude::{App, AppEvent, EventSender};
+
+/// An adapter bridges the accessibility tree of a window to the accessibility
+/// API of the platform.
+///
+/// Every window uses an [AccessKit](https://accesskit.dev) adapter by default,
+/// an application can replace it by registering a factory with
+/// [`App::set_accessibility_adapter`](crate::prelude::App::set_accessibility_adapter).
+/// The adapter is created before the window becomes visible.
+///
+/// When the platform requests an action, the adapter should send an
+/// [`AppEvent::SemanticsAction`](crate::prelude::AppEvent::SemanticsAction) by
+/// the [`App::event_sender`](crate::prelude::App::event_sender), and the
+/// application performs the action in the event loop.
+pub trait AccessibilityAdapter {
+  /// Receive the latest accessibility tree of the window after a drawn frame
+  /// changes it.
+  fn update_tree(&mut self, tree: &AccessibilityTree);
+
+  /// Process the native event of the window before the framework does.
+  fn process_event(&mut self, _window: &Window, _event: &WindowEvent) {}
+}
+
+/// The factory to create the accessibility adapter of a window.
+pub type AccessibilityAdapterFactory =
+  Box<dyn Fn(WindowId, &Window) -> Box<dyn AccessibilityAdapter>>;
+
+/// The node id of the window, it's the root of the AccessKit tree.
+const ROOT_ID: NodeId = NodeId(0);
+
+/// The default adapter, it maps the accessibility tree to an AccessKit tree.
+pub(crate) struct AccessKitAdapter {
+  adapter: accesskit_winit::Adapter,
+  shared: Arc<Mutex<SharedTree>>,
+  classes: NodeClassSet,
+  scale_factor: f64,
+  /// The last tree, it's resent when the scale factor changes.
+  tree: Option<AccessibilityTree>,
+}
+
+/// The state shared with the thread that AccessKit calls back on.
+#[derive(Default)]
+struct SharedTree {
+  ids: NodeIds,
+  /// The full tree of the last update, to initialize the platform adapter.
+  update: Option<TreeUpdate>,
+}
+
+/// The AccessKit node ids of the widgets, a widget keeps its id as long as it
+/// stays in the tree.
+#[derive(Default)]
+struct NodeIds {
+  nodes: HashMap<WidgetId, NodeId>,
+  widgets: HashMap<NodeId, WidgetId>,
+  last: u64,
+}
+
+struct ActionHandler {
+  wnd_id: WindowId,
+  shared: Arc<Mutex<SharedTree>>,
+  sender: EventSender,
+}
+
+impl AccessKitAdapter {
+  pub(crate) fn new(wnd_id: WindowId, window: &Window) -> Self {
+    let shared = Arc::new(Mutex::new(SharedTree::default()));
+    let source = {
+      let shared = shared.clone();
+      move || {
+        let update = shared.lock().unwrap().update.clone();
+        update.unwrap_or_else(|| {
+          let root = NodeBuilder::new(accesskit::Role::Window).build(&mut NodeClassSet::new());
+          TreeUpdate {
+            nodes: vec![(ROOT_ID, root)],
+            tree: Some(Tree::new(ROOT_ID)),
+            focus: ROOT_ID,
+          }
+        })
+      }
+    };
+    let handler = ActionHandler { wnd_id, shared: shared.clone(), sender: App::event_sender() };
+    let adapter = accesskit_winit::Adapter::with_action_handler(window, source, Box::new(handler));
+    Self {
+      adapter,
+      shared,
+      classes: NodeClassSet::new(),
+      scale_factor: window.scale_factor(),
+      tree: None,
+    }
+  }
+}
+
+impl AccessibilityAdapter for AccessKitAdapter {
+  fn update_tree(&mut self, tree: &AccessibilityTree) {
+    let update = {
+      let mut shared = self.shared.lock().unwrap();
+      let update = shared
+        .ids
+        .tree_update(tree, self.scale_factor, &mut self.classes);
+      shared.update = Some(update.clone());
+      update
+    };
+    self.tree = Some(tree.clone());
+    self.adapter.update_if_active(|| update);
+  }
+
+  fn process_event(&mut self, window: &Window, event: &WindowEvent) {
+    self.adapter.process_event(window, event);
+    if let WindowEvent::ScaleFactorChanged { scale_factor, .. } = event {
+      self.scale_factor = *scale_factor;
+      if let Some(tree) = self.tree.take() {
+        self.update_tree(&tree);
+      }
+    }
+  }
+}
+
+impl accesskit::ActionHandler for ActionHandler {
+  fn do_action(&mut self, request: ActionRequest) {
+    let Some(action) = semantics_action(request.action) else { return };
+    let id = self
+      .shared
+      .lock()
+      .unwrap()
+      .ids
+      .widgets
+      .get(&request.target)
+      .copied();
+    if let Some(id) = id {
+      self
+        .sender
+        .send(AppEvent::SemanticsAction { wnd_id: self.wnd_id, id, action });
+    }
+  }
+}
+
+impl NodeIds {
+  /// Convert the accessibility tree to a full AccessKit tree, the rects of the
+  /// nodes are scaled to the physical pixels.
+  fn tree_update(
+    &mut self, tree: &AccessibilityTree, scale_factor: f64, classes: &mut NodeClassSet,
+  ) -> TreeUpdate {
+    let mut old = std::mem::take(&mut self.nodes);
+    old.remove(&tree.root.id);
+    self.nodes.insert(tree.root.id, ROOT_ID);
+
+    let mut nodes = vec![];
+    let mut root = self.build(&tree.root, &mut old, classes, &mut nodes);
+    root.set_transform(Affine::scale(scale_factor));
+    nodes.push((ROOT_ID, root.build(classes)));
+
+    self.widgets = self
+      .nodes
+      .iter()
+      .map(|(wid, id)| (*id, *wid))
+      .collect();
+    let focus = tree
+      .focus
+      .and_then(|id| self.nodes.get(&id).copied())
+      .unwrap_or(ROOT_ID);
+    TreeUpdate { nodes, tree: Some(Tree::new(ROOT_ID)), focus }
+  }
+
+  fn build(
+    &mut self, node: &AccessNode, old: &mut HashMap<WidgetId, NodeId>, classes: &mut NodeClassSet,
+    nodes: &mut Vec<(NodeId, Node)>,
+  ) -> NodeBuilder {
+    let mut builder = NodeBuilder::new(accesskit_role(node.role));
+    if let Some(label) = &node.label {
+      builder.set_name(&**label);
+    }
+    if let Some(value) = &node.value {
+      builder.set_value(&**value);
+    }
+    if let Some(checked) = node.checked {
+      builder.set_checked(match checked {
+        Toggled::False => Checked::False,
+        Toggled::True => Checked::True,
+        Toggled::Mixed => Checked::Mixed,
+      });
+    }
+    if let Some(expanded) = node.expanded {
+      builder.set_expanded(expanded);
+    }
+    if let Some(selected) = node.selected {
+      builder.set_selected(selected);
+    }
+    if node.disabled {
+      builder.set_disabled();
+    }
+    for action in node.actions.iter() {
+      builder.add_action(accesskit_action(*action));
+    }
+    let (min, max) = (node.rect.min(), node.rect.max());
+    builder.set_bounds(accesskit::Rect::new(
+      min.x as f64,
+      min.y as f64,
+      max.x as f64,
+      max.y as f64,
+    ));
+
+    let mut children = Vec::with_capacity(node.children.len());
+    for c in node.children.iter() {
+      let id = old.remove(&c.id).unwrap_or_else(|| {
+        self.last += 1;
+        NodeId(self.last)
+      });
+      self.nodes.insert(c.id, id);
+      let child = self.build(c, old, classes, nodes);
+      nodes.push((id, child.build(classes)));
+      children.push(id);
+    }
+    builder.set_children(children);
+    builder
+  }
+}
+
+fn accesskit_role(role: Role) -> accesskit::Role {
+  use accesskit::Role as A;
+  match role {
+    Role::Generic => A::GenericContainer,
+    Role::Window => A::Window,
+    Role::Button => A::Button,
+    Role::Checkbox => A::CheckBox,
+    Role::Radio => A::RadioButton,
+    Role::Switch => A::Switch,
+    Role::Slider => A::Slider,
+    Role::ProgressIndicator => A::ProgressIndicator,
+    Role::TextInput => A::TextInput,
+    Role::MultilineTextInput => A::MultilineTextInput,
+    Role::ComboBox => A::ComboBox,
+    Role::StaticText => A::StaticText,
+    Role::Link => A::Link,
+    Role::Image => A::Image,
+    Role::List => A::List,
+    Role::ListItem => A::ListItem,
+    Role::ListBox => A::ListBox,
+    Role::ListBoxOption => A::ListBoxOption,
+    Role::Tab => A::Tab,
+    Role::TabList => A::TabList,
+    Role::TabPanel => A::TabPanel,
+    Role::Menu => A::Menu,
+    Role::MenuItem => A::MenuItem,
+    Role::Dialog => A::Dialog,
+    Role::ScrollView => A::ScrollView,
+  }
+}
+
+fn accesskit_action(action: SemanticsAction) -> Action {
+  match action {
+    SemanticsAction::Click => Action::Default,
+    SemanticsAction::Focus => Action::Focus,
+    SemanticsAction::Increment => Action::Increment,
+    SemanticsAction::Decrement => Action::Decrement,
+    SemanticsAction::Expand => Action::Expand,
+    SemanticsAction::Collapse => Action::Collapse,
+    SemanticsAction::ScrollIntoView => Action::ScrollIntoView,
+  }
+}
+
+fn semantics_action(action: Action) -> Option<SemanticsAction> {
+  let action = match action {
+    Action::Default => SemanticsAction::Click,
+    Action::Focus => SemanticsAction::Focus,
+    Action::Increment => SemanticsAction::Increment,
+    Action::Decrement => SemanticsAction::Decrement,
+    Action::Expand => SemanticsAction::Expand,
+    Action::Collapse => SemanticsAction::Collapse,
+    Action::ScrollIntoView => SemanticsAction::ScrollIntoView,
+    _ => return None,
+  };
+  Some(action)
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+
+  use super::*;
+
+  #[test]
+  fn map_to_accesskit_tree() {
+    reset_test_env!();
+
+    let (checked, w_checked) = split_value(false);
+    let mut wnd = TestWindow::new(fn_widget! {
+      @MockMulti {
+        @MockBox {
+          size: Size::new(50., 20.),
+          semantics: pipe!(
+            Semantics::new(Role::Checkbox)
+              .with_label("Agree")
+              .with_checked(if *$checked { Toggled::True } else { Toggled::False })
+              .with_action(SemanticsAction::Click)
+          ),
+        }
+        @MockBox {
+          size: Size::new(50., 20.),
+          semantics: Semantics::new(Role::Button).with_label("Ok"),
+        }
+      }
+    });
+    wnd.draw_frame();
+
+    let mut ids = NodeIds::default();
+    let mut classes = NodeClassSet::new();
+    let update = ids.tree_update(&wnd.accessibility_tree(), 2., &mut classes);
+    assert_eq!(update.tree, Some(Tree::new(ROOT_ID)));
+    assert_eq!(update.focus, ROOT_ID);
+    let node = |update: &TreeUpdate, name: &str| {
+      update
+        .nodes
+        .iter()
+        .find(|(_, n)| n.name() == Some(name))
+        .map(|(id, n)| (*id, n.clone()))
+        .unwrap()
+    };
+    let (agree, node_agree) = node(&update, "Agree");
+    assert_eq!(node_agree.role(), accesskit::Role::CheckBox);
+    assert_eq!(node_agree.checked(), Some(Checked::False));
+    assert!(node_agree.supports_action(Action::Default));
+    let (ok, node_ok) = node(&update, "Ok");
+    assert_eq!(node_ok.bounds(), Some(accesskit::Rect::new(50., 0., 100., 20.)));
+    let (_, root) = update
+      .nodes
+      .iter()
+      .find(|(id, _)| *id == ROOT_ID)
+      .unwrap();
+    assert_eq!(root.children(), [agree, ok]);
+    assert_eq!(root.transform(), Some(&Affine::scale(2.)));
+
+    // The action request maps back to the widget.
+    let tree = wnd.accessibility_tree();
+    assert_eq!(ids.widgets.get(&agree).copied(), tree.find_by_label("Agree").map(|n| n.id));
+
+    // The nodes keep their ids after the tree changes.
+    *w_checked.write() = true;
+    wnd.draw_frame();
+    let update = ids.tree_update(&wnd.accessibility_tree(), 2., &mut classes);
+    let (id, node_agree) = node(&update, "Agree");
+    assert_eq!(id, agree);
+    assert_eq!(node_agree.checked(), Some(Checked::True));
+    assert_eq!(node(&update, "Ok").0, ok);
+  }
+}
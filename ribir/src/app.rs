@@ -7,6 +7,7 @@ use winit::{
 };
 
 use crate::{
+  accessibility::{AccessKitAdapter, AccessibilityAdapter, AccessibilityAdapterFactory},
   register_platform_app_events_handlers,
   winit_shell_wnd::{WinitShellWnd, new_id},
 };
@@ -19,6 +20,7 @@ pub struct App {
   #[cfg(not(target_family = "wasm"))]
   active_wnd: std::cell::Cell<Option<WindowId>>,
   events_stream: MutRefItemSubject<'static, AppEvent, Infallible>,
+  accessibility_adapter: RefCell<Option<AccessibilityAdapterFactory>>,
 }
 
 /// Attributes for creating a new window.
@@ -53,6 +55,8 @@ pub enum AppEvent {
   WndFocusChanged(WindowId, bool),
   /// The custom event, you can send any data with this event.
   Custom(Box<dyn Any + Send>),
+  /// The assistive technologies request an action on a widget of the window.
+  SemanticsAction { wnd_id: WindowId, id: WidgetId, action: SemanticsAction },
 }
 
 /// A sender to send event to the application event loop from which the
@@ -93,6 +97,14 @@ impl App {
         let Some(wnd) = AppCtx::get_window(wnd_id) else {
           return;
         };
+        if let Some(shell) = wnd
+          .shell_wnd()
+          .borrow_mut()
+          .as_any_mut()
+          .downcast_mut::<WinitShellWnd>()
+        {
+          shell.process_accessibility_event(&event);
+        }
         match event {
           WindowEvent::CloseRequested => {
             AppCtx::remove_wnd(wnd_id);
@@ -108,7 +120,7 @@ impl App {
             wnd.draw_frame(true);
           }
           WindowEvent::RedrawRequested => {
-            AppCtx::frame_ticks().clone().next(Instant::now());
+            AppCtx::frame_ticks().clone().next(AppCtx::now());
 
             if let Some(wnd) = AppCtx::get_window(wnd_id) {
               // if the window is not visible, don't draw it./
@@ -150,6 +162,22 @@ impl App {
             }
             wnd.process_mouse_input(device_id, state, button);
           }
+          WindowEvent::Touch(touch) => {
+            if touch.phase == TouchPhase::Started {
+              wnd.force_exit_pre_edit()
+            }
+            let pos = touch
+              .location
+              .to_logical::<f32>(wnd.device_pixel_ratio() as f64);
+            wnd.process_touch(touch.id, touch.phase, Point::new(pos.x, pos.y));
+          }
+          WindowEvent::HoveredFile(path) => {
+            wnd.process_external_drag(ExternalData::Files(vec![path]))
+          }
+          WindowEvent::HoveredFileCancelled => wnd.process_external_drag_cancel(),
+          WindowEvent::DroppedFile(path) => {
+            wnd.process_external_drop(ExternalData::Files(vec![path]))
+          }
           #[allow(deprecated)]
           event => wnd.processes_native_event(event),
         }
@@ -169,7 +197,7 @@ impl App {
         if run_count > 0 {
           loop_handle.set_control_flow(ControlFlow::Poll);
         } else if let Some(t) = Timer::recently_timeout() {
-          let control = ControlFlow::wait_duration(t.duration_since(Instant::now()));
+          let control = ControlFlow::wait_duration(t.duration_since(AppCtx::now()));
           loop_handle.set_control_flow(control);
         } else {
           loop_handle.set_control_flow(ControlFlow::Wait);
@@ -178,6 +206,15 @@ impl App {
       Event::NewEvents(StartCause::Poll | StartCause::ResumeTimeReached { .. }) => {
         Timer::wake_timeout_futures()
       }
+      Event::UserEvent(AppEvent::SemanticsAction { wnd_id, id, action }) => {
+        if let Some(wnd) = AppCtx::get_window(wnd_id) {
+          wnd.perform_semantics_action(id, action);
+          wnd.emit_events();
+          if wnd.need_draw() {
+            request_redraw(&wnd)
+          }
+        }
+      }
       Event::UserEvent(mut event) => {
         AppCtx::spawn_local(async move {
           App::shared()
@@ -231,6 +268,25 @@ impl App {
     wnd
   }
 
+  /// Register the factory to create the accessibility adapter for the windows
+  /// created after this call, it overrides the default AccessKit adapter.
+  pub fn set_accessibility_adapter(
+    factory: impl Fn(WindowId, &winit::window::Window) -> Box<dyn AccessibilityAdapter> + 'static,
+  ) {
+    *App::shared().accessibility_adapter.borrow_mut() = Some(Box::new(factory));
+  }
+
+  pub(crate) fn new_accessibility_adapter(
+    wnd: &winit::window::Window,
+  ) -> Box<dyn AccessibilityAdapter> {
+    let id = new_id(wnd.id());
+    let factory = App::shared().accessibility_adapter.borrow();
+    match factory.as_ref() {
+      Some(f) => f(id, wnd),
+      None => Box::new(AccessKitAdapter::new(id, wnd)),
+    }
+  }
+
   /// create a new window with the `root` widget
   #[allow(clippy::await_holding_refcell_ref)]
   pub async fn new_window(root: GenWidget, attrs: WindowAttributes) -> Sc<Window> {
@@ -318,6 +374,7 @@ impl App {
         event_loop_proxy: event_loop.create_proxy(),
         event_loop: RefCell::new(Some(event_loop)),
         events_stream: <_>::default(),
+        accessibility_adapter: RefCell::new(None),
         #[cfg(not(target_family = "wasm"))]
         active_wnd: std::cell::Cell::new(None),
       };
@@ -548,13 +605,15 @@ impl Default for WindowAttributes {
 #[cfg(test)]
 mod tests {
 
-  use ribir_core::{prelude::*, test_helper::*};
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
   use winit::event::Ime;
 
   use super::App;
 
   #[test]
   fn ime_pre_edit() {
+    reset_test_env!();
+
     let log = Stateful::new(vec![]);
     let log2 = log.clone_writer();
 
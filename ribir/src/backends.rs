@@ -1,9 +1,14 @@
-#[cfg(feature = "wgpu")]
+#[cfg(all(feature = "wgpu", not(feature = "cpu")))]
 mod wgpu_backend;
-#[cfg(feature = "wgpu")]
-pub(crate) use wgpu_backend::WgpuBackend as Backend;
+#[cfg(all(feature = "wgpu", not(feature = "cpu")))]
+pub(crate) use wgpu_backend::{WgpuBackend as Backend, render_commands};
 
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(feature = "cpu")]
+mod cpu_backend;
+#[cfg(feature = "cpu")]
+pub(crate) use cpu_backend::{CpuBackend as Backend, render_commands};
+
+#[cfg(not(any(feature = "wgpu", feature = "cpu")))]
 mod mock_backend;
-#[cfg(not(any(feature = "wgpu")))]
+#[cfg(not(any(feature = "wgpu", feature = "cpu")))]
 pub(crate) use mock_backend::MockBackend as Backend;
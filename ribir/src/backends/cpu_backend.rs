@@ -0,0 +1,99 @@
+use std::num::NonZeroU32;
+
+use ribir_core::prelude::{
+  Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Transform,
+  image::ColorFormat,
+};
+use softbuffer::{Context, Surface};
+use winit::window::Window;
+
+use crate::winit_shell_wnd::WinitBackend;
+
+/// Rasterize the frame by the CPU, and blit it to the window by `softbuffer`,
+/// so it works without a GPU.
+pub struct CpuBackend<'a> {
+  surface: Surface<&'a Window, &'a Window>,
+  backend: ribir_cpu::CpuBackend,
+  frame: PixelImage,
+}
+
+impl<'a> WinitBackend<'a> for CpuBackend<'a> {
+  async fn new(window: &'a Window) -> CpuBackend<'a> {
+    let ctx = Context::new(window).expect("Failed to create the softbuffer context.");
+    let surface = Surface::new(&ctx, window).expect("Failed to create the softbuffer surface.");
+    let size = window.inner_size();
+    let size = DeviceSize::new(size.width as i32, size.height as i32);
+
+    let mut cpu = CpuBackend {
+      surface,
+      backend: ribir_cpu::CpuBackend::new(),
+      frame: new_frame(DeviceSize::zero()),
+    };
+    cpu.on_resize(size);
+
+    cpu
+  }
+
+  fn on_resize(&mut self, size: DeviceSize) {
+    if size.width == self.frame.width() as i32 && size.height == self.frame.height() as i32 {
+      return;
+    }
+    self.frame = new_frame(size);
+    let width = NonZeroU32::new(self.frame.width());
+    let height = NonZeroU32::new(self.frame.height());
+    if let (Some(width), Some(height)) = (width, height) {
+      self
+        .surface
+        .resize(width, height)
+        .expect("Failed to resize the softbuffer surface.");
+    }
+  }
+
+  fn begin_frame(&mut self, surface_color: Color) { self.backend.begin_frame(surface_color); }
+
+  fn draw_commands(
+    &mut self, viewport: DeviceRect, global_matrix: &Transform, commands: &[PaintCommand],
+  ) {
+    self
+      .backend
+      .draw_commands(viewport, commands, global_matrix, &mut self.frame);
+  }
+
+  fn end_frame(&mut self) {
+    self.backend.end_frame();
+    if self.frame.width() == 0 || self.frame.height() == 0 {
+      return;
+    }
+
+    // The softbuffer pixels are `0RGB` in native endian.
+    let mut buffer = self
+      .surface
+      .buffer_mut()
+      .expect("Failed to get the softbuffer buffer.");
+    let pixels = self.frame.pixel_bytes().chunks_exact(4);
+    for (dst, src) in buffer.iter_mut().zip(pixels) {
+      *dst = u32::from_be_bytes([0, src[0], src[1], src[2]]);
+    }
+    buffer
+      .present()
+      .expect("Failed to present the frame.");
+  }
+}
+
+/// Render the commands to an image of the `size` by the CPU.
+pub(crate) fn render_commands(
+  size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand], surface: Color,
+) -> PixelImage {
+  let mut img = new_frame(size);
+  let mut backend = ribir_cpu::CpuBackend::new();
+  backend.begin_frame(surface);
+  backend.draw_commands(DeviceRect::from_size(size), commands, global_matrix, &mut img);
+  backend.end_frame();
+  img
+}
+
+fn new_frame(size: DeviceSize) -> PixelImage {
+  let (width, height) = (size.width.max(0) as u32, size.height.max(0) as u32);
+  let data = vec![0; (width * height * 4) as usize];
+  PixelImage::new(data.into(), width, height, ColorFormat::Rgba8)
+}
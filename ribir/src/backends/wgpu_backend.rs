@@ -1,5 +1,10 @@
-use ribir_core::prelude::{Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, Transform};
-use ribir_gpu::Surface;
+use std::sync::Mutex;
+
+use ribir_core::prelude::{
+  AppCtx, Color, DeviceRect, DeviceSize, PaintCommand, PainterBackend, PixelImage, Transform,
+  image::ColorFormat,
+};
+use ribir_gpu::{GPUBackend, GPUBackendImpl, Surface, Texture, WgpuImpl};
 
 use crate::winit_shell_wnd::WinitBackend;
 
@@ -44,3 +49,28 @@ impl<'a> WinitBackend<'a> for WgpuBackend<'a> {
     self.surface.present();
   }
 }
+
+/// Render the commands to an image of the `size` by a headless GPU device.
+pub(crate) fn render_commands(
+  size: DeviceSize, global_matrix: &Transform, commands: &[PaintCommand], surface: Color,
+) -> PixelImage {
+  // Reuse the device for the later rendering, it's expensive to create.
+  static WGPU_IMPL: Mutex<Option<WgpuImpl>> = Mutex::new(None);
+
+  let mut container = WGPU_IMPL.lock().unwrap();
+  let wgpu_impl = container
+    .take()
+    .unwrap_or_else(|| AppCtx::wait_future(WgpuImpl::headless()));
+  let mut backend = GPUBackend::new(wgpu_impl);
+  let rect = DeviceRect::from_size(size);
+  let mut texture = backend
+    .get_impl_mut()
+    .new_texture(size, ColorFormat::Rgba8);
+  backend.begin_frame(surface);
+  backend.draw_commands(rect, commands, global_matrix, &mut texture);
+  let img = texture.copy_as_image(&rect, backend.get_impl_mut());
+  backend.end_frame();
+  *container = Some(backend.into_impl());
+
+  AppCtx::wait_future(img).unwrap()
+}
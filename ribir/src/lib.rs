@@ -1,4 +1,7 @@
 pub use ribir_core as core;
+pub mod accessibility;
+#[cfg(feature = "cpu")]
+pub use ribir_cpu as cpu;
 #[cfg(feature = "widgets")]
 pub use ribir_widgets as widgets;
 pub mod app;
@@ -6,6 +9,8 @@ mod backends;
 
 #[cfg(not(target_arch = "wasm32"))]
 pub mod clipboard;
+#[cfg(any(feature = "wgpu", feature = "cpu"))]
+pub mod offscreen;
 mod winit_shell_wnd;
 #[cfg(feature = "material")]
 pub use ribir_material as material;
@@ -20,4 +25,6 @@ pub mod prelude {
   #[cfg(feature = "widgets")]
   pub use super::widgets::prelude::*;
   pub use crate::app::*;
+  #[cfg(any(feature = "wgpu", feature = "cpu"))]
+  pub use crate::offscreen::*;
 }
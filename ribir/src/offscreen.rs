@@ -0,0 +1,187 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+
+use ribir_core::{
+  prelude::*,
+  window::{ShellWindow, WindowFlags, WindowId},
+};
+
+use crate::backends::render_commands;
+
+/// Render widgets to images without creating a native window. It's useful to
+/// generate thumbnails or previews, even in a server process.
+///
+/// The widget is built, laid out and painted in a window that never shows, and
+/// the frame is rasterized by the painter backend that the application uses.
+/// Enable the `cpu` feature to rasterize it without a GPU.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let img = OffscreenRenderer::new(Size::new(200., 100.))
+///   .with_device_pixel_ratio(2.)
+///   .render(fn_widget! { @Text { text: "Hello world!" } });
+/// assert_eq!((img.width(), img.height()), (400, 200));
+/// ```
+pub struct OffscreenRenderer {
+  size: Size,
+  device_pixel_ratio: f32,
+}
+
+impl OffscreenRenderer {
+  /// Create a renderer that renders the widget in the `size` logic pixels.
+  pub fn new(size: Size) -> Self { Self { size, device_pixel_ratio: 1. } }
+
+  /// Set the ratio of the image pixels to the logic pixels, the default is
+  /// `1.`.
+  pub fn with_device_pixel_ratio(mut self, ratio: f32) -> Self {
+    self.device_pixel_ratio = ratio;
+    self
+  }
+
+  /// Build the widget and render its first frame to an image. The animations
+  /// are disabled, so the image is the final state of the widget.
+  pub fn render(&self, content: impl Into<GenWidget>) -> PixelImage {
+    let Self { size, device_pixel_ratio } = *self;
+    let shell = OffscreenShellWnd::new(size, device_pixel_ratio);
+    let wnd = AppCtx::new_window(Box::new(shell), content.into());
+    let mut flags = wnd.flags();
+    flags.remove(WindowFlags::ANIMATIONS);
+    wnd.set_flags(flags);
+    wnd.run_frame_tasks();
+    wnd.draw_frame(true);
+
+    let frame = wnd
+      .shell_wnd()
+      .borrow_mut()
+      .as_any_mut()
+      .downcast_mut::<OffscreenShellWnd>()
+      .unwrap()
+      .frame
+      .take();
+    AppCtx::remove_wnd(wnd.id());
+
+    let img_size = (size * device_pixel_ratio)
+      .ceil()
+      .to_i32()
+      .cast_unit();
+    let (commands, surface) = frame.unwrap_or_else(|| (vec![], Color::TRANSPARENT));
+    let matrix = Transform::scale(device_pixel_ratio, device_pixel_ratio);
+    render_commands(img_size, &matrix, &commands, surface)
+  }
+}
+
+struct OffscreenShellWnd {
+  id: WindowId,
+  size: Size,
+  device_pixel_ratio: f32,
+  cursor: CursorIcon,
+  surface: Color,
+  frame: Option<(Vec<PaintCommand>, Color)>,
+}
+
+impl OffscreenShellWnd {
+  fn new(size: Size, device_pixel_ratio: f32) -> Self {
+    // Count down from the max value to avoid conflicting with the native window
+    // ids.
+    static ID: AtomicU64 = AtomicU64::new(u64::MAX);
+    Self {
+      id: ID.fetch_sub(1, Ordering::Relaxed).into(),
+      size,
+      device_pixel_ratio,
+      cursor: CursorIcon::Default,
+      surface: Color::TRANSPARENT,
+      frame: None,
+    }
+  }
+}
+
+impl ShellWindow for OffscreenShellWnd {
+  fn id(&self) -> WindowId { self.id }
+
+  fn inner_size(&self) -> Size { self.size }
+
+  fn outer_size(&self) -> Size { self.size }
+
+  fn set_ime_cursor_area(&mut self, _: &Rect) {}
+
+  fn set_ime_allowed(&mut self, _: bool) {}
+
+  fn request_resize(&mut self, size: Size) { self.on_resize(size) }
+
+  fn on_resize(&mut self, size: Size) { self.size = size; }
+
+  fn set_min_size(&mut self, _: Size) {}
+
+  fn cursor(&self) -> CursorIcon { self.cursor }
+
+  fn set_cursor(&mut self, cursor: CursorIcon) { self.cursor = cursor; }
+
+  fn set_title(&mut self, _: &str) {}
+
+  fn set_icon(&mut self, _: &PixelImage) {}
+
+  fn is_visible(&self) -> Option<bool> { Some(false) }
+
+  fn set_visible(&mut self, _: bool) {}
+
+  fn is_resizable(&self) -> bool { false }
+
+  fn set_resizable(&mut self, _: bool) {}
+
+  fn is_minimized(&self) -> bool { false }
+
+  fn set_minimized(&mut self, _: bool) {}
+
+  fn focus_window(&mut self) {}
+
+  fn set_decorations(&mut self, _: bool) {}
+
+  fn as_any(&self) -> &dyn Any { self }
+
+  fn as_any_mut(&mut self) -> &mut dyn Any { self }
+
+  fn device_pixel_ratio(&self) -> f32 { self.device_pixel_ratio }
+
+  fn begin_frame(&mut self, surface_color: Color) { self.surface = surface_color; }
+
+  fn draw_commands(&mut self, _: Rect, commands: &[PaintCommand]) {
+    self.frame = Some((commands.to_vec(), self.surface));
+  }
+
+  fn end_frame(&mut self) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::reset_test_env;
+
+  #[test]
+  fn render_with_device_pixel_ratio() {
+    reset_test_env!();
+
+    let img = OffscreenRenderer::new(Size::new(10., 5.))
+      .with_device_pixel_ratio(2.)
+      .render(fn_widget! {
+        @Container {
+          size: Size::new(10., 5.),
+          background: Color::RED,
+        }
+      });
+
+    assert_eq!((img.width(), img.height()), (20, 10));
+    let center = (5 * 20 + 10) * 4;
+    let pixel = &img.pixel_bytes()[center..center + 4];
+    // Allow a small color error of the GPU backend.
+    let red = [255, 0, 0, 255];
+    assert!(
+      pixel
+        .iter()
+        .zip(red)
+        .all(|(a, b)| a.abs_diff(b) < 5),
+      "{pixel:?}"
+    );
+  }
+}
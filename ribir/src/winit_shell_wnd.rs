@@ -1,6 +1,7 @@
 use std::future::Future;
 
 use ribir_core::{
+  accessibility::AccessibilityTree,
   prelude::{image::ColorFormat, *},
   window::{ShellWindow, WindowId},
 };
@@ -10,8 +11,9 @@ use winit::{
 };
 
 use crate::{
+  accessibility::AccessibilityAdapter,
   backends::*,
-  prelude::{WindowAttributes, request_redraw},
+  prelude::{App, WindowAttributes, request_redraw},
 };
 pub trait WinitBackend<'a>: Sized {
   fn new(window: &'a winit::window::Window) -> impl Future<Output = Self>;
@@ -31,6 +33,7 @@ pub struct WinitShellWnd {
   pub(crate) winit_wnd: winit::window::Window,
   backend: Backend<'static>,
   cursor: CursorIcon,
+  accessibility: Box<dyn AccessibilityAdapter>,
 }
 
 impl ShellWindow for WinitShellWnd {
@@ -165,6 +168,12 @@ impl ShellWindow for WinitShellWnd {
 
   #[inline]
   fn end_frame(&mut self) { self.backend.end_frame() }
+
+  fn is_accessibility_enabled(&self) -> bool { true }
+
+  fn update_accessibility(&mut self, tree: &AccessibilityTree) {
+    self.accessibility.update_tree(tree);
+  }
 }
 
 pub(crate) fn new_id(id: winit::window::WindowId) -> WindowId {
@@ -263,12 +272,20 @@ impl WinitShellWnd {
     // Safety: a reference to winit_wnd is valid as long as the WinitShellWnd is
     // alive.
     let backend = Backend::new(unsafe { &*ptr }).await;
+    // The accessibility adapter must be created before the window is visible.
+    let accessibility = App::new_accessibility_adapter(&winit_wnd);
 
     // show the window after the render backend is ready
     if attrs.visible {
       winit_wnd.set_visible(attrs.visible);
     }
-    WinitShellWnd { backend, winit_wnd, cursor: CursorIcon::Default }
+    WinitShellWnd { backend, winit_wnd, cursor: CursorIcon::Default, accessibility }
+  }
+
+  pub(crate) fn process_accessibility_event(&mut self, event: &winit::event::WindowEvent) {
+    self
+      .accessibility
+      .process_event(&self.winit_wnd, event);
   }
 }
 
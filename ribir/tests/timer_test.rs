@@ -2,7 +2,7 @@ use ribir::core::timer::Timer;
 use rxrust::scheduler::NEW_TIMER_FN;
 
 mod test_single_thread {
-  use std::{cell::RefCell, rc::Rc, thread::sleep};
+  use std::{cell::RefCell, rc::Rc};
 
   use ribir_core::{prelude::*, reset_test_env, test_helper::*};
   use winit::event::{DeviceId, ElementState, MouseButton};
@@ -26,11 +26,8 @@ mod test_single_thread {
     wnd.draw_frame();
     wnd.assert_root_size((20., 20.).into());
 
-    sleep(Duration::from_millis(10));
-
     // trigger timeout
-    super::Timer::wake_timeout_futures();
-    AppCtx::run_until_stalled();
+    wnd.advance_time(Duration::from_millis(10));
     wnd.draw_frame();
     wnd.assert_root_size((10., 10.).into());
   }
@@ -60,7 +57,7 @@ mod test_single_thread {
       if (cond)() {
         break;
       }
-      sleep(Duration::from_millis(1));
+      wnd.advance_time(Duration::from_millis(1));
     }
   }
 
@@ -2,10 +2,13 @@ use ribir_core::prelude::Classes;
 
 mod buttons_cls;
 mod checkbox_cls;
+mod dialog_cls;
 mod input_cls;
+mod menu_cls;
 mod progress_cls;
 mod radio_cls;
 mod scrollbar_cls;
+mod select_cls;
 mod slider_cls;
 mod tooltips_cls;
 pub fn initd_classes() -> Classes {
@@ -19,6 +22,9 @@ pub fn initd_classes() -> Classes {
   tooltips_cls::init(&mut classes);
   slider_cls::init(&mut classes);
   input_cls::init(&mut classes);
+  menu_cls::init(&mut classes);
+  dialog_cls::init(&mut classes);
+  select_cls::init(&mut classes);
 
   classes
 }
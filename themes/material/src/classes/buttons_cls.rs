@@ -130,7 +130,8 @@ fn fab_init(classes: &mut Classes) {
     let w = FatObj::new(w)
       .background(background)
       .clamp(BoxClamp::min_width(btn_height).with_fixed_height(btn_height))
-      .radius(radius);
+      .radius(radius)
+      .box_shadow(md::ELEVATION_3);
 
     FatObj::new(base_interactive(w.into_widget(), radius))
       .foreground(foreground)
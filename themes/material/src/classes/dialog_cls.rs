@@ -0,0 +1,30 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const DIALOG_CLAMP: BoxClamp =
+  BoxClamp { min: Size::new(280., 0.), max: Size::new(560., f32::INFINITY) };
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(DIALOG_SCRIM, style_class! {
+    background: Palette::of(BuildCtx::get()).scrim().with_alpha(0.32),
+  });
+  classes.insert(DIALOG, style_class! {
+    background: Palette::of(BuildCtx::get()).surface_container_high(),
+    radius: Radius::all(28.),
+    padding: EdgeInsets::all(24.),
+    box_shadow: md::ELEVATION_3,
+    clamp: DIALOG_CLAMP,
+  });
+  classes.insert(DIALOG_TITLE, style_class! {
+    margin: EdgeInsets::only_bottom(16.),
+    text_style: TypographyTheme::of(BuildCtx::get()).headline_small.text.clone(),
+    foreground: Palette::of(BuildCtx::get()).on_surface(),
+  });
+  classes.insert(DIALOG_CONTENT, style_class! {
+    text_style: TypographyTheme::of(BuildCtx::get()).body_medium.text.clone(),
+    foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+  });
+  classes.insert(DIALOG_ACTIONS, style_class! { margin: EdgeInsets::only_top(24.) });
+}
@@ -1,5 +1,5 @@
 use ribir_core::prelude::*;
-use ribir_widgets::input::{INPUT, TEXT_CARET, TEXT_SELECTION, TEXTAREA};
+use ribir_widgets::input::{INPUT, RICH_TEXTAREA, TEXT_CARET, TEXT_SELECTION, TEXTAREA};
 
 use crate::md;
 
@@ -49,4 +49,5 @@ pub(super) fn init(classes: &mut Classes) {
   }
   classes.insert(INPUT, input_border);
   classes.insert(TEXTAREA, input_border);
+  classes.insert(RICH_TEXTAREA, input_border);
 }
@@ -0,0 +1,53 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const MENU_ITEM_HEIGHT: f32 = 48.;
+const MENU_BAR_HEIGHT: f32 = 40.;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(MENU, style_class! {
+    background: Palette::of(BuildCtx::get()).surface_container(),
+    radius: md::RADIUS_4,
+    padding: EdgeInsets::vertical(8.),
+    box_shadow: md::ELEVATION_2,
+    clamp: BoxClamp::min_width(112.),
+  });
+  classes.insert(MENU_ITEM, style_class! {
+    padding: md::EDGES_HOR_12,
+    clamp: BoxClamp::fixed_height(MENU_ITEM_HEIGHT),
+    cursor: CursorIcon::Pointer,
+  });
+  classes.insert(MENU_ITEM_ACTIVE, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.on_surface().with_alpha(0.08))
+      .into_widget()
+  });
+  classes.insert(MENU_ITEM_INACTIVE, empty_cls);
+  classes.insert(MENU_ITEM_DISABLED, style_class! { opacity: 0.38 });
+  classes.insert(MENU_SEPARATOR, style_class! { margin: EdgeInsets::vertical(8.) });
+  classes.insert(MENU_ACCELERATOR, style_class! {
+    margin: EdgeInsets::only_left(24.),
+    foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+  });
+
+  classes.insert(MENU_BAR, style_class! {
+    background: Palette::of(BuildCtx::get()).surface_container(),
+    clamp: BoxClamp::fixed_height(MENU_BAR_HEIGHT),
+  });
+  classes.insert(MENU_BAR_ITEM, style_class! {
+    padding: md::EDGES_HOR_12,
+    v_align: VAlign::Center,
+    cursor: CursorIcon::Pointer,
+  });
+  classes.insert(MENU_BAR_ITEM_ACTIVE, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.on_surface().with_alpha(0.08))
+      .radius(md::RADIUS_4)
+      .into_widget()
+  });
+  classes.insert(MENU_BAR_ITEM_INACTIVE, empty_cls);
+}
@@ -0,0 +1,64 @@
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
+
+use crate::*;
+
+const FIELD_HEIGHT: f32 = 56.;
+const FIELD_MIN_WIDTH: f32 = 210.;
+const OPTION_HEIGHT: f32 = 48.;
+const PANEL_MAX_HEIGHT: f32 = 280.;
+
+pub(super) fn init(classes: &mut Classes) {
+  classes.insert(SELECT, |w| {
+    let mut w = FatObj::new(w);
+    let palette = Palette::of(BuildCtx::get());
+    let (blur, focus) = (palette.outline(), palette.primary());
+    rdl! {
+      @ $w {
+        padding: md::EDGES_HOR_16,
+        clamp: BoxClamp::fixed_height(FIELD_HEIGHT).with_min_width(FIELD_MIN_WIDTH),
+        radius: md::RADIUS_4,
+        cursor: CursorIcon::Pointer,
+        border: pipe! {
+          let color = if $w.has_focus() { focus } else { blur };
+          Border::all(BorderSide::new(1., color.into()))
+        },
+      }
+    }
+    .into_widget()
+  });
+  classes.insert(COMBO_BOX, style_class! {
+    clamp: BoxClamp::min_width(FIELD_MIN_WIDTH),
+  });
+  classes.insert(COMBO_BOX_SELECTED, style_class! {
+    margin: EdgeInsets::only_right(8.),
+    foreground: Palette::of(BuildCtx::get()).primary(),
+  });
+
+  classes.insert(SELECT_PANEL, style_class! {
+    background: Palette::of(BuildCtx::get()).surface_container(),
+    radius: md::RADIUS_4,
+    padding: EdgeInsets::vertical(8.),
+    box_shadow: md::ELEVATION_2,
+    clamp: BoxClamp::UNLIMITED.with_max_height(PANEL_MAX_HEIGHT),
+  });
+  classes.insert(SELECT_OPTION, style_class! {
+    padding: md::EDGES_HOR_12,
+    clamp: BoxClamp::fixed_height(OPTION_HEIGHT),
+    cursor: CursorIcon::Pointer,
+  });
+  classes.insert(SELECT_OPTION_ACTIVE, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.on_surface().with_alpha(0.08))
+      .into_widget()
+  });
+  classes.insert(SELECT_OPTION_SELECTED, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.secondary_container())
+      .into_widget()
+  });
+  classes.insert(SELECT_OPTION_INACTIVE, empty_cls);
+  classes.insert(SELECT_OPTION_DISABLED, style_class! { opacity: 0.38 });
+}
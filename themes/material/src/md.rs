@@ -110,6 +110,25 @@ pub const EDGES_HOR_32: EdgeInsets = EdgeInsets::horizontal(32.);
 pub const EDGES_HOR_36: EdgeInsets = EdgeInsets::horizontal(36.);
 pub const EDGES_HOR_48: EdgeInsets = EdgeInsets::horizontal(48.);
 
+// Elevations, the shadows of the surfaces in different levels.
+// See https://m3.material.io/styles/elevation/tokens
+pub const ELEVATION_0: BoxShadow = elevation(0., 0., 0.);
+pub const ELEVATION_1: BoxShadow = elevation(1., 3., 1.);
+pub const ELEVATION_2: BoxShadow = elevation(2., 6., 2.);
+pub const ELEVATION_3: BoxShadow = elevation(4., 8., 3.);
+pub const ELEVATION_4: BoxShadow = elevation(6., 10., 4.);
+pub const ELEVATION_5: BoxShadow = elevation(8., 12., 6.);
+
+const fn elevation(y: f32, blur_radius: f32, spread: f32) -> BoxShadow {
+  BoxShadow {
+    offset: Vector::new(0., y),
+    blur_radius,
+    spread,
+    color: Color::new(0, 0, 0, 38),
+    inset: false,
+  }
+}
+
 // Borders
 pub fn border_2() -> VariantMap<Color, impl Fn(Color) -> Border> {
   BuildCtx::color().map(|color| Border::all(BorderSide::new(2., color.into())))
@@ -1,3 +1,57 @@
-use ribir_core::prelude::Classes;
+use ribir_core::prelude::*;
+use ribir_widgets::prelude::*;
 
-pub fn initd_classes() -> Classes { Classes::default() }
+const MENU_ITEM_HEIGHT: f32 = 32.;
+const MENU_BAR_HEIGHT: f32 = 32.;
+
+pub fn initd_classes() -> Classes {
+  let mut classes = Classes::default();
+  menu_cls(&mut classes);
+  classes
+}
+
+fn menu_cls(classes: &mut Classes) {
+  classes.insert(MENU, style_class! {
+    background: Palette::of(BuildCtx::get()).surface(),
+    border: Border::all(BorderSide::new(1., Palette::of(BuildCtx::get()).outline_variant().into())),
+    radius: Radius::all(4.),
+    padding: EdgeInsets::vertical(4.),
+    clamp: BoxClamp::min_width(112.),
+  });
+  classes.insert(MENU_ITEM, style_class! {
+    padding: EdgeInsets::horizontal(8.),
+    clamp: BoxClamp::fixed_height(MENU_ITEM_HEIGHT),
+    cursor: CursorIcon::Pointer,
+  });
+  classes.insert(MENU_ITEM_ACTIVE, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.primary().with_alpha(0.12))
+      .into_widget()
+  });
+  classes.insert(MENU_ITEM_INACTIVE, empty_cls);
+  classes.insert(MENU_ITEM_DISABLED, style_class! { opacity: 0.38 });
+  classes.insert(MENU_SEPARATOR, style_class! { margin: EdgeInsets::vertical(4.) });
+  classes.insert(MENU_ACCELERATOR, style_class! {
+    margin: EdgeInsets::only_left(16.),
+    foreground: Palette::of(BuildCtx::get()).on_surface_variant(),
+  });
+
+  classes.insert(MENU_BAR, style_class! {
+    background: Palette::of(BuildCtx::get()).surface(),
+    clamp: BoxClamp::fixed_height(MENU_BAR_HEIGHT),
+  });
+  classes.insert(MENU_BAR_ITEM, style_class! {
+    padding: EdgeInsets::horizontal(8.),
+    v_align: VAlign::Center,
+    cursor: CursorIcon::Pointer,
+  });
+  classes.insert(MENU_BAR_ITEM_ACTIVE, |w| {
+    let palette = Palette::of(BuildCtx::get());
+    FatObj::new(w)
+      .background(palette.primary().with_alpha(0.12))
+      .radius(Radius::all(4.))
+      .into_widget()
+  });
+  classes.insert(MENU_BAR_ITEM_INACTIVE, empty_cls);
+}
@@ -276,12 +276,14 @@ impl<'c> ButtonChild<'c> {
   /// - If both an icon and a label are present, the `btn` class will be
   ///   assigned to the button, the `btn_icon` class will be assigned to the
   ///   icon, and the `btn_label` class will be assigned to the label.
+  ///
+  /// The button is described as a `Button` role to the assistive technologies.
   fn compose_to_widget(
     self,
     [btn, btn_leading_icon, btn_trialing_icon, btn_label, icon_only, label_only]: [ClassName; 6],
   ) -> Widget<'c> {
     let Self { label, icon } = self;
-    match (label, icon) {
+    let content = match (label, icon) {
       (None, None) => void!( class: btn ).into_widget(),
       (None, Some(icon)) => fat_obj! {
         class: icon_only,
@@ -306,7 +308,12 @@ impl<'c> ButtonChild<'c> {
         }
       }
       .into_widget(),
+    };
+    fat_obj! {
+      semantics: Semantics::new(Role::Button).with_action(SemanticsAction::Click),
+      @ { content }
     }
+    .into_widget()
   }
 }
 
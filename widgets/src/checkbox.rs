@@ -77,6 +77,19 @@ impl Checkbox {
       CHECKBOX_UNCHECKED
     }
   }
+
+  fn semantics(&self) -> Semantics {
+    let checked = if self.indeterminate {
+      Toggled::Mixed
+    } else if self.checked {
+      Toggled::True
+    } else {
+      Toggled::False
+    };
+    Semantics::new(Role::Checkbox)
+      .with_checked(checked)
+      .with_action(SemanticsAction::Click)
+  }
 }
 
 impl ComposeChild<'static> for Checkbox {
@@ -89,6 +102,7 @@ impl ComposeChild<'static> for Checkbox {
         @Void { class: CHECKBOX }
       };
       @FatObj {
+        semantics: pipe!($this.semantics()),
         on_tap: move |_| $this.write().switch_check(),
         on_key_up: move |k| if *k.key() == VirtualKey::Named(NamedKey::Space) {
           $this.write().switch_check()
@@ -102,12 +116,39 @@ impl ComposeChild<'static> for Checkbox {
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
   use crate::prelude::*;
 
+  #[test]
+  fn checkbox_semantics() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Checkbox { @ { "Agree" } }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.accessibility_tree();
+    assert_eq!(
+      tree.to_string(),
+      "Window
+  Checkbox \"Agree\" checked=false focusable actions=[Click, Focus]
+    StaticText \"Agree\""
+    );
+    let checkbox = tree.find_by_label("Agree").unwrap();
+    assert_eq!(checkbox.role, Role::Checkbox);
+    assert_eq!(checkbox.checked, Some(Toggled::False));
+
+    wnd.perform_semantics_action(checkbox.id, SemanticsAction::Click);
+    wnd.draw_frame();
+    let tree = wnd.accessibility_tree();
+    let checkbox = tree.find_by_label("Agree").unwrap();
+    assert_eq!(checkbox.checked, Some(Toggled::True));
+  }
+
   widget_image_tests!(
     checkbox,
     WidgetTester::new(self::column! {
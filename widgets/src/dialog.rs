@@ -0,0 +1,482 @@
+use std::{
+  cell::{Cell, RefCell},
+  future::Future,
+  pin::Pin,
+  rc::Rc,
+  task::{Context, Poll, Waker},
+};
+
+use ribir_core::{prelude::*, window::WindowId};
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "The class name for the panel of a dialog."]
+  DIALOG,
+  #[doc = "The class name for the title of a dialog."]
+  DIALOG_TITLE,
+  #[doc = "The class name for the content of a dialog."]
+  DIALOG_CONTENT,
+  #[doc = "The class name for the row of the buttons at the end of a dialog."]
+  DIALOG_ACTIONS,
+  #[doc = "The class name for the mask covering the window below a modal dialog."]
+  DIALOG_SCRIM,
+}
+
+/// A dialog panel with an optional title and its content.
+///
+/// The dialog is a focus scope that traps the focus, the `Tab` navigation
+/// cycles within it, and it's focused when it shows. Use [`show_dialog`] to
+/// show it as a modal over the window.
+///
+/// Put the buttons of the dialog in a row with the [`DIALOG_ACTIONS`] class
+/// to align them like the [`AlertDialog`].
+#[derive(Declare)]
+pub struct Dialog {
+  #[declare(default)]
+  pub title: CowArc<str>,
+}
+
+impl<'c> ComposeChild<'c> for Dialog {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let title = $this.title.clone();
+      let title = (!title.is_empty()).then(move || @Text {
+        class: DIALOG_TITLE,
+        text: pipe!($this.title.clone()),
+      });
+      let panel = @Column {
+        class: DIALOG,
+        tab_index: -1_i16,
+        semantics: pipe!(Semantics::new(Role::Dialog).with_label($this.title.clone())),
+        @ { title }
+        @Class { class: DIALOG_CONTENT, @ { child } }
+      };
+      @FocusScope {
+        skip_host: false,
+        trap_focus: true,
+        @ { panel.auto_focus(true) }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// A dialog that shows a message and the buttons, the result of it is the
+/// index of the button selected.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let result = AlertDialog::new("Discard draft?", "The changes will be lost.")
+///         .button("Cancel")
+///         .button("Discard")
+///         .show(e.window());
+///       let _ = AppCtx::spawn_local(async move {
+///         if result.await == Some(1) {
+///           println!("discarded");
+///         }
+///       });
+///     },
+///     @{ "Discard" }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Clone)]
+pub struct AlertDialog {
+  title: CowArc<str>,
+  content: CowArc<str>,
+  buttons: Vec<CowArc<str>>,
+}
+
+impl AlertDialog {
+  pub fn new(title: impl Into<CowArc<str>>, content: impl Into<CowArc<str>>) -> Self {
+    Self { title: title.into(), content: content.into(), buttons: vec![] }
+  }
+
+  /// Append a button to the end of the dialog.
+  pub fn button(mut self, label: impl Into<CowArc<str>>) -> Self {
+    self.buttons.push(label.into());
+    self
+  }
+
+  /// Show the dialog as a modal, the future resolves to the index of the
+  /// button selected, or `None` if the dialog is dismissed.
+  pub fn show(self, wnd: Sc<Window>) -> impl Future<Output = Option<usize>> {
+    show_dialog(wnd, move |handle| self.dialog(handle))
+  }
+
+  fn dialog(&self, handle: &DialogHandle<usize>) -> Widget<'static> {
+    let buttons = self
+      .buttons
+      .iter()
+      .enumerate()
+      .map(|(idx, label)| {
+        let (handle, label) = (handle.clone(), label.clone());
+        text_button! {
+          on_tap: move |_| handle.close(idx),
+          @ { label.clone() }
+        }
+        .into_widget()
+      })
+      .collect::<Vec<_>>();
+    let (title, content) = (self.title.clone(), self.content.clone());
+    dialog! {
+      title,
+      @Column {
+        align_items: Align::Stretch,
+        @Text { text: content }
+        @Row {
+          class: DIALOG_ACTIONS,
+          justify_content: JustifyContent::End,
+          @ { buttons }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// Show the dialog built by `dialog` as a modal over the window, the future
+/// resolves to the value the dialog closes with by [`DialogHandle::close`], or
+/// `None` if it's dismissed.
+///
+/// The window below is covered by a mask, tapping the mask or pressing
+/// `Escape` dismisses the dialog. The dialogs shown from a dialog stack on it,
+/// and the focus moves back to the widget focused before the dialog shows
+/// when it closes. The dialog fades in and out by the `EASE_OUT` and `EASE_IN`
+/// transitions of the theme.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @FilledButton {
+///     on_tap: move |e| {
+///       let name = show_dialog(e.window(), |handle| {
+///         let (ok, cancel) = (handle.clone(), handle.clone());
+///         dialog! {
+///           title: "Rename",
+///           @Row {
+///             class: DIALOG_ACTIONS,
+///             @TextButton { on_tap: move |_| cancel.dismiss(), @{ "Cancel" } }
+///             @TextButton { on_tap: move |_| ok.close("New name"), @{ "OK" } }
+///           }
+///         }
+///         .into_widget()
+///       });
+///       let _ = AppCtx::spawn_local(async move {
+///         if let Some(name) = name.await {
+///           println!("{name}");
+///         }
+///       });
+///     },
+///     @{ "Rename" }
+///   }
+/// };
+/// App::run(w);
+/// ```
+pub fn show_dialog<T: 'static>(
+  wnd: Sc<Window>, dialog: impl Fn(&DialogHandle<T>) -> Widget<'static> + 'static,
+) -> impl Future<Output = Option<T>> {
+  let session = Rc::new(DialogSession {
+    wnd_id: wnd.id(),
+    restore_focus: wnd.focusing(),
+    overlay: RefCell::new(None),
+    closed: Cell::new(false),
+    result: RefCell::new(None),
+    waker: RefCell::new(None),
+  });
+  let handle = DialogHandle(session.clone());
+  let overlay = Overlay::new(move || modal(&handle, dialog(&handle)), OverlayStyle {
+    auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE,
+    mask: None,
+  });
+  *session.overlay.borrow_mut() = Some(overlay.clone());
+
+  // The dialog is usually shown in an event callback, in which the overlay
+  // can't be built, so show it in the next frame if it's not closed yet.
+  let weak = Rc::downgrade(&session);
+  AppCtx::once_next_frame(move |_| {
+    if weak.upgrade().is_some_and(|s| !s.closed.get()) {
+      overlay.show(wnd.clone());
+    }
+  });
+
+  DialogFuture(session)
+}
+
+/// The handle to close a dialog shown by [`show_dialog`].
+pub struct DialogHandle<T>(Rc<DialogSession<T>>);
+
+struct DialogSession<T> {
+  wnd_id: WindowId,
+  restore_focus: Option<WidgetId>,
+  overlay: RefCell<Option<Overlay>>,
+  closed: Cell<bool>,
+  result: RefCell<Option<T>>,
+  waker: RefCell<Option<Waker>>,
+}
+
+impl<T> Clone for DialogHandle<T> {
+  fn clone(&self) -> Self { Self(self.0.clone()) }
+}
+
+impl<T> DialogHandle<T> {
+  /// Close the dialog with the `value` as its result.
+  pub fn close(&self, value: T) { self.finish(Some(value)) }
+
+  /// Close the dialog without a result.
+  pub fn dismiss(&self) { self.finish(None) }
+
+  pub fn is_closed(&self) -> bool { self.0.closed.get() }
+
+  fn finish(&self, value: Option<T>) {
+    let session = &self.0;
+    if session.closed.replace(true) {
+      return;
+    }
+    if let Some(overlay) = session.overlay.borrow_mut().take() {
+      overlay.close();
+    }
+    if let (Some(id), Some(wnd)) = (session.restore_focus, AppCtx::get_window(session.wnd_id)) {
+      wnd.request_focus(id);
+    }
+    *session.result.borrow_mut() = value;
+    if let Some(waker) = session.waker.borrow_mut().take() {
+      waker.wake();
+    }
+  }
+}
+
+struct DialogFuture<T>(Rc<DialogSession<T>>);
+
+impl<T> Future for DialogFuture<T> {
+  type Output = Option<T>;
+
+  fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+    let session = &self.0;
+    if session.closed.get() {
+      Poll::Ready(session.result.borrow_mut().take())
+    } else {
+      *session.waker.borrow_mut() = Some(cx.waker().clone());
+      Poll::Pending
+    }
+  }
+}
+
+/// Place the dialog at the center of a mask over the window, it fades in when
+/// it shows and fades out after it closes.
+fn modal<T: 'static>(handle: &DialogHandle<T>, dialog: Widget<'static>) -> Widget<'static> {
+  let (tap, esc) = (handle.clone(), handle.clone());
+  fn_widget! {
+    let dialog = FatObj::new(dialog);
+    let mut scrim = @Container {
+      size: Size::splat(f32::INFINITY),
+      opacity: 0.,
+      tab_index: -1_i16,
+      on_tap: move |e| if e.target() == e.current_target() {
+        tap.dismiss();
+      },
+      on_key_down: move |e| if *e.key() == VirtualKey::Named(NamedKey::Escape) {
+        e.stop_propagation();
+        esc.dismiss();
+      },
+    };
+    let mut exit = Some(transitions::EASE_IN.of(BuildCtx::get()));
+    let fade = part_writer!(&mut scrim.opacity)
+      .transition(transitions::EASE_OUT.of(BuildCtx::get()));
+    @Class {
+      class: DIALOG_SCRIM,
+      @ $scrim {
+        keep_alive: pipe!($fade.is_running() || $scrim.opacity != 0.),
+        on_mounted: move |_| $scrim.write().opacity = 1.,
+        on_disposed: move |_| {
+          if let Some(exit) = exit.take() {
+            $fade.write().transition = exit;
+          }
+          $scrim.write().opacity = 0.;
+        },
+        @ $dialog {
+          h_align: HAlign::Center,
+          v_align: VAlign::Center,
+        }
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  const WND_SIZE: Size = Size::new(400., 400.);
+
+  fn press(wnd: &mut TestWindow, key: NamedKey) {
+    let (code, key) = (PhysicalKey::Code(KeyCode::F24), VirtualKey::Named(key));
+    let state = ElementState::Pressed;
+    wnd.processes_keyboard_event(code, key, false, KeyLocation::Standard, state);
+    wnd.draw_frame();
+  }
+
+  fn focused(wnd: &TestWindow) -> Option<String> {
+    let tree = wnd.accessibility_tree();
+    let label = tree
+      .root
+      .iter()
+      .find(|n| n.focused)
+      .and_then(|n| n.label.as_ref().map(|l| l.to_string()));
+    label
+  }
+
+  fn dialogs(wnd: &TestWindow) -> usize {
+    let tree = wnd.accessibility_tree();
+    tree
+      .root
+      .iter()
+      .filter(|n| n.role == Role::Dialog)
+      .count()
+  }
+
+  fn focusable(label: &'static str) -> Widget<'static> {
+    container! {
+      size: Size::new(50., 20.),
+      tab_index: 0_i16,
+      semantics: Semantics::new(Role::Button).with_label(label),
+    }
+    .into_widget()
+  }
+
+  #[test]
+  fn focus_trap_and_restore() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      let open = FatObj::new(focusable("open"));
+      @Column {
+        @ { open.auto_focus(true) }
+        @ { focusable("other") }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    assert_eq!(focused(&wnd).as_deref(), Some("open"));
+
+    let result = show_dialog::<()>(wnd.0.clone(), |_| {
+      dialog! {
+        title: "Edit",
+        @Column {
+          @ { focusable("a") }
+          @ { focusable("b") }
+        }
+      }
+      .into_widget()
+    });
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 1);
+    assert_eq!(focused(&wnd).as_deref(), Some("Edit"));
+
+    // The focus cycles within the dialog.
+    press(&mut wnd, NamedKey::Tab);
+    assert_eq!(focused(&wnd).as_deref(), Some("a"));
+    press(&mut wnd, NamedKey::Tab);
+    assert_eq!(focused(&wnd).as_deref(), Some("b"));
+    press(&mut wnd, NamedKey::Tab);
+    assert_eq!(focused(&wnd).as_deref(), Some("Edit"));
+    press(&mut wnd, NamedKey::Tab);
+    assert_eq!(focused(&wnd).as_deref(), Some("a"));
+
+    press(&mut wnd, NamedKey::Escape);
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 0);
+    assert_eq!(focused(&wnd).as_deref(), Some("open"));
+    assert_eq!(AppCtx::wait_future(result), None);
+  }
+
+  #[test]
+  fn stacked_dialogs() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(fn_widget! { @Void {} }, WND_SIZE);
+    wnd.draw_frame();
+
+    let first = Rc::new(RefCell::new(None));
+    let first2 = first.clone();
+    let result1 = show_dialog(wnd.0.clone(), move |handle: &DialogHandle<i32>| {
+      *first2.borrow_mut() = Some(handle.clone());
+      dialog! { title: "First", @ { focusable("one") } }.into_widget()
+    });
+    wnd.draw_frame();
+    let result2 = AlertDialog::new("Second", "Sure?")
+      .button("Cancel")
+      .button("OK")
+      .show(wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 2);
+    assert_eq!(focused(&wnd).as_deref(), Some("Second"));
+
+    let ok = wnd
+      .accessibility_tree()
+      .find_by_label("OK")
+      .unwrap()
+      .id;
+    wnd.perform_semantics_action(ok, SemanticsAction::Click);
+    wnd.draw_frame();
+    assert_eq!(AppCtx::wait_future(result2), Some(1));
+    assert_eq!(dialogs(&wnd), 1);
+    assert_eq!(focused(&wnd).as_deref(), Some("First"));
+
+    let first = first.borrow().clone().unwrap();
+    assert!(!first.is_closed());
+    first.close(7);
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 0);
+    assert_eq!(AppCtx::wait_future(result1), Some(7));
+  }
+
+  #[test]
+  fn tap_scrim_dismiss() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new_with_size(fn_widget! { @Void {} }, WND_SIZE);
+    wnd.draw_frame();
+    let result = AlertDialog::new("Alert", "Message")
+      .button("OK")
+      .show(wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 1);
+
+    // Tapping inside the dialog keeps it.
+    let rect = wnd
+      .accessibility_tree()
+      .find_by_label("Alert")
+      .unwrap()
+      .rect;
+    wnd.mouse_move_to(rect.center());
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 1);
+
+    wnd.mouse_move_to(Point::new(2., 2.));
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(dialogs(&wnd), 0);
+    assert_eq!(AppCtx::wait_future(result), None);
+  }
+}
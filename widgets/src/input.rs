@@ -4,12 +4,14 @@ use ribir_core::prelude::*;
 
 use crate::prelude::*;
 
+mod edit_history;
 mod edit_text;
 mod text_glyphs;
 
 mod text_editable;
 mod text_selectable;
 
+pub use edit_history::DEFAULT_HISTORY_DEPTH;
 pub use edit_text::*;
 pub use text_editable::*;
 pub use text_glyphs::*;
@@ -20,6 +22,8 @@ class_names!(
   INPUT,
   ///Class name for the text area widget
   TEXTAREA,
+  ///Class name for the rich text area widget
+  RICH_TEXTAREA,
 );
 
 /// The `Input` struct is a widget that represents a text input field
@@ -54,13 +58,15 @@ pub struct Input {
 }
 
 impl Input {
-  /// set the text and the caret selection will be reset to the start.
+  /// set the text and the caret selection will be reset to the start, the
+  /// edit history is cleared.
   pub fn set_text(&mut self, text: &str) {
     let v = text
       .chars()
       .filter(|c| *c != '\n' && *c != '\r')
       .collect::<String>();
     *self.basic.text_mut() = InputText::new(v);
+    self.basic.clear_history();
     let selection = &mut self.basic.selection;
     selection.from = CaretPosition::default();
     selection.to = CaretPosition::default();
@@ -78,6 +84,24 @@ impl Input {
 
   /// return the selection range of the text
   pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
+
+  /// Undo the last edit, return whether there is an edit to undo. The
+  /// consecutive typing or deleting is undone as a whole.
+  pub fn undo(&mut self) -> bool { self.basic.undo() }
+
+  /// Redo the last undone edit, return whether there is an edit to redo.
+  pub fn redo(&mut self) -> bool { self.basic.redo() }
+
+  pub fn can_undo(&self) -> bool { self.basic.can_undo() }
+
+  pub fn can_redo(&self) -> bool { self.basic.can_redo() }
+
+  /// The maximum number of the edits that can undo,
+  /// [`DEFAULT_HISTORY_DEPTH`] by default.
+  pub fn history_depth(&self) -> usize { self.basic.history_depth() }
+
+  /// Set the maximum number of the edits that can undo.
+  pub fn set_history_depth(&mut self, depth: usize) { self.basic.set_history_depth(depth); }
 }
 
 /// The `TextArea` struct is a widget that represents a text input field
@@ -92,9 +116,11 @@ pub struct TextArea {
 }
 
 impl TextArea {
-  /// set the text and the caret selection will be reset to the start.
+  /// set the text and the caret selection will be reset to the start, the
+  /// edit history is cleared.
   pub fn set_text(&mut self, text: &str) {
     *self.basic.text_mut() = text.to_string().into();
+    self.basic.clear_history();
     let selection = &mut self.basic.selection;
     selection.from = CaretPosition::default();
     selection.to = CaretPosition::default();
@@ -112,6 +138,77 @@ impl TextArea {
 
   /// return the selection range of the text
   pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
+
+  /// Undo the last edit, return whether there is an edit to undo. The
+  /// consecutive typing or deleting is undone as a whole.
+  pub fn undo(&mut self) -> bool { self.basic.undo() }
+
+  /// Redo the last undone edit, return whether there is an edit to redo.
+  pub fn redo(&mut self) -> bool { self.basic.redo() }
+
+  pub fn can_undo(&self) -> bool { self.basic.can_undo() }
+
+  pub fn can_redo(&self) -> bool { self.basic.can_redo() }
+
+  /// The maximum number of the edits that can undo,
+  /// [`DEFAULT_HISTORY_DEPTH`] by default.
+  pub fn history_depth(&self) -> usize { self.basic.history_depth() }
+
+  /// Set the maximum number of the edits that can undo.
+  pub fn set_history_depth(&mut self, depth: usize) { self.basic.set_history_depth(depth); }
+}
+
+/// The `RichTextArea` is a [`TextArea`] that edits an [`AttributedText`], the
+/// inserted text extends the style of the span before it.
+///
+/// The style changes are recorded in the edit history as the text edits, so
+/// the undo and redo restore the styles too.
+#[derive(Declare)]
+pub struct RichTextArea {
+  #[declare(skip)]
+  basic: BasicEditor<RichText>,
+}
+
+impl RichTextArea {
+  /// set the text and the caret selection will be reset to the start, the
+  /// edit history is cleared.
+  pub fn set_text(&mut self, text: AttributedText) {
+    *self.basic.text_mut() = RichText(text);
+    self.basic.clear_history();
+    let selection = &mut self.basic.selection;
+    selection.from = CaretPosition::default();
+    selection.to = CaretPosition::default();
+  }
+
+  pub fn text(&self) -> &AttributedText { &self.basic.text().0 }
+
+  /// Apply the style to the selected text, it can be undone.
+  pub fn apply_style(&mut self, style: &SpanStyle) {
+    self
+      .basic
+      .restyle(|text, rg| text.0.apply_style(rg, style));
+  }
+
+  /// set the caret selection, and the caret position will be set to the `to`
+  /// cluster
+  pub fn select(&mut self, from: usize, to: usize) {
+    let selection = &mut self.basic.selection;
+    selection.from = CaretPosition { cluster: from, position: None };
+    selection.to = CaretPosition { cluster: to, position: None };
+  }
+
+  /// return the selection range of the text
+  pub fn selection(&self) -> Range<usize> { self.basic.cluster_rg() }
+
+  /// Undo the last edit, return whether there is an edit to undo.
+  pub fn undo(&mut self) -> bool { self.basic.undo() }
+
+  /// Redo the last undone edit, return whether there is an edit to redo.
+  pub fn redo(&mut self) -> bool { self.basic.redo() }
+
+  pub fn can_undo(&self) -> bool { self.basic.can_undo() }
+
+  pub fn can_redo(&self) -> bool { self.basic.can_redo() }
 }
 
 #[derive(Clone, Eq, PartialEq, Default)]
@@ -152,6 +249,46 @@ impl EditText for InputText {
   fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize> { self.0.del_rg_str(rg) }
 }
 
+/// The editable text of the [`RichTextArea`].
+#[derive(Clone, PartialEq, Default)]
+pub struct RichText(AttributedText);
+
+impl RichText {
+  pub fn new(v: AttributedText) -> Self { RichText(v) }
+  pub fn text(&self) -> &AttributedText { &self.0 }
+}
+
+impl BaseText for RichText {
+  fn len(&self) -> usize { self.0.text().len() }
+  fn substr(&self, rg: Range<usize>) -> Substr { self.0.text().substr(rg) }
+  fn measure_bytes(&self, byte_from: usize, char_len: isize) -> usize {
+    self.0.text().measure_bytes(byte_from, char_len)
+  }
+  fn select_token(&self, byte_from: usize) -> Range<usize> { self.0.text().select_token(byte_from) }
+}
+
+impl VisualText for RichText {
+  fn layout_glyphs(&self, clamp: BoxClamp, ctx: &LayoutCtx) -> VisualGlyphs {
+    let style = Provider::of::<TextStyle>(ctx).unwrap();
+    let text = self.0.text().substr(..);
+    rich_text_glyph(text, self.0.spans(), &style, TextAlign::Start, clamp.max)
+  }
+
+  fn paint(&self, painter: &mut Painter, style: PaintingStyle, glyphs: &VisualGlyphs, rect: Rect) {
+    paint_rich_text(painter, glyphs, self.0.spans(), style, rect);
+  }
+}
+
+impl EditText for RichText {
+  fn insert_str(&mut self, at: usize, v: &str) -> usize { self.0.insert_str(at, v) }
+
+  fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize> { self.0.del_rg_str(rg) }
+
+  fn spans(&self) -> &[TextSpan] { self.0.spans() }
+
+  fn set_spans(&mut self, spans: Vec<TextSpan>) { self.0.set_spans(spans); }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
 pub struct CaretPosition {
   /// the cluster of the caret
@@ -164,6 +301,7 @@ impl Compose for Input {
   fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
     focus_scope! {
       skip_host: true,
+      semantics: pipe!(Semantics::new(Role::TextInput).with_value($this.text().clone())),
       @TextClamp {
         rows: Some(1.),
         cols: Some(20.),
@@ -181,6 +319,9 @@ impl Compose for Input {
 impl Compose for TextArea {
   fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
     focus_scope! {
+      semantics: pipe!(
+        Semantics::new(Role::MultilineTextInput).with_value($this.text().clone())
+      ),
       @TextClamp {
         rows: Some(2.),
         cols: Some(20.),
@@ -195,6 +336,26 @@ impl Compose for TextArea {
   }
 }
 
+impl Compose for RichTextArea {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    focus_scope! {
+      semantics: pipe!(
+        Semantics::new(Role::MultilineTextInput).with_value($this.text().text().clone())
+      ),
+      @TextClamp {
+        rows: Some(2.),
+        cols: Some(20.),
+        class: RICH_TEXTAREA,
+        @Scrollbar {
+          text_overflow: TextOverflow::AutoWrap,
+          @part_writer!(&mut this.basic)
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use ribir_core::{prelude::*, reset_test_env, test_helper::*};
@@ -253,4 +414,193 @@ mod tests {
     wnd.draw_frame();
     assert_eq!(*value.read(), "hello");
   }
+
+  fn press_key(wnd: &TestWindow, code: KeyCode, key: &str) {
+    let key = VirtualKey::Character(key.into());
+    let key_code = PhysicalKey::Code(code);
+    let state = ElementState::Pressed;
+    wnd.processes_keyboard_event(key_code, key, false, KeyLocation::Standard, state);
+  }
+
+  #[test]
+  fn input_undo_redo() {
+    reset_test_env!();
+    let (value, w_value) = split_value(String::default());
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let input = @Input { auto_focus: true };
+      *$w_holder.write() = Some(input.clone_writer());
+      watch!($input.text().clone())
+        .subscribe(move |text| *$w_value.write() = text.to_string());
+      input
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    let input = holder.write().take().unwrap();
+    assert!(!input.read().can_undo());
+
+    for c in "hello world".chars() {
+      wnd.processes_receive_chars(c.to_string());
+    }
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello world");
+
+    // The typing is coalesced by words.
+    assert!(input.write().undo());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ");
+    assert!(input.write().undo());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "");
+    assert!(!input.read().can_undo());
+
+    assert!(input.write().redo());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ");
+
+    // A new edit clears the redo history.
+    wnd.processes_receive_chars("ribir".into());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ribir");
+    assert!(!input.read().can_redo());
+
+    // The IME commit is a single step.
+    wnd.processes_ime_pre_edit(ImePreEdit::Begin);
+    wnd.processes_ime_pre_edit(ImePreEdit::PreEdit { value: "!".into(), cursor: None });
+    wnd.processes_ime_pre_edit(ImePreEdit::End);
+    wnd.processes_receive_chars("!".into());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ribir!");
+    assert!(input.write().undo());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ribir");
+
+    input.write().set_history_depth(1);
+    assert!(input.write().undo());
+    assert!(!input.write().undo());
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello ");
+  }
+
+  #[test]
+  fn undo_by_shortcut() {
+    reset_test_env!();
+    let (value, w_value) = split_value(String::default());
+    let w = fn_widget! {
+      let input = @TextArea { auto_focus: true };
+      watch!($input.text().clone())
+        .subscribe(move |text| *$w_value.write() = text.to_string());
+      input
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    wnd.processes_receive_chars("hello".into());
+    wnd.draw_frame();
+    for _ in 0..2 {
+      let key = VirtualKey::Named(NamedKey::Backspace);
+      let key_code = PhysicalKey::Code(KeyCode::Backspace);
+      let state = ElementState::Pressed;
+      wnd.processes_keyboard_event(key_code, key, false, KeyLocation::Standard, state);
+    }
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hel");
+
+    let command =
+      if cfg!(target_os = "macos") { ModifiersState::SUPER } else { ModifiersState::CONTROL };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(command.into()));
+    // The consecutive deleting is undone as a whole.
+    press_key(&wnd, KeyCode::KeyZ, "z");
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello");
+
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(
+      (command | ModifiersState::SHIFT).into(),
+    ));
+    press_key(&wnd, KeyCode::KeyZ, "Z");
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hel");
+
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(command.into()));
+    press_key(&wnd, KeyCode::KeyZ, "z");
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hello");
+    press_key(&wnd, KeyCode::KeyY, "y");
+    wnd.draw_frame();
+    assert_eq!(*value.read(), "hel");
+  }
+
+  #[test]
+  fn rich_text_area_edit() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let area = @RichTextArea { auto_focus: true };
+      *$w_holder.write() = Some(area.clone_writer());
+      area
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(200., 200.));
+    wnd.draw_frame();
+    let area = holder.write().take().unwrap();
+    let bold = SpanStyle::default().with_font_weight(FontWeight::BOLD);
+    area.write().set_text(
+      AttributedText::default()
+        .push("Hello ", SpanStyle::default())
+        .push("world", bold.clone()),
+    );
+    area.write().select(11, 11);
+    wnd.draw_frame();
+
+    // The typed text extends the span before the caret.
+    wnd.processes_receive_chars("!".into());
+    wnd.draw_frame();
+    let spans = |area: &State<RichTextArea>| {
+      let area = area.read();
+      area
+        .text()
+        .spans()
+        .iter()
+        .map(|s| (s.range.start, s.range.end))
+        .collect::<Vec<_>>()
+    };
+    assert_eq!(&**area.read().text().text(), "Hello world!");
+    assert_eq!(spans(&area), [(6, 12)]);
+
+    area.write().select(0, 5);
+    area
+      .write()
+      .apply_style(&SpanStyle::default().with_color(Color::RED));
+    assert_eq!(spans(&area), [(0, 5), (6, 12)]);
+
+    // The style change is undone before the typing.
+    assert!(area.write().undo());
+    wnd.draw_frame();
+    assert_eq!(&**area.read().text().text(), "Hello world!");
+    assert_eq!(spans(&area), [(6, 12)]);
+    assert!(area.write().undo());
+    wnd.draw_frame();
+    assert_eq!(&**area.read().text().text(), "Hello world");
+    assert_eq!(spans(&area), [(6, 11)]);
+
+    assert!(area.write().redo());
+    assert!(area.write().redo());
+    wnd.draw_frame();
+    assert_eq!(spans(&area), [(0, 5), (6, 12)]);
+
+    // The deleted text is restored with its styles.
+    area.write().select(0, 12);
+    wnd.processes_receive_chars("x".into());
+    wnd.draw_frame();
+    assert!(area.write().undo());
+    wnd.draw_frame();
+    assert_eq!(&**area.read().text().text(), "Hello world!");
+    assert_eq!(spans(&area), [(0, 5), (6, 12)]);
+  }
 }
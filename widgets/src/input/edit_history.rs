@@ -0,0 +1,171 @@
+use std::{collections::VecDeque, ops::Range};
+
+use ribir_core::prelude::TextSpan;
+
+use super::text_selectable::Selection;
+
+/// The default maximum number of the steps that an editor can undo.
+pub const DEFAULT_HISTORY_DEPTH: usize = 100;
+
+/// The kind of an edit, the consecutive edits of the same kind may be merged
+/// into one step.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum EditKind {
+  /// Type the characters one by one.
+  Typing,
+  /// Delete the text before the caret.
+  Backspace,
+  /// Delete the text after the caret.
+  Delete,
+  /// The edits that never merge, such as paste, cut and the IME commit.
+  Other,
+}
+
+/// An edit replaces the `deleted` text at the position `at` with the
+/// `inserted` text, a style change only replaces the styles.
+#[derive(Debug, Clone)]
+pub(crate) struct EditStep {
+  pub(crate) kind: EditKind,
+  pub(crate) at: usize,
+  pub(crate) deleted: String,
+  pub(crate) inserted: String,
+  /// The selection before the edit.
+  pub(crate) before: Selection,
+  /// The selection after the edit.
+  pub(crate) after: Selection,
+  /// The styles before and after the edit, `None` if the text has no styles.
+  pub(crate) styles: Option<(Vec<TextSpan>, Vec<TextSpan>)>,
+}
+
+/// The undo and redo stacks of a text editor.
+#[derive(Debug)]
+pub(crate) struct EditHistory {
+  undo: VecDeque<EditStep>,
+  redo: Vec<EditStep>,
+  depth: usize,
+  /// The next edit starts a new step even if it can merge.
+  sealed: bool,
+}
+
+impl EditStep {
+  /// The range of the inserted text after the edit.
+  pub(crate) fn inserted_rg(&self) -> Range<usize> { self.at..self.at + self.inserted.len() }
+
+  /// The range of the deleted text before the edit.
+  pub(crate) fn deleted_rg(&self) -> Range<usize> { self.at..self.at + self.deleted.len() }
+
+  /// Merge the `next` edit into this step if they are consecutive edits of the
+  /// same kind, return whether it merged.
+  fn merge(&mut self, next: &EditStep) -> bool {
+    if self.kind != next.kind || self.after != next.before {
+      return false;
+    }
+    match self.kind {
+      EditKind::Typing => {
+        // A step keeps a word and the spaces after it.
+        let same_word = !self.inserted.ends_with(char::is_whitespace)
+          || next.inserted.starts_with(char::is_whitespace);
+        let mergeable = next.deleted.is_empty() && next.at == self.inserted_rg().end;
+        if mergeable && same_word {
+          self.inserted.push_str(&next.inserted);
+        } else {
+          return false;
+        }
+      }
+      EditKind::Backspace => {
+        if !next.inserted.is_empty() || next.deleted_rg().end != self.at {
+          return false;
+        }
+        self.at = next.at;
+        self.deleted.insert_str(0, &next.deleted);
+      }
+      EditKind::Delete => {
+        if !next.inserted.is_empty() || next.at != self.at {
+          return false;
+        }
+        self.deleted.push_str(&next.deleted);
+      }
+      EditKind::Other => return false,
+    }
+    self.after = next.after;
+    if self.styles.is_some() || next.styles.is_some() {
+      let before = self
+        .styles
+        .take()
+        .map(|(b, _)| b)
+        .unwrap_or_default();
+      let after = next.styles.as_ref().map(|(_, a)| a.clone());
+      self.styles = Some((before, after.unwrap_or_default()));
+    }
+    true
+  }
+}
+
+impl EditHistory {
+  /// Record an edit, it clears the redo stack.
+  pub(crate) fn record(&mut self, step: EditStep) {
+    let restyled = step
+      .styles
+      .as_ref()
+      .is_some_and(|(before, after)| before != after);
+    if step.deleted.is_empty() && step.inserted.is_empty() && !restyled {
+      return;
+    }
+    self.redo.clear();
+    let merged = !std::mem::take(&mut self.sealed)
+      && self
+        .undo
+        .back_mut()
+        .is_some_and(|last| last.merge(&step));
+    if !merged {
+      self.push_undo(step);
+    }
+  }
+
+  /// Make the next edit start a new step.
+  pub(crate) fn seal(&mut self) { self.sealed = true; }
+
+  pub(crate) fn take_undo(&mut self) -> Option<EditStep> {
+    self.sealed = true;
+    self.undo.pop_back()
+  }
+
+  pub(crate) fn push_redo(&mut self, step: EditStep) { self.redo.push(step); }
+
+  pub(crate) fn take_redo(&mut self) -> Option<EditStep> {
+    self.sealed = true;
+    self.redo.pop()
+  }
+
+  pub(crate) fn push_undo(&mut self, step: EditStep) {
+    self.undo.push_back(step);
+    if self.undo.len() > self.depth {
+      self.undo.pop_front();
+    }
+  }
+
+  pub(crate) fn can_undo(&self) -> bool { !self.undo.is_empty() }
+
+  pub(crate) fn can_redo(&self) -> bool { !self.redo.is_empty() }
+
+  pub(crate) fn depth(&self) -> usize { self.depth }
+
+  /// Set the maximum number of the steps that can undo, the oldest steps are
+  /// dropped if there are more.
+  pub(crate) fn set_depth(&mut self, depth: usize) {
+    self.depth = depth;
+    let drop = self.undo.len().saturating_sub(depth);
+    self.undo.drain(..drop);
+  }
+
+  pub(crate) fn clear(&mut self) {
+    self.undo.clear();
+    self.redo.clear();
+  }
+}
+
+impl Default for EditHistory {
+  fn default() -> Self {
+    Self { undo: VecDeque::new(), redo: vec![], depth: DEFAULT_HISTORY_DEPTH, sealed: false }
+  }
+}
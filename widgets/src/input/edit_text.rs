@@ -1,20 +1,42 @@
 use std::ops::Range;
 
-use ribir_core::prelude::{CowArc, Substr};
+use ribir_core::prelude::{CowArc, Substr, TextSpan};
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
-pub trait BaseText: Eq {
+pub trait BaseText: PartialEq {
   fn measure_bytes(&self, byte_from: usize, char_len: isize) -> usize;
   fn select_token(&self, byte_from: usize) -> Range<usize>;
   fn substr(&self, rg: Range<usize>) -> Substr;
   fn len(&self) -> usize;
   fn is_empty(&self) -> bool { self.len() == 0 }
+
+  /// Return the byte range of the paragraph that contains `byte_from`, the
+  /// line breaks are not included.
+  fn select_paragraph(&self, byte_from: usize) -> Range<usize> {
+    let text = self.substr(0..self.len());
+    let text: &str = &text;
+    let at = byte_from.min(text.len());
+    let start = text[..at]
+      .rfind(['\r', '\n'])
+      .map_or(0, |i| i + 1);
+    let end = text[at..]
+      .find(['\r', '\n'])
+      .map_or(text.len(), |i| at + i);
+    Range { start, end }
+  }
 }
 
 pub trait EditText: BaseText {
   fn insert_str(&mut self, at: usize, s: &str) -> usize;
 
   fn del_rg_str(&mut self, rg: Range<usize>) -> Range<usize>;
+
+  /// The styles of the text that the edit history keeps, a plain text has no
+  /// styles.
+  fn spans(&self) -> &[TextSpan] { &[] }
+
+  /// Restore the styles kept by the edit history.
+  fn set_spans(&mut self, _spans: Vec<TextSpan>) {}
 }
 
 impl BaseText for CowArc<str> {
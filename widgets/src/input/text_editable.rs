@@ -4,6 +4,7 @@ use ribir_core::prelude::*;
 
 use super::{
   CaretPosition,
+  edit_history::{EditHistory, EditKind, EditStep},
   edit_text::EditText,
   text_selectable::{Selection, TextSelectable},
 };
@@ -18,6 +19,11 @@ class_names! {
 pub struct BasicEditor<T: 'static> {
   host: TextSelectable<T>,
   pre_edit: Option<PreEditState>,
+  #[declare(skip)]
+  history: EditHistory,
+  /// The IME ends the pre-edit, and the next chars are the committed text.
+  #[declare(skip)]
+  ime_committing: bool,
 }
 
 impl<T: Default + VisualText + EditText + Clone + 'static> Compose for BasicEditor<T> {
@@ -43,7 +49,15 @@ impl<T: Default + VisualText + EditText + Clone + 'static> Compose for BasicEdit
           },
           on_key_down: move |k| {
             let mut this = $this.write();
-            if !this.keys_handle(k) {
+            if !this.edit_with_key(k) {
+              this.forget_modifies();
+            }
+          },
+          on_command: move |e| {
+            let mut this = $this.write();
+            if this.edit_with_command(e.command()) {
+              e.handle();
+            } else {
               this.forget_modifies();
             }
           },
@@ -98,6 +112,69 @@ impl<T: EditText + 'static> BasicEditor<T> {
       .unwrap_or_default()
   }
 
+  /// Undo the last edit, return whether there is an edit to undo.
+  pub fn undo(&mut self) -> bool {
+    let Some(step) = self.history.take_undo() else { return false };
+    self.del_rg_str(step.inserted_rg());
+    self.insert_str(step.at, &step.deleted);
+    if let Some((before, _)) = &step.styles {
+      self.text_mut().set_spans(before.clone());
+    }
+    self.host.selection = step.before;
+    self.history.push_redo(step);
+    true
+  }
+
+  /// Redo the last undone edit, return whether there is an edit to redo.
+  pub fn redo(&mut self) -> bool {
+    let Some(step) = self.history.take_redo() else { return false };
+    self.del_rg_str(step.deleted_rg());
+    self.insert_str(step.at, &step.inserted);
+    if let Some((_, after)) = &step.styles {
+      self.text_mut().set_spans(after.clone());
+    }
+    self.host.selection = step.after;
+    self.history.push_undo(step);
+    true
+  }
+
+  pub fn can_undo(&self) -> bool { self.history.can_undo() }
+
+  pub fn can_redo(&self) -> bool { self.history.can_redo() }
+
+  /// The maximum number of the edits that can undo.
+  pub fn history_depth(&self) -> usize { self.history.depth() }
+
+  /// Set the maximum number of the edits that can undo, the oldest edits are
+  /// forgotten if there are more.
+  pub fn set_history_depth(&mut self, depth: usize) { self.history.set_depth(depth); }
+
+  /// Forget all the edits, call it when the text is replaced.
+  pub fn clear_history(&mut self) { self.history.clear(); }
+
+  /// Change the styles of the selected text by `f`, and record the change as
+  /// an edit.
+  pub(crate) fn restyle(&mut self, f: impl FnOnce(&mut T, Range<usize>)) {
+    let rg = self.cluster_rg();
+    if rg.is_empty() {
+      return;
+    }
+    let spans = self.text().spans().to_vec();
+    f(self.text_mut(), rg.clone());
+    let styles = self.styles_change(spans);
+    let selection = self.host.selection;
+    let step = EditStep {
+      kind: EditKind::Other,
+      at: rg.start,
+      deleted: String::new(),
+      inserted: String::new(),
+      before: selection,
+      after: selection,
+      styles,
+    };
+    self.history.record(step);
+  }
+
   fn chars_handle(&mut self, event: &CharsEvent) -> bool {
     if event.common.with_command_key() {
       return false;
@@ -108,50 +185,38 @@ impl<T: EditText + 'static> BasicEditor<T> {
       .chars()
       .filter(|c| !c.is_control() || c.is_ascii_whitespace())
       .collect::<String>();
+    let kind =
+      if std::mem::take(&mut self.ime_committing) { EditKind::Other } else { EditKind::Typing };
     if !chars.is_empty() {
-      self.insert(&chars);
+      self.record_insert(&chars, kind);
       return true;
     }
     false
   }
 
-  fn keys_handle(&mut self, event: &KeyboardEvent) -> bool {
-    let mut deal = false;
-    if event.with_command_key() {
-      deal = self.edit_with_command(event);
-    }
-    if !deal {
-      deal = self.edit_with_key(event);
-    }
-    deal
-  }
-
-  fn edit_with_command(&mut self, event: &KeyboardEvent) -> bool {
-    if !event.with_command_key() {
-      return false;
-    }
-    // use the physical key to make sure the keyboard with different
-    // layout use the same key as shortcut.
-    match event.key_code() {
-      PhysicalKey::Code(KeyCode::KeyV) => {
+  fn edit_with_command(&mut self, command: &CommandId) -> bool {
+    match command {
+      c if c == &CommandId::PASTE => {
         let clipboard = AppCtx::clipboard();
         let txt = clipboard.borrow_mut().read_text();
         if let Ok(txt) = txt {
-          self.insert(&txt);
+          self.record_insert(&txt, EditKind::Other);
           return true;
         }
       }
-      PhysicalKey::Code(KeyCode::KeyX) => {
+      c if c == &CommandId::CUT => {
         let rg = self.cluster_rg();
         if !rg.is_empty() {
-          let txt = self.substr(rg).to_string();
-          self.del_sel();
+          let txt = self.substr(rg.clone()).to_string();
+          self.record_delete(rg, EditKind::Other);
           let clipboard = AppCtx::clipboard();
           let _ = clipboard.borrow_mut().clear();
           let _ = clipboard.borrow_mut().write_text(&txt);
           return true;
         }
       }
+      c if c == &CommandId::UNDO => return self.undo(),
+      c if c == &CommandId::REDO => return self.redo(),
       _ => {}
     };
     false
@@ -161,19 +226,23 @@ impl<T: EditText + 'static> BasicEditor<T> {
     match key.key() {
       VirtualKey::Named(NamedKey::Backspace) => {
         let mut rg = self.cluster_rg();
+        let mut kind = EditKind::Other;
         if rg.is_empty() {
           let len = self.measure_bytes(rg.start, -1);
           rg = Range { start: rg.start - len, end: rg.start };
+          kind = EditKind::Backspace;
         }
-        !self.delete(rg).is_empty()
+        !self.record_delete(rg, kind).is_empty()
       }
       VirtualKey::Named(NamedKey::Delete) => {
         let mut rg = self.cluster_rg();
+        let mut kind = EditKind::Other;
         if rg.is_empty() {
           let len = self.measure_bytes(rg.start, 1);
           rg = Range { start: rg.start, end: rg.start + len };
+          kind = EditKind::Delete;
         }
-        !self.delete(rg).is_empty()
+        !self.record_delete(rg, kind).is_empty()
       }
       _ => false,
     }
@@ -189,6 +258,49 @@ impl<T: EditText + 'static> BasicEditor<T> {
 
   fn del_sel(&mut self) -> Range<usize> { self.delete(self.cluster_rg()) }
 
+  /// Replace the selection with the chars and record the edit.
+  fn record_insert(&mut self, chars: &str, kind: EditKind) -> usize {
+    let before = self.host.selection;
+    let spans = self.text().spans().to_vec();
+    let at = self.cluster_rg().start;
+    let deleted = self.clamped_substr(self.cluster_rg());
+    let len = self.insert(chars);
+    let inserted = self.substr(at..at + len).to_string();
+    let after = self.host.selection;
+    let styles = self.styles_change(spans);
+    self
+      .history
+      .record(EditStep { kind, at, deleted, inserted, before, after, styles });
+    len
+  }
+
+  /// Delete the text in the range and record the edit.
+  fn record_delete(&mut self, rg: Range<usize>, kind: EditKind) -> Range<usize> {
+    let before = self.host.selection;
+    let spans = self.text().spans().to_vec();
+    let deleted = self.clamped_substr(rg.clone());
+    let rg = self.delete(rg);
+    let after = self.host.selection;
+    let styles = self.styles_change(spans);
+    let inserted = String::new();
+    let step = EditStep { kind, at: rg.start, deleted, inserted, before, after, styles };
+    self.history.record(step);
+    rg
+  }
+
+  /// The styles before and after an edit, `None` if the text has no styles.
+  fn styles_change(&self, before: Vec<TextSpan>) -> Option<(Vec<TextSpan>, Vec<TextSpan>)> {
+    let after = self.text().spans();
+    (!before.is_empty() || !after.is_empty()).then(|| (before, after.to_vec()))
+  }
+
+  fn clamped_substr(&self, rg: Range<usize>) -> String {
+    let len = self.text().len();
+    self
+      .substr(rg.start.min(len)..rg.end.min(len))
+      .to_string()
+  }
+
   fn delete(&mut self, rg: Range<usize>) -> Range<usize> {
     let del_rg = self.del_rg_str(rg);
     self.host.selection = Selection::splat(CaretPosition { cluster: del_rg.start, position: None });
@@ -200,7 +312,8 @@ impl<T: EditText + 'static> BasicEditor<T> {
   fn process_pre_edit(&mut self, e: &ImePreEditEvent) {
     match &e.pre_edit {
       ImePreEdit::Begin => {
-        self.del_sel();
+        self.history.seal();
+        self.record_delete(self.cluster_rg(), EditKind::Other);
         self.pre_edit = Some(PreEditState { position: self.cluster_rg().start, value: None });
       }
       ImePreEdit::PreEdit { value, cursor } => {
@@ -235,6 +348,7 @@ impl<T: EditText + 'static> BasicEditor<T> {
         if let Some(PreEditState { value: Some(txt), position, .. }) = self.pre_edit.take() {
           self.delete(Range { start: position, end: position + txt.len() });
         }
+        self.ime_committing = true;
       }
     }
   }
@@ -90,7 +90,10 @@ impl<T: VisualText + 'static> Render for TextGlyphs<T> {
 
     let style = Provider::of::<PaintingStyle>(ctx).map(|p| p.clone());
     let visual_glyphs = self.glyphs().unwrap();
-    paint_text(ctx.painter(), &visual_glyphs, style.unwrap_or(PaintingStyle::Fill), box_rect);
+    let style = style.unwrap_or(PaintingStyle::Fill);
+    self
+      .text
+      .paint(ctx.painter(), style, &visual_glyphs, box_rect);
   }
 }
 
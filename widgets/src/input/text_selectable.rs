@@ -39,7 +39,7 @@ where
   pub text: TextGlyphs<T>,
 }
 
-#[derive(Copy, Clone, Default)]
+#[derive(Copy, Clone, Default, Debug, PartialEq, Eq)]
 pub struct Selection {
   pub from: CaretPosition,
   pub to: CaretPosition,
@@ -88,6 +88,18 @@ impl<T: VisualText + Clone + 'static> Compose for TextSelectable<T> {
             *$selection.write() = new_sel;
           }
         },
+        on_command: move |e| {
+          if e.is_handled() {
+            return;
+          }
+          let new_sel = $this.select_with_command(e.command());
+          if let Some(new_sel) = new_sel {
+            e.handle();
+            if let Some(new_sel) = new_sel {
+              *$selection.write() = new_sel;
+            }
+          }
+        },
         on_pointer_down: move |e| {
           let caret = $this.glyphs().map(|g| g.caret_position_from_pos(e.position()));
           if let Some(caret) = caret {
@@ -110,6 +122,16 @@ impl<T: VisualText + Clone + 'static> Compose for TextSelectable<T> {
             $selection.write().to = CaretPosition{ cluster: rg.end, position: None };
           }
         },
+        on_triple_tap: move |e| {
+          let caret = $this
+            .glyphs()
+            .map(|glyphs| glyphs.caret_position_from_pos(e.position()));
+          if let Some(caret) = caret {
+            let rg = $this.text().select_paragraph(caret.cluster);
+            $selection.write().from = CaretPosition{ cluster: rg.start, position: None };
+            $selection.write().to = CaretPosition{ cluster: rg.end, position: None };
+          }
+        },
         @Stack {
           @OnlySizedByParent {
             @Stack {
@@ -156,9 +178,6 @@ impl Selection {
 
 impl<T: BaseText> TextSelectable<T> {
   fn select_with_key(&self, event: &KeyboardEvent) -> Option<Selection> {
-    if let Ok(selection) = self.deal_with_command(event) {
-      return selection;
-    }
     let glyphs = self.glyphs()?;
     let cur_sel = &self.selection;
     let text = &self.text;
@@ -200,13 +219,12 @@ impl<T: BaseText> TextSelectable<T> {
     Some(Selection { from, to: new_caret })
   }
 
-  fn deal_with_command(&self, event: &KeyboardEvent) -> Result<Option<Selection>, ()> {
-    if !event.with_command_key() {
-      return Err(());
-    }
+  /// Handle the copy and select all commands, return `None` if the command is
+  /// not handled, otherwise return the new selection if it's changed.
+  fn select_with_command(&self, command: &CommandId) -> Option<Option<Selection>> {
     let text = self.text.text();
-    match event.key_code() {
-      PhysicalKey::Code(KeyCode::KeyC) => {
+    match command {
+      c if c == &CommandId::COPY => {
         let rg = self.cluster_rg();
         let text = text.substr(rg);
         if !text.is_empty() {
@@ -214,20 +232,20 @@ impl<T: BaseText> TextSelectable<T> {
           let _ = clipboard.borrow_mut().clear();
           let _ = clipboard.borrow_mut().write_text(&text);
         }
-        Ok(None)
+        Some(None)
       }
-      PhysicalKey::Code(KeyCode::KeyA) => {
+      c if c == &CommandId::SELECT_ALL => {
         if text.len() > 0 {
           let selection = Selection {
             from: CaretPosition { cluster: 0, position: None },
             to: CaretPosition { cluster: text.len(), position: None },
           };
-          Ok(Some(selection))
+          Some(Some(selection))
         } else {
-          Ok(None)
+          Some(None)
         }
       }
-      _ => Err(()),
+      _ => None,
     }
   }
 }
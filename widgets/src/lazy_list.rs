@@ -0,0 +1,947 @@
+use std::{
+  cell::{Cell, RefCell},
+  ops::Range,
+  rc::Rc,
+};
+
+use ribir_core::prelude::*;
+
+use crate::{layout::Direction, scrollbar::Scrollbar};
+
+/// Describe how to get the extent of an item along the main axis of a lazy
+/// widget.
+///
+/// The extent of the items that are not built yet is still needed to
+/// calculate the size of the whole content, so the scroll bars and the scroll
+/// position keep stable while the items are built and disposed.
+#[derive(Clone)]
+pub enum ItemExtent {
+  /// All the items have the same extent.
+  Fixed(f32),
+  /// The extent of every item is known before it is built, and given by the
+  /// index of the item.
+  Variable(Rc<dyn Fn(usize) -> f32>),
+  /// The extent of the items is unknown before they are built. The items not
+  /// built yet use the estimated extent, and the built items use their real
+  /// size after laid out.
+  Estimated(f32),
+}
+
+/// The builder to build the item of a lazy widget by its index.
+#[derive(Clone)]
+pub struct ItemBuilder(Rc<dyn Fn(usize) -> Widget<'static>>);
+
+impl ItemExtent {
+  fn same_as(&self, other: &Self) -> bool {
+    match (self, other) {
+      (Self::Fixed(a), Self::Fixed(b)) | (Self::Estimated(a), Self::Estimated(b)) => a == b,
+      (Self::Variable(a), Self::Variable(b)) => Rc::ptr_eq(a, b),
+      _ => false,
+    }
+  }
+}
+
+impl ItemBuilder {
+  fn same_as(&self, other: &Self) -> bool { Rc::ptr_eq(&self.0, &other.0) }
+}
+
+/// A virtualized list that only builds the items inside the viewport and an
+/// overscan band around it.
+///
+/// Rather than building all its children up front, `LazyList` accepts a count
+/// of items and a builder to build the item by its index. When the list is
+/// scrolled, the items that move out of the built range are disposed, and
+/// their slots are recycled to host the items that move in.
+///
+/// The list is built on a `Scrollbar`, use `LazyList::scrollable_widget` to
+/// access the `ScrollableWidget` of it. Because an item may not be built,
+/// use `LazyList::jump_to_item` and `LazyList::visible_item` to scroll to an
+/// item by its index.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _list = fn_widget! {
+///   @LazyList {
+///     count: 10000usize,
+///     item_extent: ItemExtent::Fixed(24.),
+///     item_builder: |idx: usize| @Text { text: idx.to_string() }.into_widget(),
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct LazyList {
+  /// The count of the items in the list.
+  pub count: usize,
+  /// The extent of the items along the main axis.
+  #[declare(default = ItemExtent::Estimated(48.))]
+  pub item_extent: ItemExtent,
+  /// The space between two items along the main axis.
+  #[declare(default)]
+  pub spacing: f32,
+  /// The pixels beyond the viewport, in both leading and trailing edges, that
+  /// the items should be built in advance.
+  #[declare(default = 250.)]
+  pub overscan: f32,
+  /// The direction of the list.
+  #[declare(default = Direction::Vertical)]
+  pub direction: Direction,
+  #[declare(custom)]
+  item_builder: ItemBuilder,
+  #[declare(skip)]
+  lazy: LazyInner,
+}
+
+/// A virtualized grid that only builds the items inside the viewport and an
+/// overscan band around it.
+///
+/// `LazyGrid` places its items in lines, every line has `cross_axis_cnt`
+/// items, and the extent of a line is the extent of its largest item. The
+/// cross axis is divided evenly by the items of a line. Like the `LazyList`,
+/// only the lines near the viewport are built.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir_core::prelude::*;
+/// use ribir_widgets::prelude::*;
+///
+/// let _grid = fn_widget! {
+///   @LazyGrid {
+///     count: 10000usize,
+///     cross_axis_cnt: 4usize,
+///     item_extent: ItemExtent::Fixed(64.),
+///     item_builder: |idx: usize| @Text { text: idx.to_string() }.into_widget(),
+///   }
+/// };
+/// ```
+#[derive(Declare)]
+pub struct LazyGrid {
+  /// The count of the items in the grid.
+  pub count: usize,
+  /// The count of the items in a line.
+  #[declare(default = 1usize)]
+  pub cross_axis_cnt: usize,
+  /// The extent of the items along the main axis.
+  #[declare(default = ItemExtent::Estimated(48.))]
+  pub item_extent: ItemExtent,
+  /// The space between two lines.
+  #[declare(default)]
+  pub main_spacing: f32,
+  /// The space between two items in a line.
+  #[declare(default)]
+  pub cross_spacing: f32,
+  /// The pixels beyond the viewport, in both leading and trailing edges, that
+  /// the items should be built in advance.
+  #[declare(default = 250.)]
+  pub overscan: f32,
+  /// The direction of the main axis.
+  #[declare(default = Direction::Vertical)]
+  pub direction: Direction,
+  #[declare(custom)]
+  item_builder: ItemBuilder,
+  #[declare(skip)]
+  lazy: LazyInner,
+}
+
+pub trait LazyListDeclarerCustomExtend {
+  /// Initialize the builder to build the item by its index.
+  fn item_builder(self, f: impl Fn(usize) -> Widget<'static> + 'static) -> Self;
+}
+
+impl LazyListDeclarerCustomExtend for FatObj<LazyListDeclarer> {
+  fn item_builder(mut self, f: impl Fn(usize) -> Widget<'static> + 'static) -> Self {
+    self.item_builder = Some(DeclareInit::Value(ItemBuilder(Rc::new(f))));
+    self
+  }
+}
+
+pub trait LazyGridDeclarerCustomExtend {
+  /// Initialize the builder to build the item by its index.
+  fn item_builder(self, f: impl Fn(usize) -> Widget<'static> + 'static) -> Self;
+}
+
+impl LazyGridDeclarerCustomExtend for FatObj<LazyGridDeclarer> {
+  fn item_builder(mut self, f: impl Fn(usize) -> Widget<'static> + 'static) -> Self {
+    self.item_builder = Some(DeclareInit::Value(ItemBuilder(Rc::new(f))));
+    self
+  }
+}
+
+macro_rules! impl_lazy_common_methods {
+  ($name:ident) => {
+    impl $name {
+      /// Return the `ScrollableWidget` of the widget. You can utilize it to
+      /// scroll the content or access scroll information.
+      pub fn scrollable_widget(&self) -> &Stateful<ScrollableWidget> { &self.lazy.scroll }
+
+      /// Replace the builder of the items, all the built items will be
+      /// rebuilt.
+      pub fn set_item_builder(&mut self, f: impl Fn(usize) -> Widget<'static> + 'static) {
+        self.item_builder = ItemBuilder(Rc::new(f));
+      }
+
+      /// Return the range of the items that are built.
+      pub fn built_items(&self) -> Range<usize> { self.lazy.built_items() }
+
+      /// Return the rect of the item in the scroll content, whether the item is
+      /// built or not. For an item not built yet, its extent may be estimated.
+      pub fn item_rect(&self, index: usize) -> Option<Rect> {
+        self.lazy.layout.borrow().item_rect(index)
+      }
+
+      /// Scroll to make the item at the leading edge of the view.
+      pub fn jump_to_item(&self, index: usize) {
+        let anchor = match self.direction {
+          Direction::Horizontal => Anchor::left(0.),
+          Direction::Vertical => Anchor::top(0.),
+        };
+        self.lazy.visible_item(index, anchor);
+      }
+
+      /// Keep the item visible in the scroll view with the given anchor
+      /// relative to the view, like `ScrollableWidget::visible_widget` but
+      /// works for the item that has not been built yet.
+      ///
+      /// If the extent of the item is estimated, the scroll position will be
+      /// corrected after the item is built and laid out.
+      pub fn visible_item(&self, index: usize, anchor: Anchor) {
+        self.lazy.visible_item(index, anchor);
+      }
+    }
+  };
+}
+
+impl_lazy_common_methods!(LazyList);
+impl_lazy_common_methods!(LazyGrid);
+
+impl Compose for LazyList {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let params = move |this: &Self| LazyParams {
+      count: this.count,
+      cross_axis_cnt: 1,
+      item_extent: this.item_extent.clone(),
+      main_spacing: this.spacing,
+      cross_spacing: 0.,
+      overscan: this.overscan,
+      direction: this.direction,
+      item_builder: this.item_builder.clone(),
+    };
+    lazy_compose(this, |this| &this.lazy, params)
+  }
+}
+
+impl Compose for LazyGrid {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    let params = move |this: &Self| LazyParams {
+      count: this.count,
+      cross_axis_cnt: this.cross_axis_cnt.max(1),
+      item_extent: this.item_extent.clone(),
+      main_spacing: this.main_spacing,
+      cross_spacing: this.cross_spacing,
+      overscan: this.overscan,
+      direction: this.direction,
+      item_builder: this.item_builder.clone(),
+    };
+    lazy_compose(this, |this| &this.lazy, params)
+  }
+}
+
+struct LazyParams {
+  count: usize,
+  cross_axis_cnt: usize,
+  item_extent: ItemExtent,
+  main_spacing: f32,
+  cross_spacing: f32,
+  overscan: f32,
+  direction: Direction,
+  item_builder: ItemBuilder,
+}
+
+fn lazy_compose<T: 'static>(
+  this: impl StateWriter<Value = T>, inner: fn(&T) -> &LazyInner,
+  params: impl Fn(&T) -> LazyParams + 'static,
+) -> Widget<'static> {
+  let lazy = inner(&this.read()).clone();
+  lazy.apply_params(params(&this.read()));
+
+  fn_widget! {
+    let lazy2 = lazy.clone();
+    let u = this.modifies().subscribe(move |_| {
+      lazy2.apply_params(params(&$this));
+      lazy2.sync_window();
+    });
+
+    let scroll = lazy.scroll.clone_writer();
+    let lazy2 = lazy.clone();
+    let u2 = watch! {
+      let scroll = $scroll;
+      (scroll.get_scroll_pos(), scroll.scroll_view_size(), scroll.scroll_content_size())
+    }
+    .distinct_until_changed()
+    .subscribe(move |_| {
+      lazy2.sync_window();
+      lazy2.apply_pending_reveal();
+    });
+
+    let window = lazy.window.clone_watcher();
+    // Not use `distinct_pipe!` for the same reason as the slot below, the list
+    // may be built and laid out in the same frame, e.g. in an overlay.
+    let last_slots = Rc::new(Cell::new(window.read().slots));
+    let slots = pipe!($window.slots)
+      .value_chain(move |s| s.filter(move |(_, v)| last_slots.replace(*v) != *v).box_it())
+      .map(move |slots| {
+        (0..slots)
+          .map(|slot| {
+            let window = window.clone_watcher();
+            // Not use `distinct_pipe!` here, a slot may be created and changed in
+            // the same frame, and the change will be skipped as the initial value.
+            let key = move |w: &LazyWindow| (w.item_of_slot(slot), w.generation);
+            let last = Rc::new(Cell::new(key(&window.read())));
+            pipe!(key(&$window))
+              .value_chain(move |s| s.filter(move |(_, k)| last.replace(*k) != *k).box_it())
+              .map(move |(item, _)| match item {
+                Some(index) => {
+                  let builder = $window.builder.clone();
+                  builder.map_or_else(|| Void.into_widget(), |b| (b.0)(index))
+                }
+                None => Void.into_widget(),
+              })
+              .into_widget()
+          })
+          .collect::<Vec<_>>()
+      });
+
+    let lazy2 = lazy.clone();
+    let panel = FatObj::new(LazyPanel { lazy: lazy.clone() });
+    let scrollbar = FatObj::new(Scrollbar { scroll: lazy.scroll.clone_writer() });
+    @ $scrollbar {
+      on_disposed: move |_| {
+        u.unsubscribe();
+        u2.unsubscribe();
+      },
+      @ $panel {
+        on_performed_layout: move |_| {
+          lazy2.sync_window();
+          lazy2.apply_pending_reveal();
+        },
+        @ { slots }
+      }
+    }
+  }
+  .into_widget()
+}
+
+/// The states shared by the lazy widget and its inner parts.
+#[derive(Default)]
+struct LazyInner {
+  scroll: Stateful<ScrollableWidget>,
+  layout: Rc<RefCell<LazyLayout>>,
+  window: Stateful<LazyWindow>,
+}
+
+impl Clone for LazyInner {
+  fn clone(&self) -> Self {
+    Self {
+      scroll: self.scroll.clone_writer(),
+      layout: self.layout.clone(),
+      window: self.window.clone_writer(),
+    }
+  }
+}
+
+/// The range of the lines that are built, and how they are assigned to the
+/// slots.
+#[derive(Default)]
+struct LazyWindow {
+  /// The range of the built items.
+  items: Range<usize>,
+  /// The count of the slots, every slot hosts an item. A slot is recycled to
+  /// host another item when its item moves out of the built range.
+  slots: usize,
+  /// Increased every time the items need to be rebuilt.
+  generation: usize,
+  builder: Option<ItemBuilder>,
+}
+
+impl LazyWindow {
+  fn item_of_slot(&self, slot: usize) -> Option<usize> {
+    item_of_slot(slot, self.slots, &self.items)
+  }
+}
+
+fn item_of_slot(slot: usize, slots: usize, items: &Range<usize>) -> Option<usize> {
+  if slots == 0 {
+    return None;
+  }
+  let index = items.start + (slot + slots - items.start % slots) % slots;
+  (index < items.end).then_some(index)
+}
+
+/// The main axis information of all the lines, built or not.
+struct LazyLayout {
+  count: usize,
+  cross_axis_cnt: usize,
+  item_extent: ItemExtent,
+  main_spacing: f32,
+  cross_spacing: f32,
+  overscan: f32,
+  direction: Direction,
+  /// The measured extents of the lines, `NaN` if not measured.
+  measured: Vec<f32>,
+  /// The start offset of every line, and the end offset of the last line.
+  offsets: Vec<f32>,
+  offsets_dirty: bool,
+  /// The cross size of the content in the last layout.
+  cross_size: f32,
+  pending_reveal: Option<(usize, Anchor)>,
+}
+
+impl Default for LazyLayout {
+  fn default() -> Self {
+    Self {
+      count: 0,
+      cross_axis_cnt: 1,
+      item_extent: ItemExtent::Estimated(48.),
+      main_spacing: 0.,
+      cross_spacing: 0.,
+      overscan: 0.,
+      direction: Direction::Vertical,
+      measured: vec![],
+      offsets: vec![0.],
+      offsets_dirty: false,
+      cross_size: 0.,
+      pending_reveal: None,
+    }
+  }
+}
+
+impl LazyLayout {
+  fn lines(&self) -> usize { self.count.div_ceil(self.cross_axis_cnt) }
+
+  fn line_extent(&self, line: usize) -> f32 {
+    match &self.item_extent {
+      ItemExtent::Fixed(e) => *e,
+      ItemExtent::Variable(f) => (0..self.cross_axis_cnt)
+        .map(|i| line * self.cross_axis_cnt + i)
+        .take_while(|idx| *idx < self.count)
+        .fold(0., |max, idx| f(idx).max(max)),
+      ItemExtent::Estimated(e) => {
+        let m = self
+          .measured
+          .get(line)
+          .copied()
+          .unwrap_or(f32::NAN);
+        if m.is_nan() { *e } else { m }
+      }
+    }
+  }
+
+  fn is_line_measured(&self, line: usize) -> bool {
+    match self.item_extent {
+      ItemExtent::Estimated(_) => self
+        .measured
+        .get(line)
+        .is_some_and(|m| !m.is_nan()),
+      _ => true,
+    }
+  }
+
+  fn update_offsets(&mut self) {
+    if !self.offsets_dirty {
+      return;
+    }
+    let lines = self.lines();
+    let mut offsets = Vec::with_capacity(lines + 1);
+    let mut offset = 0.;
+    for line in 0..lines {
+      offsets.push(offset);
+      offset += self.line_extent(line) + self.main_spacing;
+    }
+    offsets.push(offset);
+    self.offsets = offsets;
+    self.offsets_dirty = false;
+  }
+
+  fn line_offset(&self, line: usize) -> f32 {
+    match self.item_extent {
+      ItemExtent::Fixed(e) => line as f32 * (e + self.main_spacing),
+      _ => self.offsets[line.min(self.offsets.len() - 1)],
+    }
+  }
+
+  fn total_extent(&self) -> f32 {
+    let lines = self.lines();
+    if lines == 0 { 0. } else { self.line_offset(lines) - self.main_spacing }
+  }
+
+  /// Return the line that contains the offset.
+  fn line_at(&self, offset: f32) -> usize {
+    let lines = self.lines();
+    if lines == 0 || offset <= 0. {
+      return 0;
+    }
+    let line = match self.item_extent {
+      ItemExtent::Fixed(e) => {
+        let stride = e + self.main_spacing;
+        if stride > 0. { (offset / stride) as usize } else { 0 }
+      }
+      _ => self.offsets[..lines]
+        .partition_point(|o| *o <= offset)
+        .saturating_sub(1),
+    };
+    line.min(lines - 1)
+  }
+
+  /// Return the range of lines intersecting with the range of the main axis.
+  fn lines_in(&self, start: f32, end: f32) -> Range<usize> {
+    let lines = self.lines();
+    if lines == 0 || end <= 0. || start >= self.total_extent() {
+      return 0..0;
+    }
+    let first = self.line_at(start);
+    let last = match self.item_extent {
+      ItemExtent::Fixed(e) => {
+        let stride = e + self.main_spacing;
+        if stride > 0. { (end / stride).ceil() as usize } else { lines }
+      }
+      _ => self.offsets[..lines].partition_point(|o| *o < end),
+    };
+    first..last.clamp(first + 1, lines)
+  }
+
+  fn item_rect(&self, index: usize) -> Option<Rect> {
+    if index >= self.count {
+      return None;
+    }
+    let line = index / self.cross_axis_cnt;
+    let col = index % self.cross_axis_cnt;
+    let main_pos = self.line_offset(line);
+    let main = self.line_extent(line);
+    let cross = self.cell_cross_extent();
+    let cross_pos = col as f32 * (cross + self.cross_spacing);
+    Some(match self.direction {
+      Direction::Horizontal => Rect::new(Point::new(main_pos, cross_pos), Size::new(main, cross)),
+      Direction::Vertical => Rect::new(Point::new(cross_pos, main_pos), Size::new(cross, main)),
+    })
+  }
+
+  fn cell_cross_extent(&self) -> f32 {
+    let cnt = self.cross_axis_cnt as f32;
+    ((self.cross_size - self.cross_spacing * (cnt - 1.)) / cnt).max(0.)
+  }
+
+  fn main_of(&self, p: Point) -> f32 {
+    match self.direction {
+      Direction::Horizontal => p.x,
+      Direction::Vertical => p.y,
+    }
+  }
+
+  fn main_of_size(&self, s: Size) -> f32 {
+    match self.direction {
+      Direction::Horizontal => s.width,
+      Direction::Vertical => s.height,
+    }
+  }
+}
+
+impl LazyInner {
+  fn apply_params(&self, params: LazyParams) {
+    let LazyParams {
+      count,
+      cross_axis_cnt,
+      item_extent,
+      main_spacing,
+      cross_spacing,
+      overscan,
+      direction,
+      item_builder,
+    } = params;
+    let mut content_changed = !self
+      .window
+      .read()
+      .builder
+      .as_ref()
+      .is_some_and(|b| b.same_as(&item_builder));
+    {
+      let mut layout = self.layout.borrow_mut();
+      content_changed |= count != layout.count || !item_extent.same_as(&layout.item_extent);
+      let lines_changed = count != layout.count || cross_axis_cnt != layout.cross_axis_cnt;
+      if lines_changed || !matches!(item_extent, ItemExtent::Estimated(_)) {
+        layout.measured.clear();
+      }
+      let lines = count.div_ceil(cross_axis_cnt);
+      layout.measured.resize(lines, f32::NAN);
+      layout.count = count;
+      layout.cross_axis_cnt = cross_axis_cnt;
+      layout.item_extent = item_extent;
+      layout.main_spacing = main_spacing;
+      layout.cross_spacing = cross_spacing;
+      layout.overscan = overscan;
+      layout.direction = direction;
+      layout.offsets_dirty = true;
+      layout.update_offsets();
+    }
+
+    let scrollable = match direction {
+      Direction::Horizontal => Scrollable::X,
+      Direction::Vertical => Scrollable::Y,
+    };
+    if self.scroll.read().scrollable != scrollable {
+      self.scroll.write().scrollable = scrollable;
+    }
+
+    let mut window = self.window.write();
+    window.builder = Some(item_builder);
+    // Only rebuild the built items if their content may change.
+    if content_changed {
+      window.generation += 1;
+    }
+    let items = window.items.start.min(count)..window.items.end.min(count);
+    window.items = items;
+  }
+
+  fn built_items(&self) -> Range<usize> { self.window.read().items.clone() }
+
+  /// Update the built range of lines if the viewport is not covered by it.
+  fn sync_window(&self) {
+    let (pos, page) = {
+      let scroll = self.scroll.read();
+      (scroll.get_scroll_pos(), scroll.scroll_view_size())
+    };
+
+    let mut layout = self.layout.borrow_mut();
+    layout.update_offsets();
+    let start = layout.main_of(pos);
+    let end = start + layout.main_of_size(page);
+    let visible = layout.lines_in(start, end);
+    let overscan = layout.overscan;
+    let target = layout.lines_in(start - overscan, end + overscan);
+    let cross = layout.cross_axis_cnt;
+    let count = layout.count;
+    drop(layout);
+
+    let window = self.window.read();
+    let built = window.items.start / cross..window.items.end.div_ceil(cross);
+    let covered = visible.is_empty()
+      || (built.start <= visible.start && visible.end <= built.end && !built.is_empty());
+    let too_large = built.len() > target.len() * 2 + 1;
+    if covered && !too_large && window.items.end <= count {
+      return;
+    }
+    let items = (target.start * cross).min(count)..(target.end * cross).min(count);
+    if items == window.items {
+      return;
+    }
+    // Reserve a line for the partial lines at the both edges of the view, so
+    // scrolling not always changes the count of the slots.
+    let grow_slots = items.len() > window.slots;
+    drop(window);
+
+    let mut window = self.window.write();
+    if grow_slots {
+      window.slots = items.len() + cross;
+    }
+    window.items = items;
+  }
+
+  fn visible_item(&self, index: usize, anchor: Anchor) {
+    let mut layout = self.layout.borrow_mut();
+    layout.update_offsets();
+    if index >= layout.count {
+      return;
+    }
+    layout.pending_reveal = Some((index, anchor));
+    drop(layout);
+    self.apply_pending_reveal();
+  }
+
+  /// Scroll to the pending item, and keep it pending until the item is
+  /// measured and the content size of the scrollable widget is synced.
+  fn apply_pending_reveal(&self) {
+    let mut layout = self.layout.borrow_mut();
+    let Some((index, anchor)) = layout.pending_reveal else { return };
+    let Some(rect) = layout.item_rect(index) else {
+      layout.pending_reveal = None;
+      return;
+    };
+
+    let content = self.scroll.read().scroll_content_size();
+    let synced = layout.main_of_size(content) >= layout.total_extent() - f32::EPSILON;
+    if synced && layout.is_line_measured(index / layout.cross_axis_cnt) {
+      layout.pending_reveal = None;
+    }
+    drop(layout);
+
+    let mut scroll = self.scroll.write();
+    let old = scroll.get_scroll_pos();
+    scroll.visible_content_box(rect, anchor);
+    if scroll.get_scroll_pos() == old {
+      scroll.forget_modifies();
+    }
+  }
+}
+
+/// The render of the lazy widgets, it lays out the built items by their
+/// index, and reports the size of all the items, whether they are built or
+/// not.
+#[derive(MultiChild)]
+struct LazyPanel {
+  lazy: LazyInner,
+}
+
+impl Render for LazyPanel {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let mut layout = self.lazy.layout.borrow_mut();
+    let window = self.lazy.window.read();
+    let is_horizontal = layout.direction.is_horizontal();
+    let cross_max = if is_horizontal { clamp.max.height } else { clamp.max.width };
+    let cnt = layout.cross_axis_cnt;
+    let cell_cross = if cross_max.is_finite() {
+      let cross = (cross_max - layout.cross_spacing * (cnt - 1) as f32) / cnt as f32;
+      Some(cross.max(0.))
+    } else {
+      None
+    };
+
+    let (ctx, children) = ctx.split_children();
+    let mut laid_out = vec![];
+    let mut max_cross: f32 = 0.;
+    let mut measured_changed = false;
+    for (slot, c) in children.enumerate() {
+      let Some(index) = item_of_slot(slot, window.slots, &window.items) else {
+        ctx.perform_child_layout(c, BoxClamp::default());
+        continue;
+      };
+      let line = index / cnt;
+      let (min_main, max_main) = match &layout.item_extent {
+        ItemExtent::Estimated(_) => (0., f32::INFINITY),
+        _ => {
+          let e = layout.line_extent(line);
+          (e, e)
+        }
+      };
+      let (min_cross, max_cross_c) = match cell_cross {
+        Some(cross) if cnt > 1 => (cross, cross),
+        Some(cross) => (0., cross),
+        None => (0., f32::INFINITY),
+      };
+      let child_clamp = if is_horizontal {
+        BoxClamp { min: Size::new(min_main, min_cross), max: Size::new(max_main, max_cross_c) }
+      } else {
+        BoxClamp { min: Size::new(min_cross, min_main), max: Size::new(max_cross_c, max_main) }
+      };
+      let size = ctx.perform_child_layout(c, child_clamp);
+      let (main, cross) =
+        if is_horizontal { (size.width, size.height) } else { (size.height, size.width) };
+      max_cross = max_cross.max(cross);
+      laid_out.push((c, index, main));
+    }
+
+    if matches!(layout.item_extent, ItemExtent::Estimated(_)) {
+      let mut line_max = std::collections::BTreeMap::<usize, f32>::new();
+      for (_, index, main) in laid_out.iter() {
+        let m = line_max.entry(index / cnt).or_default();
+        *m = m.max(*main);
+      }
+      for (line, main) in line_max {
+        if layout.measured[line] != main {
+          layout.measured[line] = main;
+          measured_changed = true;
+        }
+      }
+    }
+    if measured_changed {
+      layout.offsets_dirty = true;
+      layout.update_offsets();
+    }
+
+    let cross_size = cell_cross.map_or(max_cross, |_| cross_max);
+    layout.cross_size = cross_size;
+    let cell_cross = layout.cell_cross_extent();
+    for (c, index, _) in laid_out {
+      let main_pos = layout.line_offset(index / cnt);
+      let cross_pos = (index % cnt) as f32 * (cell_cross + layout.cross_spacing);
+      let pos = if is_horizontal {
+        Point::new(main_pos, cross_pos)
+      } else {
+        Point::new(cross_pos, main_pos)
+      };
+      ctx.update_position(c, pos);
+    }
+
+    let main = layout.total_extent();
+    let size =
+      if is_horizontal { Size::new(main, cross_size) } else { Size::new(cross_size, main) };
+    clamp.clamp(size)
+  }
+
+  #[inline]
+  fn only_sized_by_parent(&self) -> bool { false }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+
+  use super::*;
+
+  fn built_list(
+    count: usize, item_extent: ItemExtent,
+  ) -> (impl Fn() -> Widget<'static>, Stateful<LazyList>, Rc<Cell<usize>>) {
+    let built = Rc::new(Cell::new(0));
+    let c_built = built.clone();
+    let list = Stateful::new(LazyList {
+      count,
+      item_extent,
+      spacing: 0.,
+      overscan: 0.,
+      direction: Direction::Vertical,
+      item_builder: ItemBuilder(Rc::new(move |_| {
+        c_built.set(c_built.get() + 1);
+        fn_widget! { @MockBox { size: Size::new(100., 20.) } }.into_widget()
+      })),
+      lazy: LazyInner::default(),
+    });
+    let c_list = list.clone_writer();
+    (move || c_list.clone_writer().into_widget(), list, built)
+  }
+
+  #[test]
+  fn only_build_visible_items() {
+    reset_test_env!();
+
+    let (w, list, built) = built_list(10000, ItemExtent::Fixed(20.));
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    assert_eq!(list.read().built_items(), 0..5);
+    assert_eq!(built.get(), 5);
+
+    let content = list
+      .read()
+      .scrollable_widget()
+      .read()
+      .scroll_content_size();
+    assert_eq!(content.height, 200000.);
+
+    list
+      .read()
+      .scrollable_widget()
+      .write()
+      .jump_to(Point::new(0., 30.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(list.read().built_items(), 1..7);
+    // Only the new items are built, the others are kept.
+    assert_eq!(built.get(), 7);
+  }
+
+  #[test]
+  fn keep_items_if_content_not_change() {
+    reset_test_env!();
+
+    let (w, list, built) = built_list(10000, ItemExtent::Fixed(20.));
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(built.get(), 5);
+
+    list.write().spacing = 0.;
+    list.write().overscan = 0.;
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(built.get(), 5);
+
+    // The items are rebuilt for the new count.
+    list.write().count = 20000;
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(built.get(), 10);
+  }
+
+  #[test]
+  fn jump_to_unbuilt_item() {
+    reset_test_env!();
+
+    let (w, list, _) = built_list(10000, ItemExtent::Fixed(20.));
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+
+    list.read().jump_to_item(5000);
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    let scroll_pos = list
+      .read()
+      .scrollable_widget()
+      .read()
+      .get_scroll_pos();
+    assert_eq!(scroll_pos, Point::new(0., 100000.));
+    assert_eq!(list.read().built_items(), 5000..5005);
+  }
+
+  #[test]
+  fn estimated_extent() {
+    reset_test_env!();
+
+    let (w, list, _) = built_list(100, ItemExtent::Estimated(50.));
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    // The widgets are regenerated in the next frame, so every frame measures
+    // more items until the view is filled.
+    for _ in 0..5 {
+      wnd.draw_frame();
+    }
+
+    // The built items are measured as 20 pixels height, and the others are
+    // estimated.
+    let items = list.read().built_items();
+    assert_eq!(items, 0..5);
+    let content = list
+      .read()
+      .scrollable_widget()
+      .read()
+      .scroll_content_size();
+    assert_eq!(content.height, 5. * 20. + 95. * 50.);
+    assert_eq!(
+      list.read().item_rect(6),
+      Some(Rect::new(Point::new(0., 150.), Size::new(100., 50.)))
+    );
+  }
+
+  #[test]
+  fn lazy_grid_lines() {
+    reset_test_env!();
+
+    let w = fn_widget! {
+      @LazyGrid {
+        count: 1000usize,
+        cross_axis_cnt: 4usize,
+        item_extent: ItemExtent::Fixed(25.),
+        overscan: 0.,
+        item_builder: |_| @MockBox { size: Size::zero() }.into_widget(),
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, Size::new(100., 100.));
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    let grid_size = wnd
+      .layout_info_by_path(&[0, 0, 0])
+      .unwrap()
+      .size
+      .unwrap();
+    assert_eq!(grid_size, Size::new(100., 250. * 25.));
+    let second = wnd.layout_info_by_path(&[0, 0, 0, 1]).unwrap();
+    assert_eq!(second.pos, Point::new(25., 0.));
+    assert_eq!(second.size, Some(Size::new(25., 25.)));
+  }
+}
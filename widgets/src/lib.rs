@@ -2,19 +2,24 @@ pub mod avatar;
 pub mod buttons;
 pub mod checkbox;
 pub mod common_widget;
+pub mod dialog;
 pub mod divider;
 pub mod grid_view;
 pub mod icon;
 pub mod input;
 pub mod label;
 pub mod layout;
+pub mod lazy_list;
 pub mod link;
 pub mod lists;
+pub mod menu;
 pub mod path;
 pub mod progress;
 pub mod radio;
 pub mod scrollbar;
+pub mod select;
 pub mod select_region;
+pub mod selectable_text;
 pub mod slider;
 pub mod tabs;
 pub mod text_field;
@@ -22,8 +27,9 @@ pub mod text_field;
 pub mod transform_box;
 pub mod prelude {
   pub use super::{
-    avatar::*, buttons::*, checkbox::*, common_widget::*, divider::*, grid_view::*, icon::*,
-    input::*, label::*, layout::*, link::*, lists::*, path::*, progress::*, radio::*, scrollbar::*,
-    select_region::*, slider::*, tabs::*, text_field::*, transform_box::*,
+    avatar::*, buttons::*, checkbox::*, common_widget::*, dialog::*, divider::*, grid_view::*,
+    icon::*, input::*, label::*, layout::*, lazy_list::*, link::*, lists::*, menu::*, path::*,
+    progress::*, radio::*, scrollbar::*, select::*, select_region::*, selectable_text::*,
+    slider::*, tabs::*, text_field::*, transform_box::*,
   };
 }
@@ -292,6 +292,7 @@ impl<'c> ComposeChild<'c> for ListItem {
                     text_style: headline_style,
                   }
                   @{ supporting.map(|supporting|  {
+                    let overflow = supporting_style.overflow;
                     @ConstrainedBox {
                       clamp: {
                         let line_height = supporting_style.line_height;
@@ -304,6 +305,10 @@ impl<'c> ComposeChild<'c> for ListItem {
                         text: supporting.0.0,
                         foreground:  Palette::of(ctx).on_surface_variant(),
                         text_style: supporting_style,
+                        text_overflow: pipe!(match overflow {
+                          TextOverflow::MaxLines(_) => TextOverflow::MaxLines($this.line_number),
+                          overflow => overflow,
+                        }),
                       }
                     }
                   })}
@@ -332,7 +337,12 @@ pub struct ListItemStyle {
   pub padding_style: Option<EdgeInsets>,
   pub label_gap: Option<EdgeInsets>,
   pub item_align: fn(usize) -> Align,
+  /// The style of the headline text, it ends with an ellipsis if the text is
+  /// too long by default.
   pub headline_style: TextStyle,
+  /// The style of the supporting text. If its overflow is
+  /// `TextOverflow::MaxLines`, the text is truncated to the `line_number` of
+  /// the item.
   pub supporting_style: TextStyle,
   pub leading_config: EdgeWidgetStyle,
   pub trailing_config: EdgeWidgetStyle,
@@ -348,8 +358,14 @@ impl CustomStyle for ListItemStyle {
         if num >= 2 { Align::Start } else { Align::Center }
       },
       label_gap: Some(EdgeInsets::only_left(16.)),
-      headline_style: typography.body_large.text.clone(),
-      supporting_style: typography.body_medium.text.clone(),
+      headline_style: TextStyle {
+        overflow: TextOverflow::Ellipsis(EllipsisMode::End),
+        ..typography.body_large.text.clone()
+      },
+      supporting_style: TextStyle {
+        overflow: TextOverflow::MaxLines(1),
+        ..typography.body_medium.text.clone()
+      },
       leading_config: EdgeWidgetStyle {
         icon: EdgeItemStyle { size: Size::splat(24.), gap: Some(EdgeInsets::only_left(16.)) },
         text: EdgeTextItemStyle {
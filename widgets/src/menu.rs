@@ -0,0 +1,1274 @@
+use std::{
+  cell::RefCell,
+  rc::Rc,
+  time::{Duration, Instant},
+};
+
+use ribir_core::{prelude::*, window::WindowId};
+
+use crate::prelude::*;
+
+class_names! {
+  #[doc = "The class name for the popup panel of a menu."]
+  MENU,
+  #[doc = "The class name for an item or a submenu row of a menu."]
+  MENU_ITEM,
+  #[doc = "The class name for the row highlighted by the pointer or the keyboard."]
+  MENU_ITEM_ACTIVE,
+  #[doc = "The class name for an enabled row that is not highlighted."]
+  MENU_ITEM_INACTIVE,
+  #[doc = "The class name for a disabled row."]
+  MENU_ITEM_DISABLED,
+  #[doc = "The class name for the separator between the rows."]
+  MENU_SEPARATOR,
+  #[doc = "The class name for the accelerator text at the end of an item."]
+  MENU_ACCELERATOR,
+  #[doc = "The class name for the menu bar."]
+  MENU_BAR,
+  #[doc = "The class name for a label of the menu bar."]
+  MENU_BAR_ITEM,
+  #[doc = "The class name for the label of the menu bar whose menu is open or \
+  highlighted by the keyboard."]
+  MENU_BAR_ITEM_ACTIVE,
+  #[doc = "The class name for the other labels of the menu bar."]
+  MENU_BAR_ITEM_INACTIVE,
+}
+
+/// The time the typed characters are kept to search the items.
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// A menu is a list of the items that shows in a popup, it's built as a value
+/// and shown by [`Menu::show_at`], a [`ContextMenu`] or a [`MenuBar`].
+///
+/// An item can be checkable, disabled, display its accelerator and run an
+/// action or a command of the [`CommandRegistry`] when it's selected. The
+/// command is fired from the widget focused before the menu opened.
+///
+/// The menu moves with the arrow keys, `Home` and `End`, selects with `Enter`
+/// or `Space`, opens a submenu with `Right` and closes with `Left` or
+/// `Escape`. Typing the characters highlights the item starting with them.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let wrap = Stateful::new(true);
+///   @ContextMenu {
+///     menu: pipe!(*$wrap).map(move |checked| {
+///       Menu::new()
+///         .item(MenuItem::new("Copy").command(CommandId::COPY))
+///         .item(MenuItem::new("Paste").command(CommandId::PASTE))
+///         .separator()
+///         .item(
+///           MenuItem::new("Word Wrap")
+///             .checked(checked)
+///             .on_select(move || {
+///               let mut wrap = $wrap.write();
+///               *wrap = !*wrap;
+///             }),
+///         )
+///         .submenu(SubMenu::new(
+///           "Zoom",
+///           Menu::new()
+///             .item(MenuItem::new("Zoom In").accelerator("Ctrl+="))
+///             .item(MenuItem::new("Zoom Out").accelerator("Ctrl+-")),
+///         ))
+///     }),
+///     @Container { size: Size::new(200., 200.) }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Clone, Default)]
+pub struct Menu {
+  entries: Vec<MenuEntry>,
+}
+
+/// An entry of the [`Menu`].
+#[derive(Clone)]
+pub enum MenuEntry {
+  Item(MenuItem),
+  SubMenu(SubMenu),
+  Separator,
+}
+
+/// An item of the [`Menu`], it runs its action and fires its command when
+/// selected.
+#[derive(Clone)]
+pub struct MenuItem {
+  label: CowArc<str>,
+  checked: Option<bool>,
+  disabled: bool,
+  accelerator: Option<KeyBinding>,
+  command: Option<CommandId>,
+  action: Option<Rc<RefCell<dyn FnMut()>>>,
+}
+
+/// An entry of the [`Menu`] that opens another menu, it is also a menu of the
+/// [`MenuBar`].
+#[derive(Clone)]
+pub struct SubMenu {
+  label: CowArc<str>,
+  disabled: bool,
+  menu: Menu,
+}
+
+/// The handle of a showing menu, the menu closes itself when an item is
+/// selected or the user taps outside it.
+#[derive(Clone)]
+pub struct MenuHandle(Rc<MenuSession>);
+
+impl Menu {
+  pub fn new() -> Self { Self::default() }
+
+  pub fn item(mut self, item: MenuItem) -> Self {
+    self.entries.push(MenuEntry::Item(item));
+    self
+  }
+
+  pub fn submenu(mut self, submenu: SubMenu) -> Self {
+    self.entries.push(MenuEntry::SubMenu(submenu));
+    self
+  }
+
+  pub fn separator(mut self) -> Self {
+    self.entries.push(MenuEntry::Separator);
+    self
+  }
+
+  pub fn entries(&self) -> &[MenuEntry] { &self.entries }
+
+  /// Show the menu with its left top corner at `pos` in the window, it flips
+  /// to the other side of `pos` if there is no enough space.
+  pub fn show_at(&self, pos: Point, wnd: Sc<Window>) -> MenuHandle {
+    MenuSession::show(self.clone(), Placement::At(pos), &wnd, None, false)
+  }
+
+  /// Show the menu below the `target` widget, or above it if there is no
+  /// enough space. The `target` should be laid out, otherwise the menu shows
+  /// at the left top corner of the window.
+  pub fn show_below(&self, target: TrackId, wnd: Sc<Window>) -> MenuHandle {
+    let rect = global_rect(&target, &wnd).unwrap_or_else(Rect::zero);
+    MenuSession::show(self.clone(), Placement::Below(rect), &wnd, None, false)
+  }
+
+  fn first_enabled(&self) -> Option<usize> { self.step(None, true) }
+
+  /// The next enabled entry after `from` in the direction, it wraps around the
+  /// ends of the menu.
+  fn step(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+    let len = self.entries.len();
+    (1..=len)
+      .map(|i| match (from, forward) {
+        (Some(from), true) => (from + i) % len,
+        (Some(from), false) => (from + len - i) % len,
+        (None, true) => i - 1,
+        (None, false) => len - i,
+      })
+      .find(|i| self.entries[*i].is_enabled())
+  }
+
+  /// The enabled entry whose label starts with `prefix`, searching from
+  /// `from` and wrapping around.
+  fn search(&self, prefix: &str, from: usize) -> Option<usize> {
+    let len = self.entries.len();
+    (0..len).map(|i| (from + i) % len).find(|i| {
+      let entry = &self.entries[*i];
+      entry.is_enabled()
+        && entry
+          .label()
+          .is_some_and(|l| l.to_lowercase().starts_with(prefix))
+    })
+  }
+}
+
+impl MenuEntry {
+  pub fn label(&self) -> Option<&CowArc<str>> {
+    match self {
+      MenuEntry::Item(item) => Some(&item.label),
+      MenuEntry::SubMenu(sub) => Some(&sub.label),
+      MenuEntry::Separator => None,
+    }
+  }
+
+  pub fn is_enabled(&self) -> bool {
+    match self {
+      MenuEntry::Item(item) => !item.disabled,
+      MenuEntry::SubMenu(sub) => !sub.disabled,
+      MenuEntry::Separator => false,
+    }
+  }
+}
+
+impl MenuItem {
+  pub fn new(label: impl Into<CowArc<str>>) -> Self {
+    Self {
+      label: label.into(),
+      checked: None,
+      disabled: false,
+      accelerator: None,
+      command: None,
+      action: None,
+    }
+  }
+
+  /// Make the item checkable and set its check state.
+  pub fn checked(mut self, checked: bool) -> Self {
+    self.checked = Some(checked);
+    self
+  }
+
+  pub fn disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  /// Display the key binding at the end of the item, the binding of the
+  /// command is displayed if it's not set.
+  ///
+  /// # Panics
+  ///
+  /// Panics if the binding is invalid.
+  pub fn accelerator(mut self, binding: &str) -> Self {
+    let binding = binding
+      .parse()
+      .unwrap_or_else(|e| panic!("Invalid accelerator `{binding}`: {e}"));
+    self.accelerator = Some(binding);
+    self
+  }
+
+  /// Fire the command when the item is selected.
+  pub fn command(mut self, command: CommandId) -> Self {
+    self.command = Some(command);
+    self
+  }
+
+  /// Run the action when the item is selected.
+  pub fn on_select(mut self, action: impl FnMut() + 'static) -> Self {
+    self.action = Some(Rc::new(RefCell::new(action)));
+    self
+  }
+
+  pub fn label(&self) -> &CowArc<str> { &self.label }
+
+  pub fn is_checked(&self) -> Option<bool> { self.checked }
+
+  fn accelerator_text(&self) -> Option<String> {
+    if let Some(binding) = &self.accelerator {
+      return Some(binding.to_string());
+    }
+    let command = self.command.as_ref()?;
+    let commands = AppCtx::commands().borrow();
+    commands
+      .bindings(command)
+      .first()
+      .map(|b| b.to_string())
+  }
+}
+
+impl SubMenu {
+  pub fn new(label: impl Into<CowArc<str>>, menu: Menu) -> Self {
+    Self { label: label.into(), disabled: false, menu }
+  }
+
+  pub fn disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  pub fn label(&self) -> &CowArc<str> { &self.label }
+
+  pub fn menu(&self) -> &Menu { &self.menu }
+}
+
+impl MenuHandle {
+  /// Close the menu and its submenus.
+  pub fn close(&self) { self.0.close(true) }
+
+  pub fn is_showing(&self) -> bool { !self.0.levels.borrow().is_empty() }
+
+  fn is(&self, session: &Rc<MenuSession>) -> bool { Rc::ptr_eq(&self.0, session) }
+}
+
+/// Where a menu panel shows, the rectangles are in the window coordinate and
+/// resolved when the menu opens, because the widgets they come from may be
+/// relaid out when the menu shows.
+#[derive(Clone)]
+enum Placement {
+  At(Point),
+  /// On the right of a row, for a submenu.
+  Beside(Rect),
+  /// Below a label, for the menu of the menu bar.
+  Below(Rect),
+}
+
+impl Placement {
+  /// The preferred left top corner of the menu, and the point its right
+  /// bottom corner aligns to if the menu flips.
+  fn anchors(&self) -> (Point, Point) {
+    match self {
+      Placement::At(pos) => (*pos, *pos),
+      Placement::Beside(r) => (Point::new(r.max_x(), r.min_y()), Point::new(r.min_x(), r.max_y())),
+      Placement::Below(r) => (Point::new(r.min_x(), r.max_y()), Point::new(r.max_x(), r.min_y())),
+    }
+  }
+}
+
+/// The rectangle of the widget in the window, `None` if it's removed or not
+/// laid out.
+pub(crate) fn global_rect(track: &TrackId, wnd: &Window) -> Option<Rect> {
+  let id = track.get()?;
+  let size = wnd.widget_size(id)?;
+  Some(Rect::new(wnd.map_to_global(Point::zero(), id), size))
+}
+
+/// Place a menu of `size` at `pos`, or end at `alt` if it overflows `limit`,
+/// and keep it inside `0..limit`.
+pub(crate) fn fit_in(pos: f32, alt: f32, size: f32, limit: f32) -> f32 {
+  let pos = if pos + size > limit { alt - size } else { pos };
+  pos.min(limit - size).max(0.)
+}
+
+/// The links from the menus to the menu bar that opens them.
+struct MenuBarLink {
+  /// Switch to the menu next to the current one by the offset.
+  switch: Box<dyn Fn(isize)>,
+  /// The pointer moves to a position of the window while the menu is open.
+  hover: Box<dyn Fn(Point)>,
+}
+
+struct MenuLevel {
+  overlay: Overlay,
+  focus: Option<Rc<dyn Fn()>>,
+}
+
+/// A menu and its open submenus, one level for each.
+struct MenuSession {
+  wnd_id: WindowId,
+  restore_focus: Option<WidgetId>,
+  levels: RefCell<Vec<MenuLevel>>,
+  bar: Option<MenuBarLink>,
+  on_close: RefCell<Option<Box<dyn FnOnce()>>>,
+}
+
+impl MenuSession {
+  fn show(
+    menu: Menu, placement: Placement, wnd: &Sc<Window>, bar: Option<MenuBarLink>, keyboard: bool,
+  ) -> MenuHandle {
+    let session = Rc::new(MenuSession {
+      wnd_id: wnd.id(),
+      restore_focus: wnd.focusing(),
+      levels: RefCell::new(vec![]),
+      bar,
+      on_close: RefCell::new(None),
+    });
+    session.open(menu, placement, keyboard);
+    MenuHandle(session)
+  }
+
+  fn window(&self) -> Option<Sc<Window>> { AppCtx::get_window(self.wnd_id) }
+
+  fn level_cnt(&self) -> usize { self.levels.borrow().len() }
+
+  /// Open a new level of the menu, `keyboard` means it's opened by the
+  /// keyboard, the menu is focused and its first item is highlighted.
+  fn open(self: &Rc<Self>, menu: Menu, placement: Placement, keyboard: bool) {
+    let Some(wnd) = self.window() else { return };
+    let level = self.level_cnt();
+    let session = self.clone();
+    let overlay = Overlay::new(
+      move || {
+        let panel = menu_panel(menu.clone(), level, placement.clone(), &session, keyboard);
+        if level == 0 { menu_mask(&session, panel) } else { panel }
+      },
+      OverlayStyle { auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE, mask: None },
+    );
+    self
+      .levels
+      .borrow_mut()
+      .push(MenuLevel { overlay: overlay.clone(), focus: None });
+
+    // The menu is usually opened in an event callback, in which the overlay
+    // can't be built, so show it in the next frame if it's still open.
+    let this = Rc::downgrade(self);
+    AppCtx::once_next_frame(move |_| {
+      let open = this.upgrade().is_some_and(|s| {
+        s.levels
+          .borrow()
+          .get(level)
+          .is_some_and(|l| l.overlay == overlay)
+      });
+      if open {
+        overlay.show(wnd.clone());
+      }
+    });
+  }
+
+  fn set_focus(&self, level: usize, focus: impl Fn() + 'static) {
+    if let Some(l) = self.levels.borrow_mut().get_mut(level) {
+      l.focus = Some(Rc::new(focus));
+    }
+  }
+
+  /// Close the levels from `level`, the submenus of it are closed too.
+  fn close_from(&self, level: usize) {
+    let closing = {
+      let mut levels = self.levels.borrow_mut();
+      let at = level.min(levels.len());
+      levels.split_off(at)
+    };
+    closing
+      .into_iter()
+      .rev()
+      .for_each(|l| l.overlay.close());
+  }
+
+  /// Close a submenu and move the focus back to its parent.
+  fn close_submenu(&self, level: usize) {
+    self.close_from(level);
+    let focus = level
+      .checked_sub(1)
+      .and_then(|l| self.levels.borrow().get(l)?.focus.clone());
+    if let Some(focus) = focus {
+      focus();
+    }
+  }
+
+  /// Close all the levels, and move the focus back to the widget focused
+  /// before the menu opened if `restore_focus` is true.
+  fn close(&self, restore_focus: bool) {
+    if self.level_cnt() == 0 {
+      return;
+    }
+    self.close_from(0);
+    if let (true, Some(id), Some(wnd)) = (restore_focus, self.restore_focus, self.window()) {
+      wnd.request_focus(id);
+    }
+    let on_close = self.on_close.borrow_mut().take();
+    if let Some(on_close) = on_close {
+      on_close();
+    }
+  }
+
+  /// Fire the command from the widget focused before the menu opened.
+  fn execute(&self, command: &CommandId) {
+    let Some(wnd) = self.window() else { return };
+    match self.restore_focus {
+      Some(id) => wnd.execute_command_from(command, id),
+      None => wnd.execute_command(command),
+    };
+  }
+}
+
+/// The transparent mask below the menus, tapping it closes the menus.
+fn menu_mask(session: &Rc<MenuSession>, panel: Widget<'static>) -> Widget<'static> {
+  let (tap, hover) = (session.clone(), session.clone());
+  fn_widget! {
+    @Container {
+      size: Size::splat(f32::INFINITY),
+      on_tap: move |e| if e.target() == e.current_target() {
+        tap.close(true);
+      },
+      on_pointer_move: move |e| if let Some(bar) = hover.bar.as_ref() {
+        (bar.hover)(e.global_pos());
+      },
+      @ { panel }
+    }
+  }
+  .into_widget()
+}
+
+/// The state of a showing level of the menu.
+struct MenuPanel {
+  menu: Menu,
+  level: usize,
+  session: Rc<MenuSession>,
+  rows: Vec<TrackId>,
+  active: Option<usize>,
+  typed: String,
+  typed_at: Option<Instant>,
+}
+
+impl MenuPanel {
+  fn row_class(&self, idx: usize) -> ClassName {
+    if !self.menu.entries[idx].is_enabled() {
+      MENU_ITEM_DISABLED
+    } else if self.active == Some(idx) {
+      MENU_ITEM_ACTIVE
+    } else {
+      MENU_ITEM_INACTIVE
+    }
+  }
+
+  /// Highlight the entry, the submenu of the previous active entry is closed.
+  fn set_active(&mut self, idx: Option<usize>) {
+    if self.active != idx {
+      self.active = idx;
+      self.session.close_from(self.level + 1);
+    }
+  }
+
+  fn open_submenu(&self, keyboard: bool) -> bool {
+    let Some(idx) = self.active else { return false };
+    let MenuEntry::SubMenu(sub) = &self.menu.entries[idx] else { return false };
+    if sub.disabled {
+      return false;
+    }
+    let rect = self
+      .session
+      .window()
+      .and_then(|wnd| global_rect(&self.rows[idx], &wnd));
+    if let (true, Some(rect)) = (self.session.level_cnt() == self.level + 1, rect) {
+      self
+        .session
+        .open(sub.menu.clone(), Placement::Beside(rect), keyboard);
+    }
+    true
+  }
+
+  fn type_ahead(&mut self, chars: &str) {
+    let now = AppCtx::now();
+    if self
+      .typed_at
+      .is_none_or(|at| now.duration_since(at) > TYPE_AHEAD_TIMEOUT)
+    {
+      self.typed.clear();
+    }
+    if self.typed.is_empty() && chars.trim().is_empty() {
+      return;
+    }
+    self.typed.push_str(&chars.to_lowercase());
+    self.typed_at = Some(now);
+
+    // Typing the same letter repeatedly cycles through the entries starting
+    // with it, so the search starts after the active entry.
+    let mut chars = self.typed.chars();
+    let first = chars.next().unwrap_or_default();
+    let (prefix, from) = match self.active {
+      Some(active) if chars.all(|c| c == first) => (&self.typed[..first.len_utf8()], active + 1),
+      Some(active) => (self.typed.as_str(), active),
+      None => (self.typed.as_str(), 0),
+    };
+    if let Some(idx) = self.menu.search(prefix, from) {
+      self.set_active(Some(idx));
+    }
+  }
+}
+
+/// Select the entry, an item closes the menu and runs, a submenu opens.
+fn select_entry(panel: &impl StateWriter<Value = MenuPanel>, idx: usize) {
+  let (session, entry) = {
+    let p = panel.read();
+    (p.session.clone(), p.menu.entries[idx].clone())
+  };
+  match entry {
+    MenuEntry::Item(item) if !item.disabled => {
+      // Close the menu first, so the focus is back before the command fires.
+      session.close(true);
+      if let Some(action) = item.action {
+        (action.borrow_mut())();
+      }
+      // The command event can't be emitted within another event, fire it in
+      // the next frame.
+      if let Some(command) = item.command {
+        AppCtx::once_next_frame(move |_| session.execute(&command));
+      }
+    }
+    MenuEntry::SubMenu(_) => {
+      let mut p = panel.write();
+      p.set_active(Some(idx));
+      p.open_submenu(true);
+    }
+    _ => {}
+  }
+}
+
+fn menu_panel(
+  menu: Menu, level: usize, placement: Placement, session: &Rc<MenuSession>, keyboard: bool,
+) -> Widget<'static> {
+  let session = session.clone();
+  fn_widget! {
+    let checkable = menu
+      .entries
+      .iter()
+      .any(|e| matches!(e, MenuEntry::Item(item) if item.checked.is_some()));
+    let mut rows = vec![];
+    let mut tracks = vec![];
+    for entry in menu.entries.iter() {
+      let mut row = FatObj::new(menu_row(entry, checkable));
+      tracks.push($row.track_id());
+      rows.push(row);
+    }
+
+    let active = if keyboard { menu.first_enabled() } else { None };
+    let panel = Stateful::new(MenuPanel {
+      menu: menu.clone(),
+      level,
+      session: session.clone(),
+      rows: tracks,
+      active,
+      typed: String::new(),
+      typed_at: None,
+    });
+
+    let rows = rows.into_iter().enumerate().map(|(idx, row)| {
+      if matches!(menu.entries[idx], MenuEntry::Separator) {
+        let separator = @Divider { class: MENU_SEPARATOR };
+        return separator.into_widget();
+      }
+      let (p1, p2) = (panel.clone_writer(), panel.clone_writer());
+      @Class {
+        class: distinct_pipe!($panel.row_class(idx)),
+        @ $row {
+          class: MENU_ITEM,
+          semantics: pipe!(row_semantics(&$panel, idx)),
+          on_tap: move |_| select_entry(&p1, idx),
+          on_pointer_enter: move |_| {
+            let mut p = p2.write();
+            if p.menu.entries[idx].is_enabled() {
+              p.set_active(Some(idx));
+              p.open_submenu(false);
+            }
+          },
+        }
+      }
+      .into_widget()
+    }).collect::<Vec<_>>();
+
+    let (x_anchor, y_anchor) = (placement.clone(), placement);
+    let mut layout = FatObj::new(MenuLayout).auto_focus(level == 0 || keyboard);
+    let focus = layout.get_request_focus_widget().clone_writer();
+    session.set_focus(level, move || focus.read().request_focus());
+    let selector = panel.clone_writer();
+    @ $layout {
+      class: MENU,
+      semantics: Semantics::new(Role::Menu),
+      tab_index: -1_i16,
+      global_anchor_x: GlobalAnchorX::custom(move |host, wnd| {
+        let (pos, alt) = x_anchor.anchors();
+        let size = host_size(host, wnd)?;
+        Ok(fit_in(pos.x, alt.x, size.width, wnd.size().width))
+      }),
+      global_anchor_y: GlobalAnchorY::custom(move |host, wnd| {
+        let (pos, alt) = y_anchor.anchors();
+        let size = host_size(host, wnd)?;
+        Ok(fit_in(pos.y, alt.y, size.height, wnd.size().height))
+      }),
+      on_pointer_enter: move |_| $layout.request_focus(),
+      on_chars: move |e| $panel.write().type_ahead(&e.chars),
+      on_key_down: move |e| {
+        let key = e.key().clone();
+        let mut p = $panel.write();
+        match key {
+          VirtualKey::Named(NamedKey::ArrowDown) => {
+            let next = p.menu.step(p.active, true);
+            p.set_active(next);
+          }
+          VirtualKey::Named(NamedKey::ArrowUp) => {
+            let prev = p.menu.step(p.active, false);
+            p.set_active(prev);
+          }
+          VirtualKey::Named(NamedKey::Home) => {
+            let first = p.menu.step(None, true);
+            p.set_active(first);
+          }
+          VirtualKey::Named(NamedKey::End) => {
+            let last = p.menu.step(None, false);
+            p.set_active(last);
+          }
+          VirtualKey::Named(NamedKey::Enter | NamedKey::Space) => {
+            if let Some(idx) = p.active {
+              drop(p);
+              select_entry(&selector, idx);
+            }
+          }
+          VirtualKey::Named(NamedKey::ArrowRight) => {
+            if !p.open_submenu(true) {
+              let session = p.session.clone();
+              drop(p);
+              if let Some(bar) = session.bar.as_ref() {
+                (bar.switch)(1);
+              }
+            }
+          }
+          VirtualKey::Named(NamedKey::ArrowLeft) if level > 0 => p.session.close_submenu(level),
+          VirtualKey::Named(NamedKey::ArrowLeft) => {
+            let session = p.session.clone();
+            drop(p);
+            if let Some(bar) = session.bar.as_ref() {
+              (bar.switch)(-1);
+            }
+          }
+          VirtualKey::Named(NamedKey::Escape) if level > 0 => p.session.close_submenu(level),
+          VirtualKey::Named(NamedKey::Escape) => p.session.close(true),
+          _ => return,
+        }
+        e.stop_propagation();
+      },
+      @ { rows }
+    }
+    .into_widget()
+  }
+  .into_widget()
+}
+
+pub(crate) fn host_size(host: &TrackId, wnd: &Window) -> Result<Size, ()> {
+  let id = host.get().ok_or(())?;
+  wnd.widget_size(id).ok_or(())
+}
+
+fn row_semantics(panel: &MenuPanel, idx: usize) -> Semantics {
+  let entry = &panel.menu.entries[idx];
+  let mut semantics = Semantics::new(Role::MenuItem)
+    .with_selected(panel.active == Some(idx))
+    .with_disabled(!entry.is_enabled())
+    .with_action(SemanticsAction::Click);
+  if let Some(label) = entry.label() {
+    semantics = semantics.with_label(label.clone());
+  }
+  match entry {
+    MenuEntry::Item(MenuItem { checked: Some(checked), .. }) => {
+      semantics.with_checked(if *checked { Toggled::True } else { Toggled::False })
+    }
+    MenuEntry::SubMenu(_) => {
+      let expanded = panel.active == Some(idx) && panel.session.level_cnt() > panel.level + 1;
+      semantics.with_expanded(expanded)
+    }
+    _ => semantics,
+  }
+}
+
+fn menu_row(entry: &MenuEntry, checkable: bool) -> Widget<'static> {
+  let Some(label) = entry.label().cloned() else { return Void.into_widget() };
+  let checked = matches!(entry, MenuEntry::Item(MenuItem { checked: Some(true), .. }));
+  let trailing: Option<Widget<'static>> = match entry {
+    MenuEntry::Item(item) => item
+      .accelerator_text()
+      .map(|text| text! { text, class: MENU_ACCELERATOR }.into_widget()),
+    MenuEntry::SubMenu(_) => Some(icon! { @ { svgs::CHEVRON_RIGHT } }.into_widget()),
+    MenuEntry::Separator => None,
+  };
+  fn_widget! {
+    let check = checkable.then(|| @Icon {
+      opacity: if checked { 1. } else { 0. },
+      @ { svgs::DONE }
+    });
+    @Row {
+      justify_content: JustifyContent::SpaceBetween,
+      align_items: Align::Center,
+      @Row {
+        align_items: Align::Center,
+        @ { check }
+        @Text { text: label.clone() }
+      }
+      @ { trailing }
+    }
+  }
+  .into_widget()
+}
+
+/// Lays out the rows of a menu in a column, all the rows have the width of
+/// the widest one.
+#[derive(MultiChild)]
+struct MenuLayout;
+
+impl Render for MenuLayout {
+  fn perform_layout(&self, clamp: BoxClamp, ctx: &mut LayoutCtx) -> Size {
+    let (ctx, children) = ctx.split_children();
+    let children = children.collect::<Vec<_>>();
+    let loose = BoxClamp { min: Size::zero(), max: Size::new(f32::INFINITY, clamp.max.height) };
+    let width = children
+      .iter()
+      .fold(clamp.min.width, |w, c| w.max(ctx.perform_child_layout(*c, loose).width))
+      .min(clamp.max.width);
+
+    let mut y = 0.;
+    for c in children {
+      let size = ctx.perform_child_layout(c, BoxClamp::fixed_width(width));
+      ctx.update_position(c, Point::new(0., y));
+      y += size.height;
+    }
+    clamp.clamp(Size::new(width, y))
+  }
+
+  #[inline]
+  fn paint(&self, _: &mut PaintingCtx) {}
+}
+
+/// A widget that shows a [`Menu`] when the user requests the context menu on
+/// its child, by the secondary button of the mouse, a long press of the touch,
+/// or the `ContextMenu` key and `Shift+F10` when the child is focused.
+///
+/// See [`Menu`] for an example.
+#[derive(Declare)]
+pub struct ContextMenu {
+  pub menu: Menu,
+}
+
+impl<'c> ComposeChild<'c> for ContextMenu {
+  type Child = Widget<'c>;
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    fn_widget! {
+      let child = FatObj::new(child);
+      @ $child {
+        on_context_menu: move |e| {
+          $this.menu.show_at(e.global_pos(), e.window());
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// A row of labels that opens their menus, once a menu is open, hovering
+/// another label or pressing `Left` and `Right` switches to its menu.
+///
+/// The bar is focusable, `Left` and `Right` move between the labels, and
+/// `Down`, `Enter` or `Space` opens the menu of the highlighted label.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let file = Menu::new()
+///   .item(MenuItem::new("New").accelerator("Ctrl+N"))
+///   .item(MenuItem::new("Open...").accelerator("Ctrl+O"));
+/// let edit = Menu::new()
+///   .item(MenuItem::new("Undo").command(CommandId::UNDO))
+///   .item(MenuItem::new("Redo").command(CommandId::REDO));
+/// let w = fn_widget! {
+///   @MenuBar {
+///     menus: vec![SubMenu::new("File", file.clone()), SubMenu::new("Edit", edit.clone())],
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct MenuBar {
+  pub menus: Vec<SubMenu>,
+  /// The label highlighted by the keyboard or whose menu is open.
+  #[declare(skip)]
+  active: Option<usize>,
+  #[declare(skip)]
+  opened: Option<MenuHandle>,
+}
+
+impl MenuBar {
+  /// The index of the menu that is open.
+  pub fn opened(&self) -> Option<usize> { self.opened.as_ref().and(self.active) }
+
+  /// Close the open menu.
+  pub fn close(&mut self) {
+    if let Some(handle) = self.opened.take() {
+      self.active = None;
+      handle.close();
+    }
+  }
+
+  fn label_class(&self, idx: usize) -> ClassName {
+    if self.active == Some(idx) { MENU_BAR_ITEM_ACTIVE } else { MENU_BAR_ITEM_INACTIVE }
+  }
+}
+
+/// The shared context of the labels and the menus of a [`MenuBar`].
+struct BarCtx {
+  bar: Box<dyn StateWriter<Value = MenuBar>>,
+  labels: RefCell<Vec<TrackId>>,
+  wnd_id: WindowId,
+}
+
+impl BarCtx {
+  /// Open the menu at `idx`, the menu open before is closed.
+  fn open(self: &Rc<Self>, idx: usize, keyboard: bool) {
+    let Some(wnd) = AppCtx::get_window(self.wnd_id) else { return };
+    let label = self
+      .labels
+      .borrow()
+      .get(idx)
+      .and_then(|t| global_rect(t, &wnd));
+    let Some(label) = label else { return };
+    let (old, sub) = {
+      let mut bar = self.bar.write();
+      let Some(sub) = bar.menus.get(idx).cloned() else { return };
+      bar.active = Some(idx);
+      (bar.opened.take(), sub)
+    };
+    if let Some(old) = old {
+      old.0.close(false);
+    }
+    if sub.disabled {
+      return;
+    }
+
+    let (switch, hover) = (self.clone(), self.clone());
+    let link = MenuBarLink {
+      switch: Box::new(move |offset| switch.switch(offset)),
+      hover: Box::new(move |pos| {
+        let idx = hover.label_at(pos);
+        if idx.is_some_and(|idx| hover.bar.read().active != Some(idx)) {
+          hover.open(idx.unwrap(), false);
+        }
+      }),
+    };
+    let placement = Placement::Below(label);
+    let handle = MenuSession::show(sub.menu, placement, &wnd, Some(link), keyboard);
+    let (this, session) = (self.clone(), handle.0.clone());
+    *handle.0.on_close.borrow_mut() = Some(Box::new(move || {
+      let opened = this
+        .bar
+        .read()
+        .opened
+        .as_ref()
+        .is_some_and(|h| h.is(&session));
+      if opened {
+        let mut bar = this.bar.write();
+        bar.opened = None;
+        bar.active = None;
+      }
+    }));
+    self.bar.write().opened = Some(handle);
+  }
+
+  /// Move the active label by the offset, and open its menu if a menu is
+  /// open.
+  fn switch(self: &Rc<Self>, offset: isize) {
+    let (len, active, opened) = {
+      let bar = self.bar.read();
+      (bar.menus.len() as isize, bar.active, bar.opened.is_some())
+    };
+    if len == 0 {
+      return;
+    }
+    let idx = active.map_or(0, |a| (a as isize + offset).rem_euclid(len)) as usize;
+    if opened {
+      self.open(idx, true);
+    } else {
+      self.bar.write().active = Some(idx);
+    }
+  }
+
+  fn label_at(&self, pos: Point) -> Option<usize> {
+    let wnd = AppCtx::get_window(self.wnd_id)?;
+    self.labels.borrow().iter().position(|t| {
+      t.get()
+        .and_then(|id| {
+          let size = wnd.widget_size(id)?;
+          Some(Rect::new(wnd.map_to_global(Point::zero(), id), size).contains(pos))
+        })
+        .unwrap_or(false)
+    })
+  }
+}
+
+impl Compose for MenuBar {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let ctx = Rc::new(BarCtx {
+        bar: this.clone_boxed_writer(),
+        labels: RefCell::new(vec![]),
+        wnd_id: BuildCtx::get().window().id(),
+      });
+      let ctx2 = ctx.clone();
+      let this2 = this.clone_writer();
+      let labels = distinct_pipe!($this.menus.len()).map(move |len| {
+        let mut tracks = vec![];
+        let labels = (0..len)
+          .map(|idx| {
+            let this = this2.clone_writer();
+            let ctx = ctx2.clone();
+            let mut label = @Text {
+              text: pipe!($this.menus.get(idx).map_or(CowArc::default(), |m| m.label.clone())),
+            };
+            tracks.push($label.track_id());
+            @Class {
+              class: distinct_pipe!($this.label_class(idx)),
+              @ $label {
+                class: MENU_BAR_ITEM,
+                semantics: pipe!(
+                  Semantics::new(Role::MenuItem)
+                    .with_expanded($this.opened() == Some(idx))
+                    .with_action(SemanticsAction::Click)
+                ),
+                on_tap: move |_| {
+                  if ctx.bar.read().opened() == Some(idx) {
+                    ctx.bar.write().close();
+                  } else {
+                    ctx.open(idx, false);
+                  }
+                },
+              }
+            }
+            .into_widget()
+          })
+          .collect::<Vec<_>>();
+        *ctx2.labels.borrow_mut() = tracks;
+        labels
+      });
+
+      @Row {
+        class: MENU_BAR,
+        tab_index: 0_i16,
+        on_key_down: move |e| {
+          match e.key() {
+            VirtualKey::Named(NamedKey::ArrowLeft) => ctx.switch(-1),
+            VirtualKey::Named(NamedKey::ArrowRight) => ctx.switch(1),
+            VirtualKey::Named(NamedKey::ArrowDown | NamedKey::Enter | NamedKey::Space) => {
+              let active = ctx.bar.read().active;
+              ctx.open(active.unwrap_or(0), true);
+            }
+            VirtualKey::Named(NamedKey::Escape) => ctx.bar.write().active = None,
+            _ => return,
+          }
+          e.stop_propagation();
+        },
+        on_blur: move |_| {
+          let mut bar = $this.write();
+          if bar.opened.is_none() {
+            bar.active = None;
+          }
+        },
+        @ { labels }
+      }
+    }
+    .into_widget()
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{reset_test_env, test_helper::*};
+  use winit::event::ElementState;
+
+  use super::*;
+
+  const WND_SIZE: Size = Size::new(400., 400.);
+
+  fn press(wnd: &mut TestWindow, key: NamedKey) {
+    let (code, key) = (PhysicalKey::Code(KeyCode::F24), VirtualKey::Named(key));
+    let state = ElementState::Pressed;
+    wnd.processes_keyboard_event(code, key, false, KeyLocation::Standard, state);
+    wnd.draw_frame();
+  }
+
+  fn type_chars(wnd: &mut TestWindow, chars: &str) {
+    wnd.processes_receive_chars(chars.into());
+    wnd.draw_frame();
+  }
+
+  fn active_item(wnd: &TestWindow) -> Option<String> {
+    let tree = wnd.accessibility_tree();
+    tree
+      .root
+      .iter()
+      .filter(|n| n.role == Role::MenuItem && n.selected == Some(true))
+      .last()
+      .and_then(|n| n.label.as_ref().map(|l| l.to_string()))
+  }
+
+  fn menus(wnd: &TestWindow) -> Vec<Rect> {
+    let tree = wnd.accessibility_tree();
+    tree
+      .root
+      .iter()
+      .filter(|n| n.role == Role::Menu)
+      .map(|n| n.rect)
+      .collect()
+  }
+
+  fn click(wnd: &mut TestWindow, label: &str) {
+    let id = wnd
+      .accessibility_tree()
+      .find_by_label(label)
+      .unwrap()
+      .id;
+    wnd.perform_semantics_action(id, SemanticsAction::Click);
+    wnd.draw_frame();
+  }
+
+  fn empty_wnd() -> TestWindow {
+    let mut wnd = TestWindow::new_with_size(fn_widget! { @Void {} }, WND_SIZE);
+    wnd.draw_frame();
+    wnd
+  }
+
+  #[test]
+  fn keyboard_navigation() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let (l1, l2) = (log.clone_writer(), log.clone_writer());
+    let menu = Menu::new()
+      .item(MenuItem::new("Cut").on_select(move || l1.write().push("cut")))
+      .item(MenuItem::new("Copy").disabled(true))
+      .separator()
+      .item(MenuItem::new("Paste").on_select(move || l2.write().push("paste")));
+
+    let mut wnd = empty_wnd();
+    let handle = menu.show_at(Point::new(10., 10.), wnd.0.clone());
+    wnd.draw_frame();
+    assert_eq!(menus(&wnd).len(), 1);
+    assert_eq!(active_item(&wnd), None);
+
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Cut"));
+    // The disabled item and the separator are skipped.
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Paste"));
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Cut"));
+    press(&mut wnd, NamedKey::End);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Paste"));
+
+    press(&mut wnd, NamedKey::Enter);
+    assert_eq!(&*log.read(), &["paste"]);
+    assert!(!handle.is_showing());
+    wnd.draw_frame();
+    assert!(menus(&wnd).is_empty());
+  }
+
+  #[test]
+  fn submenu_and_type_ahead() {
+    reset_test_env!();
+
+    let recent = Menu::new()
+      .item(MenuItem::new("a.txt"))
+      .item(MenuItem::new("b.txt"));
+    let menu = Menu::new()
+      .item(MenuItem::new("Open"))
+      .submenu(SubMenu::new("Recent", recent))
+      .item(MenuItem::new("Save"))
+      .item(MenuItem::new("Save As"));
+
+    let mut wnd = empty_wnd();
+    let handle = menu.show_at(Point::new(10., 10.), wnd.0.clone());
+    wnd.draw_frame();
+
+    type_chars(&mut wnd, "s");
+    assert_eq!(active_item(&wnd).as_deref(), Some("Save"));
+    type_chars(&mut wnd, "s");
+    assert_eq!(active_item(&wnd).as_deref(), Some("Save As"));
+    type_chars(&mut wnd, "s");
+    assert_eq!(active_item(&wnd).as_deref(), Some("Save"));
+
+    press(&mut wnd, NamedKey::Home);
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Recent"));
+    press(&mut wnd, NamedKey::ArrowRight);
+    wnd.draw_frame();
+    let rects = menus(&wnd);
+    assert_eq!(rects.len(), 2);
+    // The submenu shows on the right of its row.
+    assert_eq!(rects[1].min_x(), rects[0].max_x());
+    assert_eq!(active_item(&wnd).as_deref(), Some("a.txt"));
+
+    press(&mut wnd, NamedKey::ArrowLeft);
+    wnd.draw_frame();
+    assert_eq!(menus(&wnd).len(), 1);
+    assert_eq!(active_item(&wnd).as_deref(), Some("Recent"));
+
+    press(&mut wnd, NamedKey::Escape);
+    assert!(!handle.is_showing());
+  }
+
+  #[test]
+  fn flip_inside_window() {
+    reset_test_env!();
+
+    let menu = Menu::new()
+      .item(MenuItem::new("Undo").accelerator("Ctrl+Z"))
+      .item(MenuItem::new("Redo"));
+    let mut wnd = empty_wnd();
+    let pos = Point::new(390., 395.);
+    menu.show_at(pos, wnd.0.clone());
+    wnd.draw_frame();
+    wnd.draw_frame();
+
+    let rect = menus(&wnd)[0];
+    assert!(rect.width() > 0. && rect.height() > 0.);
+    assert_eq!(rect.max_x(), pos.x);
+    assert_eq!(rect.max_y(), pos.y);
+
+    // The rows have the same width.
+    let tree = wnd.accessibility_tree();
+    let undo = tree.find_by_label("Undo").unwrap().rect;
+    let redo = tree.find_by_label("Redo").unwrap().rect;
+    assert_eq!(undo.width(), redo.width());
+  }
+
+  #[test]
+  fn context_menu_fires_command() {
+    reset_test_env!();
+
+    let log = Stateful::new(vec![]);
+    let c_log = log.clone_writer();
+    let w = fn_widget! {
+      @ContextMenu {
+        menu: Menu::new()
+          .item(MenuItem::new("Copy").command(CommandId::COPY))
+          .item(MenuItem::new("Paste").command(CommandId::PASTE)),
+        @MockBox {
+          size: Size::new(100., 100.),
+          auto_focus: true,
+          on_command: move |e| $c_log.write().push(e.command().to_string()),
+        }
+      }
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+
+    let key = VirtualKey::Named(NamedKey::ContextMenu);
+    let code = PhysicalKey::Code(KeyCode::ContextMenu);
+    wnd.processes_keyboard_event(code, key, false, KeyLocation::Standard, ElementState::Pressed);
+    wnd.draw_frame();
+    wnd.draw_frame();
+    let rects = menus(&wnd);
+    assert_eq!(rects.len(), 1);
+    assert_eq!(rects[0].origin, Point::new(0., 100.));
+
+    click(&mut wnd, "Paste");
+    assert_eq!(&*log.read(), &["paste"]);
+    assert!(menus(&wnd).is_empty());
+  }
+
+  #[test]
+  fn menu_bar_switch() {
+    reset_test_env!();
+
+    let file = Menu::new().item(MenuItem::new("New"));
+    let edit = Menu::new().item(MenuItem::new("Undo"));
+    let bar = Stateful::new(None);
+    let w_bar = bar.clone_writer();
+    let w = fn_widget! {
+      let bar = @MenuBar {
+        menus: vec![SubMenu::new("File", file.clone()), SubMenu::new("Edit", edit.clone())],
+      };
+      *$w_bar.write() = Some(bar.clone_writer());
+      bar
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    let opened = || bar.read().as_ref().unwrap().read().opened();
+
+    click(&mut wnd, "File");
+    assert_eq!(opened(), Some(0));
+    assert!(
+      wnd
+        .accessibility_tree()
+        .find_by_label("New")
+        .is_some()
+    );
+
+    press(&mut wnd, NamedKey::ArrowRight);
+    wnd.draw_frame();
+    assert_eq!(opened(), Some(1));
+    let tree = wnd.accessibility_tree();
+    assert!(tree.find_by_label("New").is_none());
+    assert!(tree.find_by_label("Undo").is_some());
+
+    press(&mut wnd, NamedKey::Escape);
+    assert_eq!(opened(), None);
+  }
+}
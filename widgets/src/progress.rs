@@ -202,6 +202,8 @@ mod tests {
       }
     })
     .with_wnd_size(Size::new(300., 200.))
+    // Capture the indeterminate progresses one frame after they start.
+    .on_initd(|wnd| wnd.advance_time(Duration::from_millis(16)))
     .with_comparison(0.002)
   );
 }
@@ -67,6 +67,13 @@ impl Radio {
   fn radio_class_name(&self) -> ClassName {
     if self.selected { RADIO_SELECTED } else { RADIO_UNSELECTED }
   }
+
+  fn semantics(&self) -> Semantics {
+    let checked = if self.selected { Toggled::True } else { Toggled::False };
+    Semantics::new(Role::Radio)
+      .with_checked(checked)
+      .with_action(SemanticsAction::Click)
+  }
 }
 
 impl ComposeChild<'static> for Radio {
@@ -79,6 +86,7 @@ impl ComposeChild<'static> for Radio {
         @Void { class: RADIO }
       };
       @FatObj {
+        semantics: pipe!($this.semantics()),
         on_tap: move |_| $this.write().selected = true,
         on_key_up: move |k| if *k.key() == VirtualKey::Named(NamedKey::Space) {
           $this.write().selected = true
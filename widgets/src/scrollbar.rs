@@ -24,7 +24,7 @@ use crate::layout::{Stack, StackDeclareExtend, StackFit};
 /// `Provider::of::<ScrollableWidget>` to retrieve the scroll status and
 /// determine the scrollbar's appearance.
 pub struct Scrollbar {
-  scroll: Stateful<ScrollableWidget>,
+  pub(crate) scroll: Stateful<ScrollableWidget>,
 }
 
 class_names! {
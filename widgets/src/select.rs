@@ -0,0 +1,998 @@
+use std::{cell::Cell, rc::Rc};
+
+use ribir_core::{prelude::*, window::WindowId};
+
+use crate::{
+  menu::{fit_in, global_rect, host_size},
+  prelude::*,
+};
+
+class_names! {
+  #[doc = "The class name for the field of a select."]
+  SELECT,
+  #[doc = "The class name for the field of a combo box."]
+  COMBO_BOX,
+  #[doc = "The class name for the selected labels before the input of a multiple combo box."]
+  COMBO_BOX_SELECTED,
+  #[doc = "The class name for the popup list of the options."]
+  SELECT_PANEL,
+  #[doc = "The class name for an option of the popup list."]
+  SELECT_OPTION,
+  #[doc = "The class name for the option highlighted by the pointer or the keyboard."]
+  SELECT_OPTION_ACTIVE,
+  #[doc = "The class name for a selected option that is not highlighted."]
+  SELECT_OPTION_SELECTED,
+  #[doc = "The class name for the other enabled options."]
+  SELECT_OPTION_INACTIVE,
+  #[doc = "The class name for a disabled option."]
+  SELECT_OPTION_DISABLED,
+}
+
+/// The extent of an option before it's built, the built options use their
+/// real size.
+const OPTION_EXTENT: f32 = 48.;
+
+/// An option of the [`Select`] and the [`ComboBox`], it's a value with the
+/// label to display.
+#[derive(Clone)]
+pub struct SelectOption<T> {
+  value: T,
+  label: CowArc<str>,
+  disabled: bool,
+}
+
+impl<T> SelectOption<T> {
+  pub fn new(value: T, label: impl Into<CowArc<str>>) -> Self {
+    Self { value, label: label.into(), disabled: false }
+  }
+
+  /// A disabled option is listed but can't be picked.
+  pub fn disabled(mut self, disabled: bool) -> Self {
+    self.disabled = disabled;
+    self
+  }
+
+  pub fn value(&self) -> &T { &self.value }
+
+  pub fn label(&self) -> &CowArc<str> { &self.label }
+
+  pub fn is_disabled(&self) -> bool { self.disabled }
+}
+
+/// A field that displays the selected options, and picks the options from a
+/// popup list.
+///
+/// The popup lists the options in a [`LazyList`], only the options near the
+/// view are built, so it works well with a large set of options.
+///
+/// The focused field opens the popup with `Down`, `Up`, `Enter` or `Space`.
+/// In the popup, the arrow keys, `Home` and `End` move between the enabled
+/// options, `Enter` and `Space` pick the highlighted option, and `Escape`
+/// closes it. A single select closes the popup when an option is picked,
+/// while a `multiple` one toggles the option and keeps the popup open.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let select = @Select {
+///     placeholder: "Fruit",
+///     options: vec![
+///       SelectOption::new(1, "Apple"),
+///       SelectOption::new(2, "Banana"),
+///       SelectOption::new(3, "Cherry").disabled(true),
+///     ],
+///   };
+///   @Column {
+///     @Text {
+///       text: pipe! {
+///         let value = $select.value().copied();
+///         format!("The selected value: {value:?}")
+///       }
+///     }
+///     @ { select }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct Select<T: 'static> {
+  pub options: Vec<SelectOption<T>>,
+  /// The values of the selected options, at most one if it's not `multiple`.
+  #[declare(default)]
+  pub selected: Vec<T>,
+  /// Whether more than one option can be selected.
+  #[declare(default)]
+  pub multiple: bool,
+  /// The text displayed when no option is selected.
+  #[declare(default)]
+  pub placeholder: CowArc<str>,
+  #[declare(skip)]
+  listbox: Listbox,
+}
+
+/// An input that filters the options by the typed text, and picks the options
+/// from a popup list, like the [`Select`].
+///
+/// Typing opens the popup and lists the options whose label contains the
+/// text, ignoring the case. `Down` opens the popup, the arrow keys move
+/// between the listed options, `Enter` picks the highlighted option and
+/// `Escape` closes it.
+///
+/// A single combo box fills the input with the label of the picked option. A
+/// `multiple` one displays the selected labels before the input, clears the
+/// input when an option is picked, and `Backspace` in the empty input
+/// unselects the last selected option.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   let options = ["Rust", "Ruby", "Python", "Go"]
+///     .into_iter()
+///     .map(|lang| SelectOption::new(lang, lang))
+///     .collect::<Vec<_>>();
+///   @ComboBox { options, multiple: true }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct ComboBox<T: 'static> {
+  pub options: Vec<SelectOption<T>>,
+  /// The values of the selected options, at most one if it's not `multiple`.
+  #[declare(default)]
+  pub selected: Vec<T>,
+  /// Whether more than one option can be selected.
+  #[declare(default)]
+  pub multiple: bool,
+  #[declare(skip)]
+  query: CowArc<str>,
+  #[declare(skip)]
+  listbox: Listbox,
+}
+
+impl<T> Select<T> {
+  /// The value of the first selected option.
+  pub fn value(&self) -> Option<&T> { self.selected.first() }
+
+  /// Whether the popup list is open.
+  pub fn is_open(&self) -> bool { self.listbox.is_open() }
+
+  /// Close the popup list.
+  pub fn close(&mut self) { self.listbox.close(false) }
+}
+
+impl<T> ComboBox<T> {
+  /// The value of the first selected option.
+  pub fn value(&self) -> Option<&T> { self.selected.first() }
+
+  /// The text in the input that filters the options.
+  pub fn query(&self) -> &CowArc<str> { &self.query }
+
+  /// Whether the popup list is open.
+  pub fn is_open(&self) -> bool { self.listbox.is_open() }
+
+  /// Close the popup list.
+  pub fn close(&mut self) { self.listbox.close(false) }
+}
+
+/// The popup list shared by the [`Select`] and the [`ComboBox`].
+#[derive(Default)]
+struct Listbox {
+  overlay: Option<Overlay>,
+  /// The indices of the listed options.
+  shown: Rc<[usize]>,
+  /// Increased every time the listed options change, to rebuild the list.
+  generation: usize,
+  /// The position in `shown` of the highlighted option.
+  active: Option<usize>,
+  wnd_id: Option<WindowId>,
+  /// The widget focused when the popup opened, the pointer moves the focus
+  /// out when it taps the popup, so it's focused again after the tap.
+  restore_focus: Option<WidgetId>,
+}
+
+impl Listbox {
+  fn is_open(&self) -> bool { self.overlay.is_some() }
+
+  fn option_at(&self, pos: usize) -> Option<usize> { self.shown.get(pos).copied() }
+
+  fn close(&mut self, restore_focus: bool) {
+    if let Some(overlay) = self.overlay.take() {
+      overlay.close();
+      self.active = None;
+      if restore_focus {
+        self.restore_focus();
+      }
+    }
+  }
+
+  fn restore_focus(&self) {
+    let wnd = self.wnd_id.and_then(AppCtx::get_window);
+    if let (Some(id), Some(wnd)) = (self.restore_focus, wnd) {
+      wnd.request_focus(id);
+    }
+  }
+}
+
+/// The common parts of the [`Select`] and the [`ComboBox`] to work with the
+/// popup list.
+trait Picker: 'static {
+  type Value: PartialEq + Clone + 'static;
+
+  fn options(&self) -> &[SelectOption<Self::Value>];
+
+  fn selected(&self) -> &[Self::Value];
+
+  fn selected_mut(&mut self) -> &mut Vec<Self::Value>;
+
+  fn multiple(&self) -> bool;
+
+  fn listbox(&self) -> &Listbox;
+
+  fn listbox_mut(&mut self) -> &mut Listbox;
+
+  /// The indices of the options to list.
+  fn shown(&self) -> Vec<usize>;
+
+  /// Called after the option at `idx` is picked and the selection is updated.
+  fn picked(&mut self, _idx: usize) {}
+
+  fn is_enabled(&self, idx: usize) -> bool {
+    self
+      .options()
+      .get(idx)
+      .is_some_and(|o| !o.disabled)
+  }
+
+  fn is_selected(&self, idx: usize) -> bool {
+    self
+      .options()
+      .get(idx)
+      .is_some_and(|o| self.selected().contains(&o.value))
+  }
+
+  /// The labels of the selected options, in the order of the options.
+  fn display_text(&self) -> String {
+    let labels = self
+      .options()
+      .iter()
+      .filter(|o| self.selected().contains(&o.value))
+      .map(|o| &*o.label)
+      .collect::<Vec<_>>();
+    labels.join(", ")
+  }
+
+  /// The position of the next enabled option listed after `from` in the
+  /// direction, it wraps around the ends of the list.
+  fn step(&self, from: Option<usize>, forward: bool) -> Option<usize> {
+    let shown = &self.listbox().shown;
+    let len = shown.len();
+    (1..=len)
+      .map(|i| match (from, forward) {
+        (Some(from), true) => (from + i) % len,
+        (Some(from), false) => (from + len - i) % len,
+        (None, true) => i - 1,
+        (None, false) => len - i,
+      })
+      .find(|pos| self.is_enabled(shown[*pos]))
+  }
+
+  /// List the options again, and highlight the first selected option, or the
+  /// first enabled one.
+  fn relist(&mut self) {
+    let shown: Rc<[usize]> = self.shown().into();
+    let selected = shown
+      .iter()
+      .position(|idx| self.is_enabled(*idx) && self.is_selected(*idx));
+    let listbox = self.listbox_mut();
+    listbox.shown = shown;
+    listbox.generation += 1;
+    listbox.active = selected;
+    if selected.is_none() {
+      self.listbox_mut().active = self.step(None, true);
+    }
+  }
+}
+
+impl<T: PartialEq + Clone + 'static> Picker for Select<T> {
+  type Value = T;
+
+  fn options(&self) -> &[SelectOption<T>] { &self.options }
+
+  fn selected(&self) -> &[T] { &self.selected }
+
+  fn selected_mut(&mut self) -> &mut Vec<T> { &mut self.selected }
+
+  fn multiple(&self) -> bool { self.multiple }
+
+  fn listbox(&self) -> &Listbox { &self.listbox }
+
+  fn listbox_mut(&mut self) -> &mut Listbox { &mut self.listbox }
+
+  fn shown(&self) -> Vec<usize> { (0..self.options.len()).collect() }
+}
+
+impl<T: PartialEq + Clone + 'static> Picker for ComboBox<T> {
+  type Value = T;
+
+  fn options(&self) -> &[SelectOption<T>] { &self.options }
+
+  fn selected(&self) -> &[T] { &self.selected }
+
+  fn selected_mut(&mut self) -> &mut Vec<T> { &mut self.selected }
+
+  fn multiple(&self) -> bool { self.multiple }
+
+  fn listbox(&self) -> &Listbox { &self.listbox }
+
+  fn listbox_mut(&mut self) -> &mut Listbox { &mut self.listbox }
+
+  fn shown(&self) -> Vec<usize> {
+    // The input of a single combo box holds the label of the picked option,
+    // all the options are listed until the user types.
+    if self.query.is_empty() || (!self.multiple && *self.query == *self.display_text()) {
+      return (0..self.options.len()).collect();
+    }
+    let query = self.query.to_lowercase();
+    self
+      .options
+      .iter()
+      .enumerate()
+      .filter(|(_, o)| o.label.to_lowercase().contains(&query))
+      .map(|(idx, _)| idx)
+      .collect()
+  }
+
+  fn picked(&mut self, idx: usize) {
+    if self.multiple {
+      if !self.query.is_empty() {
+        self.query = CowArc::default();
+        self.relist();
+      }
+    } else {
+      self.query = self.options[idx].label.clone();
+    }
+  }
+}
+
+impl<T: PartialEq + Clone + 'static> Compose for Select<T> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let wnd_id = BuildCtx::get().window().id();
+      let mut field = @Row {
+        justify_content: JustifyContent::SpaceBetween,
+        align_items: Align::Center,
+      };
+      let track = $field.track_id();
+      let (tap, keys, track2) = (this.clone_writer(), this.clone_writer(), track.clone());
+      @ $field {
+        class: SELECT,
+        semantics: pipe!(
+          Semantics::new(Role::ComboBox)
+            .with_label($this.placeholder.clone())
+            .with_value($this.display_text())
+            .with_expanded($this.listbox.is_open())
+            .with_action(SemanticsAction::Click)
+        ),
+        on_tap: move |_| toggle(&tap, wnd_id, &track),
+        on_key_down: move |e| {
+          let key = e.key().clone();
+          let open = keys.read().listbox.is_open();
+          let handled = match key {
+            VirtualKey::Named(NamedKey::Tab) => {
+              if open {
+                keys.write().listbox.close(false);
+              }
+              false
+            }
+            VirtualKey::Named(NamedKey::Space) if open => {
+              listbox_key(&keys, &VirtualKey::Named(NamedKey::Enter))
+            }
+            VirtualKey::Named(
+              NamedKey::ArrowDown | NamedKey::ArrowUp | NamedKey::Enter | NamedKey::Space
+            ) if !open => {
+              open_listbox(&keys, wnd_id, &track2);
+              true
+            }
+            key => listbox_key(&keys, &key),
+          };
+          if handled {
+            e.stop_propagation();
+          }
+        },
+        on_disposed: move |_| $this.write().listbox.close(false),
+        @Text {
+          text: pipe!{
+            let text = $this.display_text();
+            if text.is_empty() { $this.placeholder.clone() } else { text.into() }
+          },
+        }
+        @Icon { @ { svgs::ARROW_DROP_DOWN } }
+      }
+    }
+    .into_widget()
+  }
+}
+
+impl<T: PartialEq + Clone + 'static> Compose for ComboBox<T> {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let wnd_id = BuildCtx::get().window().id();
+      let multiple = $this.multiple;
+      let input = @Input {};
+      if !multiple {
+        let text = $this.display_text();
+        $input.write().set_text(&text);
+        this.silent().query = text.into();
+      }
+
+      let mut field = @Row { align_items: Align::Center };
+      let track = $field.track_id();
+
+      // The typed text filters the options, and the picked option changes the
+      // text.
+      let (this2, track2) = (this.clone_writer(), track.clone());
+      let u1 = watch!($input.text().clone())
+        .distinct_until_changed()
+        .subscribe(move |text| {
+          if this2.read().query == text {
+            return;
+          }
+          let mut combo = this2.write();
+          combo.query = text;
+          if combo.listbox.is_open() {
+            combo.relist();
+          } else {
+            drop(combo);
+            open_listbox(&this2, wnd_id, &track2);
+          }
+        });
+      let u2 = watch!($this.query.clone())
+        .distinct_until_changed()
+        .subscribe(move |query| {
+          if *$input.text() != query {
+            $input.write().set_text(&query);
+          }
+        });
+
+      let (tap, keys, track3) = (this.clone_writer(), this.clone_writer(), track.clone());
+      let selected = multiple.then(|| @Text {
+        class: COMBO_BOX_SELECTED,
+        text: pipe!($this.display_text()),
+      });
+      @ $field {
+        class: COMBO_BOX,
+        semantics: pipe!(
+          Semantics::new(Role::ComboBox)
+            .with_value($this.display_text())
+            .with_expanded($this.listbox.is_open())
+        ),
+        on_key_down_capture: move |e| {
+          // Check the text before the input deletes it.
+          let unselect = multiple
+            && *e.key() == VirtualKey::Named(NamedKey::Backspace)
+            && $input.text().is_empty()
+            && !$this.selected.is_empty();
+          if unselect {
+            $this.write().selected.pop();
+          }
+        },
+        on_key_down: move |e| {
+          let key = e.key().clone();
+          let open = keys.read().listbox.is_open();
+          let handled = match key {
+            VirtualKey::Named(NamedKey::Tab) => {
+              if open {
+                keys.write().listbox.close(false);
+              }
+              false
+            }
+            // Move the caret of the input.
+            VirtualKey::Named(NamedKey::Home | NamedKey::End) => false,
+            VirtualKey::Named(NamedKey::ArrowDown) if !open => {
+              open_listbox(&keys, wnd_id, &track3);
+              true
+            }
+            key => listbox_key(&keys, &key),
+          };
+          if handled {
+            e.stop_propagation();
+          }
+        },
+        on_disposed: move |_| {
+          u1.unsubscribe();
+          u2.unsubscribe();
+          $this.write().listbox.close(false);
+        },
+        @ { selected }
+        @ { input }
+        @Icon {
+          on_tap: move |_| toggle(&tap, wnd_id, &track),
+          @ { svgs::ARROW_DROP_DOWN }
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+fn toggle<P: Picker>(this: &impl StateWriter<Value = P>, wnd_id: WindowId, field: &TrackId) {
+  if this.read().listbox().is_open() {
+    this.write().listbox_mut().close(false);
+  } else {
+    open_listbox(this, wnd_id, field);
+  }
+}
+
+/// Open the popup list below the field, or above it if there is no enough
+/// space.
+fn open_listbox<P: Picker>(this: &impl StateWriter<Value = P>, wnd_id: WindowId, field: &TrackId) {
+  let Some(wnd) = AppCtx::get_window(wnd_id) else { return };
+  if this.read().listbox().is_open() {
+    return;
+  }
+  let Some(rect) = global_rect(field, &wnd) else { return };
+  let host = this.clone_writer();
+  let overlay = Overlay::new(move || listbox_popup(host.clone_writer(), rect), OverlayStyle {
+    auto_close_policy: AutoClosePolicy::NOT_AUTO_CLOSE,
+    mask: None,
+  });
+  {
+    let mut picker = this.write();
+    let listbox = picker.listbox_mut();
+    listbox.overlay = Some(overlay.clone());
+    listbox.wnd_id = Some(wnd_id);
+    listbox.restore_focus = wnd.focusing();
+    picker.relist();
+  }
+
+  // The popup is usually opened in an event callback, in which the overlay
+  // can't be built, so show it in the next frame if it's still open.
+  let this = this.clone_writer();
+  AppCtx::once_next_frame(move |_| {
+    let open = this.read().listbox().overlay.as_ref() == Some(&overlay);
+    if open {
+      overlay.show(wnd.clone());
+    }
+  });
+}
+
+/// Handle the key for the open popup list, return if the key is handled.
+fn listbox_key<P: Picker>(this: &impl StateWriter<Value = P>, key: &VirtualKey) -> bool {
+  let (active, target) = {
+    let picker = this.read();
+    let active = picker.listbox().active;
+    if !picker.listbox().is_open() {
+      return false;
+    }
+    let target = match key {
+      VirtualKey::Named(NamedKey::ArrowDown) => picker.step(active, true),
+      VirtualKey::Named(NamedKey::ArrowUp) => picker.step(active, false),
+      VirtualKey::Named(NamedKey::Home) => picker.step(None, true),
+      VirtualKey::Named(NamedKey::End) => picker.step(None, false),
+      VirtualKey::Named(NamedKey::Enter) => {
+        if let Some(pos) = active {
+          drop(picker);
+          pick(this, pos);
+        }
+        return true;
+      }
+      VirtualKey::Named(NamedKey::Escape) => {
+        drop(picker);
+        this.write().listbox_mut().close(false);
+        return true;
+      }
+      _ => return false,
+    };
+    (active, target)
+  };
+  if active != target {
+    this.write().listbox_mut().active = target;
+  }
+  true
+}
+
+/// Pick the listed option at `pos`, a multiple picker toggles it, otherwise
+/// it's selected and the popup closes.
+fn pick<P: Picker>(this: &impl StateWriter<Value = P>, pos: usize) {
+  let mut picker = this.write();
+  let Some(idx) = picker.listbox().option_at(pos) else { return };
+  if !picker.is_enabled(idx) {
+    picker.forget_modifies();
+    return;
+  }
+  let value = picker.options()[idx].value.clone();
+  if picker.multiple() {
+    let selected = picker.selected_mut();
+    match selected.iter().position(|v| *v == value) {
+      Some(at) => {
+        selected.remove(at);
+      }
+      None => selected.push(value),
+    }
+    picker.listbox_mut().active = Some(pos);
+    picker.listbox().restore_focus();
+  } else {
+    *picker.selected_mut() = vec![value];
+    picker.listbox_mut().close(true);
+  }
+  picker.picked(idx);
+}
+
+/// The popup list and the transparent mask below it, tapping the mask closes
+/// the popup.
+fn listbox_popup<P: Picker>(this: impl StateWriter<Value = P>, field: Rect) -> Widget<'static> {
+  fn_widget! {
+    // Not use `distinct_pipe!` here, the options may be relisted in the frame
+    // the popup is created, and the change will be skipped as the initial value.
+    let last = Rc::new(Cell::new($this.listbox().generation));
+    let this2 = this.clone_writer();
+    let list = pipe!($this.listbox().generation)
+      .value_chain(move |s| s.filter(move |(_, g)| last.replace(*g) != *g).box_it())
+      .map(move |_| option_list(this2.clone_writer()));
+    @Container {
+      size: Size::splat(f32::INFINITY),
+      on_tap: move |e| if e.target() == e.current_target() {
+        $this.write().listbox_mut().close(true);
+      },
+      @ConstrainedBox {
+        clamp: BoxClamp::fixed_width(field.width()),
+        global_anchor_x: GlobalAnchorX::custom(move |host, wnd| {
+          let size = host_size(host, wnd)?;
+          Ok(fit_in(field.min_x(), field.max_x(), size.width, wnd.size().width))
+        }),
+        global_anchor_y: GlobalAnchorY::custom(move |host, wnd| {
+          let size = host_size(host, wnd)?;
+          Ok(fit_in(field.max_y(), field.min_y(), size.height, wnd.size().height))
+        }),
+        @ { list }
+      }
+    }
+  }
+  .into_widget()
+}
+
+fn option_list<P: Picker>(this: impl StateWriter<Value = P>) -> Widget<'static> {
+  let count = this.read().listbox().shown.len();
+  if count == 0 {
+    return Void.into_widget();
+  }
+  fn_widget! {
+    let this2 = this.clone_writer();
+    let list = @LazyList {
+      count,
+      item_extent: ItemExtent::Estimated(OPTION_EXTENT),
+      item_builder: move |pos: usize| option_row(this2.clone_writer(), pos),
+    };
+    let u = watch!($this.listbox().active)
+      .distinct_until_changed()
+      .subscribe(move |active| if let Some(pos) = active {
+        $list.visible_item(pos, Anchor::default());
+      });
+    @ $list {
+      class: SELECT_PANEL,
+      semantics: Semantics::new(Role::ListBox),
+      on_disposed: move |_| u.unsubscribe(),
+    }
+  }
+  .into_widget()
+}
+
+fn option_class<P: Picker>(picker: &P, pos: usize) -> ClassName {
+  let listbox = picker.listbox();
+  match listbox.option_at(pos) {
+    Some(idx) if !picker.is_enabled(idx) => SELECT_OPTION_DISABLED,
+    Some(_) if listbox.active == Some(pos) => SELECT_OPTION_ACTIVE,
+    Some(idx) if picker.is_selected(idx) => SELECT_OPTION_SELECTED,
+    _ => SELECT_OPTION_INACTIVE,
+  }
+}
+
+fn option_semantics<P: Picker>(picker: &P, pos: usize, label: CowArc<str>) -> Semantics {
+  let idx = picker.listbox().option_at(pos);
+  Semantics::new(Role::ListBoxOption)
+    .with_label(label)
+    .with_selected(idx.is_some_and(|idx| picker.is_selected(idx)))
+    .with_disabled(!idx.is_some_and(|idx| picker.is_enabled(idx)))
+    .with_action(SemanticsAction::Click)
+}
+
+fn option_row<P: Picker>(this: impl StateWriter<Value = P>, pos: usize) -> Widget<'static> {
+  let label = {
+    let picker = this.read();
+    let option = picker
+      .listbox()
+      .option_at(pos)
+      .and_then(|idx| picker.options().get(idx));
+    option.map(|o| o.label.clone())
+  };
+  let Some(label) = label else { return Void.into_widget() };
+  fn_widget! {
+    let check = $this.multiple().then(|| @Icon {
+      opacity: pipe! {
+        let picker = $this;
+        let selected = picker.listbox().option_at(pos).is_some_and(|idx| picker.is_selected(idx));
+        if selected { 1. } else { 0. }
+      },
+      @ { svgs::DONE }
+    });
+    let (label2, tap) = (label.clone(), this.clone_writer());
+    @Class {
+      class: distinct_pipe!(option_class(&*$this, pos)),
+      @Row {
+        class: SELECT_OPTION,
+        h_align: HAlign::Stretch,
+        align_items: Align::Center,
+        semantics: pipe!(option_semantics(&*$this, pos, label2.clone())),
+        on_tap: move |_| pick(&tap, pos),
+        on_pointer_enter: move |_| {
+          let enabled = {
+            let picker = $this;
+            let active = picker.listbox().active;
+            active != Some(pos)
+              && picker.listbox().option_at(pos).is_some_and(|idx| picker.is_enabled(idx))
+          };
+          if enabled {
+            $this.write().listbox_mut().active = Some(pos);
+          }
+        },
+        @ { check }
+        @Text { text: label }
+      }
+    }
+  }
+  .into_widget()
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{accessibility::AccessNode, reset_test_env, test_helper::*};
+  use winit::event::{DeviceId, ElementState, MouseButton, WindowEvent};
+
+  use super::*;
+
+  const WND_SIZE: Size = Size::new(400., 400.);
+
+  fn press(wnd: &mut TestWindow, key: NamedKey) {
+    let (code, key) = (PhysicalKey::Code(KeyCode::F24), VirtualKey::Named(key));
+    let state = ElementState::Pressed;
+    wnd.processes_keyboard_event(code, key, false, KeyLocation::Standard, state);
+    wnd.draw_frame();
+  }
+
+  fn node(wnd: &TestWindow, role: Role) -> Option<AccessNode> {
+    let tree = wnd.accessibility_tree();
+    let node = tree.root.iter().find(|n| n.role == role).cloned();
+    node
+  }
+
+  fn option_labels(wnd: &TestWindow) -> Vec<String> {
+    let tree = wnd.accessibility_tree();
+    tree
+      .root
+      .iter()
+      .filter(|n| n.role == Role::ListBoxOption)
+      .filter_map(|n| n.label.as_ref().map(|l| l.to_string()))
+      .collect()
+  }
+
+  fn option(wnd: &TestWindow, label: &str) -> Option<AccessNode> {
+    let tree = wnd.accessibility_tree();
+    let node = tree
+      .root
+      .iter()
+      .find(|n| n.role == Role::ListBoxOption && n.label.as_deref() == Some(label))
+      .cloned();
+    node
+  }
+
+  fn click_option(wnd: &mut TestWindow, label: &str) {
+    let id = option(wnd, label).unwrap().id;
+    wnd.perform_semantics_action(id, SemanticsAction::Click);
+    wnd.draw_frame();
+  }
+
+  #[test]
+  fn select_by_keyboard() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let select = @Select {
+        auto_focus: true,
+        options: vec![
+          SelectOption::new(1, "One"),
+          SelectOption::new(2, "Two").disabled(true),
+          SelectOption::new(3, "Three"),
+        ],
+      };
+      *$w_holder.write() = Some(select.clone_writer());
+      select
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    let select = holder.write().take().unwrap();
+
+    press(&mut wnd, NamedKey::ArrowDown);
+    wnd.draw_frame();
+    assert!(select.read().is_open());
+    assert_eq!(option_labels(&wnd), ["One", "Two", "Three"]);
+    assert_eq!(select.read().listbox.active, Some(0));
+
+    // The disabled option is skipped, and the navigation wraps around.
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(select.read().listbox.active, Some(2));
+    press(&mut wnd, NamedKey::ArrowDown);
+    assert_eq!(select.read().listbox.active, Some(0));
+    press(&mut wnd, NamedKey::End);
+    assert_eq!(select.read().listbox.active, Some(2));
+
+    press(&mut wnd, NamedKey::Enter);
+    wnd.draw_frame();
+    assert!(!select.read().is_open());
+    assert_eq!(select.read().value(), Some(&3));
+    assert!(option_labels(&wnd).is_empty());
+    let field = node(&wnd, Role::ComboBox).unwrap();
+    assert!(field.focused);
+    assert_eq!(field.value.as_deref(), Some("Three"));
+
+    // The selected option is highlighted when the popup opens again.
+    press(&mut wnd, NamedKey::Space);
+    wnd.draw_frame();
+    assert_eq!(select.read().listbox.active, Some(2));
+    press(&mut wnd, NamedKey::Escape);
+    wnd.draw_frame();
+    assert!(!select.read().is_open());
+    assert_eq!(select.read().value(), Some(&3));
+  }
+
+  #[test]
+  fn multiple_select_by_pointer() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let select = @Select {
+        auto_focus: true,
+        multiple: true,
+        options: vec![
+          SelectOption::new('a', "A"),
+          SelectOption::new('b', "B").disabled(true),
+          SelectOption::new('c', "C"),
+        ],
+      };
+      *$w_holder.write() = Some(select.clone_writer());
+      select
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    let select = holder.write().take().unwrap();
+
+    let field = node(&wnd, Role::ComboBox).unwrap();
+    wnd.perform_semantics_action(field.id, SemanticsAction::Click);
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(select.read().is_open());
+
+    // The popup keeps open, and the options are toggled.
+    click_option(&mut wnd, "A");
+    click_option(&mut wnd, "B");
+    click_option(&mut wnd, "C");
+    assert_eq!(select.read().selected, ['a', 'c']);
+    click_option(&mut wnd, "A");
+    assert_eq!(select.read().selected, ['c']);
+    assert!(select.read().is_open());
+    assert_eq!(option(&wnd, "C").unwrap().selected, Some(true));
+    assert!(option(&wnd, "B").unwrap().disabled);
+
+    assert!(node(&wnd, Role::ComboBox).unwrap().focused);
+    press(&mut wnd, NamedKey::Escape);
+    wnd.draw_frame();
+    assert!(!select.read().is_open());
+  }
+
+  #[test]
+  fn combo_box_filter() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let combo = @ComboBox {
+        options: ["Apple", "Banana", "Cherry", "Mango"]
+          .into_iter()
+          .map(|fruit| SelectOption::new(fruit, fruit))
+          .collect::<Vec<_>>(),
+      };
+      *$w_holder.write() = Some(combo.clone_writer());
+      combo
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    let combo = holder.write().take().unwrap();
+
+    // Focus the input by tapping it.
+    let input = node(&wnd, Role::TextInput).unwrap();
+    let device_id = unsafe { DeviceId::dummy() };
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::CursorMoved {
+      device_id,
+      position: (input.rect.center().x as f64, input.rect.center().y as f64).into(),
+    });
+    wnd.process_mouse_input(device_id, ElementState::Pressed, MouseButton::Left);
+    wnd.process_mouse_input(device_id, ElementState::Released, MouseButton::Left);
+    wnd.draw_frame();
+
+    wnd.processes_receive_chars("AN".into());
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert!(combo.read().is_open());
+    assert_eq!(option_labels(&wnd), ["Banana", "Mango"]);
+
+    press(&mut wnd, NamedKey::ArrowDown);
+    press(&mut wnd, NamedKey::Enter);
+    wnd.draw_frame();
+    assert!(!combo.read().is_open());
+    assert_eq!(combo.read().value(), Some(&"Mango"));
+    assert_eq!(
+      node(&wnd, Role::TextInput)
+        .unwrap()
+        .value
+        .as_deref(),
+      Some("Mango")
+    );
+
+    // All the options are listed until the user types again.
+    press(&mut wnd, NamedKey::ArrowDown);
+    wnd.draw_frame();
+    assert_eq!(option_labels(&wnd), ["Apple", "Banana", "Cherry", "Mango"]);
+    assert_eq!(combo.read().listbox.active, Some(3));
+
+    // No option matches.
+    wnd.processes_receive_chars("x".into());
+    wnd.draw_frame();
+    assert!(combo.read().is_open());
+    assert!(option_labels(&wnd).is_empty());
+    press(&mut wnd, NamedKey::Escape);
+    wnd.draw_frame();
+    assert!(!combo.read().is_open());
+  }
+
+  #[test]
+  fn virtualized_options() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let select = @Select {
+        auto_focus: true,
+        options: (0..10000_usize)
+          .map(|i| SelectOption::new(i, format!("Option {i}")))
+          .collect::<Vec<_>>(),
+      };
+      *$w_holder.write() = Some(select.clone_writer());
+      select
+    };
+    let mut wnd = TestWindow::new_with_size(w, WND_SIZE);
+    wnd.draw_frame();
+    let select = holder.write().take().unwrap();
+
+    press(&mut wnd, NamedKey::ArrowDown);
+    wnd.draw_frame();
+    let built = option_labels(&wnd);
+    assert!(!built.is_empty() && built.len() < 100);
+    assert_eq!(built[0], "Option 0");
+
+    press(&mut wnd, NamedKey::End);
+    wnd.draw_frame();
+    wnd.draw_frame();
+    assert_eq!(select.read().listbox.active, Some(9999));
+    let built = option_labels(&wnd);
+    assert!(built.len() < 100);
+    assert!(built.iter().any(|l| l == "Option 9999"));
+  }
+}
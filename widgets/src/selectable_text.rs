@@ -0,0 +1,352 @@
+use std::{cell::RefCell, rc::Rc};
+
+use ribir_core::prelude::*;
+
+use crate::prelude::*;
+
+/// A read-only text that can be selected and copied.
+///
+/// The text supports selecting by dragging the mouse, selecting a word by
+/// double click and a paragraph by triple click, `Ctrl+A` to select all and
+/// `Ctrl+C` to copy the selection (`Cmd` on macOS).
+///
+/// Place it in a [`SelectionArea`] to let the selection span multiple texts.
+///
+/// # Example
+///
+/// ```no_run
+/// use ribir::prelude::*;
+///
+/// let w = fn_widget! {
+///   @SelectionArea {
+///     @Column {
+///       @SelectableText { text: "error: file not found" }
+///       @SelectableText { text: "  --> src/main.rs:1:1" }
+///     }
+///   }
+/// };
+/// App::run(w);
+/// ```
+#[derive(Declare)]
+pub struct SelectableText {
+  #[declare(custom)]
+  host: TextSelectable<CowArc<str>>,
+}
+
+pub trait SelectableTextDeclarerExtend {
+  fn text<const M: usize>(self, text: impl DeclareInto<CowArc<str>, M>) -> Self;
+}
+
+impl SelectableTextDeclarerExtend for FatObj<SelectableTextDeclarer> {
+  fn text<const M: usize>(mut self, text: impl DeclareInto<CowArc<str>, M>) -> Self {
+    let host = text
+      .declare_into()
+      .map(|text| TextSelectable { selection: <_>::default(), text: TextGlyphs::new(text) });
+    self.host = Some(host);
+    self
+  }
+}
+
+impl SelectableText {
+  pub fn text(&self) -> &CowArc<str> { self.host.text() }
+
+  pub fn selection(&self) -> Selection { self.host.selection }
+
+  /// Select the text in the cluster range `rg`.
+  pub fn select(&mut self, rg: std::ops::Range<usize>) {
+    let len = self.text().len();
+    self.host.selection = Selection {
+      from: CaretPosition { cluster: rg.start.min(len), position: None },
+      to: CaretPosition { cluster: rg.end.min(len), position: None },
+    };
+  }
+
+  pub fn select_all(&mut self) { self.select(0..self.text().len()) }
+
+  pub fn clear_selection(&mut self) { self.host.selection = Selection::default(); }
+
+  /// The text of the current selection.
+  pub fn selected_text(&self) -> Substr {
+    BaseText::substr(self.host.text(), self.host.cluster_rg())
+  }
+}
+
+impl Compose for SelectableText {
+  fn compose(this: impl StateWriter<Value = Self>) -> Widget<'static> {
+    fn_widget! {
+      let area = Provider::of::<SelectionGroup>(BuildCtx::get()).map(|g| g.clone());
+      let area2 = area.clone();
+      let area3 = area.clone();
+      let area4 = area.clone();
+      let area5 = area.clone();
+      let this2 = this.clone_boxed_writer();
+      let host = FatObj::new(part_writer!(&mut this.host));
+      @Stack {
+        on_mounted: move |e| if let Some(area) = &area {
+          area.join(e.current_target(), this2.clone_boxed_writer());
+        },
+        on_disposed: move |e| if let Some(area) = &area2 {
+          area.leave(e.current_target());
+        },
+        on_custom_concrete_event: move |e: &mut PointerSelectEvent| {
+          let Some(area) = &area3 else { return };
+          if let PointerSelectData::Move { from, to } | PointerSelectData::End { from, to } =
+            e.data()
+          {
+            let wnd = e.window();
+            let from = wnd.map_to_global(*from, e.target());
+            let to = wnd.map_to_global(*to, e.target());
+            area.select_between(e.current_target(), from, to, &wnd);
+          }
+        },
+        on_pointer_down: move |e| if let Some(area) = &area4 {
+          if !e.with_shift_key() {
+            area.clear_others(e.current_target());
+          }
+        },
+        @ $host {
+          tab_index: -1i16,
+          // Listen on the text itself, so the area receives the command that
+          // the text handles.
+          on_command: move |e| if let Some(area) = &area5 {
+            match e.command() {
+              c if c == &CommandId::SELECT_ALL => area.select_all(),
+              c if c == &CommandId::COPY => area.copy(),
+              _ => return,
+            }
+            e.handle();
+          },
+        }
+      }
+    }
+    .into_widget()
+  }
+}
+
+/// A container that lets the selection span all the [`SelectableText`]
+/// descendants.
+///
+/// Dragging from one text to another selects the texts between them, `Ctrl+A`
+/// selects all the texts and `Ctrl+C` copies the selected parts joined by
+/// line breaks. The texts are ordered by the order they are mounted.
+#[derive(Declare, Default)]
+pub struct SelectionArea {
+  #[declare(skip)]
+  group: SelectionGroup,
+}
+
+#[derive(Clone, Default)]
+struct SelectionGroup(Rc<RefCell<Vec<SelectionMember>>>);
+
+struct SelectionMember {
+  id: WidgetId,
+  text: Box<dyn StateWriter<Value = SelectableText>>,
+}
+
+impl SelectionArea {
+  /// The selected text of all the members, joined by line breaks.
+  pub fn selected_text(&self) -> String { self.group.selected_text() }
+
+  pub fn select_all(&self) { self.group.select_all() }
+
+  pub fn clear_selection(&self) { self.group.clear_others(None) }
+}
+
+impl<'c> ComposeChild<'c> for SelectionArea {
+  type Child = Widget<'c>;
+
+  fn compose_child(this: impl StateWriter<Value = Self>, child: Self::Child) -> Widget<'c> {
+    let group = this.read().group.clone();
+    Providers::new([Provider::new(group)]).with_child(child)
+  }
+}
+
+impl SelectionGroup {
+  fn join(&self, id: WidgetId, text: Box<dyn StateWriter<Value = SelectableText>>) {
+    self
+      .0
+      .borrow_mut()
+      .push(SelectionMember { id, text });
+  }
+
+  fn leave(&self, id: WidgetId) { self.0.borrow_mut().retain(|m| m.id != id); }
+
+  fn clear_others(&self, id: impl Into<Option<WidgetId>>) {
+    let id = id.into();
+    for m in self.0.borrow().iter() {
+      if Some(m.id) != id && !m.text.read().host.cluster_rg().is_empty() {
+        m.text.write().clear_selection();
+      }
+    }
+  }
+
+  fn select_all(&self) {
+    for m in self.0.borrow().iter() {
+      m.text.write().select_all();
+    }
+  }
+
+  fn selected_text(&self) -> String {
+    let members = self.0.borrow();
+    let texts = members
+      .iter()
+      .map(|m| m.text.read().selected_text().to_string())
+      .filter(|s| !s.is_empty())
+      .collect::<Vec<_>>();
+    texts.join("\n")
+  }
+
+  fn copy(&self) {
+    let text = self.selected_text();
+    if !text.is_empty() {
+      let clipboard = AppCtx::clipboard();
+      let _ = clipboard.borrow_mut().clear();
+      let _ = clipboard.borrow_mut().write_text(&text);
+    }
+  }
+
+  /// Select from the global position `from` in the member `anchor` to the
+  /// global position `to`.
+  fn select_between(&self, anchor: WidgetId, from: Point, to: Point, wnd: &Window) {
+    let members = self.0.borrow();
+    let Some(start) = members.iter().position(|m| m.id == anchor) else { return };
+    let rect_of = |id| {
+      let origin = wnd.map_to_global(Point::zero(), id);
+      Rect::new(origin, wnd.widget_size(id).unwrap_or_default())
+    };
+    let distance = |rc: Rect| {
+      let dx = (rc.min_x() - to.x).max(to.x - rc.max_x()).max(0.);
+      let dy = (rc.min_y() - to.y).max(to.y - rc.max_y()).max(0.);
+      dx * dx + dy * dy
+    };
+    let Some(end) = (0..members.len()).min_by(|a, b| {
+      distance(rect_of(members[*a].id)).total_cmp(&distance(rect_of(members[*b].id)))
+    }) else {
+      return;
+    };
+
+    let caret_at = |m: &SelectionMember, pos: Point| {
+      let pos = wnd.map_from_global(pos, m.id);
+      m.text
+        .read()
+        .host
+        .glyphs()
+        .map_or(0, |g| g.caret_position_from_pos(pos).cluster)
+    };
+    for (idx, m) in members.iter().enumerate() {
+      let len = m.text.read().text().len();
+      let rg = match idx {
+        idx if idx == start && idx == end => caret_at(m, from)..caret_at(m, to),
+        idx if idx == start => caret_at(m, from)..if start < end { len } else { 0 },
+        idx if idx == end => (if start < end { 0 } else { len })..caret_at(m, to),
+        idx if start.min(end) < idx && idx < start.max(end) => 0..len,
+        _ => {
+          if !m.text.read().host.cluster_rg().is_empty() {
+            m.text.write().clear_selection();
+          }
+          continue;
+        }
+      };
+      m.text.write().select(rg);
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use ribir_core::{prelude::*, reset_test_env, test_helper::*};
+  use winit::event::{ElementState, Modifiers, WindowEvent};
+
+  use super::*;
+
+  fn press_ctrl_key(wnd: &mut TestWindow, code: KeyCode, key: &str) {
+    #[cfg(target_os = "macos")]
+    let modifiers = ModifiersState::SUPER;
+    #[cfg(not(target_os = "macos"))]
+    let modifiers = ModifiersState::CONTROL;
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(Modifiers::from(modifiers)));
+    let key = VirtualKey::Character(key.into());
+    let state = ElementState::Pressed;
+    wnd.processes_keyboard_event(PhysicalKey::Code(code), key, false, KeyLocation::Standard, state);
+    // The keyboard event is delayed, keep the modifiers until it's dispatched.
+    wnd.draw_frame();
+    #[allow(deprecated)]
+    wnd.processes_native_event(WindowEvent::ModifiersChanged(Modifiers::default()));
+  }
+
+  #[test]
+  fn select_word_and_paragraph() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let text = @SelectableText { text: "hello world\nsecond line" };
+      *$w_holder.write() = Some(text.clone_writer());
+      text
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(300., 200.));
+    wnd.draw_frame();
+    let text = holder.write().take().unwrap();
+
+    wnd.mouse_move_to(Point::new(5., 5.));
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(&*text.read().selected_text(), "hello");
+
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(&*text.read().selected_text(), "hello world");
+
+    press_ctrl_key(&mut wnd, KeyCode::KeyA, "a");
+    assert_eq!(&*text.read().selected_text(), "hello world\nsecond line");
+  }
+
+  #[test]
+  fn selection_across_texts() {
+    reset_test_env!();
+    let holder = Stateful::new(None);
+    let w_holder = holder.clone_writer();
+    let w = fn_widget! {
+      let area = @SelectionArea {};
+      *$w_holder.write() = Some(area.clone_writer());
+      @ $area {
+        @Column {
+          @SizedBox { size: Size::new(200., 20.), @SelectableText { text: "first" } }
+          @SizedBox { size: Size::new(200., 20.), @SelectableText { text: "second" } }
+          @SizedBox { size: Size::new(200., 20.), @SelectableText { text: "third" } }
+        }
+      }
+    };
+
+    let mut wnd = TestWindow::new_with_size(w, Size::new(300., 200.));
+    wnd.draw_frame();
+    let area = holder.write().take().unwrap();
+
+    // Drag from the start of the first text to the end of the last text.
+    wnd.mouse_move_to(Point::new(1., 5.));
+    wnd.mouse_press();
+    wnd.mouse_move_to(Point::new(10., 5.));
+    wnd.mouse_move_to(Point::new(100., 30.));
+    wnd.mouse_move_to(Point::new(150., 45.));
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(area.read().selected_text(), "first\nsecond\nthird");
+
+    // Click collapses the selection of the other texts.
+    wnd.mouse_move_to(Point::new(5., 25.));
+    wnd.mouse_press();
+    wnd.mouse_release();
+    wnd.draw_frame();
+    assert_eq!(area.read().selected_text(), "");
+
+    // Select all the texts with the keyboard from the focused one.
+    press_ctrl_key(&mut wnd, KeyCode::KeyA, "a");
+    assert_eq!(area.read().selected_text(), "first\nsecond\nthird");
+  }
+}
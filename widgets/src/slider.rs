@@ -96,6 +96,25 @@ impl Slider {
     let active = (self.ratio() * divisions as f32) as usize;
     Some(stop_indicator_track(divisions + 1, 0..active, vec![active]))
   }
+
+  /// Move the value a division forward or backward, or a tenth of the range if
+  /// the slider is continuous.
+  fn step(&mut self, forward: bool) {
+    let step = match self.divisions {
+      Some(divisions) if divisions > 0 => 1. / divisions as f32,
+      _ => 0.1,
+    };
+    let ratio = self.ratio();
+    self.set_to(if forward { ratio + step } else { ratio - step });
+  }
+
+  fn semantics(&self) -> Semantics {
+    let precision = precision(self.min, self.max);
+    Semantics::new(Role::Slider)
+      .with_value(format!("{:.1$}", self.value, precision))
+      .with_action(SemanticsAction::Increment)
+      .with_action(SemanticsAction::Decrement)
+  }
 }
 
 fn precision(min: f32, max: f32) -> usize {
@@ -115,6 +134,14 @@ impl Compose for Slider {
       let drag_info = Stateful::new(None);
       @ Stack {
         class: SLIDER_CONTAINER,
+        semantics: pipe!($this.semantics()),
+        on_custom_concrete_event: move |e: &mut CustomEvent<SemanticsAction>| {
+          match e.data() {
+            SemanticsAction::Increment => $this.write().step(true),
+            SemanticsAction::Decrement => $this.write().step(false),
+            _ => {}
+          }
+        },
         @ $row {
           v_align: VAlign::Center,
           on_tap: move |e| {
@@ -377,11 +404,31 @@ fn stop_indicator_track(cnt: usize, actives: Range<usize>, filter: Vec<usize>) -
 
 #[cfg(test)]
 mod tests {
-  use ribir_core::test_helper::*;
+  use ribir_core::{reset_test_env, test_helper::*};
   use ribir_dev_helper::*;
 
   use super::*;
 
+  #[test]
+  fn slider_semantics_actions() {
+    reset_test_env!();
+
+    let mut wnd = TestWindow::new(fn_widget! {
+      @Slider { value: 50., divisions: Some(10) }
+    });
+    wnd.draw_frame();
+
+    let tree = wnd.accessibility_tree();
+    let slider = tree.find(|n| n.role == Role::Slider).unwrap();
+    assert_eq!(slider.value.as_deref(), Some("50.00"));
+
+    wnd.perform_semantics_action(slider.id, SemanticsAction::Increment);
+    wnd.draw_frame();
+    let tree = wnd.accessibility_tree();
+    let slider = tree.find(|n| n.role == Role::Slider).unwrap();
+    assert_eq!(slider.value.as_deref(), Some("60.00"));
+  }
+
   widget_image_tests!(
     slider_widgets,
     WidgetTester::new(self::column! {
@@ -72,6 +72,8 @@ pub struct TabsStyle {
   pub icon_pos: Position,
   pub active_color: Brush,
   pub foreground: Brush,
+  /// The style of the tab label, it ends with an ellipsis if the label is too
+  /// long by default.
   pub label_style: TextStyle,
   pub indicator: IndicatorStyle,
 }
@@ -87,7 +89,10 @@ impl CustomStyle for TabsStyle {
       icon_pos: Position::Top,
       active_color: palette.primary().into(),
       foreground: palette.on_surface_variant().into(),
-      label_style: TypographyTheme::of(ctx).title_small.text.clone(),
+      label_style: TextStyle {
+        overflow: TextOverflow::Ellipsis(EllipsisMode::End),
+        ..TypographyTheme::of(ctx).title_small.text.clone()
+      },
       indicator: IndicatorStyle { extent: 3., measure: Some(60.) },
     }
   }
@@ -192,6 +197,11 @@ impl Tabs {
                 Position::Top | Position::Bottom => Direction::Vertical,
               },
               reverse: matches!(icon_pos, Position::Right | Position::Bottom),
+              semantics: pipe!(
+                Semantics::new(Role::Tab)
+                  .with_selected($tabs.cur_idx == idx)
+                  .with_action(SemanticsAction::Click)
+              ),
               on_tap: move |_| if $tabs.cur_idx != idx {
                 $tabs.write().cur_idx = idx;
               },
@@ -248,6 +258,7 @@ impl ComposeChild<'static> for Tabs {
         };
         let mut flex = @Flex {
           align_items: Align::Stretch,
+          semantics: Role::TabList,
           direction: pipe!(match $this.pos {
             Position::Top | Position::Bottom => Direction::Horizontal,
             Position::Left | Position::Right => Direction::Vertical,
@@ -320,7 +331,10 @@ impl ComposeChild<'static> for Tabs {
           },
           @ { header }
           @Expanded {
-            @ { pipe!($this.cur_idx).map(move |idx| panes[idx].gen_widget()) }
+            @FatObj {
+              semantics: Role::TabPanel,
+              @ { pipe!($this.cur_idx).map(move |idx| panes[idx].gen_widget()) }
+            }
           }
         }
       }